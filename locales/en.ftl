@@ -0,0 +1,134 @@
+### English text of the rtop interface, the fallback for messages a translation lacks.
+### Translations copy the ids and keep every `{ $name }` placeable.
+
+## Panels
+processes = Processes
+memory = Memory
+network = Network
+disk = Disk
+
+## Bottom key bar
+hint-quit = quit
+hint-focus = focus
+hint-theme = theme
+hint-save = save
+hint-move = move
+hint-sort = sort
+hint-search = search
+hint-kill = kill
+hint-mark = mark
+hint-menu = menu
+hint-tree = tree
+hint-fold = fold
+hint-details = details
+hint-select-volume = select volume
+hint-partitions = partitions
+hint-retry-kill-elevated = retry kill elevated
+hint-inspect-graph = inspect graph
+hint-interface = interface
+hint-bits-bytes = bits/bytes
+hint-speed-test = speed test
+hint-next-gpu = next GPU
+hint-select-core = select core
+hint-core-processes = core processes
+
+## Kill dialog
+kill-title = Confirm Termination
+kill-confirm = Terminate '{ $name }' (PID { $pid })?
+signal-title = Confirm SIG{ $signal }
+signal-confirm = Send SIG{ $signal } to '{ $name }' (PID { $pid })?
+kill-yes = Yes
+kill-no = No
+signal-keys = ↑/↓: signal  Enter: confirm  Esc: cancel
+signal-label = Signal:
+protected-title = Protected Process
+protected-warning = ⚠ '{ $name }' (PID { $pid }) is a protected process.
+protected-consequence = Terminating it can end your session or take down the system.
+protected-type-yes = Type yes to terminate:
+unit-confirm = '{ $name }' (PID { $pid }) runs in { $unit }. Restart the unit or terminate the process?
+unit-choice = It runs in { $unit }; Tab chooses:
+unit-restart = Restart unit
+unit-terminate = Terminate
+batch-confirm = Signal these { $count } processes?
+batch-protected = It is one of { $count } marked processes:
+more-count = … { $count } more
+
+## Process menu
+menu-details = Details
+menu-kill = Kill with signal…
+menu-mark = Mark for batch kill
+menu-nice-lower = Lower priority (nice +5)
+menu-nice-raise = Raise priority (nice -5)
+menu-pin = Pin to the core it last ran on
+menu-unpin = Run on every core
+menu-copy = Copy PID and command line
+menu-watch = Watch: keep it selected as the list re-sorts
+
+## Process details
+detail-exited = PID { $pid } has exited · Esc back to the list
+detail-kernel-thread = kernel thread
+detail-container = container
+detail-parent = parent
+detail-no-parent = none
+detail-user = user
+detail-unknown = unknown
+detail-priority = priority
+detail-command = command
+detail-exe = exe
+detail-cwd = cwd
+detail-started = started
+detail-started-at = { $time } · up { $uptime }
+detail-memory = memory
+detail-cores = cores
+detail-no-samples = no samples yet
+detail-cores-used = ran on { $used } of { $cores } cores over the last { $samples } samples
+detail-files = files
+detail-files-denied = not permitted
+detail-files-none = none on disk
+detail-cpu = cpu
+detail-cpu-usage = { $now }% now · peak { $peak }% over { $samples } samples
+detail-esc-back = Esc back
+gpu-processes-title = GPU processes
+gpu-processes-none = no process is using the GPU
+
+## Status bar
+status-restricted-sources = run with elevated privileges to see { $sources }
+
+## Popups
+esc-close = Esc close
+events-title = Alert timeline
+events-empty = No alerts raised this session
+compare-title = Changes since bookmark
+compare-no-bookmark = No bookmark yet: press v to bookmark the current snapshot
+compare-keys = v bookmark now · Esc close
+compare-bookmarked = Bookmarked { $time } · { $ago } ago
+compare-system = SYSTEM
+compare-bookmark = BOOKMARK
+compare-now = NOW
+compare-change = CHANGE
+compare-cpu = CPU
+compare-swap = Swap
+compare-disk = Disk { $name }
+compare-started = Started ({ $count })
+compare-exited = Exited ({ $count })
+compare-changed = Changed ({ $count })
+stats-title = Session statistics
+stats-monitoring = monitoring since { $since } · running { $running }
+stats-restored-since = graphs reach back to { $since }, restored from the previous session
+core-title = Processes on core { $core }
+core-offline = offline
+core-empty = No process last ran on core { $core }
+interfaces-title = Network interfaces
+interfaces-all = All
+interfaces-keys = ↑/↓ select · Enter show · Esc close
+inhibitors-title = Inhibitor locks
+inhibitors-unavailable = Inhibitor locks are unavailable (no systemd-logind)
+inhibitors-empty = Nothing is holding off sleep, idle or shutdown
+inhibitors-loading = Asking logind…
+inhibitors-summary = { $blocking } blocking · { $delaying } delaying
+inhibitors-mode = MODE
+inhibitors-what = WHAT
+inhibitors-pid = PID
+inhibitors-process = PROCESS
+inhibitors-user = USER
+inhibitors-who = WHO
@@ -0,0 +1,128 @@
+### Spanish translation of the rtop interface.
+
+## Panels
+processes = Procesos
+memory = Memoria
+network = Red
+disk = Disco
+
+## Bottom key bar
+hint-quit = salir
+hint-focus = foco
+hint-theme = tema
+hint-save = guardar
+hint-move = mover
+hint-sort = ordenar
+hint-search = buscar
+hint-kill = terminar
+hint-menu = menú
+hint-tree = árbol
+hint-details = detalles
+hint-partitions = particiones
+hint-retry-kill-elevated = reintentar con privilegios
+hint-inspect-graph = inspeccionar gráfica
+hint-interface = interfaz
+hint-bits-bytes = bits/bytes
+hint-speed-test = test de velocidad
+hint-next-gpu = siguiente GPU
+hint-select-core = elegir núcleo
+hint-core-processes = procesos del núcleo
+
+## Kill dialog
+kill-title = Confirmar terminación
+kill-confirm = ¿Terminar '{ $name }' (PID { $pid })?
+signal-title = Confirmar SIG{ $signal }
+signal-confirm = ¿Enviar SIG{ $signal } a '{ $name }' (PID { $pid })?
+kill-yes = Sí
+kill-no = No
+signal-keys = ↑/↓: señal  Enter: confirmar  Esc: cancelar
+signal-label = Señal:
+protected-title = Proceso protegido
+protected-warning = ⚠ '{ $name }' (PID { $pid }) es un proceso protegido.
+protected-consequence = Terminarlo puede cerrar tu sesión o tumbar el sistema.
+protected-type-yes = Escribe yes para terminar:
+unit-confirm = '{ $name }' (PID { $pid }) se ejecuta en { $unit }. ¿Reiniciar la unidad o terminar el proceso?
+unit-choice = Se ejecuta en { $unit }; Tab elige:
+unit-restart = Reiniciar unidad
+unit-terminate = Terminar
+batch-confirm = ¿Enviar la señal a estos { $count } procesos?
+batch-protected = Es uno de los { $count } procesos marcados:
+more-count = … { $count } más
+
+## Process menu
+menu-details = Detalles
+menu-kill = Enviar una señal…
+menu-mark = Marcar para terminar en lote
+menu-nice-lower = Bajar prioridad (nice +5)
+menu-nice-raise = Subir prioridad (nice -5)
+menu-pin = Fijar al núcleo donde se ejecutó
+menu-unpin = Ejecutar en todos los núcleos
+menu-copy = Copiar PID y línea de comandos
+menu-watch = Seguir: mantenerlo elegido al reordenar
+
+## Process details
+detail-exited = El PID { $pid } ha terminado · Esc vuelve a la lista
+detail-kernel-thread = hilo del kernel
+detail-container = contenedor
+detail-parent = padre
+detail-no-parent = ninguno
+detail-user = usuario
+detail-unknown = desconocido
+detail-priority = prioridad
+detail-command = comando
+detail-exe = exe
+detail-cwd = cwd
+detail-started = inicio
+detail-started-at = { $time } · activo desde hace { $uptime }
+detail-memory = memoria
+detail-cores = núcleos
+detail-no-samples = aún sin muestras
+detail-cores-used = se ejecutó en { $used } de { $cores } núcleos en las últimas { $samples } muestras
+detail-files = archivos
+detail-files-denied = sin permiso
+detail-files-none = ninguno en disco
+detail-cpu = cpu
+detail-cpu-usage = { $now }% ahora · máximo { $peak }% en { $samples } muestras
+detail-esc-back = Esc vuelve
+gpu-processes-title = Procesos en la GPU
+gpu-processes-none = ningún proceso está usando la GPU
+
+## Status bar
+status-restricted-sources = ejecuta con privilegios elevados para ver { $sources }
+
+## Popups
+esc-close = Esc cerrar
+events-title = Historial de alertas
+events-empty = Ninguna alerta en esta sesión
+compare-title = Cambios desde el marcador
+compare-no-bookmark = Aún no hay marcador: pulsa v para marcar la instantánea actual
+compare-keys = v marcar ahora · Esc cerrar
+compare-bookmarked = Marcada a las { $time } · hace { $ago }
+compare-system = SISTEMA
+compare-bookmark = MARCADOR
+compare-now = AHORA
+compare-change = CAMBIO
+compare-swap = Swap
+compare-disk = Disco { $name }
+compare-started = Iniciados ({ $count })
+compare-exited = Terminados ({ $count })
+compare-changed = Cambiados ({ $count })
+stats-title = Estadísticas de la sesión
+stats-monitoring = monitorizando desde { $since } · en marcha { $running }
+stats-restored-since = las gráficas llegan hasta { $since }, recuperadas de la sesión anterior
+core-title = Procesos en el núcleo { $core }
+core-offline = desconectado
+core-empty = Ningún proceso se ejecutó por última vez en el núcleo { $core }
+interfaces-title = Interfaces de red
+interfaces-all = Todas
+interfaces-keys = ↑/↓ elegir · Enter mostrar · Esc cerrar
+inhibitors-title = Bloqueos de inhibición
+inhibitors-unavailable = Los bloqueos de inhibición no están disponibles (sin systemd-logind)
+inhibitors-empty = Nada está impidiendo la suspensión, la inactividad o el apagado
+inhibitors-loading = Consultando a logind…
+inhibitors-summary = { $blocking } bloqueando · { $delaying } retrasando
+inhibitors-mode = MODO
+inhibitors-what = QUÉ
+inhibitors-process = PROCESO
+inhibitors-user = USUARIO
+inhibitors-who = QUIÉN
@@ -39,12 +39,51 @@ pub enum Action {
     /// Switch to next process sorting option
     SwitchProcessSort,
 
+    /// Flip the process list between ascending and descending for the current sort key
+    ReverseProcessSort,
+
     /// Switch to next chart type
     SwitchChartType,
 
     /// Toggle process tree view
     ToggleProcessTree,
 
+    /// Fold (`true`) or unfold the tree branch under the selected process
+    CollapseProcessBranch(bool),
+
+    /// Show average CPU% since rtop started instead of current usage
+    ToggleCumulativeCpu,
+
+    /// Switch process CPU% between per-core (Irix) and whole-machine (Solaris) scale
+    ToggleIrixMode,
+
+    /// Hide processes below the idle CPU and memory thresholds
+    ToggleHideIdle,
+
+    /// Cycle between all processes, host processes only and container processes only
+    CycleProcessOrigin,
+
+    /// List only browsers, shells, daemons, kernel threads or other processes, then all again
+    CycleProcessCategory,
+
+    /// Cycle the process owner filter: everyone, my processes, root's processes
+    CycleProcessOwner,
+
+    /// List only suspected memory leaks, fastest growing first
+    ToggleLeakView,
+
+    /// Order the process tree by whole-subtree CPU/memory and show the totals
+    ToggleSubtreeSort,
+
+    /// Toggle the page fault and swap columns in the process list
+    ToggleFaultColumns,
+
+    /// Toggle full command lines in the process list
+    ToggleFullCommandLine,
+
+    /// Toggle masking of process arguments
+    TogglePrivacyMode,
+
     /// Start process search/filter input
     StartProcessSearch,
 
@@ -54,15 +93,27 @@ pub enum Action {
     /// Delete one character from process search input
     BackspaceProcessSearch,
 
+    /// Switch process search between substring, regex and fuzzy matching
+    CycleSearchMatchMode,
+
     /// Confirm process search input
     ConfirmProcessSearch,
 
     /// Cancel process search input
     CancelProcessSearch,
 
-    /// Open process termination confirmation for selected process
+    /// Open process termination confirmation for the marked processes, or the selected one
     RequestProcessKill,
 
+    /// Mark or unmark the selected process for a batch kill and move to the next one
+    ToggleProcessMark,
+
+    /// Unmark every marked process
+    ClearProcessMarks,
+
+    /// Keep the selection on the selected process as the list re-sorts, or stop following it
+    ToggleFollowProcess,
+
     /// Toggle selection between Yes/No in kill confirmation
     ToggleProcessKillChoice,
 
@@ -72,8 +123,130 @@ pub enum Action {
     /// Cancel process termination confirmation
     CancelProcessKill,
 
-    /// Cycle through network interfaces
-    CycleNetworkInterface,
+    /// Pick the next (true) or previous signal in the kill dialog
+    CycleKillSignal(bool),
+
+    /// Append one character to the "yes" typed to kill a protected process
+    UpdateKillConfirmation(char),
+
+    /// Delete one character from the typed kill confirmation
+    BackspaceKillConfirmation,
+
+    /// Send SIGTERM to the selected process without asking
+    QuickKillProcess,
+
+    /// Resend a refused signal through the configured privilege helper
+    RetryKillElevated,
+
+    /// Change the nice value of the selected process by this much
+    ReniceProcess(i32),
+
+    /// Pin the selected process to the core it last ran on (`true`), or let it run on every
+    /// core again (`false`)
+    PinProcess(bool),
+
+    /// Copy the PID and command line of the selected process to the clipboard
+    CopyProcessInfo,
+
+    /// Open the context menu for the selected process
+    OpenToolMenu,
+
+    /// Highlight the previous (`false`) or next (`true`) entry in the menu
+    MoveToolSelection(bool),
+
+    /// Run the highlighted menu entry; tools run with the TUI suspended
+    LaunchTool,
+
+    /// Close the process menu
+    CloseToolMenu,
+
+    /// Open or close the network interface picker
+    ToggleInterfacePicker,
+
+    /// Highlight the previous (`false`) or next (`true`) interface in the picker
+    MoveInterfaceSelection(bool),
+
+    /// Show the highlighted interface in the network panel and close the picker
+    SelectNetworkInterface,
+
+    /// Show network rates in bits or bytes per second
+    ToggleNetworkBits,
+
+    /// Measure bandwidth against the configured speed-test endpoint
+    RunSpeedTest,
+
+    /// Move keyboard focus to the next panel (process list first, then side panels in slot order)
+    FocusNextPanel,
+
+    /// Cycle which GPU is shown with full details
+    CyclePrimaryGpu,
+
+    /// Show or hide the off-CPU / block I/O top offenders popup
+    #[cfg(feature = "ebpf")]
+    ToggleOffenders,
+
+    /// Enter or leave keyboard panel-move mode
+    TogglePanelMoveMode,
+
+    /// In panel-move mode, pick the next panel to move
+    SelectNextPanel,
+
+    /// In panel-move mode, move the picked panel one slot earlier (`false`) or later (`true`)
+    MovePanel(bool),
+
+    /// Sort the process list by this key (a column title was clicked)
+    SortProcessesBy(crate::data::snapshot::ProcessSortBy),
+
+    /// Exchange two panels' slots (title bar dragged onto another panel)
+    SwapPanels(crate::layout::Panel, crate::layout::Panel),
+
+    /// Show or hide a side panel, letting the others take its space
+    TogglePanel(crate::layout::Panel),
+
+    /// Drag a pane border to a percent of the screen width or column height
+    ResizePane(crate::layout::PaneBorder, u16),
+
+    /// Write the pane sizes to the config file once a border drag ends
+    SavePaneSizes,
+
+    /// Move the focused panel's graph cursor one sample older (`false`) or newer (`true`)
+    MoveGraphCursor(bool),
+
+    /// Leave graph inspection mode
+    ClearGraphCursor,
+
+    /// Show or hide the session min/avg/max statistics popup
+    ToggleSessionStats,
+
+    /// Write the session statistics to a JSON file
+    ExportSessionStats,
+
+    /// Show or hide the timeline of raised and cleared alerts
+    ToggleEventTimeline,
+
+    /// Show or hide the logind sleep/idle inhibitor locks and their holders
+    ToggleInhibitors,
+
+    /// Show or hide the persisted hourly history charts
+    ToggleHistoryView,
+
+    /// Keep the current snapshot to compare later ones against
+    BookmarkSnapshot,
+
+    /// Show or hide the changes since the bookmarked snapshot
+    ToggleSnapshotDiff,
+
+    /// Switch the history charts between the last 24 hours and the last 7 days
+    CycleHistoryRange,
+
+    /// Show or hide the partition layout of the disk behind the selected volume
+    ToggleDiskDetail,
+
+    /// Show or hide the processes last scheduled on the selected CPU core
+    ToggleCoreProcesses,
+
+    /// Show collect/render timings next to rtop's own resource usage
+    ToggleSelfUsageDebug,
 
     /// Save current configuration
     SaveConfig,
@@ -84,3 +257,20 @@ pub enum Action {
     /// Error occurred
     Error(String),
 }
+
+impl Action {
+    /// Changes something outside rtop (signals or renices a process, restarts a service), so
+    /// it is refused in read-only mode
+    pub fn is_destructive(&self) -> bool {
+        matches!(
+            self,
+            Action::RequestProcessKill
+                | Action::ConfirmProcessKill
+                | Action::QuickKillProcess
+                | Action::RetryKillElevated
+                | Action::ToggleProcessMark
+                | Action::ReniceProcess(_)
+                | Action::PinProcess(_)
+        )
+    }
+}
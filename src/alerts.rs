@@ -0,0 +1,340 @@
+use crate::{
+    config::{AppConfig, GpuTempLimit},
+    data::snapshot::SystemSnapshot,
+};
+use chrono::{DateTime, Local};
+use std::collections::{HashMap, VecDeque};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum AlertSeverity {
+    Warning,
+    Critical,
+}
+
+#[derive(Debug, Clone)]
+pub struct Alert {
+    /// Stable identifier so the same condition is tracked across snapshots
+    pub key: String,
+    pub severity: AlertSeverity,
+    pub message: String,
+}
+
+/// An alert being raised or clearing, kept for the event timeline
+#[derive(Debug, Clone)]
+pub struct AlertEvent {
+    pub at: DateTime<Local>,
+    pub severity: AlertSeverity,
+    /// The alert's message when it was raised, or its last message before it cleared
+    pub message: String,
+    /// How long the condition lasted, in seconds; `None` when this event raised it
+    pub recovered_after: Option<u64>,
+}
+
+/// Evaluates alert conditions against each new snapshot and keeps the active set
+#[derive(Default)]
+pub struct AlertEngine {
+    active: Vec<Alert>,
+    /// When each active alert was first raised
+    since: HashMap<String, DateTime<Local>>,
+    /// Raised and recovered alerts, oldest first
+    events: VecDeque<AlertEvent>,
+}
+
+impl AlertEngine {
+    const MAX_EVENTS: usize = 100;
+    /// How long a recovery stays in the status line when nothing else is alerting
+    const RECOVERY_NOTICE_SECS: i64 = 30;
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Re-evaluate every condition; returns whether an alert became critical, either newly
+    /// raised as critical or escalated from a warning
+    pub fn update(
+        &mut self,
+        snapshot: &SystemSnapshot,
+        config: &AppConfig,
+        now: DateTime<Local>,
+    ) -> bool {
+        let mut active = Vec::new();
+        Self::check_disk_fill(snapshot, config, &mut active);
+        Self::check_socket_exhaustion(snapshot, &mut active);
+        Self::check_kernel_limits(snapshot, &mut active);
+        Self::check_memory_pressure(snapshot, &mut active);
+        Self::check_latency(snapshot, &mut active);
+        Self::check_gpu_temperature(snapshot, config, &mut active);
+        Self::check_remote_mounts(snapshot, &mut active);
+
+        active.sort_by(|a, b| b.severity.cmp(&a.severity).then_with(|| a.key.cmp(&b.key)));
+
+        for cleared in self
+            .active
+            .iter()
+            .filter(|old| !active.iter().any(|alert| alert.key == old.key))
+        {
+            let since = self.since.remove(&cleared.key).unwrap_or(now);
+            self.events.push_back(AlertEvent {
+                at: now,
+                severity: cleared.severity,
+                message: cleared.message.clone(),
+                recovered_after: Some((now - since).num_seconds().max(0) as u64),
+            });
+        }
+        for raised in &active {
+            if self.since.contains_key(&raised.key) {
+                // A warning turning critical is logged again; the condition keeps its start
+                let escalated = self
+                    .active
+                    .iter()
+                    .any(|old| old.key == raised.key && old.severity < raised.severity);
+                if escalated {
+                    self.events.push_back(AlertEvent {
+                        at: now,
+                        severity: raised.severity,
+                        message: raised.message.clone(),
+                        recovered_after: None,
+                    });
+                }
+                continue;
+            }
+            self.since.insert(raised.key.clone(), now);
+            self.events.push_back(AlertEvent {
+                at: now,
+                severity: raised.severity,
+                message: raised.message.clone(),
+                recovered_after: None,
+            });
+        }
+        while self.events.len() > Self::MAX_EVENTS {
+            self.events.pop_front();
+        }
+        let turned_critical = active.iter().any(|alert| {
+            alert.severity == AlertSeverity::Critical
+                && !self
+                    .active
+                    .iter()
+                    .any(|old| old.key == alert.key && old.severity == AlertSeverity::Critical)
+        });
+        self.active = active;
+        turned_critical
+    }
+
+    /// Most severe active alert, used for the status line
+    pub fn headline(&self) -> Option<&Alert> {
+        self.active.first()
+    }
+
+    /// The latest event if it is a recovery from the last few seconds, for the status line
+    pub fn recent_recovery(&self, now: DateTime<Local>) -> Option<&AlertEvent> {
+        self.events.back().filter(|event| {
+            event.recovered_after.is_some()
+                && (now - event.at).num_seconds() < Self::RECOVERY_NOTICE_SECS
+        })
+    }
+
+    pub fn events(&self) -> &VecDeque<AlertEvent> {
+        &self.events
+    }
+
+    /// Raise an alert once `used` reaches the `warn`/`crit` share of `limit`
+    fn check_usage(
+        out: &mut Vec<Alert>,
+        (key, label): (&str, &str),
+        used: u64,
+        limit: u64,
+        (warn, crit): (f64, f64),
+    ) {
+        if limit == 0 {
+            return;
+        }
+        let ratio = used as f64 / limit as f64;
+        let severity = if ratio >= crit {
+            AlertSeverity::Critical
+        } else if ratio >= warn {
+            AlertSeverity::Warning
+        } else {
+            return;
+        };
+        out.push(Alert {
+            key: key.to_string(),
+            severity,
+            message: format!("{} {:.0}% used ({}/{})", label, ratio * 100.0, used, limit),
+        });
+    }
+
+    /// Warn before ephemeral ports run out
+    fn check_socket_exhaustion(snapshot: &SystemSnapshot, out: &mut Vec<Alert>) {
+        let Some(sockets) = &snapshot.socket_stats else {
+            return;
+        };
+        Self::check_usage(
+            out,
+            ("ephemeral-ports", "ephemeral ports"),
+            u64::from(sockets.ephemeral_in_use),
+            u64::from(sockets.ephemeral_range_size),
+            (0.70, 0.90),
+        );
+    }
+
+    /// Warn before the file, PTY or PID tables fill up
+    fn check_kernel_limits(snapshot: &SystemSnapshot, out: &mut Vec<Alert>) {
+        let Some(limits) = &snapshot.kernel_limits else {
+            return;
+        };
+        Self::check_usage(
+            out,
+            ("fd-exhaustion", "file descriptors"),
+            limits.fds_allocated,
+            limits.fds_max,
+            (0.90, 0.97),
+        );
+        Self::check_usage(
+            out,
+            ("pty-exhaustion", "PTYs"),
+            limits.ptys,
+            limits.ptys_max,
+            (0.80, 0.95),
+        );
+        Self::check_usage(
+            out,
+            ("pid-exhaustion", "PIDs"),
+            limits.tasks,
+            limits.pid_max,
+            (0.80, 0.95),
+        );
+    }
+
+    /// Warn when little memory is left before the kernel has to swap or OOM-kill
+    fn check_memory_pressure(snapshot: &SystemSnapshot, out: &mut Vec<Alert>) {
+        let pressure = snapshot.memory_pressure_percent();
+        let severity = if pressure >= 95.0 {
+            AlertSeverity::Critical
+        } else if pressure >= 85.0 {
+            AlertSeverity::Warning
+        } else {
+            return;
+        };
+        out.push(Alert {
+            key: "memory-pressure".to_string(),
+            severity,
+            message: format!(
+                "memory pressure {:.0}% ({} available)",
+                pressure,
+                crate::format::bytes(snapshot.available_memory)
+            ),
+        });
+    }
+
+    /// Warn about lossy latency targets, naming Wi-Fi power saving when it is the likely cause
+    fn check_latency(snapshot: &SystemSnapshot, out: &mut Vec<Alert>) {
+        let power_saving: Vec<&str> = snapshot
+            .networks
+            .iter()
+            .filter(|net| net.power_save == Some(true))
+            .map(|net| net.name.as_str())
+            .collect();
+        for probe in snapshot.probes.iter().filter(|probe| probe.is_degraded()) {
+            let loss = probe.loss_percent();
+            let mut message = format!("{} losing {:.0}% of probes", probe.label, loss);
+            if !power_saving.is_empty() {
+                message.push_str(&format!(
+                    "; Wi-Fi power saving on {}",
+                    power_saving.join(", ")
+                ));
+            }
+            out.push(Alert {
+                key: format!("latency:{}", probe.label),
+                severity: if loss >= 50.0 {
+                    AlertSeverity::Critical
+                } else {
+                    AlertSeverity::Warning
+                },
+                message,
+            });
+        }
+    }
+
+    /// Warn as each GPU nears the temperature it throttles at
+    fn check_gpu_temperature(snapshot: &SystemSnapshot, config: &AppConfig, out: &mut Vec<Alert>) {
+        for gpu in &snapshot.gpus {
+            let Some(temp) = gpu.temp else {
+                continue;
+            };
+            let (warning, critical) = GpuTempLimit::for_gpu(&config.gpu_temp_limits, gpu);
+            let severity = if temp >= critical {
+                AlertSeverity::Critical
+            } else if temp >= warning {
+                AlertSeverity::Warning
+            } else {
+                continue;
+            };
+            out.push(Alert {
+                key: format!("gpu-temp:{}", gpu.name),
+                severity,
+                message: format!("{} at {:.0}°C (limit {:.0}°C)", gpu.name, temp, critical),
+            });
+        }
+    }
+
+    /// Flag network mounts whose server stopped answering or answers slowly
+    fn check_remote_mounts(snapshot: &SystemSnapshot, out: &mut Vec<Alert>) {
+        for disk in &snapshot.disks {
+            let Some(remote) = disk.remote else {
+                continue;
+            };
+            let (severity, message) = if let Some(secs) = remote.stale_secs {
+                (
+                    AlertSeverity::Critical,
+                    format!(
+                        "{} not responding for {}",
+                        disk.name,
+                        crate::format::duration(secs)
+                    ),
+                )
+            } else if remote.is_slow() {
+                (
+                    AlertSeverity::Warning,
+                    format!(
+                        "{} slow to respond ({:.0}ms)",
+                        disk.name,
+                        remote.latency_ms.unwrap_or_default()
+                    ),
+                )
+            } else {
+                continue;
+            };
+            out.push(Alert {
+                key: format!("disk-stale:{}", disk.name),
+                severity,
+                message,
+            });
+        }
+    }
+
+    fn check_disk_fill(snapshot: &SystemSnapshot, config: &AppConfig, out: &mut Vec<Alert>) {
+        let horizon = config.disk_full_horizon_hours.saturating_mul(3600);
+        if horizon == 0 {
+            return;
+        }
+
+        for disk in &snapshot.disks {
+            let Some(eta) = disk.time_to_full else {
+                continue;
+            };
+            if eta >= horizon {
+                continue;
+            }
+            let severity = if eta < horizon / 4 {
+                AlertSeverity::Critical
+            } else {
+                AlertSeverity::Warning
+            };
+            out.push(Alert {
+                key: format!("disk-full:{}", disk.name),
+                severity,
+                message: format!("{} full in {}", disk.name, crate::format::eta(eta)),
+            });
+        }
+    }
+}
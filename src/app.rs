@@ -1,33 +1,77 @@
 use crate::data::snapshot::{ColorScheme, ProcessSortBy, SystemSnapshot};
 use crate::{
     action::Action,
+    alerts::{Alert, AlertEngine, AlertEvent, AlertSeverity},
+    compare::{Bookmark, SnapshotDiff},
     components::{
-        cpu::CpuComponent, disk::DiskComponent, gpu::GpuComponent, memory::MemoryComponent,
-        network::NetworkComponent, process::ProcessComponent, Component,
+        compare::ComparePopup,
+        core_detail::CoreProcessesPopup,
+        cpu::CpuComponent,
+        cursor::GraphCursor,
+        disk::DiskComponent,
+        disk_detail::DiskDetailPopup,
+        events::EventTimeline,
+        gpu::GpuComponent,
+        history::HistoryView,
+        inhibitors::InhibitorsPopup,
+        interface_picker::InterfacePicker,
+        memory::MemoryComponent,
+        network::NetworkComponent,
+        process::ProcessComponent,
+        stats::StatsPopup,
+        tools::{MenuEntry, ToolMenu},
+        Component,
     },
     config::AppConfig,
-    data::DataManager,
+    data::{
+        collector::DataCollector,
+        probes::ProbeTarget,
+        speedtest::{SharedSpeedTest, SpeedTestEndpoint, SpeedTestStatus},
+        Collector, CollectorCommand, DataManager,
+    },
+    layout::{PaneBorder, Panel, PanelLayout},
+    rollups::Rollups,
+    saved_history::SavedHistory,
+    stats::SessionStats,
+    status_line,
     theme::Theme,
     tui::Tui,
 };
 use crossterm::event::{Event as CrosstermEvent, KeyEvent, KeyEventKind, MouseEvent};
+use ratatui::{backend::TestBackend, buffer::Buffer, Terminal};
 use std::time::Duration;
 use tokio::sync::mpsc;
 
 pub struct App {
     pub should_quit: bool,
-    pub tui: Tui,
     pub cpu_component: CpuComponent,
     pub gpu_component: GpuComponent,
     pub memory_component: MemoryComponent,
     pub network_component: NetworkComponent,
     pub disk_component: DiskComponent,
     pub process_component: ProcessComponent,
+    #[cfg(feature = "ebpf")]
+    pub offenders_popup: crate::components::offenders::OffendersPopup,
+    pub tool_menu: ToolMenu,
+    pub stats_popup: StatsPopup,
+    pub disk_detail: DiskDetailPopup,
+    pub core_processes: CoreProcessesPopup,
+    pub inhibitors: InhibitorsPopup,
+    pub interface_picker: InterfacePicker,
+    pub event_timeline: EventTimeline,
+    pub history_view: HistoryView,
+    pub compare_popup: ComparePopup,
 
     #[allow(dead_code)]
     pub theme: Theme,
-    #[allow(dead_code)]
-    pub data_manager: DataManager,
+    pub config: AppConfig,
+    pub alerts: AlertEngine,
+    /// Min/avg/max of collected snapshots since startup
+    pub session_stats: SessionStats,
+    /// Hourly averages across sessions; only `App::new` attaches the on-disk copy
+    pub rollups: Rollups,
+    /// Snapshot the compare view diffs the live one against
+    pub bookmark: Option<Bookmark>,
     pub snapshot: crate::data::snapshot::SystemSnapshot,
     #[allow(dead_code)]
     pub tick_rate: Duration,
@@ -37,20 +81,101 @@ pub struct App {
     target_snapshot: crate::data::snapshot::SystemSnapshot,
     interpolation_factor: f32,
     last_update_time: std::time::Instant,
+
+    // Adaptive polling: intervals are stretched on battery power or when nobody is interacting
+    collector_tx: Option<mpsc::UnboundedSender<CollectorCommand>>,
+    last_input_time: std::time::Instant,
+    poll_multiplier: f64,
+
+    // Attention on critical alerts: a bell rung by the run loop and a brief inverted frame
+    bell_pending: bool,
+    flash_until: Option<std::time::Instant>,
+    /// Slow link: fewer frames, no interpolation and flat gradients
+    low_bandwidth: bool,
+    /// `--read-only`: destructive actions are refused before they reach a component
+    read_only: bool,
+
+    // Own overhead: render time of the last frame, shown with collect time in debug mode
+    last_render_ms: f64,
+    last_frame_bytes: usize,
+    show_self_debug: bool,
+
+    // Side panel placement, reorderable from the keyboard or by dragging title bars
+    panel_layout: PanelLayout,
+    panel_areas: Vec<(Panel, ratatui::layout::Rect)>,
+    moving_panel: Option<Panel>,
+    dragged_panel: Option<Panel>,
+    /// Left panels, process list and right panels as last rendered
+    column_areas: Vec<ratatui::layout::Rect>,
+    /// Border being dragged to resize the panes next to it
+    resizing: Option<PaneBorder>,
+    /// Panel whose keys the bottom bar shows; `None` is the process list
+    focused_panel: Option<Panel>,
+    /// External tool to run once the event loop can hand it the terminal
+    pending_command: Option<Vec<String>>,
+    /// Text to put on the clipboard through the terminal at the next frame
+    pending_clipboard: Option<String>,
+    /// On-demand bandwidth test, kept apart from the passively collected snapshot
+    speed_test: SharedSpeedTest,
+    /// Process the collector was last asked to break down open files for
+    inspected_pid: Option<u32>,
+    /// Process the collector was last asked to keep recent CPU% samples for
+    selected_pid: Option<u32>,
+    /// Collector built from the config by `new`, restored graphs included, handed to the
+    /// polling task on start
+    collector: Option<DataCollector>,
 }
 
 impl App {
+    /// How long the UI stays inverted when `alert_bell` flashes
+    const FLASH_DURATION: Duration = Duration::from_millis(300);
+
     pub async fn new(tick_rate: Duration) -> Result<Self, Box<dyn std::error::Error>> {
-        let tui = Tui::new()?;
+        let config = AppConfig::load().unwrap_or_default();
+        let mut collector = Self::configured_collector(&config);
+
+        let restored = config
+            .persist_history
+            .then(|| SavedHistory::load(chrono::Local::now().timestamp()))
+            .flatten();
+        if let Some(saved) = restored {
+            collector.restore_history(saved);
+        }
+
+        let mut app = Self::with_collector(&mut collector, config, tick_rate);
+        app.rollups = Rollups::load();
+        app.collector = Some(collector);
+        Ok(app)
+    }
+
+    /// Apply the language and number format from `config` and build a collector honoring it
+    pub fn configured_collector(config: &AppConfig) -> DataCollector {
+        crate::i18n::init(&config.language);
+        crate::format::init(config.unit_base, &config.decimal_separator);
+        let mut collector = DataCollector::new();
+        collector.set_command_limits(config.cmd_max_args, config.exe_max_len);
+        collector.set_leak_window(Duration::from_secs(config.leak_window_mins * 60));
+        collector.set_sensor_overrides(config.sensors.clone());
+        collector.set_gpu_filter(config.gpus.clone());
+        collector
+    }
 
-        // Initialize data manager
-        let mut data_manager = DataManager::new(1000); // 1 second update interval
+    /// Render one frame of the given size off-screen, for `--once`
+    pub fn render_buffer(&mut self, width: u16, height: u16) -> std::io::Result<Buffer> {
+        let mut terminal = Terminal::new(TestBackend::new(width, height))?;
+        terminal.draw(|f| self.render(f))?;
+        Ok(terminal.backend().buffer().clone())
+    }
 
+    /// Build the app from an initial snapshot of `collector`, without touching the terminal
+    pub fn with_collector(
+        collector: &mut dyn Collector,
+        config: AppConfig,
+        tick_rate: Duration,
+    ) -> Self {
         // Get initial snapshot
-        let mut snapshot = data_manager.collector.collect();
-        if let Some(config) = AppConfig::load() {
-            snapshot.color_scheme = Theme::canonicalize_color_scheme(config.color_scheme);
-        }
+        let mut snapshot = collector.collect();
+        snapshot.color_scheme = Theme::canonicalize_color_scheme(config.color_scheme);
 
         // Initialize theme
         let theme = Theme::new(snapshot.color_scheme);
@@ -61,24 +186,57 @@ impl App {
         let theme_clone = theme.clone();
 
         let cpu_component = CpuComponent::new(snapshot_clone.clone(), theme_clone.clone());
-        let gpu_component = GpuComponent::new(snapshot_clone.clone(), theme_clone.clone());
+        let mut gpu_component = GpuComponent::new(snapshot_clone.clone(), theme_clone.clone());
+        gpu_component.primary_gpu = config.primary_gpu.clone();
+        gpu_component.temp_limits = config.gpu_temp_limits.clone();
         let memory_component = MemoryComponent::new(snapshot_clone.clone(), theme_clone.clone());
-        let network_component = NetworkComponent::new(snapshot_clone.clone(), theme_clone.clone());
-        let disk_component = DiskComponent::new(snapshot_clone.clone(), theme_clone.clone());
-        let process_component = ProcessComponent::new(snapshot_clone, theme_clone);
+        let mut network_component =
+            NetworkComponent::new(snapshot_clone.clone(), theme_clone.clone());
+        network_component.nicknames = config.interface_names.clone();
+        let mut disk_component = DiskComponent::new(snapshot_clone.clone(), theme_clone.clone());
+        disk_component.full_horizon_secs = config.disk_full_horizon_hours.saturating_mul(3600);
+        let core_processes = CoreProcessesPopup::new(snapshot_clone.clone(), theme_clone.clone());
+        let mut interface_picker =
+            InterfacePicker::new(snapshot_clone.clone(), theme_clone.clone());
+        interface_picker.nicknames = config.interface_names.clone();
+        let mut process_component = ProcessComponent::new(snapshot_clone, theme_clone);
+        process_component.privacy_mode = config.privacy_mode;
+        process_component.irix_mode = config.irix_mode;
+        process_component.hide_idle = config.hide_idle_processes;
+        process_component.idle_cpu_threshold = config.idle_process_cpu_percent;
+        process_component.idle_memory_threshold = config.idle_process_memory_mb * 1024 * 1024;
+        process_component.elevate_with = config.elevate_with.clone();
+        process_component.protected_processes = config.protected_processes.clone();
+        let panel_layout = PanelLayout::new(&config.panel_order)
+            .with_sizes(&config.column_widths, &config.panel_heights)
+            .with_hidden(&config.hidden_panels);
 
-        Ok(Self {
+        Self {
             should_quit: false,
-            tui,
             cpu_component,
             gpu_component,
             memory_component,
             network_component,
             disk_component,
             process_component,
+            #[cfg(feature = "ebpf")]
+            offenders_popup: crate::components::offenders::OffendersPopup::new(theme.clone()),
+            tool_menu: ToolMenu::new(config.external_tools.clone(), theme.clone()),
+            stats_popup: StatsPopup::new(theme.clone()),
+            disk_detail: DiskDetailPopup::new(theme.clone()),
+            core_processes,
+            inhibitors: InhibitorsPopup::new(theme.clone()),
+            interface_picker,
+            event_timeline: EventTimeline::new(theme.clone()),
+            history_view: HistoryView::new(theme.clone()),
+            compare_popup: ComparePopup::new(theme.clone()),
 
             theme,
-            data_manager,
+            config,
+            alerts: AlertEngine::new(),
+            session_stats: SessionStats::new(chrono::Local::now()),
+            bookmark: None,
+            rollups: Rollups::default(),
             snapshot: snapshot.clone(),
             tick_rate,
 
@@ -87,21 +245,79 @@ impl App {
             target_snapshot: snapshot.clone(),
             interpolation_factor: 1.0,
             last_update_time: std::time::Instant::now(),
-        })
+
+            collector_tx: None,
+            last_input_time: std::time::Instant::now(),
+            poll_multiplier: 1.0,
+
+            bell_pending: false,
+            flash_until: None,
+            low_bandwidth: false,
+            read_only: false,
+
+            last_render_ms: 0.0,
+            last_frame_bytes: 0,
+            show_self_debug: false,
+
+            panel_layout,
+            panel_areas: Vec::new(),
+            moving_panel: None,
+            dragged_panel: None,
+            column_areas: Vec::new(),
+            resizing: None,
+            focused_panel: None,
+            pending_command: None,
+            pending_clipboard: None,
+            speed_test: SharedSpeedTest::default(),
+            inspected_pid: None,
+            selected_pid: None,
+            collector: None,
+        }
     }
 
     pub async fn run(&mut self) -> Result<(), Box<dyn std::error::Error>> {
+        let mut tui = Tui::new()?;
+
         // Create channels for communication
         let (snapshot_tx, mut snapshot_rx) = mpsc::unbounded_channel::<SystemSnapshot>();
 
+        let (collector_tx, collector_rx) = mpsc::unbounded_channel::<CollectorCommand>();
+        self.collector_tx = Some(collector_tx);
+
         // Start data collection in the background
-        let mut data_manager = DataManager::new(self.snapshot.update_interval);
+        let collector = self
+            .collector
+            .take()
+            .unwrap_or_else(|| Self::configured_collector(&self.config));
+        let mut data_manager = DataManager::new(collector, self.snapshot.update_interval);
+        let mut probe_targets: Vec<ProbeTarget> = self
+            .config
+            .latency_targets
+            .iter()
+            .cloned()
+            .map(ProbeTarget::Ping)
+            .collect();
+        let dns_host = self.config.dns_probe_host.trim();
+        if !dns_host.is_empty() {
+            probe_targets.push(ProbeTarget::Dns(dns_host.to_string()));
+        }
+        if !probe_targets.is_empty() {
+            let probes = crate::data::probes::spawn_probes(
+                probe_targets,
+                Duration::from_secs(self.config.latency_interval_secs.max(1)),
+            );
+            data_manager.collector.set_probe_source(probes);
+        }
         tokio::spawn(async move {
-            data_manager.start_polling(snapshot_tx).await;
+            data_manager.start_polling(snapshot_tx, collector_rx).await;
         });
 
-        // Set up high-frequency polling for smooth animations
-        let frame_duration = Duration::from_millis(16); // ~60 FPS
+        // Set up high-frequency polling for smooth animations, unless every byte counts
+        let base_frame_duration = if self.low_bandwidth {
+            Duration::from_millis(250)
+        } else {
+            Duration::from_millis(16) // ~60 FPS
+        };
 
         // Track if we need to redraw the UI
         let mut needs_redraw = true;
@@ -110,14 +326,29 @@ impl App {
         loop {
             // Receive new snapshots first
             let mut new_snapshot_received = false;
-            while let Ok(mut new_snapshot) = snapshot_rx.try_recv() {
-                self.apply_ui_state_to_snapshot(&mut new_snapshot);
-                // Interpolate from the currently displayed values to the new target.
-                self.snapshot = self.interpolated_snapshot.clone();
-                self.target_snapshot = new_snapshot;
-                self.interpolation_factor = 0.0;
+            while let Ok(new_snapshot) = snapshot_rx.try_recv() {
+                self.receive_snapshot(new_snapshot);
                 new_snapshot_received = true;
             }
+            // A re-sort can move another process under the cursor
+            if new_snapshot_received {
+                self.update_selected_process();
+            }
+            if std::mem::take(&mut self.bell_pending) {
+                tui.bell()?;
+            }
+            // Keep drawing while the flash is up and once more to clear it
+            let flashing = self.flash_until.is_some();
+            if self
+                .flash_until
+                .is_some_and(|until| std::time::Instant::now() >= until)
+            {
+                self.flash_until = None;
+            }
+
+            self.update_poll_multiplier();
+            self.update_inspected_process();
+            let frame_duration = base_frame_duration.mul_f64(self.poll_multiplier);
 
             // Update interpolation factor based on time elapsed
             let now = std::time::Instant::now();
@@ -136,16 +367,19 @@ impl App {
             let current_hash = self.calculate_snapshot_hash();
 
             // Only redraw if the snapshot has changed significantly or we received a new snapshot
-            if new_snapshot_received || last_snapshot_hash.map_or(true, |last| last != current_hash)
+            if new_snapshot_received
+                || flashing
+                || last_snapshot_hash.map_or(true, |last| last != current_hash)
             {
                 // Draw UI - need to separate this to avoid borrowing issues
-                self.draw_frame()?;
+                self.draw_frame(&mut tui)?;
                 last_snapshot_hash = Some(current_hash);
                 needs_redraw = false;
             }
 
             // Handle events
             if crossterm::event::poll(frame_duration)? {
+                self.last_input_time = std::time::Instant::now();
                 match crossterm::event::read()? {
                     CrosstermEvent::Key(key) => {
                         // Ignore key release events to avoid double-handling keys like Esc.
@@ -164,7 +398,7 @@ impl App {
                     }
                     CrosstermEvent::Resize(width, height) => {
                         // Force redraw on resize
-                        self.tui.resize(width, height)?;
+                        tui.resize(width, height)?;
                         needs_redraw = true;
                     }
                     CrosstermEvent::FocusGained
@@ -174,9 +408,18 @@ impl App {
                     }
                 }
 
+                if let Some(argv) = self.pending_command.take() {
+                    self.run_external_command(&mut tui, &argv)?;
+                    needs_redraw = true;
+                }
+                if let Some(text) = self.pending_clipboard.take() {
+                    tui.copy_to_clipboard(&text)?;
+                }
+                self.update_selected_process();
+
                 // Redraw if needed after handling events
                 if needs_redraw {
-                    self.draw_frame()?;
+                    self.draw_frame(&mut tui)?;
                     last_snapshot_hash = Some(self.calculate_snapshot_hash());
                     needs_redraw = false;
                 }
@@ -191,10 +434,141 @@ impl App {
             }
         }
 
-        self.tui.exit()?;
+        tui.exit()?;
+        // Keep the hour in progress for the next session
+        let _ = self.rollups.save();
+        if self.config.persist_history {
+            let _ = SavedHistory::from_snapshot(
+                &self.target_snapshot,
+                chrono::Local::now().timestamp(),
+            )
+            .save();
+        }
         Ok(())
     }
 
+    /// Take a freshly collected snapshot as the new interpolation target
+    fn receive_snapshot(&mut self, mut new_snapshot: SystemSnapshot) {
+        self.apply_ui_state_to_snapshot(&mut new_snapshot);
+        crate::crash::record_snapshot(&new_snapshot);
+        let turned_critical = self
+            .alerts
+            .update(&new_snapshot, &self.config, chrono::Local::now());
+        if turned_critical {
+            self.bell_pending |= self.config.alert_bell.rings();
+            if self.config.alert_bell.flashes() {
+                self.flash_until = Some(std::time::Instant::now() + Self::FLASH_DURATION);
+            }
+        }
+        self.session_stats
+            .observe(&new_snapshot, chrono::Local::now());
+        self.process_component.observe(&new_snapshot);
+        if self
+            .rollups
+            .observe(&new_snapshot, chrono::Local::now().timestamp())
+        {
+            // Losing the last hour to a crash is fine; writing every sample is not
+            let _ = self.rollups.save();
+        }
+        // Keep inspected graph points on the same sample as the history scrolls
+        let cpu_len = new_snapshot.cpu_history.first().map_or(0, |h| h.len());
+        self.cpu_component.cursor.advance(cpu_len);
+        self.memory_component
+            .cursor
+            .advance(new_snapshot.memory_history.len());
+        self.network_component
+            .cursor
+            .advance(new_snapshot.network_history.len());
+        // Interpolate from the currently displayed values to the new target.
+        self.snapshot = self.interpolated_snapshot.clone();
+        self.target_snapshot = new_snapshot;
+        self.refresh_snapshot_diff();
+        // Animating towards the new values costs a frame per step; jump there on slow links
+        self.interpolation_factor = if self.low_bandwidth { 1.0 } else { 0.0 };
+    }
+
+    /// Diff the bookmark against the latest snapshot, only while the compare popup shows it
+    fn refresh_snapshot_diff(&mut self) {
+        if !self.compare_popup.visible {
+            return;
+        }
+        self.compare_popup.bookmarked_at = self.bookmark.as_ref().map(|bookmark| bookmark.at);
+        self.compare_popup.diff = self
+            .bookmark
+            .as_ref()
+            .map(|bookmark| SnapshotDiff::between(&bookmark.snapshot, &self.target_snapshot));
+    }
+
+    /// Trade smoothness for fewer bytes per frame, e.g. when running over SSH
+    pub fn set_low_bandwidth(&mut self, low_bandwidth: bool) {
+        self.low_bandwidth = low_bandwidth;
+        self.theme.simple_gradients = low_bandwidth;
+        self.sync_components();
+    }
+
+    pub fn set_read_only(&mut self, read_only: bool) {
+        self.read_only = read_only;
+        self.process_component.read_only = read_only;
+        self.tool_menu.read_only = read_only;
+    }
+
+    /// Have the collector inspect the process in the detail view, if any
+    fn update_inspected_process(&mut self) {
+        let pid = self.process_component.details_pid();
+        if pid == self.inspected_pid {
+            return;
+        }
+        self.inspected_pid = pid;
+        if let Some(tx) = &self.collector_tx {
+            let _ = tx.send(CollectorCommand::InspectProcess(pid));
+        }
+    }
+
+    /// Have the collector send the CPU% history of the highlighted process, which its graph
+    /// under the list shows
+    fn update_selected_process(&mut self) {
+        let pid = self
+            .process_component
+            .selected_process()
+            .map(|(pid, _)| pid);
+        if pid == self.selected_pid {
+            return;
+        }
+        self.selected_pid = pid;
+        if let Some(tx) = &self.collector_tx {
+            let _ = tx.send(CollectorCommand::SelectProcess(pid));
+        }
+    }
+
+    /// Recompute the polling slowdown and tell the collector when it changes
+    fn update_poll_multiplier(&mut self) {
+        let on_battery = self
+            .target_snapshot
+            .battery_info
+            .as_ref()
+            .and_then(|battery| battery.status.as_deref())
+            .is_some_and(|status| status.eq_ignore_ascii_case("discharging"));
+        let idle = self.config.idle_after_secs > 0
+            && self.last_input_time.elapsed().as_secs() >= self.config.idle_after_secs;
+
+        let mut multiplier = 1.0;
+        if on_battery {
+            multiplier *= self.config.battery_poll_multiplier;
+        }
+        if idle {
+            multiplier *= self.config.idle_poll_multiplier;
+        }
+        let multiplier = multiplier.max(1.0);
+
+        if multiplier != self.poll_multiplier {
+            self.poll_multiplier = multiplier;
+            if let Some(tx) = &self.collector_tx {
+                let interval = Duration::from_millis(self.snapshot.update_interval);
+                let _ = tx.send(CollectorCommand::SetInterval(interval.mul_f64(multiplier)));
+            }
+        }
+    }
+
     /// Perform linear interpolation between current and target snapshots
     fn interpolate_snapshots(&mut self) {
         // Start from target so non-interpolated fields (process list, disks, networks, histories)
@@ -338,11 +712,128 @@ impl App {
         hasher.finish()
     }
 
+    /// Spans for one `{field}` of the status template; empty for unknown fields and for data
+    /// this machine does not have, like a battery
+    fn status_field(
+        field: &str,
+        s: &SystemSnapshot,
+        theme: &Theme,
+        clock_format: &str,
+        session: &SessionStats,
+    ) -> Vec<ratatui::text::Span<'static>> {
+        use crate::widgets::gauge::{SegmentColor, StackedBar};
+        use ratatui::{
+            style::{Color, Modifier, Style},
+            text::Span,
+        };
+
+        let styled = |text: String, color: Color| {
+            vec![Span::styled(
+                text,
+                Style::default()
+                    .fg(theme.get_color(color))
+                    .bg(theme.get_color(Color::DarkGray)),
+            )]
+        };
+        match field {
+            "hostname" => styled(format!("host:{}", s.hostname), Color::White),
+            "uptime" => styled(format!("uptime:{}", s.uptime), Color::White),
+            "load" => styled(format!("load:{}", s.load_avg), Color::Yellow),
+            // Nothing counted yet before the first collection
+            "tasks" if s.task_totals.processes == 0 => Vec::new(),
+            "tasks" => {
+                let totals = &s.task_totals;
+                let mut text = format!("tasks:{}", totals.processes);
+                if let Some(threads) = totals.threads {
+                    text.push_str(&format!(" thr:{}", threads));
+                }
+                if let Some(open_files) = totals.open_files {
+                    text.push_str(&format!(" fd:{}", open_files));
+                }
+                styled(text, Color::LightCyan)
+            }
+            "time" => styled(
+                format!("time:{}", chrono::Local::now().format(clock_format)),
+                Color::Green,
+            ),
+            // Formatted like the running time in the session statistics
+            "session" => styled(
+                format!(
+                    "session:{}",
+                    crate::format::duration(session.running_secs(chrono::Local::now()))
+                ),
+                Color::Gray,
+            ),
+            "theme" => vec![Span::styled(
+                format!("theme:{}", Self::theme_name(s.color_scheme)),
+                Style::default()
+                    .fg(theme.get_color(Color::LightMagenta))
+                    .bg(theme.get_color(Color::DarkGray))
+                    .add_modifier(Modifier::BOLD),
+            )],
+            "ip" => match &s.primary_ip {
+                Some(ip) => styled(format!("ip:{}", ip), Color::LightCyan),
+                None => Vec::new(),
+            },
+            "self" => styled(
+                format!(
+                    "self cpu:{:.1}% mem:{}",
+                    s.self_usage.cpu_usage,
+                    crate::format::bytes(s.self_usage.memory)
+                ),
+                Color::Gray,
+            ),
+            "battery" => {
+                // Desktops report a placeholder "N/A" battery
+                let Some((level, battery)) = s
+                    .battery_info
+                    .as_ref()
+                    .filter(|battery| battery.status.as_deref() != Some("N/A"))
+                    .and_then(|battery| Some((battery.level?, battery)))
+                else {
+                    return Vec::new();
+                };
+                let color = if level > 50.0 {
+                    Color::Green
+                } else if level > 20.0 {
+                    Color::Yellow
+                } else {
+                    Color::Red
+                };
+                let charging = battery
+                    .status
+                    .as_deref()
+                    .is_some_and(|status| status.eq_ignore_ascii_case("charging"));
+                let bar = StackedBar::new(theme, 100.0)
+                    .segment(
+                        f64::from(level),
+                        SegmentColor::Solid(theme.get_color(color)),
+                        "",
+                    )
+                    .track(SegmentColor::Solid(theme.get_color(Color::Black)), "");
+                let text = Style::default()
+                    .fg(theme.get_color(Color::White))
+                    .bg(theme.get_color(Color::DarkGray));
+                let mut spans = vec![Span::styled("bat:", text)];
+                spans.extend(bar.spans(6));
+                spans.push(Span::styled(
+                    format!("{:.0}%{}", level, if charging { "+" } else { "" }),
+                    text,
+                ));
+                spans
+            }
+            _ => Vec::new(),
+        }
+    }
+
     fn render_top_status_line(
         f: &mut ratatui::Frame,
         area: ratatui::layout::Rect,
-        snapshot: &SystemSnapshot,
-        theme: &Theme,
+        (snapshot, session): (&SystemSnapshot, &SessionStats),
+        (theme, config): (&Theme, &AppConfig),
+        (alert, recovery): (Option<&Alert>, Option<&AlertEvent>),
+        (poll_multiplier, low_bandwidth, read_only): (f64, bool, bool),
+        (render_ms, frame_bytes): (Option<f64>, usize),
     ) {
         use ratatui::{
             style::{Color, Style},
@@ -350,11 +841,12 @@ impl App {
             widgets::Paragraph,
         };
 
-        let current_time = chrono::Local::now().format("%H:%M:%S").to_string();
-
         let s = snapshot;
-        let theme_name = Self::theme_name(s.color_scheme);
-        let status_line = Line::from(vec![
+        // The template's own text sits on the bar like the fields around it
+        let base = Style::default()
+            .fg(theme.get_color(Color::White))
+            .bg(theme.get_color(Color::DarkGray));
+        let mut status_line = Line::from(vec![
             Span::styled(
                 " RTOP ",
                 Style::default()
@@ -362,38 +854,105 @@ impl App {
                     .bg(theme.get_color(Color::Cyan))
                     .add_modifier(ratatui::style::Modifier::BOLD),
             ),
-            Span::styled(
-                format!("  host:{}  ", s.hostname),
+            Span::styled("  ", base),
+        ]);
+        let clock_format = status_line::clock_format(
+            &config.clock_format,
+            config.clock_show_date,
+            config.clock_show_timezone,
+        );
+        status_line.spans.extend(status_line::compose(
+            &status_line::parse(&config.status_template),
+            |field| Self::status_field(field, s, theme, &clock_format, session),
+            |text| Span::styled(text.to_string(), base),
+        ));
+        status_line.spans.push(Span::raw(" "));
+        if let Some(render_ms) = render_ms {
+            status_line.spans.push(Span::styled(
+                format!(
+                    "collect:{:.1}ms render:{:.1}ms out:{} ",
+                    s.self_usage.collect_ms,
+                    render_ms,
+                    crate::format::bytes(frame_bytes as u64)
+                ),
                 Style::default()
-                    .fg(theme.get_color(Color::White))
+                    .fg(theme.get_color(Color::LightCyan))
                     .bg(theme.get_color(Color::DarkGray)),
-            ),
-            Span::styled(
-                format!("uptime:{}  ", s.uptime),
+            ));
+        }
+
+        if poll_multiplier > 1.0 {
+            status_line.spans.push(Span::styled(
+                format!(" eco x{:.0} ", poll_multiplier),
                 Style::default()
-                    .fg(theme.get_color(Color::White))
+                    .fg(theme.get_color(Color::LightGreen))
                     .bg(theme.get_color(Color::DarkGray)),
-            ),
-            Span::styled(
-                format!("load:{}  ", s.load_avg),
+            ));
+        }
+        if low_bandwidth {
+            status_line.spans.push(Span::styled(
+                " low-bw ",
                 Style::default()
-                    .fg(theme.get_color(Color::Yellow))
+                    .fg(theme.get_color(Color::LightGreen))
                     .bg(theme.get_color(Color::DarkGray)),
-            ),
-            Span::styled(
-                format!("time:{} ", current_time),
+            ));
+        }
+        if read_only {
+            status_line.spans.push(Span::styled(
+                " read-only ",
                 Style::default()
-                    .fg(theme.get_color(Color::Green))
+                    .fg(theme.get_color(Color::LightYellow))
                     .bg(theme.get_color(Color::DarkGray)),
-            ),
-            Span::styled(
-                format!(" theme:{} ", theme_name),
+            ));
+        }
+        if s.elevated {
+            status_line.spans.push(Span::styled(
+                " # root ",
                 Style::default()
-                    .fg(theme.get_color(Color::LightMagenta))
-                    .bg(theme.get_color(Color::DarkGray))
+                    .fg(theme.get_color(Color::LightRed))
+                    .bg(theme.get_color(Color::DarkGray)),
+            ));
+        }
+
+        if let Some(alert) = alert {
+            let color = match alert.severity {
+                AlertSeverity::Critical => Color::Red,
+                AlertSeverity::Warning => Color::Yellow,
+            };
+            status_line.spans.push(Span::styled(
+                format!(" ⚠ {} ", alert.message),
+                Style::default()
+                    .fg(theme.get_color(Color::Black))
+                    .bg(theme.get_color(color))
                     .add_modifier(ratatui::style::Modifier::BOLD),
-            ),
-        ]);
+            ));
+        } else if let Some(recovery) = recovery {
+            status_line.spans.push(Span::styled(
+                format!(
+                    " ✓ cleared after {}: {} ",
+                    crate::format::duration(recovery.recovered_after.unwrap_or(0)),
+                    recovery.message
+                ),
+                Style::default()
+                    .fg(theme.get_color(Color::Black))
+                    .bg(theme.get_color(Color::Green))
+                    .add_modifier(ratatui::style::Modifier::BOLD),
+            ));
+        }
+
+        // One hint for everything permissions hid, instead of a "No perm" per reading
+        if !s.elevated && !s.restricted_sources.is_empty() {
+            status_line.spans.push(Span::styled(
+                format!(
+                    " {} ",
+                    crate::i18n::tr_args(
+                        "status-restricted-sources",
+                        &[("sources", &s.restricted_sources.join(", "))],
+                    )
+                ),
+                Style::default().fg(theme.get_color(Color::Gray)),
+            ));
+        }
 
         let block = Paragraph::new(status_line).style(
             Style::default()
@@ -408,7 +967,9 @@ impl App {
         area: ratatui::layout::Rect,
         theme: &Theme,
         active_scheme: ColorScheme,
+        (focus_label, hints): (&str, &[(&'static str, &'static str)]),
     ) {
+        use crate::i18n::tr;
         use ratatui::{
             style::{Color, Style},
             text::{Line, Span},
@@ -416,41 +977,36 @@ impl App {
         };
 
         let theme_name = Self::theme_name(active_scheme);
-        let keybinds_line = Line::from(vec![
+        let mut spans = vec![
             Span::styled(
-                " [q] quit ",
+                format!(" [q] {} ", tr("hint-quit")),
                 Style::default().fg(theme.get_color(Color::Red)),
             ),
             Span::styled(
-                " [↑/↓] move ",
+                format!(" [Tab] {} ", tr("hint-focus")),
                 Style::default().fg(theme.get_color(Color::Green)),
             ),
             Span::styled(
-                " [s] sort ",
-                Style::default().fg(theme.get_color(Color::Cyan)),
-            ),
-            Span::styled(
-                " [S] search ",
-                Style::default().fg(theme.get_color(Color::LightCyan)),
-            ),
-            Span::styled(
-                " [k] kill ",
-                Style::default().fg(theme.get_color(Color::LightRed)),
-            ),
-            Span::styled(
-                " [T] tree ",
-                Style::default().fg(theme.get_color(Color::Magenta)),
+                format!(" {}: ", focus_label),
+                Style::default()
+                    .fg(theme.get_color(Color::White))
+                    .add_modifier(ratatui::style::Modifier::BOLD),
             ),
+        ];
+        // Keys of the focused panel, between the global quit/focus and theme/save keys
+        spans.extend(hints.iter().map(|(key, description)| {
             Span::styled(
-                " [i] interface ",
-                Style::default().fg(theme.get_color(Color::LightBlue)),
-            ),
+                format!("[{}] {} ", key, tr(description)),
+                Style::default().fg(theme.get_color(Color::Cyan)),
+            )
+        }));
+        spans.extend([
             Span::styled(
-                " [t] theme ",
+                format!(" [t] {} ", tr("hint-theme")),
                 Style::default().fg(theme.get_color(Color::Yellow)),
             ),
             Span::styled(
-                " [w] save ",
+                format!(" [w] {} ", tr("hint-save")),
                 Style::default().fg(theme.get_color(Color::LightGreen)),
             ),
             Span::styled(
@@ -460,6 +1016,7 @@ impl App {
                     .add_modifier(ratatui::style::Modifier::BOLD),
             ),
         ]);
+        let keybinds_line = Line::from(spans);
 
         let block = Paragraph::new(keybinds_line).style(
             Style::default()
@@ -473,23 +1030,149 @@ impl App {
         &mut self,
         key: KeyEvent,
     ) -> Result<Option<Action>, Box<dyn std::error::Error>> {
+        if self.process_component.is_typed_kill_confirm_active() {
+            return match key.code {
+                crossterm::event::KeyCode::Tab | crossterm::event::KeyCode::BackTab => {
+                    Ok(Some(Action::ToggleProcessKillChoice))
+                }
+                crossterm::event::KeyCode::Up => Ok(Some(Action::CycleKillSignal(false))),
+                crossterm::event::KeyCode::Down => Ok(Some(Action::CycleKillSignal(true))),
+                crossterm::event::KeyCode::Char(c) => Ok(Some(Action::UpdateKillConfirmation(c))),
+                crossterm::event::KeyCode::Backspace => Ok(Some(Action::BackspaceKillConfirmation)),
+                crossterm::event::KeyCode::Enter => Ok(Some(Action::ConfirmProcessKill)),
+                crossterm::event::KeyCode::Esc => Ok(Some(Action::CancelProcessKill)),
+                _ => Ok(None),
+            };
+        }
         if self.process_component.is_kill_confirm_active() {
             return match key.code {
                 crossterm::event::KeyCode::Left
                 | crossterm::event::KeyCode::Right
                 | crossterm::event::KeyCode::Tab
                 | crossterm::event::KeyCode::BackTab => Ok(Some(Action::ToggleProcessKillChoice)),
+                crossterm::event::KeyCode::Up => Ok(Some(Action::CycleKillSignal(false))),
+                crossterm::event::KeyCode::Down => Ok(Some(Action::CycleKillSignal(true))),
                 crossterm::event::KeyCode::Enter => Ok(Some(Action::ConfirmProcessKill)),
                 crossterm::event::KeyCode::Esc => Ok(Some(Action::CancelProcessKill)),
                 _ => Ok(None),
             };
         }
 
+        #[cfg(feature = "ebpf")]
+        if self.offenders_popup.visible {
+            return match key.code {
+                crossterm::event::KeyCode::Esc | crossterm::event::KeyCode::Char('O') => {
+                    Ok(Some(Action::ToggleOffenders))
+                }
+                crossterm::event::KeyCode::Char('q') => Ok(Some(Action::Quit)),
+                _ => Ok(None),
+            };
+        }
+
+        if self.history_view.visible {
+            return match key.code {
+                crossterm::event::KeyCode::Esc | crossterm::event::KeyCode::Char('H') => {
+                    Ok(Some(Action::ToggleHistoryView))
+                }
+                crossterm::event::KeyCode::Tab => Ok(Some(Action::CycleHistoryRange)),
+                crossterm::event::KeyCode::Char('q') => Ok(Some(Action::Quit)),
+                _ => Ok(None),
+            };
+        }
+
+        if self.compare_popup.visible {
+            return match key.code {
+                crossterm::event::KeyCode::Esc | crossterm::event::KeyCode::Char('d') => {
+                    Ok(Some(Action::ToggleSnapshotDiff))
+                }
+                crossterm::event::KeyCode::Char('v') => Ok(Some(Action::BookmarkSnapshot)),
+                crossterm::event::KeyCode::Char('q') => Ok(Some(Action::Quit)),
+                _ => Ok(None),
+            };
+        }
+
+        if self.event_timeline.visible {
+            return match key.code {
+                crossterm::event::KeyCode::Esc | crossterm::event::KeyCode::Char('E') => {
+                    Ok(Some(Action::ToggleEventTimeline))
+                }
+                crossterm::event::KeyCode::Char('q') => Ok(Some(Action::Quit)),
+                _ => Ok(None),
+            };
+        }
+
+        if self.disk_detail.visible {
+            return match key.code {
+                crossterm::event::KeyCode::Esc | crossterm::event::KeyCode::Enter => {
+                    Ok(Some(Action::ToggleDiskDetail))
+                }
+                crossterm::event::KeyCode::Char('q') => Ok(Some(Action::Quit)),
+                _ => Ok(None),
+            };
+        }
+
+        if self.core_processes.visible {
+            return match key.code {
+                crossterm::event::KeyCode::Esc | crossterm::event::KeyCode::Enter => {
+                    Ok(Some(Action::ToggleCoreProcesses))
+                }
+                crossterm::event::KeyCode::Char('q') => Ok(Some(Action::Quit)),
+                _ => Ok(None),
+            };
+        }
+
+        if self.inhibitors.visible {
+            return match key.code {
+                crossterm::event::KeyCode::Esc | crossterm::event::KeyCode::Char('Z') => {
+                    Ok(Some(Action::ToggleInhibitors))
+                }
+                crossterm::event::KeyCode::Char('q') => Ok(Some(Action::Quit)),
+                _ => Ok(None),
+            };
+        }
+
+        if self.interface_picker.visible {
+            return match key.code {
+                crossterm::event::KeyCode::Up => Ok(Some(Action::MoveInterfaceSelection(false))),
+                crossterm::event::KeyCode::Down => Ok(Some(Action::MoveInterfaceSelection(true))),
+                crossterm::event::KeyCode::Enter => Ok(Some(Action::SelectNetworkInterface)),
+                crossterm::event::KeyCode::Esc | crossterm::event::KeyCode::Char('i') => {
+                    Ok(Some(Action::ToggleInterfacePicker))
+                }
+                crossterm::event::KeyCode::Char('q') => Ok(Some(Action::Quit)),
+                _ => Ok(None),
+            };
+        }
+
+        if self.stats_popup.visible {
+            return match key.code {
+                crossterm::event::KeyCode::Esc | crossterm::event::KeyCode::Char('I') => {
+                    Ok(Some(Action::ToggleSessionStats))
+                }
+                crossterm::event::KeyCode::Char('e') => Ok(Some(Action::ExportSessionStats)),
+                crossterm::event::KeyCode::Char('q') => Ok(Some(Action::Quit)),
+                _ => Ok(None),
+            };
+        }
+
+        if self.tool_menu.visible {
+            return match key.code {
+                crossterm::event::KeyCode::Up => Ok(Some(Action::MoveToolSelection(false))),
+                crossterm::event::KeyCode::Down => Ok(Some(Action::MoveToolSelection(true))),
+                crossterm::event::KeyCode::Enter => Ok(Some(Action::LaunchTool)),
+                crossterm::event::KeyCode::Esc
+                | crossterm::event::KeyCode::Char('x')
+                | crossterm::event::KeyCode::Char('m') => Ok(Some(Action::CloseToolMenu)),
+                _ => Ok(None),
+            };
+        }
+
         if self.process_component.is_search_mode() {
             return match key.code {
                 crossterm::event::KeyCode::Esc => Ok(Some(Action::CancelProcessSearch)),
                 crossterm::event::KeyCode::Enter => Ok(Some(Action::ConfirmProcessSearch)),
                 crossterm::event::KeyCode::Backspace => Ok(Some(Action::BackspaceProcessSearch)),
+                crossterm::event::KeyCode::Tab => Ok(Some(Action::CycleSearchMatchMode)),
                 crossterm::event::KeyCode::Char(c) => {
                     if key
                         .modifiers
@@ -505,21 +1188,74 @@ impl App {
             };
         }
 
+        if self.moving_panel.is_some() {
+            return match key.code {
+                crossterm::event::KeyCode::Tab => Ok(Some(Action::SelectNextPanel)),
+                crossterm::event::KeyCode::Up | crossterm::event::KeyCode::Left => {
+                    Ok(Some(Action::MovePanel(false)))
+                }
+                crossterm::event::KeyCode::Down | crossterm::event::KeyCode::Right => {
+                    Ok(Some(Action::MovePanel(true)))
+                }
+                crossterm::event::KeyCode::Enter
+                | crossterm::event::KeyCode::Esc
+                | crossterm::event::KeyCode::Char('M') => Ok(Some(Action::TogglePanelMoveMode)),
+                crossterm::event::KeyCode::Char('q') => Ok(Some(Action::Quit)),
+                _ => Ok(None),
+            };
+        }
+
         match key.code {
+            crossterm::event::KeyCode::Esc
+                if self.graph_cursor().is_some_and(|c| c.is_active()) =>
+            {
+                Ok(Some(Action::ClearGraphCursor))
+            }
+            crossterm::event::KeyCode::Esc | crossterm::event::KeyCode::Backspace
+                if self.process_component.is_detail_view_open() =>
+            {
+                Ok(Some(Action::Back))
+            }
+            crossterm::event::KeyCode::Esc if self.process_component.marked_count() > 0 => {
+                Ok(Some(Action::ClearProcessMarks))
+            }
             crossterm::event::KeyCode::Char('q') | crossterm::event::KeyCode::Esc => {
                 Ok(Some(Action::Quit))
             }
+            // With the process list focused the arrows fold the tree; on a graph they move its cursor
+            crossterm::event::KeyCode::Left | crossterm::event::KeyCode::Right
+                if self.focused_panel.is_none() && self.process_component.show_tree =>
+            {
+                Ok(Some(Action::CollapseProcessBranch(
+                    key.code == crossterm::event::KeyCode::Left,
+                )))
+            }
+            crossterm::event::KeyCode::Left => Ok(Some(Action::MoveGraphCursor(false))),
+            crossterm::event::KeyCode::Right => Ok(Some(Action::MoveGraphCursor(true))),
             crossterm::event::KeyCode::Char('c') => Ok(Some(Action::ToggleAutoUpdate)),
             crossterm::event::KeyCode::Char('+') => Ok(Some(Action::IncreaseSpeed)),
             crossterm::event::KeyCode::Char('-') => Ok(Some(Action::DecreaseSpeed)),
             crossterm::event::KeyCode::Up => Ok(Some(Action::MoveUp)),
             crossterm::event::KeyCode::Down => Ok(Some(Action::MoveDown)),
+            crossterm::event::KeyCode::Enter if self.focused_panel == Some(Panel::Disk) => {
+                Ok(Some(Action::ToggleDiskDetail))
+            }
+            crossterm::event::KeyCode::Enter
+                if self.focused_panel == Some(Panel::Cpu)
+                    && self.cpu_component.selected_core.is_some() =>
+            {
+                Ok(Some(Action::ToggleCoreProcesses))
+            }
             crossterm::event::KeyCode::Enter => Ok(Some(Action::Enter)),
-            crossterm::event::KeyCode::Char('b') => Ok(Some(Action::Back)),
+            crossterm::event::KeyCode::Char('b') => Ok(Some(Action::ToggleNetworkBits)),
+            crossterm::event::KeyCode::Char('B') => Ok(Some(Action::RunSpeedTest)),
+            crossterm::event::KeyCode::Tab => Ok(Some(Action::FocusNextPanel)),
             crossterm::event::KeyCode::Char('t') => Ok(Some(Action::SwitchTheme)),
-            crossterm::event::KeyCode::Char('k') | crossterm::event::KeyCode::Char('K') => {
-                Ok(Some(Action::RequestProcessKill))
-            }
+            crossterm::event::KeyCode::Char('k') => Ok(Some(Action::RequestProcessKill)),
+            crossterm::event::KeyCode::Char('K') => Ok(Some(Action::QuickKillProcess)),
+            crossterm::event::KeyCode::Char(' ') => Ok(Some(Action::ToggleProcessMark)),
+            crossterm::event::KeyCode::Char('F') => Ok(Some(Action::ToggleFollowProcess)),
+            crossterm::event::KeyCode::Char('e') => Ok(Some(Action::RetryKillElevated)),
             crossterm::event::KeyCode::Char('S') => Ok(Some(Action::StartProcessSearch)),
             crossterm::event::KeyCode::Char('s')
                 if key
@@ -529,9 +1265,38 @@ impl App {
                 Ok(Some(Action::StartProcessSearch))
             }
             crossterm::event::KeyCode::Char('s') => Ok(Some(Action::SwitchProcessSort)),
+            crossterm::event::KeyCode::Char('o') => Ok(Some(Action::ReverseProcessSort)),
             crossterm::event::KeyCode::Char('n') => Ok(Some(Action::SwitchChartType)),
             crossterm::event::KeyCode::Char('T') => Ok(Some(Action::ToggleProcessTree)),
-            crossterm::event::KeyCode::Char('i') => Ok(Some(Action::CycleNetworkInterface)),
+            crossterm::event::KeyCode::Char('f') => Ok(Some(Action::ToggleFaultColumns)),
+            crossterm::event::KeyCode::Char('A') => Ok(Some(Action::ToggleSubtreeSort)),
+            crossterm::event::KeyCode::Char('C') => Ok(Some(Action::ToggleCumulativeCpu)),
+            crossterm::event::KeyCode::Char('L') => Ok(Some(Action::ToggleLeakView)),
+            crossterm::event::KeyCode::Char('a') => Ok(Some(Action::ToggleFullCommandLine)),
+            crossterm::event::KeyCode::Char('P') => Ok(Some(Action::TogglePrivacyMode)),
+            crossterm::event::KeyCode::Char('p') => Ok(Some(Action::ToggleIrixMode)),
+            crossterm::event::KeyCode::Char('h') => Ok(Some(Action::ToggleHideIdle)),
+            crossterm::event::KeyCode::Char('N') => Ok(Some(Action::CycleProcessOrigin)),
+            crossterm::event::KeyCode::Char('y') => Ok(Some(Action::CycleProcessCategory)),
+            crossterm::event::KeyCode::Char('u') => Ok(Some(Action::CycleProcessOwner)),
+            crossterm::event::KeyCode::Char('i') => Ok(Some(Action::ToggleInterfacePicker)),
+            crossterm::event::KeyCode::Char('g') => Ok(Some(Action::CyclePrimaryGpu)),
+            crossterm::event::KeyCode::Char('D') => Ok(Some(Action::ToggleSelfUsageDebug)),
+            crossterm::event::KeyCode::Char('I') => Ok(Some(Action::ToggleSessionStats)),
+            crossterm::event::KeyCode::Char('E') => Ok(Some(Action::ToggleEventTimeline)),
+            crossterm::event::KeyCode::Char('Z') => Ok(Some(Action::ToggleInhibitors)),
+            crossterm::event::KeyCode::Char('H') => Ok(Some(Action::ToggleHistoryView)),
+            crossterm::event::KeyCode::Char('v') => Ok(Some(Action::BookmarkSnapshot)),
+            crossterm::event::KeyCode::Char('d') => Ok(Some(Action::ToggleSnapshotDiff)),
+            crossterm::event::KeyCode::Char('M') => Ok(Some(Action::TogglePanelMoveMode)),
+            crossterm::event::KeyCode::Char(c @ '1'..='5') => Ok(Some(Action::TogglePanel(
+                Panel::ALL[c as usize - '1' as usize],
+            ))),
+            crossterm::event::KeyCode::Char('x') | crossterm::event::KeyCode::Char('m') => {
+                Ok(Some(Action::OpenToolMenu))
+            }
+            #[cfg(feature = "ebpf")]
+            crossterm::event::KeyCode::Char('O') => Ok(Some(Action::ToggleOffenders)),
             crossterm::event::KeyCode::Char('w') => Ok(Some(Action::SaveConfig)),
             _ => Ok(None),
         }
@@ -539,156 +1304,520 @@ impl App {
 
     fn handle_mouse_events(
         &mut self,
-        _mouse: MouseEvent,
+        mouse: MouseEvent,
     ) -> Result<Option<Action>, Box<dyn std::error::Error>> {
-        // Handle mouse events if needed
-        Ok(None)
-    }
-
-    fn draw_frame(&mut self) -> Result<(), Box<dyn std::error::Error>> {
-        use ratatui::layout::{Constraint, Direction, Layout};
-
-        self.tui.draw(|f| {
-            let size = f.area();
-
-            // Define main layout: Top (summary), Middle (panels + processes), Bottom (keybinds)
-            let main_chunks = Layout::default()
-                .direction(Direction::Vertical)
-                .constraints([
-                    Constraint::Length(1), // Top status line
-                    Constraint::Min(0),    // Main content area
-                    Constraint::Length(1), // Bottom keybinds line
-                ])
-                .split(size);
-
-            // Render top status line using interpolated snapshot
-            Self::render_top_status_line(
-                f,
-                main_chunks[0],
-                &self.interpolated_snapshot,
-                &self.theme,
-            );
+        use crossterm::event::{MouseButton, MouseEventKind};
 
-            // Split main content area into Left Panels and Central Process List
-            let middle_chunks = Layout::default()
-                .direction(Direction::Horizontal)
-                .constraints([
-                    Constraint::Percentage(33), // Left side panels (CPU, GPU, Memory)
-                    Constraint::Percentage(34), // Central process list
-                    Constraint::Percentage(33), // Right side panels (Network, Disk)
-                ])
-                .split(main_chunks[1]);
-
-            // Split Left side for CPU, GPU, Memory
-            let left_panels = Layout::default()
-                .direction(Direction::Vertical)
-                .constraints([
-                    Constraint::Percentage(33), // CPU
-                    Constraint::Percentage(33), // GPU
-                    Constraint::Percentage(34), // Memory (give the remainder to memory)
-                ])
-                .split(middle_chunks[0]);
-
-            // Split Right side for Network, Disk
-            let right_panels = Layout::default()
-                .direction(Direction::Vertical)
-                .constraints([
-                    Constraint::Percentage(50), // Network
-                    Constraint::Percentage(50), // Disk
-                ])
-                .split(middle_chunks[2]);
-
-            // Render Left side components
-            self.cpu_component.render_in_area(f, left_panels[0]);
-            self.gpu_component.render_in_area(f, left_panels[1]);
-            self.memory_component.render_in_area(f, left_panels[2]);
-
-            // Render Central process list
-            self.process_component.render_in_area(f, middle_chunks[1]);
-
-            // Render Right side components
-            self.network_component.render_in_area(f, right_panels[0]);
-            self.disk_component.render_in_area(f, right_panels[1]);
-
-            // Render bottom keybinds line
-            Self::render_bottom_keybinds(f, main_chunks[2], &self.theme, self.theme.color_scheme);
-        })?;
-        Ok(())
-    }
+        // Popups take no mouse input, and nothing behind them may react to it
+        if self.popup_visible() {
+            return Ok(None);
+        }
+        // Set when the list is drawn; the process component hit-tests against the same area
+        let list_area = self.process_component.area;
+        let panel_at = |title_only: bool| {
+            self.panel_areas.iter().find_map(|&(panel, area)| {
+                let inside = mouse.column >= area.x
+                    && mouse.column < area.x + area.width
+                    && mouse.row >= area.y
+                    && mouse.row < area.y + area.height;
+                (inside && (!title_only || mouse.row == area.y)).then_some(panel)
+            })
+        };
 
-    fn handle_action(&mut self, action: Action) -> Result<(), Box<dyn std::error::Error>> {
-        match action {
-            Action::Quit => {
-                self.should_quit = true;
+        match mouse.kind {
+            // Borders between panes resize them; the top border of a panel is its title bar
+            MouseEventKind::Down(MouseButton::Left) if self.border_at(&mouse).is_some() => {
+                self.resizing = self.border_at(&mouse);
+                Ok(None)
             }
-            Action::ToggleAutoUpdate => {
-                self.interpolated_snapshot.auto_update = !self.interpolated_snapshot.auto_update;
-                self.target_snapshot.auto_update = self.interpolated_snapshot.auto_update;
-                self.snapshot.auto_update = self.interpolated_snapshot.auto_update;
+            MouseEventKind::Drag(MouseButton::Left) => Ok(self.resizing.and_then(|border| {
+                let at = self.border_position(border, &mouse)?;
+                Some(Action::ResizePane(border, at))
+            })),
+            // Saved once the drag ends, not on every step of it
+            MouseEventKind::Up(MouseButton::Left) if self.resizing.is_some() => {
+                self.resizing = None;
+                Ok(Some(Action::SavePaneSizes))
             }
-            Action::MoveUp => {
-                self.process_component.update(action.clone())?;
+            // A click on a process column title sorts by that column
+            MouseEventKind::Down(MouseButton::Left)
+                if self
+                    .process_component
+                    .sort_at(list_area, mouse.column, mouse.row)
+                    .is_some() =>
+            {
+                self.set_focus(None);
+                Ok(self
+                    .process_component
+                    .sort_at(list_area, mouse.column, mouse.row)
+                    .map(Action::SortProcessesBy))
             }
-            Action::MoveDown => {
-                self.process_component.update(action.clone())?;
+            // A right click on a process opens its menu
+            MouseEventKind::Down(MouseButton::Right) => {
+                let selected = self
+                    .process_component
+                    .select_at(list_area, mouse.column, mouse.row);
+                Ok(selected.then_some(Action::OpenToolMenu))
             }
-            Action::ToggleProcessTree => {
-                self.process_component.update(action.clone())?;
+            // Dragging starts on a title bar and drops anywhere on the target panel
+            MouseEventKind::Down(MouseButton::Left) => {
+                self.dragged_panel = panel_at(true);
+                // Clicking a side panel focuses it; clicking anywhere else returns to the process list
+                self.set_focus(panel_at(false));
+                // A click on a process row selects it
+                self.process_component
+                    .handle_events(CrosstermEvent::Mouse(mouse))
             }
-            Action::StartProcessSearch
-            | Action::UpdateProcessSearch(_)
-            | Action::BackspaceProcessSearch
-            | Action::ConfirmProcessSearch
+            MouseEventKind::ScrollUp | MouseEventKind::ScrollDown => self
+                .process_component
+                .handle_events(CrosstermEvent::Mouse(mouse)),
+            MouseEventKind::Up(MouseButton::Left) => {
+                let Some(dragged) = self.dragged_panel.take() else {
+                    return Ok(None);
+                };
+                Ok(panel_at(false)
+                    .filter(|&target| target != dragged)
+                    .map(|target| Action::SwapPanels(dragged, target)))
+            }
+            _ => Ok(None),
+        }
+    }
+
+    /// Whether anything is drawn over the panels, so graph images would cover it
+    #[cfg(feature = "graphics")]
+    fn overlay_visible(&self) -> bool {
+        self.popup_visible() || self.moving_panel.is_some() || self.dragged_panel.is_some()
+    }
+
+    /// Whether a popup or dialog is open over the panels
+    fn popup_visible(&self) -> bool {
+        #[cfg(feature = "ebpf")]
+        if self.offenders_popup.visible {
+            return true;
+        }
+        self.process_component.is_kill_confirm_active()
+            || self.tool_menu.visible
+            || self.stats_popup.visible
+            || self.disk_detail.visible
+            || self.core_processes.visible
+            || self.inhibitors.visible
+            || self.interface_picker.visible
+            || self.event_timeline.visible
+            || self.compare_popup.visible
+            || self.history_view.visible
+    }
+
+    /// The pane border under the mouse: the edges between columns, or the bottom edge of a
+    /// panel with another one stacked below it. Sizes are stored for the full layout, so
+    /// borders whose panes are reflowing around a hidden panel cannot be dragged.
+    fn border_at(&self, mouse: &MouseEvent) -> Option<PaneBorder> {
+        let all_columns = self.column_areas.iter().all(|area| area.width > 0);
+        let column_border = self.column_areas.windows(2).position(|pair| {
+            let (left, right) = (pair[0], pair[1]);
+            all_columns
+                && (mouse.column + 1 == left.right() || mouse.column == right.x)
+                && mouse.row >= left.y
+                && mouse.row < left.bottom()
+        });
+        if let Some(i) = column_border {
+            return Some(PaneBorder::Column(i));
+        }
+        let order = self.panel_layout.order();
+        self.panel_areas.iter().find_map(|&(panel, area)| {
+            let slot = order.iter().position(|&p| p == panel)?;
+            (slot != PanelLayout::LEFT_SLOTS - 1
+                && slot != order.len() - 1
+                && self.panel_layout.column_complete(slot)
+                && mouse.row + 1 == area.bottom()
+                && mouse.column >= area.x
+                && mouse.column < area.right())
+            .then_some(PaneBorder::Slot(slot))
+        })
+    }
+
+    /// Percent of the screen width, or of the slot's column height, the mouse is dragging to
+    fn border_position(&self, border: PaneBorder, mouse: &MouseEvent) -> Option<u16> {
+        let percent = |offset: u16, start: u16, length: u16| {
+            (length > 0).then(|| {
+                (u32::from((offset + 1).saturating_sub(start)) * 100 / u32::from(length)) as u16
+            })
+        };
+        match border {
+            PaneBorder::Column(_) => {
+                let first = self.column_areas.first()?;
+                let width = self.column_areas.iter().map(|area| area.width).sum();
+                percent(mouse.column, first.x, width)
+            }
+            PaneBorder::Slot(slot) => {
+                let column = if slot < PanelLayout::LEFT_SLOTS {
+                    self.column_areas.first()?
+                } else {
+                    self.column_areas.last()?
+                };
+                percent(mouse.row, column.y, column.height)
+            }
+        }
+    }
+
+    fn draw_frame(&mut self, tui: &mut Tui) -> Result<(), Box<dyn std::error::Error>> {
+        let render_started = std::time::Instant::now();
+        tui.draw(|f| self.render(f))?;
+        #[cfg(feature = "graphics")]
+        if tui.draw_graphics()? {
+            tui.draw(|f| self.render(f))?;
+        }
+        self.last_render_ms = render_started.elapsed().as_secs_f64() * 1000.0;
+        self.last_frame_bytes = tui.last_frame_bytes;
+        Ok(())
+    }
+
+    fn render(&mut self, f: &mut ratatui::Frame) {
+        use ratatui::layout::{Constraint, Direction, Layout};
+
+        let render_ms = self.show_self_debug.then_some(self.last_render_ms);
+        let size = f.area();
+        #[cfg(feature = "graphics")]
+        crate::graphics::begin_frame(!self.overlay_visible());
+
+        // Define main layout: Top (summary), Middle (panels + processes), Bottom (keybinds)
+        let main_chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(1), // Top status line
+                Constraint::Min(0),    // Main content area
+                Constraint::Length(1), // Bottom keybinds line
+            ])
+            .split(size);
+
+        // Render top status line using interpolated snapshot
+        Self::render_top_status_line(
+            f,
+            main_chunks[0],
+            (&self.interpolated_snapshot, &self.session_stats),
+            (&self.theme, &self.config),
+            (
+                self.alerts.headline(),
+                self.alerts.recent_recovery(chrono::Local::now()),
+            ),
+            (self.poll_multiplier, self.low_bandwidth, self.read_only),
+            (render_ms, self.last_frame_bytes),
+        );
+
+        // Split main content area into Left Panels and Central Process List
+        let middle_chunks = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints(
+                // Left side panels, central process list, right side panels
+                self.panel_layout
+                    .visible_columns()
+                    .map(Constraint::Percentage),
+            )
+            .split(main_chunks[1]);
+        self.column_areas = middle_chunks.to_vec();
+
+        // Stack the visible side panels in their configured order and heights
+        self.panel_areas.clear();
+        for (column, area) in [
+            (self.panel_layout.visible_left(), middle_chunks[0]),
+            (self.panel_layout.visible_right(), middle_chunks[2]),
+        ] {
+            let areas = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints(
+                    column
+                        .iter()
+                        .map(|&(_, percent)| Constraint::Percentage(percent)),
+                )
+                .split(area);
+            self.panel_areas.extend(
+                column
+                    .iter()
+                    .map(|&(panel, _)| panel)
+                    .zip(areas.iter().copied()),
+            );
+        }
+        for (panel, area) in self.panel_areas.clone() {
+            match panel {
+                Panel::Cpu => self.cpu_component.render_in_area(f, area),
+                Panel::Gpu => self.gpu_component.render_in_area(f, area),
+                Panel::Memory => self.memory_component.render_in_area(f, area),
+                Panel::Network => self.network_component.render_in_area(f, area),
+                Panel::Disk => self.disk_component.render_in_area(f, area),
+            }
+        }
+
+        // Render Central process list
+        self.process_component.area = middle_chunks[1];
+        self.process_component.render_in_area(f, middle_chunks[1]);
+
+        if let Some(panel) = self.moving_panel.or(self.dragged_panel) {
+            self.render_panel_move_frame(f, panel);
+        }
+
+        #[cfg(feature = "ebpf")]
+        self.offenders_popup.render_in_area(f, main_chunks[1]);
+        self.tool_menu.render_in_area(f, main_chunks[1]);
+        self.stats_popup.render_in_area(f, main_chunks[1]);
+        self.disk_detail.render_in_area(f, main_chunks[1]);
+        self.core_processes.render_in_area(f, main_chunks[1]);
+        self.inhibitors.render_in_area(f, main_chunks[1]);
+        self.interface_picker.render_in_area(f, main_chunks[1]);
+        self.event_timeline.render_in_area(f, main_chunks[1]);
+        self.compare_popup.render_in_area(f, main_chunks[1]);
+        self.history_view.render_in_area(f, main_chunks[1]);
+
+        // Render bottom keybinds line
+        let (focus_label, hints) = match self.focused_panel {
+            None => (crate::i18n::tr("processes"), self.process_component.hints()),
+            Some(panel) => (panel.label(), self.component_for(panel).hints()),
+        };
+        Self::render_bottom_keybinds(
+            f,
+            main_chunks[2],
+            &self.theme,
+            self.theme.color_scheme,
+            (focus_label, &hints),
+        );
+
+        if self.flash_until.is_some() {
+            let buffer = f.buffer_mut();
+            let area = buffer.area;
+            for y in area.top()..area.bottom() {
+                for x in area.left()..area.right() {
+                    let cell = &mut buffer[(x, y)];
+                    cell.set_style(
+                        cell.style()
+                            .add_modifier(ratatui::style::Modifier::REVERSED),
+                    );
+                }
+            }
+        }
+    }
+
+    /// Run an external tool in the foreground with the TUI suspended, then wait for Enter so
+    /// output of tools that exit immediately (like `lsof`) can be read before rtop redraws
+    fn run_external_command(
+        &mut self,
+        tui: &mut Tui,
+        argv: &[String],
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        tui.suspend()?;
+        let status = std::process::Command::new(&argv[0])
+            .args(&argv[1..])
+            .status();
+        let message = match status {
+            Ok(status) if status.success() => format!("{} finished", argv[0]),
+            Ok(status) => format!("{} exited with {}", argv[0], status),
+            Err(err) => format!("Failed to run {}: {}", argv[0], err),
+        };
+        println!("\n[rtop] {} · press Enter to return", message);
+        let _ = std::io::stdin().read_line(&mut String::new());
+        tui.resume()?;
+        self.process_component.set_status(message);
+        Ok(())
+    }
+
+    /// Cursor of the focused panel's history graph, if it has one
+    fn graph_cursor(&self) -> Option<GraphCursor> {
+        match self.focused_panel? {
+            Panel::Cpu => Some(self.cpu_component.cursor),
+            Panel::Memory => Some(self.memory_component.cursor),
+            Panel::Network => Some(self.network_component.cursor),
+            Panel::Gpu | Panel::Disk => None,
+        }
+    }
+
+    fn set_process_sort(&mut self, sort_by: ProcessSortBy, reversed: bool) {
+        for snapshot in [
+            &mut self.interpolated_snapshot,
+            &mut self.target_snapshot,
+            &mut self.snapshot,
+        ] {
+            snapshot.process_sort_by = sort_by;
+            snapshot.process_sort_reversed = reversed;
+        }
+        self.process_component.resorted();
+    }
+
+    /// Move keyboard focus, leaving graph inspection on the panel losing it
+    fn set_focus(&mut self, panel: Option<Panel>) {
+        if panel != self.focused_panel {
+            self.cpu_component.cursor = GraphCursor::default();
+            self.memory_component.cursor = GraphCursor::default();
+            self.network_component.cursor = GraphCursor::default();
+            self.cpu_component.selected_core = None;
+        }
+        self.disk_component.focused = panel == Some(Panel::Disk);
+        self.focused_panel = panel;
+    }
+
+    fn component_for(&self, panel: Panel) -> &dyn Component {
+        match panel {
+            Panel::Cpu => &self.cpu_component,
+            Panel::Gpu => &self.gpu_component,
+            Panel::Memory => &self.memory_component,
+            Panel::Network => &self.network_component,
+            Panel::Disk => &self.disk_component,
+        }
+    }
+
+    /// Outline the panel being moved, replacing its title with the move-mode keys
+    fn render_panel_move_frame(&self, f: &mut ratatui::Frame, panel: Panel) {
+        use ratatui::{
+            style::{Color, Modifier, Style},
+            widgets::{Block, BorderType, Borders},
+        };
+
+        let Some(&(_, area)) = self.panel_areas.iter().find(|(p, _)| *p == panel) else {
+            return;
+        };
+        let hint = if self.moving_panel.is_some() {
+            format!(
+                " moving {} · ←/→ move · Tab next · Enter done ",
+                panel.label()
+            )
+        } else {
+            format!(" drop {} on another panel ", panel.label())
+        };
+        let frame = Block::default()
+            .title(hint)
+            .borders(Borders::ALL)
+            .border_type(BorderType::Thick)
+            .border_style(
+                Style::default()
+                    .fg(self.theme.get_color(Color::Yellow))
+                    .add_modifier(Modifier::BOLD),
+            );
+        f.render_widget(frame, area);
+    }
+
+    fn handle_action(&mut self, action: Action) -> Result<(), Box<dyn std::error::Error>> {
+        let runs_tool = matches!(action, Action::LaunchTool)
+            && matches!(self.tool_menu.selected_entry(), Some(MenuEntry::Tool(_)));
+        if self.read_only && (action.is_destructive() || runs_tool) {
+            self.process_component
+                .set_status("read-only: changing processes is disabled".to_string());
+            return Ok(());
+        }
+        match action {
+            Action::Quit => {
+                self.should_quit = true;
+            }
+            Action::ToggleAutoUpdate => {
+                self.interpolated_snapshot.auto_update = !self.interpolated_snapshot.auto_update;
+                self.target_snapshot.auto_update = self.interpolated_snapshot.auto_update;
+                self.snapshot.auto_update = self.interpolated_snapshot.auto_update;
+            }
+            Action::MoveUp | Action::MoveDown if self.focused_panel == Some(Panel::Cpu) => {
+                self.cpu_component.update(action.clone())?;
+            }
+            Action::MoveUp | Action::MoveDown if self.focused_panel == Some(Panel::Disk) => {
+                self.disk_component.update(action.clone())?;
+            }
+            Action::MoveUp => {
+                self.process_component.update(action.clone())?;
+            }
+            Action::MoveDown => {
+                self.process_component.update(action.clone())?;
+            }
+            Action::ToggleProcessTree
+            | Action::CollapseProcessBranch(_)
+            | Action::Enter
+            | Action::Back
+            | Action::ToggleSubtreeSort
+            | Action::ToggleCumulativeCpu
+            | Action::ToggleLeakView
+            | Action::ToggleFaultColumns
+            | Action::TogglePrivacyMode
+            | Action::ToggleIrixMode
+            | Action::ToggleHideIdle
+            | Action::CycleProcessOrigin
+            | Action::CycleProcessCategory
+            | Action::CycleProcessOwner => {
+                self.process_component.update(action.clone())?;
+            }
+            Action::ToggleFullCommandLine => {
+                self.process_component.update(action.clone())?;
+                // Truncated command lines are dropped at collection time, so ask for full ones.
+                if let Some(tx) = &self.collector_tx {
+                    let _ = tx.send(CollectorCommand::SetFullCommandLines(
+                        self.process_component.show_full_cmd,
+                    ));
+                }
+            }
+            Action::StartProcessSearch
+            | Action::UpdateProcessSearch(_)
+            | Action::BackspaceProcessSearch
+            | Action::ConfirmProcessSearch
             | Action::CancelProcessSearch
+            | Action::CycleSearchMatchMode
             | Action::RequestProcessKill
+            | Action::ToggleProcessMark
+            | Action::ClearProcessMarks
+            | Action::ToggleFollowProcess
             | Action::ToggleProcessKillChoice
             | Action::ConfirmProcessKill
-            | Action::CancelProcessKill => {
+            | Action::CancelProcessKill
+            | Action::CycleKillSignal(_)
+            | Action::UpdateKillConfirmation(_)
+            | Action::BackspaceKillConfirmation
+            | Action::QuickKillProcess
+            | Action::ReniceProcess(_)
+            | Action::RetryKillElevated => {
                 self.process_component.update(action.clone())?;
             }
             Action::SwitchProcessSort => {
                 let next = match self.interpolated_snapshot.process_sort_by {
                     ProcessSortBy::CpuUsage => ProcessSortBy::Memory,
-                    ProcessSortBy::Memory => ProcessSortBy::Pid,
+                    ProcessSortBy::Memory => ProcessSortBy::DiskIo,
+                    ProcessSortBy::DiskIo => ProcessSortBy::StartTime,
+                    ProcessSortBy::StartTime => ProcessSortBy::Pid,
                     ProcessSortBy::Pid => ProcessSortBy::Name,
                     ProcessSortBy::Name => ProcessSortBy::CpuUsage,
                 };
-                self.interpolated_snapshot.process_sort_by = next;
-                self.target_snapshot.process_sort_by = next;
-                self.snapshot.process_sort_by = next;
+                self.set_process_sort(next, false);
             }
-            Action::CycleNetworkInterface => {
-                let mut names: Vec<String> = self
-                    .interpolated_snapshot
-                    .networks
-                    .iter()
-                    .map(|n| n.name.clone())
-                    .collect();
-                names.sort();
-                names.dedup();
-
-                let next_interface = if names.is_empty() {
-                    None
+            // Picking the key the list is already sorted by flips the direction
+            Action::SortProcessesBy(sort_by) => {
+                let current = &self.interpolated_snapshot;
+                let reversed = sort_by == current.process_sort_by && !current.process_sort_reversed;
+                self.set_process_sort(sort_by, reversed);
+            }
+            Action::ReverseProcessSort => {
+                let current = &self.interpolated_snapshot;
+                self.set_process_sort(current.process_sort_by, !current.process_sort_reversed);
+            }
+            Action::ToggleInterfacePicker => {
+                if self.interface_picker.visible {
+                    self.interface_picker.update(action.clone())?;
                 } else {
-                    match &self.interpolated_snapshot.selected_network_interface {
-                        None => Some(names[0].clone()),
-                        Some(current) => {
-                            if let Some(pos) = names.iter().position(|name| name == current) {
-                                if pos + 1 < names.len() {
-                                    Some(names[pos + 1].clone())
-                                } else {
-                                    None
-                                }
-                            } else {
-                                Some(names[0].clone())
-                            }
-                        }
-                    }
-                };
-
-                self.interpolated_snapshot.selected_network_interface = next_interface.clone();
-                self.target_snapshot.selected_network_interface = next_interface.clone();
-                self.snapshot.selected_network_interface = next_interface;
+                    self.interface_picker.open(&self.interpolated_snapshot);
+                }
+            }
+            Action::MoveInterfaceSelection(_) => {
+                self.interface_picker.update(action.clone())?;
+            }
+            Action::SelectNetworkInterface => {
+                let interface = self.interface_picker.selected_interface();
+                self.interface_picker.update(action.clone())?;
+                self.interpolated_snapshot.selected_network_interface = interface.clone();
+                self.target_snapshot.selected_network_interface = interface.clone();
+                self.snapshot.selected_network_interface = interface;
+            }
+            Action::CyclePrimaryGpu => {
+                let gpus = &self.interpolated_snapshot.gpus;
+                if gpus.len() > 1 {
+                    let current = self
+                        .gpu_component
+                        .primary_gpu
+                        .as_deref()
+                        .and_then(|name| gpus.iter().position(|gpu| gpu.name == name))
+                        .unwrap_or(0);
+                    let next = gpus[(current + 1) % gpus.len()].name.clone();
+                    self.gpu_component.primary_gpu = Some(next.clone());
+                    self.config.primary_gpu = Some(next);
+                    let _ = self.config.save();
+                }
             }
             Action::SwitchTheme => {
                 let cycle = Theme::cycle();
@@ -700,14 +1829,182 @@ impl App {
                 let next_scheme = cycle[(idx + 1) % cycle.len()];
 
                 self.theme = Theme::new(next_scheme);
+                self.theme.simple_gradients = self.low_bandwidth;
                 self.interpolated_snapshot.color_scheme = next_scheme;
                 self.target_snapshot.color_scheme = next_scheme;
                 self.snapshot.color_scheme = next_scheme;
                 self.sync_components();
                 let _ = self.save_theme_config();
             }
+            #[cfg(feature = "ebpf")]
+            Action::ToggleOffenders => {
+                self.offenders_popup.update(action.clone())?;
+            }
+            Action::ToggleSelfUsageDebug => {
+                self.show_self_debug = !self.show_self_debug;
+            }
+            Action::ToggleSessionStats => {
+                self.stats_popup.update(action.clone())?;
+            }
+            Action::ToggleEventTimeline => {
+                self.event_timeline.update(action.clone())?;
+            }
+            Action::ToggleInhibitors => {
+                if self.inhibitors.visible {
+                    self.inhibitors.update(action.clone())?;
+                } else {
+                    // Asked once on opening rather than every tick: it spawns busctl
+                    self.inhibitors.request();
+                }
+            }
+            Action::BookmarkSnapshot => {
+                let at = chrono::Local::now();
+                self.bookmark = Some(Bookmark {
+                    at,
+                    snapshot: self.target_snapshot.clone(),
+                });
+                self.process_component
+                    .set_status(format!("Snapshot bookmarked at {}", at.format("%H:%M:%S")));
+                self.refresh_snapshot_diff();
+            }
+            Action::ToggleSnapshotDiff => {
+                self.compare_popup.update(action.clone())?;
+                self.refresh_snapshot_diff();
+            }
+            Action::ToggleDiskDetail => {
+                if self.disk_detail.visible {
+                    self.disk_detail.update(action.clone())?;
+                } else if let Some(disk) = self.disk_component.selected_disk() {
+                    // Read once on opening: the partition layout does not change between ticks
+                    self.disk_detail.device = crate::data::blockdev::describe(&disk.name);
+                    self.disk_detail.volume = disk.name;
+                    self.disk_detail.update(action.clone())?;
+                }
+            }
+            Action::ToggleCoreProcesses => {
+                if self.core_processes.visible {
+                    self.core_processes.update(action.clone())?;
+                } else if let Some(core) = self.cpu_component.selected_core {
+                    self.core_processes.core = core;
+                    self.core_processes.update(action.clone())?;
+                }
+            }
+            Action::ToggleHistoryView | Action::CycleHistoryRange => {
+                self.history_view.update(action.clone())?;
+            }
+            Action::ExportSessionStats => {
+                self.stats_popup.status = Some(match self.session_stats.export() {
+                    Ok(path) => format!("written to {}", path.display()),
+                    Err(err) => format!("export failed: {}", err),
+                });
+            }
+            Action::TogglePanelMoveMode => {
+                self.moving_panel = match self.moving_panel {
+                    Some(_) => None,
+                    None => self.panel_layout.visible().first().copied(),
+                };
+            }
+            Action::SelectNextPanel => {
+                if let Some(panel) = self.moving_panel {
+                    self.moving_panel = Some(self.panel_layout.next(panel));
+                }
+            }
+            Action::MovePanel(forward) => {
+                if let Some(panel) = self.moving_panel {
+                    self.panel_layout.shift(panel, forward);
+                }
+            }
+            Action::SwapPanels(a, b) => {
+                self.panel_layout.swap(a, b);
+            }
+            Action::ResizePane(border, at) => {
+                self.panel_layout.resize(border, at);
+            }
+            Action::SavePaneSizes => {
+                self.config.column_widths = self.panel_layout.columns().to_vec();
+                self.config.panel_heights = self.panel_layout.heights().to_vec();
+                let _ = self.config.save();
+            }
+            Action::TogglePanel(panel) => {
+                self.panel_layout.toggle_visible(panel);
+                if !self.panel_layout.is_visible(panel) {
+                    if self.focused_panel == Some(panel) {
+                        self.set_focus(None);
+                    }
+                    if self.moving_panel == Some(panel) {
+                        self.moving_panel = None;
+                    }
+                }
+            }
+            Action::FocusNextPanel => {
+                let order = self.panel_layout.visible();
+                let next = match self.focused_panel {
+                    None => order.first().copied(),
+                    Some(panel) if order.last() == Some(&panel) => None,
+                    Some(panel) => Some(self.panel_layout.next(panel)),
+                };
+                self.set_focus(next);
+            }
+            Action::ToggleNetworkBits => {
+                self.network_component.update(action.clone())?;
+            }
+            Action::MoveGraphCursor(_) | Action::ClearGraphCursor => match self.focused_panel {
+                Some(Panel::Cpu) => {
+                    self.cpu_component.update(action.clone())?;
+                }
+                Some(Panel::Memory) => {
+                    self.memory_component.update(action.clone())?;
+                }
+                Some(Panel::Network) => {
+                    self.network_component.update(action.clone())?;
+                }
+                _ => {}
+            },
+            Action::RunSpeedTest => {
+                match SpeedTestEndpoint::parse(&self.config.speedtest_endpoint) {
+                    Some(endpoint) => crate::data::speedtest::start(&self.speed_test, endpoint),
+                    None => {
+                        if let Ok(mut status) = self.speed_test.lock() {
+                            *status = SpeedTestStatus::Failed(
+                                "set speedtest_endpoint to iperf3://host or an http(s) URL"
+                                    .to_string(),
+                            );
+                        }
+                    }
+                }
+            }
+            Action::OpenToolMenu => {
+                if let Some((pid, name)) = self.process_component.selected_process() {
+                    self.tool_menu.open(pid, name);
+                }
+            }
+            Action::MoveToolSelection(_) | Action::CloseToolMenu => {
+                self.tool_menu.update(action.clone())?;
+            }
+            Action::LaunchTool => {
+                let entry = self.tool_menu.selected_entry().cloned();
+                let target = self.tool_menu.target_pid();
+                self.pending_command = self.tool_menu.selected_command();
+                self.tool_menu.update(action.clone())?;
+                if let Some(MenuEntry::Builtin { action, .. }) = entry {
+                    // The list may have re-sorted while the menu was open
+                    if target.is_some_and(|pid| self.process_component.select_pid(pid)) {
+                        self.handle_action(action)?;
+                    } else {
+                        self.process_component
+                            .set_status("The process exited while the menu was open".to_string());
+                    }
+                }
+            }
+            Action::CopyProcessInfo => {
+                if let Some(summary) = self.process_component.selected_summary() {
+                    self.process_component
+                        .set_status(format!("Copied \"{}\"", summary));
+                    self.pending_clipboard = Some(summary);
+                }
+            }
             Action::SaveConfig => {
-                let _ = self.save_theme_config();
+                let _ = self.save_config();
             }
             _ => {
                 // Handle other actions
@@ -719,6 +2016,7 @@ impl App {
 
     fn apply_ui_state_to_snapshot(&self, snapshot: &mut SystemSnapshot) {
         snapshot.process_sort_by = self.interpolated_snapshot.process_sort_by;
+        snapshot.process_sort_reversed = self.interpolated_snapshot.process_sort_reversed;
         snapshot.selected_network_interface = self
             .interpolated_snapshot
             .selected_network_interface
@@ -744,8 +2042,15 @@ impl App {
         self.gpu_component.snapshot = interpolated_snapshot_clone.clone();
         self.memory_component.snapshot = interpolated_snapshot_clone.clone();
         self.network_component.snapshot = interpolated_snapshot_clone.clone();
+        if let Ok(status) = self.speed_test.lock() {
+            self.network_component.speed_test = status.clone();
+        }
         self.disk_component.snapshot = interpolated_snapshot_clone.clone();
         self.process_component.snapshot = interpolated_snapshot_clone;
+        self.process_component.follow_selection();
+        self.process_component.poll_unit_restart();
+        self.process_component.poll_elevated_kill();
+        self.inhibitors.poll(&self.snapshot);
 
         self.cpu_component.theme = self.theme.clone();
         self.gpu_component.theme = self.theme.clone();
@@ -753,13 +2058,59 @@ impl App {
         self.network_component.theme = self.theme.clone();
         self.disk_component.theme = self.theme.clone();
         self.process_component.theme = self.theme.clone();
+        #[cfg(feature = "ebpf")]
+        {
+            self.offenders_popup.theme = self.theme.clone();
+        }
+        self.tool_menu.theme = self.theme.clone();
+        self.disk_detail.theme = self.theme.clone();
+        self.core_processes.theme = self.theme.clone();
+        self.inhibitors.theme = self.theme.clone();
+        self.interface_picker.theme = self.theme.clone();
+        self.interface_picker.show_bits = self.network_component.show_bits;
+        if self.interface_picker.visible {
+            self.interface_picker.snapshot = self.interpolated_snapshot.clone();
+        }
+        if self.core_processes.visible {
+            self.core_processes.snapshot = self.interpolated_snapshot.clone();
+        }
+        self.stats_popup.theme = self.theme.clone();
+        if self.stats_popup.visible {
+            self.stats_popup.stats = Some(self.session_stats.clone());
+            self.stats_popup.history_since = self
+                .interpolated_snapshot
+                .history_times
+                .front()
+                .and_then(|&time| {
+                    chrono::TimeZone::timestamp_opt(&chrono::Local, time, 0).single()
+                });
+        }
+        self.event_timeline.theme = self.theme.clone();
+        self.compare_popup.theme = self.theme.clone();
+        if self.event_timeline.visible {
+            self.event_timeline.events = self.alerts.events().iter().cloned().collect();
+        }
+        self.history_view.theme = self.theme.clone();
+        if self.history_view.visible {
+            self.history_view.hours = self.rollups.hours.iter().copied().collect();
+            self.history_view.warning = self.rollups.warning.clone();
+        }
     }
 
-    fn save_theme_config(&self) -> Result<(), Box<dyn std::error::Error>> {
-        let config = AppConfig {
-            color_scheme: self.theme.color_scheme,
-        };
-        config.save()
+    fn save_theme_config(&mut self) -> Result<(), Box<dyn std::error::Error>> {
+        self.config.color_scheme = self.theme.color_scheme;
+        self.config.save()
+    }
+
+    /// Persist the theme and the current panel order, sizes and visibility
+    fn save_config(&mut self) -> Result<(), Box<dyn std::error::Error>> {
+        self.config.panel_order = self.panel_layout.order().to_vec();
+        self.config.column_widths = self.panel_layout.columns().to_vec();
+        self.config.panel_heights = self.panel_layout.heights().to_vec();
+        self.config.hidden_panels = self.panel_layout.hidden().to_vec();
+        self.config.irix_mode = self.process_component.irix_mode;
+        self.config.hide_idle_processes = self.process_component.hide_idle;
+        self.save_theme_config()
     }
 
     fn theme_name(scheme: ColorScheme) -> &'static str {
@@ -774,3 +2125,2029 @@ impl App {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::mock::{self, MockCollector};
+    use crate::data::probes::ProbeStats;
+    use crate::data::snapshot::{
+        KernelLimits, MountOpenFiles, OpenFilesByMount, ProcessDetail, ProcessGpuUsage, TaskTotals,
+        ThrottleStats, ZfsArc,
+    };
+    use crossterm::event::{KeyCode, KeyModifiers};
+
+    fn test_app(collector: &mut MockCollector) -> App {
+        let mut app =
+            App::with_collector(collector, AppConfig::default(), Duration::from_millis(250));
+        // Mock PIDs may be real processes on the test machine; don't look up their units
+        app.process_component.unit_of = |_| None;
+        settle(&mut app);
+        app
+    }
+
+    /// Finish interpolation so components show the target snapshot
+    fn settle(app: &mut App) {
+        app.interpolation_factor = 1.0;
+        app.interpolate_snapshots();
+    }
+
+    fn press(app: &mut App, code: KeyCode) {
+        let key = KeyEvent::new(code, KeyModifiers::NONE);
+        if let Some(action) = app.handle_key_events(key).unwrap() {
+            app.handle_action(action).unwrap();
+        }
+    }
+
+    fn render_lines(app: &mut App) -> Vec<String> {
+        let mut terminal = Terminal::new(TestBackend::new(160, 48)).unwrap();
+        terminal.draw(|f| app.render(f)).unwrap();
+        let buffer = terminal.backend().buffer();
+        (0..buffer.area.height)
+            .map(|y| {
+                (0..buffer.area.width)
+                    .map(|x| buffer[(x, y)].symbol())
+                    .collect::<String>()
+            })
+            .collect()
+    }
+
+    fn row_of(lines: &[String], needle: &str) -> Option<usize> {
+        lines.iter().position(|line| line.contains(needle))
+    }
+
+    #[test]
+    fn test_layout_shows_status_panels_and_keybinds() {
+        let mut collector = MockCollector::new(vec![mock::sample_snapshot()]);
+        let mut app = test_app(&mut collector);
+        let lines = render_lines(&mut app);
+
+        assert!(lines[0].starts_with(" RTOP "));
+        assert!(lines[0].contains("host:mockhost"));
+        assert!(lines[47].contains("[q] quit"));
+        for name in ["alpha", "bravo", "charlie"] {
+            assert!(row_of(&lines, name).is_some(), "{} not rendered", name);
+        }
+    }
+
+    #[test]
+    fn test_status_template_picks_and_orders_fields() {
+        let mut collector = MockCollector::new(vec![mock::sample_snapshot()]);
+        let mut app = test_app(&mut collector);
+        app.config.status_template =
+            "{ip} on {hostname}  {battery}  {nonsense}  {load}".to_string();
+
+        let lines = render_lines(&mut app);
+        assert!(lines[0].contains("ip:192.168.1.20 on host:mockhost  load:0.50 0.40 0.30"));
+        assert!(!lines[0].contains("uptime:"));
+        assert!(!lines[0].contains("nonsense"));
+
+        // The literal " on " shares the fields' background
+        let mut terminal = Terminal::new(TestBackend::new(160, 48)).unwrap();
+        terminal.draw(|f| app.render(f)).unwrap();
+        let buffer = terminal.backend().buffer();
+        let ip = lines[0].find("ip:").unwrap() as u16;
+        let on = ip + "ip:192.168.1.20 ".len() as u16;
+        assert_eq!(buffer[(on, 0)].symbol(), "o");
+        assert_eq!(buffer[(on, 0)].bg, buffer[(ip, 0)].bg);
+    }
+
+    #[test]
+    fn test_status_line_shows_task_totals() {
+        let mut snapshot = mock::sample_snapshot();
+        snapshot.task_totals = TaskTotals {
+            processes: 3,
+            threads: Some(1834),
+            open_files: Some(9120),
+        };
+        let mut collector = MockCollector::new(vec![snapshot]);
+        let mut app = test_app(&mut collector);
+
+        let lines = render_lines(&mut app);
+        assert!(lines[0].contains("load:0.50 0.40 0.30  tasks:3 thr:1834 fd:9120"));
+
+        // Off Linux only the process count is known
+        let mut snapshot = mock::sample_snapshot();
+        snapshot.task_totals.processes = 3;
+        collector.push(snapshot);
+        app.receive_snapshot(collector.collect());
+        settle(&mut app);
+        let lines = render_lines(&mut app);
+        assert!(lines[0].contains("tasks:3  "));
+    }
+
+    #[test]
+    fn test_permission_gaps_collapse_into_one_hint() {
+        let mut restricted = mock::sample_snapshot();
+        restricted.restricted_sources = vec![
+            "CPU power (RAPL)".to_string(),
+            "GPU memory (debugfs)".to_string(),
+        ];
+        let mut root = restricted.clone();
+        root.elevated = true;
+        let mut collector = MockCollector::new(vec![restricted, root]);
+        let mut app = test_app(&mut collector);
+        app.config.status_template = "{hostname}".to_string();
+
+        let top = render_lines(&mut app).remove(0);
+        assert!(top.contains(
+            "run with elevated privileges to see CPU power (RAPL), GPU memory (debugfs)"
+        ));
+        assert!(!top.contains("# root"));
+
+        app.receive_snapshot(collector.collect());
+        settle(&mut app);
+        let top = render_lines(&mut app).remove(0);
+        assert!(top.contains(" # root "));
+        assert!(!top.contains("elevated privileges"));
+    }
+
+    #[test]
+    fn test_inhibitors_popup_names_the_holding_process() {
+        use crate::data::inhibitors::Inhibitor;
+        let mut collector = MockCollector::new(vec![mock::sample_snapshot()]);
+        let mut app = test_app(&mut collector);
+
+        let snapshot = app.snapshot.clone();
+        app.inhibitors.open(
+            Some(vec![Inhibitor {
+                what: "sleep:idle".to_string(),
+                who: "Media Player".to_string(),
+                why: "Playing video".to_string(),
+                mode: "block".to_string(),
+                uid: 1000,
+                pid: 200,
+            }]),
+            &snapshot,
+        );
+        let lines = render_lines(&mut app);
+        assert!(row_of(&lines, "1 blocking · 0 delaying").is_some());
+        let row = row_of(&lines, "sleep:idle").expect("lock row");
+        assert!(lines[row].contains("block"));
+        assert!(lines[row].contains("bravo"));
+        assert!(lines[row].contains("Media Player"));
+        assert!(row_of(&lines, "Playing video").is_some());
+
+        press(&mut app, KeyCode::Esc);
+        assert!(!app.inhibitors.visible);
+
+        // Opened with Z, the locks arrive from a background busctl call
+        press(&mut app, KeyCode::Char('Z'));
+        assert!(app.inhibitors.visible);
+        let deadline = std::time::Instant::now() + Duration::from_secs(10);
+        while row_of(&render_lines(&mut app), "Asking logind").is_some() {
+            assert!(
+                std::time::Instant::now() < deadline,
+                "busctl never answered"
+            );
+            std::thread::sleep(Duration::from_millis(10));
+            settle(&mut app);
+        }
+        assert!(row_of(&render_lines(&mut app), "Inhibitor locks").is_some());
+    }
+
+    #[test]
+    fn test_offline_cores_are_marked_and_left_out_of_the_averages() {
+        let mut collector = MockCollector::new(vec![mock::sample_snapshot()]);
+        let mut app = test_app(&mut collector);
+
+        // Core 2 was parked: it keeps its slot in the grid but no longer counts
+        let mut snapshot = mock::sample_snapshot();
+        snapshot.cpu_count = 3;
+        snapshot.cpu_online = vec![true, true, false, true];
+        snapshot.cpu_history = vec![[60.0].into(), [60.0].into(), [0.0].into(), [60.0].into()];
+        snapshot.cpu_frequencies = vec![3000, 3000, 0, 3000];
+        collector.push(snapshot);
+        app.receive_snapshot(collector.collect());
+        settle(&mut app);
+
+        let lines = render_lines(&mut app);
+        let stats = row_of(&lines, "Cores: 3 (1 offline)").expect("core count");
+        assert!(lines[stats].contains("Freq:  3000"));
+        assert!(row_of(&lines, " 2: offline").is_some());
+        assert!(row_of(&lines, " 3: 60.0%").is_some());
+        assert_eq!(app.cpu_component.average_history(), vec![60]);
+    }
+
+    #[test]
+    fn test_core_drill_down_lists_processes_last_on_that_core() {
+        let mut collector = MockCollector::new(vec![mock::sample_snapshot()]);
+        let mut app = test_app(&mut collector);
+
+        let mut snapshot = mock::sample_snapshot();
+        for (process, core) in snapshot.processes.iter_mut().zip([2, 2, 0]) {
+            process.last_cpu = Some(core);
+        }
+        snapshot.processes[1].core_samples = vec![0, 0, 10, 0];
+        collector.push(snapshot);
+        app.receive_snapshot(collector.collect());
+        settle(&mut app);
+
+        press(&mut app, KeyCode::Tab);
+        assert_eq!(app.focused_panel, Some(Panel::Cpu));
+        // The first ↓ picks core 0; the process list keeps its selection
+        for _ in 0..3 {
+            press(&mut app, KeyCode::Down);
+        }
+        assert_eq!(app.cpu_component.selected_core, Some(2));
+        assert_eq!(app.process_component.selected_index, 0);
+
+        press(&mut app, KeyCode::Enter);
+        let lines = render_lines(&mut app);
+        let title = row_of(&lines, "Processes on core 2").unwrap();
+        let popup = &lines[title..title + 8];
+        let bravo = row_of(popup, "bravo").unwrap();
+        assert!(popup[bravo].contains("100%"));
+        assert!(bravo < row_of(popup, "alpha").unwrap());
+        assert!(row_of(popup, "charlie").is_none());
+
+        press(&mut app, KeyCode::Esc);
+        assert!(!app.core_processes.visible);
+        // Leaving the panel drops the core selection
+        press(&mut app, KeyCode::Tab);
+        assert_eq!(app.cpu_component.selected_core, None);
+    }
+
+    #[test]
+    fn test_sort_key_reorders_processes() {
+        let mut collector = MockCollector::new(vec![mock::sample_snapshot()]);
+        let mut app = test_app(&mut collector);
+
+        let lines = render_lines(&mut app);
+        assert!(row_of(&lines, "bravo") < row_of(&lines, "charlie"));
+        assert!(row_of(&lines, "charlie") < row_of(&lines, "alpha"));
+
+        press(&mut app, KeyCode::Char('s'));
+        let lines = render_lines(&mut app);
+        assert!(row_of(&lines, "alpha") < row_of(&lines, "charlie"));
+        assert!(row_of(&lines, "charlie") < row_of(&lines, "bravo"));
+    }
+
+    #[test]
+    fn test_subtree_sort_orders_tree_by_aggregate() {
+        let mut snapshot = mock::sample_snapshot();
+        let mut child = mock::process(400, "delta", 50.0, 10 * 1024 * 1024);
+        child.parent_pid = Some(100);
+        snapshot.processes.push(child);
+        let mut collector = MockCollector::new(vec![snapshot]);
+        let mut app = test_app(&mut collector);
+
+        press(&mut app, KeyCode::Char('T'));
+        let lines = render_lines(&mut app);
+        assert!(row_of(&lines, "bravo") < row_of(&lines, "alpha"));
+
+        press(&mut app, KeyCode::Char('A'));
+        let lines = render_lines(&mut app);
+        assert!(row_of(&lines, "alpha") < row_of(&lines, "bravo"));
+        assert!(lines[row_of(&lines, "alpha").unwrap()].contains("alpha Σ55.0%"));
+    }
+
+    #[test]
+    fn test_tree_branches_fold_and_stay_folded() {
+        let mut snapshot = mock::sample_snapshot();
+        for (pid, name) in [(400, "delta"), (500, "echo")] {
+            let mut child = mock::process(pid, name, 1.0, 1024);
+            child.parent_pid = Some(100);
+            snapshot.processes.push(child);
+        }
+        let mut collector = MockCollector::new(vec![snapshot.clone()]);
+        let mut app = test_app(&mut collector);
+        let selected = |app: &App| app.process_component.selected_process().unwrap().1;
+
+        press(&mut app, KeyCode::Char('T'));
+        while selected(&app) != "alpha" {
+            press(&mut app, KeyCode::Down);
+        }
+        press(&mut app, KeyCode::Left);
+        let lines = render_lines(&mut app);
+        assert!(lines[row_of(&lines, "alpha").unwrap()].contains("▸ alpha (+2)"));
+        assert!(row_of(&lines, "delta").is_none());
+
+        // Still folded after a refresh
+        collector.push(snapshot);
+        app.receive_snapshot(collector.collect());
+        settle(&mut app);
+        assert!(row_of(&render_lines(&mut app), "echo").is_none());
+
+        press(&mut app, KeyCode::Right);
+        let lines = render_lines(&mut app);
+        assert!(row_of(&lines, "delta").is_some());
+        assert!(row_of(&lines, "▸").is_none());
+
+        // On a leaf ← goes up to the parent
+        press(&mut app, KeyCode::Down);
+        assert_ne!(selected(&app), "alpha");
+        press(&mut app, KeyCode::Left);
+        assert_eq!(selected(&app), "alpha");
+    }
+
+    #[test]
+    fn test_detail_view_shows_core_occupancy() {
+        let mut snapshot = mock::sample_snapshot();
+        // bravo (the top CPU user, selected first) was pinned to core 2
+        snapshot.processes[1].core_samples = vec![0, 0, 10, 0];
+        let mut collector = MockCollector::new(vec![snapshot]);
+        let mut app = test_app(&mut collector);
+
+        assert!(row_of(&render_lines(&mut app), "··█·").is_none());
+        press(&mut app, KeyCode::Enter);
+        let lines = render_lines(&mut app);
+        assert!(row_of(&lines, "··█· ran on 1 of 4 cores").is_some());
+    }
+
+    #[test]
+    fn test_detail_view_drills_into_selected_process_until_back() {
+        let mut snapshot = mock::sample_snapshot();
+        snapshot.processes[1].parent_pid = Some(100);
+        snapshot.inspected_process = Some(ProcessDetail {
+            pid: 200,
+            cmd: vec!["/usr/bin/bravo".to_string(), "--serve".to_string()],
+            exe: Some("/usr/bin/bravo".to_string()),
+            cwd: Some("/srv/bravo".to_string()),
+            start_time: 1_700_000_000,
+            virtual_memory: 4 << 30,
+            rss_anon: Some(300 << 20),
+            rss_file: Some(100 << 20),
+            rss_shmem: None,
+        });
+        let mut collector = MockCollector::new(vec![snapshot.clone()]);
+        let mut app = test_app(&mut collector);
+
+        press(&mut app, KeyCode::Enter);
+        app.update_inspected_process();
+        assert_eq!(app.inspected_pid, Some(200));
+        collector.push(snapshot.clone());
+        app.receive_snapshot(collector.collect());
+        collector.push(snapshot);
+        app.receive_snapshot(collector.collect());
+        settle(&mut app);
+
+        let lines = render_lines(&mut app);
+        assert!(row_of(&lines, "Processes · detail").is_some());
+        assert!(row_of(&lines, "parent   100 (alpha)").is_some());
+        assert!(row_of(&lines, "command  /usr/bin/bravo --serve").is_some());
+        assert!(row_of(&lines, "cwd      /srv/bravo").is_some());
+        assert!(row_of(&lines, "anon 300.0MB · file 100.0MB").is_some());
+        assert!(row_of(&lines, "over 3 samples").is_some());
+        assert!(row_of(&lines, "charlie").is_none());
+
+        press(&mut app, KeyCode::Esc);
+        assert!(!app.should_quit);
+        app.update_inspected_process();
+        assert_eq!(app.inspected_pid, None);
+        assert!(row_of(&render_lines(&mut app), "charlie").is_some());
+    }
+
+    #[test]
+    fn test_details_pane_attributes_open_files_to_mounts() {
+        let mut snapshot = mock::sample_snapshot();
+        snapshot.inspected_files = Some(OpenFilesByMount {
+            pid: 200,
+            accessible: true,
+            mounts: vec![
+                MountOpenFiles {
+                    mount: "/home".to_string(),
+                    open: 12,
+                    writable: 3,
+                },
+                MountOpenFiles {
+                    mount: "/".to_string(),
+                    open: 4,
+                    writable: 0,
+                },
+            ],
+        });
+        let mut collector = MockCollector::new(vec![snapshot]);
+        let mut app = test_app(&mut collector);
+
+        app.update_inspected_process();
+        assert_eq!(app.inspected_pid, None);
+        press(&mut app, KeyCode::Enter);
+        app.update_inspected_process();
+        assert_eq!(app.inspected_pid, Some(200));
+        assert!(row_of(&render_lines(&mut app), "files /home 12 (3 w) · / 4").is_some());
+    }
+
+    #[test]
+    fn test_throttle_events_show_badge() {
+        let mut throttled = mock::sample_snapshot();
+        throttled.throttle = Some(ThrottleStats {
+            core_events: 2,
+            package_events: 1,
+            power_limit_events: 0,
+            active: true,
+        });
+        let mut collector = MockCollector::new(vec![mock::sample_snapshot()]);
+        let mut app = test_app(&mut collector);
+        assert!(row_of(&render_lines(&mut app), "throttled").is_none());
+
+        collector.push(throttled);
+        app.receive_snapshot(collector.collect());
+        settle(&mut app);
+        assert!(row_of(&render_lines(&mut app), "throttled ×3").is_some());
+    }
+
+    #[test]
+    fn test_panels_reorder_by_keyboard_and_drag() {
+        use crossterm::event::{MouseButton, MouseEventKind};
+
+        let mut collector = MockCollector::new(vec![mock::sample_snapshot()]);
+        let mut app = test_app(&mut collector);
+
+        press(&mut app, KeyCode::Char('M'));
+        press(&mut app, KeyCode::Right);
+        press(&mut app, KeyCode::Enter);
+        assert_eq!(
+            app.panel_layout.left(),
+            &[Panel::Gpu, Panel::Cpu, Panel::Memory]
+        );
+
+        render_lines(&mut app);
+        let area_of = |app: &App, panel: Panel| {
+            app.panel_areas
+                .iter()
+                .find(|(p, _)| *p == panel)
+                .map(|&(_, area)| area)
+                .unwrap()
+        };
+        let mouse = |kind, column, row| MouseEvent {
+            kind,
+            column,
+            row,
+            modifiers: KeyModifiers::NONE,
+        };
+        let (cpu, disk) = (area_of(&app, Panel::Cpu), area_of(&app, Panel::Disk));
+        for event in [
+            mouse(MouseEventKind::Down(MouseButton::Left), cpu.x + 2, cpu.y),
+            mouse(
+                MouseEventKind::Up(MouseButton::Left),
+                disk.x + 4,
+                disk.y + 3,
+            ),
+        ] {
+            if let Some(action) = app.handle_mouse_events(event).unwrap() {
+                app.handle_action(action).unwrap();
+            }
+        }
+        assert_eq!(app.panel_layout.right(), &[Panel::Network, Panel::Cpu]);
+
+        let lines = render_lines(&mut app);
+        let cpu_title = row_of(&lines, "CPU · Mock CPU").unwrap();
+        assert!(lines[cpu_title].find("CPU · Mock CPU").unwrap() > 100);
+    }
+
+    #[test]
+    fn test_hidden_panels_give_their_space_away() {
+        let mut collector = MockCollector::new(vec![mock::sample_snapshot()]);
+        let mut app = test_app(&mut collector);
+        render_lines(&mut app);
+        let height_of = |app: &App, panel: Panel| {
+            app.panel_areas
+                .iter()
+                .find(|(p, _)| *p == panel)
+                .map(|&(_, area)| area.height)
+        };
+        let memory_height = height_of(&app, Panel::Memory).unwrap();
+        let list_width = app.column_areas[1].width;
+
+        press(&mut app, KeyCode::Char('2'));
+        press(&mut app, KeyCode::Char('4'));
+        press(&mut app, KeyCode::Char('5'));
+        render_lines(&mut app);
+        assert_eq!(height_of(&app, Panel::Gpu), None);
+        assert!(height_of(&app, Panel::Memory).unwrap() > memory_height);
+        // With both right panels hidden the process list takes the whole right column
+        assert_eq!(app.column_areas[2].width, 0);
+        assert!(app.column_areas[1].width > list_width);
+
+        // Tab skips the hidden panels
+        press(&mut app, KeyCode::Tab);
+        press(&mut app, KeyCode::Tab);
+        assert_eq!(app.focused_panel, Some(Panel::Memory));
+        press(&mut app, KeyCode::Char('3'));
+        assert_eq!(app.focused_panel, None);
+
+        assert_eq!(
+            app.panel_layout.hidden(),
+            &[Panel::Gpu, Panel::Network, Panel::Disk, Panel::Memory]
+        );
+    }
+
+    #[test]
+    fn test_low_bandwidth_skips_animation_and_flattens_gradients() {
+        let mut busy = mock::sample_snapshot();
+        busy.global_cpu_usage = 90.0;
+        let mut collector = MockCollector::new(vec![mock::sample_snapshot(), busy]);
+        let mut app = test_app(&mut collector);
+        app.set_low_bandwidth(true);
+        assert!(app.cpu_component.theme.simple_gradients);
+
+        // The new values are shown on the next frame, without easing towards them
+        app.receive_snapshot(collector.collect());
+        app.interpolate_snapshots();
+        assert_eq!(app.interpolated_snapshot.global_cpu_usage, 90.0);
+        assert!(row_of(&render_lines(&mut app), "low-bw").is_some());
+
+        press(&mut app, KeyCode::Char('t'));
+        assert!(app.memory_component.theme.simple_gradients);
+    }
+
+    #[test]
+    fn test_dragging_borders_resizes_panes() {
+        use crossterm::event::{MouseButton, MouseEventKind};
+
+        let mut collector = MockCollector::new(vec![mock::sample_snapshot()]);
+        let mut app = test_app(&mut collector);
+        render_lines(&mut app);
+        let drag = |app: &mut App, from: (u16, u16), to: (u16, u16)| {
+            let mut saves = 0;
+            for (kind, (column, row)) in [
+                (MouseEventKind::Down(MouseButton::Left), from),
+                (MouseEventKind::Drag(MouseButton::Left), to),
+                (MouseEventKind::Up(MouseButton::Left), to),
+            ] {
+                let event = MouseEvent {
+                    kind,
+                    column,
+                    row,
+                    modifiers: KeyModifiers::NONE,
+                };
+                match app.handle_mouse_events(event).unwrap() {
+                    // Only the release saves, and the test leaves the real config alone
+                    Some(Action::SavePaneSizes) => saves += 1,
+                    Some(action) => app.handle_action(action).unwrap(),
+                    None => {}
+                }
+            }
+            render_lines(app);
+            saves
+        };
+
+        // Widen the process list by dragging the left column's right edge to a fifth of the screen
+        let left = app.column_areas[0];
+        assert_eq!(drag(&mut app, (left.right() - 1, 10), (31, 10)), 1);
+        assert_eq!(app.panel_layout.columns(), &[20, 47, 33]);
+        assert_eq!(app.column_areas[1].x, 32);
+
+        // Give the top left panel more height: its bottom edge moves down
+        let cpu = app.panel_areas[0].1;
+        drag(&mut app, (cpu.x + 5, cpu.bottom() - 1), (cpu.x + 5, 24));
+        assert_eq!(app.panel_layout.left_heights(), &[52, 15, 33]);
+        assert_eq!(app.panel_areas[1].1.y, 25);
+        assert_eq!(app.panel_layout.order(), Panel::ALL);
+    }
+
+    #[test]
+    fn test_bottom_bar_follows_focused_panel() {
+        let mut collector = MockCollector::new(vec![mock::sample_snapshot()]);
+        let mut app = test_app(&mut collector);
+        let lines = render_lines(&mut app);
+        assert!(lines[47].contains("Processes: [↑/↓] move"));
+
+        // Process list → CPU → GPU → Memory → Network
+        for _ in 0..4 {
+            press(&mut app, KeyCode::Tab);
+        }
+        let lines = render_lines(&mut app);
+        assert!(lines[47].contains("Network: [i] interface [b] bits/bytes"));
+        assert!(!lines[47].contains("[k] kill"));
+        assert!(row_of(&lines, "RX: 0.0B/s").is_some());
+
+        press(&mut app, KeyCode::Char('b'));
+        assert!(row_of(&render_lines(&mut app), "RX: 0.0b/s").is_some());
+    }
+
+    #[test]
+    fn test_packet_loss_points_at_wifi_power_saving() {
+        let mut collector = MockCollector::new(vec![mock::sample_snapshot()]);
+        let mut app = test_app(&mut collector);
+
+        let mut lossy = mock::sample_snapshot();
+        let mut wifi = mock::network("wlan0");
+        wifi.power_save = Some(true);
+        lossy.networks = vec![mock::network("eth0"), wifi];
+        lossy.probes = vec![ProbeStats {
+            label: "gateway".to_string(),
+            history: (0..20).map(|i| (i % 4 != 0).then_some(3.0)).collect(),
+            error: None,
+        }];
+        collector.push(lossy);
+        app.receive_snapshot(collector.collect());
+        settle(&mut app);
+
+        let alert = app.alerts.headline().expect("latency alert");
+        assert_eq!(alert.key, "latency:gateway");
+        assert!(alert.message.ends_with("Wi-Fi power saving on wlan0"));
+        assert!(row_of(&render_lines(&mut app), "MTU: eth0 1500  wlan0 1500 psave").is_some());
+    }
+
+    #[test]
+    fn test_network_chart_labels_wall_clock_times() {
+        let mut collector = MockCollector::new(vec![mock::sample_snapshot()]);
+        let mut app = test_app(&mut collector);
+
+        let start = chrono::Local::now().timestamp() - 4;
+        let mut snapshot = mock::sample_snapshot();
+        snapshot.networks = vec![mock::network("eth0")];
+        snapshot.network_history = (0..5).map(|i| (i * 1000, i * 500)).collect();
+        snapshot.history_times = (start..start + 5).collect();
+        collector.push(snapshot);
+        app.receive_snapshot(collector.collect());
+        settle(&mut app);
+
+        let lines = render_lines(&mut app);
+        let first = crate::format::clock(start);
+        let last = crate::format::clock(start + 4);
+        assert!(lines
+            .iter()
+            .any(|line| line.contains(&first) && line.contains(&last)));
+    }
+
+    #[test]
+    fn test_interface_nicknames_replace_raw_names() {
+        let mut collector = MockCollector::new(vec![mock::sample_snapshot()]);
+        let mut config = AppConfig::default();
+        config
+            .interface_names
+            .insert("wlp3s0".to_string(), "WiFi".to_string());
+        let mut app = App::with_collector(&mut collector, config, Duration::from_millis(250));
+        app.process_component.unit_of = |_| None;
+
+        let mut snapshot = mock::sample_snapshot();
+        snapshot.networks = vec![mock::network("enp5s0"), mock::network("wlp3s0")];
+        collector.push(snapshot);
+        app.receive_snapshot(collector.collect());
+        settle(&mut app);
+
+        // The picker lists interfaces by raw name, after "All"
+        press(&mut app, KeyCode::Char('i'));
+        let lines = render_lines(&mut app);
+        assert!(lines.iter().any(|line| line.contains("WiFi (wlp3s0)")));
+        press(&mut app, KeyCode::Down);
+        press(&mut app, KeyCode::Down);
+        press(&mut app, KeyCode::Enter);
+        settle(&mut app);
+        assert_eq!(
+            app.snapshot.selected_network_interface.as_deref(),
+            Some("wlp3s0")
+        );
+        let lines = render_lines(&mut app);
+        assert!(lines.iter().any(|line| line.contains("Interface: WiFi")));
+        assert!(lines.iter().any(|line| line.contains("WiFi (wlp3s0) 1500")));
+    }
+
+    #[test]
+    fn test_interface_picker_scrolls_to_the_selection() {
+        let mut collector = MockCollector::new(vec![mock::sample_snapshot()]);
+        let mut app = test_app(&mut collector);
+
+        let mut snapshot = mock::sample_snapshot();
+        snapshot.networks = (0..80)
+            .map(|i| mock::network(&format!("veth{:02}", i)))
+            .collect();
+        collector.push(snapshot);
+        app.receive_snapshot(collector.collect());
+        settle(&mut app);
+
+        press(&mut app, KeyCode::Char('i'));
+        for _ in 0..70 {
+            press(&mut app, KeyCode::Down);
+        }
+        assert_eq!(
+            app.interface_picker.selected_interface().as_deref(),
+            Some("veth69")
+        );
+        let lines = render_lines(&mut app);
+        assert!(row_of(&lines, " veth69 ").is_some());
+        assert!(row_of(&lines, " veth00 ").is_none());
+        // The key hint stays below the entries
+        assert!(row_of(&lines, "Esc close").is_some());
+    }
+
+    #[test]
+    fn test_interface_picker_lists_rates_and_state() {
+        let mut collector = MockCollector::new(vec![mock::sample_snapshot()]);
+        let mut app = test_app(&mut collector);
+
+        let mut snapshot = mock::sample_snapshot();
+        let mut eth0 = mock::network("eth0");
+        eth0.received_per_sec = 2048;
+        let mut veth = mock::network("veth1a2b");
+        veth.oper_state = Some("down".to_string());
+        snapshot.networks = vec![veth, eth0];
+        collector.push(snapshot);
+        app.receive_snapshot(collector.collect());
+        settle(&mut app);
+
+        press(&mut app, KeyCode::Char('i'));
+        assert!(app.interface_picker.visible);
+        let lines = render_lines(&mut app);
+        let row = |name: &str, state: &str| {
+            lines
+                .iter()
+                .any(|line| line.contains(&format!(" {} ", name)) && line.contains(state))
+        };
+        assert!(row("eth0", "up") && row("eth0", "2.0KB/s"));
+        assert!(row("veth1a2b", "down"));
+
+        // Up stops at "All"; Down moves to eth0 and Enter shows it
+        press(&mut app, KeyCode::Up);
+        press(&mut app, KeyCode::Down);
+        press(&mut app, KeyCode::Enter);
+        assert!(!app.interface_picker.visible);
+        assert_eq!(
+            app.snapshot.selected_network_interface.as_deref(),
+            Some("eth0")
+        );
+
+        // Reopening highlights eth0; Esc leaves the choice alone, Up and Enter go back to all
+        press(&mut app, KeyCode::Char('i'));
+        press(&mut app, KeyCode::Esc);
+        assert!(!app.interface_picker.visible);
+        assert_eq!(
+            app.snapshot.selected_network_interface.as_deref(),
+            Some("eth0")
+        );
+        press(&mut app, KeyCode::Char('i'));
+        press(&mut app, KeyCode::Up);
+        press(&mut app, KeyCode::Enter);
+        assert_eq!(app.snapshot.selected_network_interface, None);
+    }
+
+    #[test]
+    fn test_restored_history_marks_the_restart() {
+        let mut collector = MockCollector::new(vec![mock::sample_snapshot()]);
+        let mut app = test_app(&mut collector);
+
+        // Three samples from the previous session, then two live ones after a ten minute gap
+        let mut snapshot = mock::sample_snapshot();
+        snapshot.history_times = [1000, 1001, 1002, 1600, 1601].into();
+        snapshot.history_gap = Some(1002);
+        snapshot.cpu_history = vec![(0..5).map(|i| i as f32 * 10.0).collect(); 4];
+        snapshot.network_history = (0..5).map(|i| (i * 1000, 0)).collect();
+        collector.push(snapshot.clone());
+        app.receive_snapshot(collector.collect());
+        settle(&mut app);
+        render_lines(&mut app);
+        assert_eq!(app.snapshot.history_gap_index(5), Some(3));
+        // A shorter series is aligned with the newest samples
+        assert_eq!(app.snapshot.history_gap_index(3), Some(1));
+
+        // Once the restored samples scroll out there is nothing left to mark
+        snapshot.history_times = [1600, 1601, 1602].into();
+        collector.push(snapshot);
+        app.receive_snapshot(collector.collect());
+        settle(&mut app);
+        assert_eq!(app.snapshot.history_gap_index(3), None);
+
+        let saved = SavedHistory::from_snapshot(&app.target_snapshot, 1603);
+        assert_eq!(saved.times, [1600, 1601, 1602]);
+        assert_eq!(saved.cpu.len(), 4);
+    }
+
+    #[test]
+    fn test_graph_cursor_reads_exact_network_sample() {
+        let mut collector = MockCollector::new(vec![mock::sample_snapshot()]);
+        let mut app = test_app(&mut collector);
+
+        let start = chrono::Local::now().timestamp() - 4;
+        let mut snapshot = mock::sample_snapshot();
+        snapshot.networks = vec![mock::network("eth0")];
+        snapshot.network_history = (0..5).map(|i| (i * 1024, 0)).collect();
+        snapshot.history_times = (start..start + 5).collect();
+        collector.push(snapshot.clone());
+        app.receive_snapshot(collector.collect());
+        settle(&mut app);
+
+        // Process list → CPU → GPU → Memory → Network, then two samples back from the newest
+        for _ in 0..4 {
+            press(&mut app, KeyCode::Tab);
+        }
+        for _ in 0..3 {
+            press(&mut app, KeyCode::Left);
+        }
+        let readout = format!("◆ {} RX 2.0KB/s", crate::format::clock(start + 2));
+        assert!(row_of(&render_lines(&mut app), &readout).is_some());
+
+        // A new sample scrolls the history; the cursor stays on the same moment
+        snapshot.network_history.push_back((9 * 1024, 0));
+        snapshot.history_times.push_back(start + 5);
+        collector.push(snapshot);
+        app.receive_snapshot(collector.collect());
+        settle(&mut app);
+        assert!(row_of(&render_lines(&mut app), &readout).is_some());
+
+        press(&mut app, KeyCode::Esc);
+        assert!(!app.should_quit);
+        assert!(row_of(&render_lines(&mut app), "◆").is_none());
+    }
+
+    #[test]
+    fn test_speed_test_needs_an_endpoint() {
+        let mut collector = MockCollector::new(vec![mock::sample_snapshot()]);
+        let mut app = test_app(&mut collector);
+        assert!(row_of(&render_lines(&mut app), "Speed test").is_none());
+
+        press(&mut app, KeyCode::Char('B'));
+        assert!(row_of(&render_lines(&mut app), "Speed test set speedtest_endpoint").is_some());
+    }
+
+    #[test]
+    fn test_tool_menu_expands_selected_pid() {
+        let mut collector = MockCollector::new(vec![mock::sample_snapshot()]);
+        let mut app = test_app(&mut collector);
+
+        press(&mut app, KeyCode::Char('x'));
+        assert!(row_of(&render_lines(&mut app), "strace -f -p 200").is_some());
+
+        // The tools come after the built-in entries
+        for _ in 0..10 {
+            press(&mut app, KeyCode::Down);
+        }
+        press(&mut app, KeyCode::Enter);
+        assert!(!app.tool_menu.visible);
+        assert_eq!(
+            app.pending_command,
+            Some(vec!["gdb".to_string(), "-p".to_string(), "200".to_string()])
+        );
+    }
+
+    #[test]
+    fn test_m_opens_the_process_menu_with_affinity_and_watch() {
+        let mut collector = MockCollector::new(vec![mock::sample_snapshot()]);
+        let mut app = test_app(&mut collector);
+
+        press(&mut app, KeyCode::Char('m'));
+        assert!(app.tool_menu.visible);
+        let lines = render_lines(&mut app);
+        assert!(row_of(&lines, "Pin to the core it last ran on").is_some());
+        assert!(row_of(&lines, "Run on every core").is_some());
+        assert!(row_of(&lines, "Watch: keep it selected").is_some());
+
+        press(&mut app, KeyCode::Char('m'));
+        assert!(!app.tool_menu.visible);
+    }
+
+    #[test]
+    fn test_mouse_events_do_not_reach_the_list_behind_a_popup() {
+        use crossterm::event::{MouseButton, MouseEventKind};
+
+        let mut collector = MockCollector::new(vec![mock::sample_snapshot()]);
+        let mut app = test_app(&mut collector);
+        let lines = render_lines(&mut app);
+        let row = row_of(&lines, "charlie").unwrap() as u16;
+        let selected = app.process_component.selected_process();
+        assert_ne!(selected.as_ref().map(|(pid, _)| *pid), Some(300));
+
+        press(&mut app, KeyCode::Char('m'));
+        for kind in [
+            MouseEventKind::Down(MouseButton::Left),
+            MouseEventKind::Down(MouseButton::Right),
+            MouseEventKind::ScrollDown,
+        ] {
+            let event = MouseEvent {
+                kind,
+                column: app.process_component.area.x + 4,
+                row,
+                modifiers: KeyModifiers::NONE,
+            };
+            assert!(app.handle_mouse_events(event).unwrap().is_none());
+        }
+        assert!(app.tool_menu.visible);
+        assert_eq!(app.process_component.selected_process(), selected);
+    }
+
+    #[test]
+    fn test_right_click_menu_runs_builtin_actions_on_that_process() {
+        use crossterm::event::{MouseButton, MouseEventKind};
+
+        let mut collector = MockCollector::new(vec![mock::sample_snapshot()]);
+        let mut app = test_app(&mut collector);
+        let lines = render_lines(&mut app);
+        let row = row_of(&lines, "charlie").unwrap() as u16;
+        let event = MouseEvent {
+            kind: MouseEventKind::Down(MouseButton::Right),
+            column: app.column_areas[1].x + 4,
+            row,
+            modifiers: KeyModifiers::NONE,
+        };
+        if let Some(action) = app.handle_mouse_events(event).unwrap() {
+            app.handle_action(action).unwrap();
+        }
+        assert_eq!(app.tool_menu.target_pid(), Some(300));
+        let lines = render_lines(&mut app);
+        assert!(row_of(&lines, "Kill with signal…").is_some());
+        assert!(row_of(&lines, "Open in strace").is_some());
+
+        for _ in 0..7 {
+            press(&mut app, KeyCode::Down);
+        }
+        press(&mut app, KeyCode::Enter);
+        assert!(!app.tool_menu.visible);
+        assert_eq!(app.pending_clipboard.as_deref(), Some("300 charlie"));
+        assert_eq!(app.pending_command, None);
+
+        // Read-only mode leaves out the entries that change the process
+        app.set_read_only(true);
+        press(&mut app, KeyCode::Char('x'));
+        let lines = render_lines(&mut app);
+        assert!(row_of(&lines, "Details").is_some());
+        assert!(row_of(&lines, "Kill with signal…").is_none());
+        assert!(row_of(&lines, "Lower priority").is_none());
+        assert!(row_of(&lines, "Open in gdb").is_none());
+        press(&mut app, KeyCode::Esc);
+    }
+
+    #[test]
+    fn test_read_only_refuses_external_tools() {
+        let mut collector = MockCollector::new(vec![mock::sample_snapshot()]);
+        let mut app = test_app(&mut collector);
+
+        // A menu opened before read-only mode still lists the tools; running one is refused
+        press(&mut app, KeyCode::Char('x'));
+        app.set_read_only(true);
+        for _ in 0..10 {
+            press(&mut app, KeyCode::Down);
+        }
+        assert!(matches!(
+            app.tool_menu.selected_entry(),
+            Some(MenuEntry::Tool(tool)) if tool.name == "gdb"
+        ));
+        press(&mut app, KeyCode::Enter);
+        assert_eq!(app.pending_command, None);
+
+        press(&mut app, KeyCode::Esc);
+        for key in '1'..='5' {
+            press(&mut app, KeyCode::Char(key));
+        }
+        let lines = render_lines(&mut app);
+        assert!(row_of(&lines, "read-only: changing processes is disabled").is_some());
+    }
+
+    #[test]
+    fn test_search_filters_process_list() {
+        let mut collector = MockCollector::new(vec![mock::sample_snapshot()]);
+        let mut app = test_app(&mut collector);
+
+        press(&mut app, KeyCode::Char('S'));
+        for c in "char".chars() {
+            press(&mut app, KeyCode::Char(c));
+        }
+        press(&mut app, KeyCode::Enter);
+
+        let lines = render_lines(&mut app);
+        assert!(row_of(&lines, "charlie").is_some());
+        assert!(row_of(&lines, "alpha").is_none());
+        assert!(row_of(&lines, "bravo").is_none());
+    }
+
+    #[test]
+    fn test_search_regex_and_fuzzy_modes_highlight_matches() {
+        let mut collector = MockCollector::new(vec![mock::sample_snapshot()]);
+        let mut app = test_app(&mut collector);
+
+        press(&mut app, KeyCode::Char('S'));
+        press(&mut app, KeyCode::Tab);
+        press(&mut app, KeyCode::Char('('));
+        let lines = render_lines(&mut app);
+        assert!(row_of(&lines, "search (regex): (_").is_some());
+        assert!(row_of(&lines, "unclosed group").is_some());
+        press(&mut app, KeyCode::Backspace);
+        for c in "^[ab]".chars() {
+            press(&mut app, KeyCode::Char(c));
+        }
+        press(&mut app, KeyCode::Enter);
+        let lines = render_lines(&mut app);
+        assert!(row_of(&lines, "regex:^[a").is_some());
+        assert!(row_of(&lines, "alpha").is_some());
+        assert!(row_of(&lines, "bravo").is_some());
+        assert!(row_of(&lines, "charlie").is_none());
+
+        // The mode sticks between searches; Tab moves on to fuzzy
+        press(&mut app, KeyCode::Char('S'));
+        for _ in 0..5 {
+            press(&mut app, KeyCode::Backspace);
+        }
+        press(&mut app, KeyCode::Tab);
+        for c in "cre".chars() {
+            press(&mut app, KeyCode::Char(c));
+        }
+        press(&mut app, KeyCode::Enter);
+        let lines = render_lines(&mut app);
+        assert!(row_of(&lines, "alpha").is_none());
+        let row = row_of(&lines, "charlie").expect("charlie matches c…r…e");
+        let at = lines[row][..lines[row].find("charlie").unwrap()]
+            .chars()
+            .count() as u16;
+        let buffer = app.render_buffer(160, 48).unwrap();
+        let underlined = |offset: u16| {
+            buffer[(at + offset, row as u16)]
+                .modifier
+                .contains(ratatui::style::Modifier::UNDERLINED)
+        };
+        assert!(underlined(0) && underlined(3) && underlined(6));
+        assert!(!underlined(1) && !underlined(2));
+    }
+
+    #[test]
+    fn test_protected_process_kill_requires_typing_yes() {
+        let mut collector = MockCollector::new(vec![mock::sample_snapshot()]);
+        let mut app = test_app(&mut collector);
+        app.process_component.protected_processes = vec!["alph*".to_string()];
+
+        press(&mut app, KeyCode::Char('S'));
+        for c in "alpha".chars() {
+            press(&mut app, KeyCode::Char(c));
+        }
+        press(&mut app, KeyCode::Enter);
+
+        // Even the no-questions-asked kill key stops at the warning
+        press(&mut app, KeyCode::Char('K'));
+        assert!(app.process_component.is_typed_kill_confirm_active());
+        assert!(row_of(&render_lines(&mut app), "'alpha' (PID 100) is a protected").is_some());
+
+        // Enter alone, or with anything but "yes", keeps the dialog open
+        press(&mut app, KeyCode::Enter);
+        assert!(app.process_component.is_kill_confirm_active());
+        for c in "yep".chars() {
+            press(&mut app, KeyCode::Char(c));
+        }
+        press(&mut app, KeyCode::Enter);
+        assert!(app.process_component.is_kill_confirm_active());
+        assert!(row_of(&render_lines(&mut app), "Type yes to terminate: yep").is_some());
+
+        press(&mut app, KeyCode::Esc);
+        assert!(!app.process_component.is_kill_confirm_active());
+    }
+
+    #[test]
+    fn test_read_only_refuses_kills_and_hides_their_keys() {
+        let mut collector = MockCollector::new(vec![mock::sample_snapshot()]);
+        let mut app = test_app(&mut collector);
+        assert!(row_of(&render_lines(&mut app), "[k] kill").is_some());
+
+        app.set_read_only(true);
+        press(&mut app, KeyCode::Char(' '));
+        press(&mut app, KeyCode::Char('k'));
+        assert!(!app.process_component.is_kill_confirm_active());
+        assert_eq!(app.process_component.marked_count(), 0);
+
+        // Hide the side panels so the process header has room for the status message
+        for key in '1'..='5' {
+            press(&mut app, KeyCode::Char(key));
+        }
+        let lines = render_lines(&mut app);
+        assert!(row_of(&lines, "read-only: changing processes is disabled").is_some());
+        assert!(lines[0].contains(" read-only "));
+        assert!(row_of(&lines, "[k] kill").is_none());
+    }
+
+    #[test]
+    fn test_marked_processes_share_one_kill_dialog() {
+        let mut collector = MockCollector::new(vec![mock::sample_snapshot()]);
+        let mut app = test_app(&mut collector);
+
+        press(&mut app, KeyCode::Char(' '));
+        press(&mut app, KeyCode::Char(' '));
+        assert_eq!(app.process_component.marked_count(), 2);
+        assert!(row_of(&render_lines(&mut app), "· 2 marked").is_some());
+
+        press(&mut app, KeyCode::Char('k'));
+        let lines = render_lines(&mut app);
+        assert!(row_of(&lines, "Signal these 2 processes?").is_some());
+        assert!(row_of(&lines, "200  bravo").is_some());
+
+        // Cancelling keeps the marks; Esc then clears them instead of quitting
+        press(&mut app, KeyCode::Esc);
+        assert!(!app.process_component.is_kill_confirm_active());
+        assert_eq!(app.process_component.marked_count(), 2);
+        press(&mut app, KeyCode::Esc);
+        assert_eq!(app.process_component.marked_count(), 0);
+        assert!(!app.should_quit);
+    }
+
+    #[test]
+    fn test_kill_dialog_offers_restarting_the_systemd_unit() {
+        let mut collector = MockCollector::new(vec![mock::sample_snapshot()]);
+        let mut app = test_app(&mut collector);
+        app.process_component.unit_of = |_| {
+            Some(crate::components::process::SystemdUnit {
+                name: "nginx.service".to_string(),
+                user: false,
+            })
+        };
+
+        press(&mut app, KeyCode::Char('k'));
+        let lines = render_lines(&mut app);
+        assert!(row_of(&lines, "'bravo' (PID 200) runs in").is_some());
+        assert!(row_of(&lines, "nginx.service. Restart the unit").is_some());
+        assert!(row_of(&lines, "Restart unit").is_some());
+
+        // Restart, Terminate, No, and back around
+        for _ in 0..3 {
+            press(&mut app, KeyCode::Tab);
+        }
+        assert!(app.process_component.is_kill_confirm_active());
+        press(&mut app, KeyCode::Esc);
+        assert!(!app.process_component.is_kill_confirm_active());
+    }
+
+    #[test]
+    fn test_kill_dialog_picks_a_signal() {
+        let mut collector = MockCollector::new(vec![mock::sample_snapshot()]);
+        let mut app = test_app(&mut collector);
+
+        // Whether `name` is the highlighted signal in the picker
+        let highlighted = |app: &mut App, name: &str| {
+            let lines = render_lines(app);
+            let row = row_of(&lines, "Signal:").expect("signal picker");
+            let at = lines[row].find(&format!(" {} ", name)).unwrap();
+            let column = lines[row][..at].chars().count() as u16 + 1;
+            let buffer = app.render_buffer(160, 48).unwrap();
+            buffer[(column, row as u16)]
+                .modifier
+                .contains(ratatui::style::Modifier::BOLD)
+        };
+
+        press(&mut app, KeyCode::Char('k'));
+        assert!(highlighted(&mut app, "TERM"));
+        assert!(row_of(&render_lines(&mut app), "Confirm Termination").is_some());
+        press(&mut app, KeyCode::Down);
+        press(&mut app, KeyCode::Down);
+        assert!(highlighted(&mut app, "STOP"));
+        assert!(!highlighted(&mut app, "TERM"));
+        // The dialog names the signal it will send
+        let lines = render_lines(&mut app);
+        assert!(row_of(&lines, "Confirm SIGSTOP").is_some());
+        assert!(row_of(&lines, "Send SIGSTOP to").is_some());
+        assert!(row_of(&lines, "Confirm Termination").is_none());
+        press(&mut app, KeyCode::Up);
+        assert!(highlighted(&mut app, "KILL"));
+        assert!(row_of(&render_lines(&mut app), "↑/↓: signal").is_some());
+
+        press(&mut app, KeyCode::Esc);
+        assert!(!app.process_component.is_kill_confirm_active());
+    }
+
+    #[test]
+    fn test_irix_mode_toggles_process_cpu_scale() {
+        let mut snapshot = mock::sample_snapshot();
+        snapshot.processes = vec![mock::process(100, "encoder", 240.0, 1024)];
+        let mut collector = MockCollector::new(vec![snapshot]);
+        let mut app = test_app(&mut collector);
+
+        let lines = render_lines(&mut app);
+        assert!(row_of(&lines, "cpu:irix").is_some());
+        assert!(lines[row_of(&lines, "encoder").unwrap()].contains("240.00%"));
+
+        // Four cores: Solaris mode shows the share of the whole machine
+        press(&mut app, KeyCode::Char('p'));
+        let lines = render_lines(&mut app);
+        assert!(row_of(&lines, "cpu:solaris").is_some());
+        assert!(lines[row_of(&lines, "encoder").unwrap()].contains(" 60.00%"));
+    }
+
+    #[test]
+    fn test_hide_idle_processes_keeps_count() {
+        let mut snapshot = mock::sample_snapshot();
+        snapshot.processes.extend([
+            mock::process(400, "sleepy", 0.05, 2 * 1024 * 1024),
+            mock::process(500, "dozy", 0.0, 1024 * 1024),
+            mock::process(600, "bigcache", 0.0, 500 * 1024 * 1024),
+        ]);
+        let mut collector = MockCollector::new(vec![snapshot]);
+        let mut app = test_app(&mut collector);
+        assert!(row_of(&render_lines(&mut app), "sleepy").is_some());
+
+        press(&mut app, KeyCode::Char('h'));
+        let lines = render_lines(&mut app);
+        assert!(row_of(&lines, "2 idle hidden").is_some());
+        assert!(row_of(&lines, "sleepy").is_none());
+        assert!(row_of(&lines, "dozy").is_none());
+        // Large but idle processes are not hidden, nor are busy small ones
+        assert!(row_of(&lines, "bigcache").is_some());
+        assert!(row_of(&lines, "bravo").is_some());
+    }
+
+    #[test]
+    fn test_origin_filter_separates_host_and_container_processes() {
+        let mut collector = MockCollector::new(vec![mock::sample_snapshot()]);
+        let mut app = test_app(&mut collector);
+
+        let mut snapshot = mock::sample_snapshot();
+        let mut postgres = mock::process(400, "postgres", 3.0, 200 << 20);
+        postgres.container = true;
+        snapshot.processes.push(postgres);
+        collector.push(snapshot);
+        app.receive_snapshot(collector.collect());
+        settle(&mut app);
+        // Hide the side panels so the process title has room for the filter
+        for key in '1'..='5' {
+            press(&mut app, KeyCode::Char(key));
+        }
+
+        press(&mut app, KeyCode::Char('N'));
+        let lines = render_lines(&mut app);
+        assert!(row_of(&lines, "· host").is_some());
+        assert!(row_of(&lines, "postgres").is_none());
+        assert!(row_of(&lines, "alpha").is_some());
+
+        press(&mut app, KeyCode::Char('N'));
+        let lines = render_lines(&mut app);
+        assert!(row_of(&lines, "· containers").is_some());
+        assert!(row_of(&lines, "postgres").is_some());
+        assert!(row_of(&lines, "alpha").is_none());
+
+        press(&mut app, KeyCode::Char('N'));
+        let lines = render_lines(&mut app);
+        assert!(row_of(&lines, "postgres").is_some());
+        assert!(row_of(&lines, "alpha").is_some());
+    }
+
+    #[test]
+    fn test_reverse_sort_flips_the_list_and_the_title_arrow() {
+        let mut collector = MockCollector::new(vec![mock::sample_snapshot()]);
+        let mut app = test_app(&mut collector);
+        for key in '1'..='5' {
+            press(&mut app, KeyCode::Char(key));
+        }
+
+        let lines = render_lines(&mut app);
+        assert!(row_of(&lines, "sort:CPU▼").is_some());
+        let busiest_first = row_of(&lines, "bravo").unwrap();
+        assert!(busiest_first < row_of(&lines, "alpha").unwrap());
+
+        press(&mut app, KeyCode::Char('o'));
+        let lines = render_lines(&mut app);
+        assert!(row_of(&lines, "sort:CPU▲").is_some());
+        assert!(row_of(&lines, "bravo").unwrap() > row_of(&lines, "alpha").unwrap());
+
+        // A new sort key starts in its natural direction; picking it again flips it
+        press(&mut app, KeyCode::Char('s'));
+        assert!(!app.snapshot.process_sort_reversed);
+        app.handle_action(Action::SortProcessesBy(ProcessSortBy::Name))
+            .unwrap();
+        assert!(row_of(&render_lines(&mut app), "sort:NAME▲").is_some());
+        app.handle_action(Action::SortProcessesBy(ProcessSortBy::Name))
+            .unwrap();
+        let lines = render_lines(&mut app);
+        assert!(row_of(&lines, "sort:NAME▼").is_some());
+        assert!(row_of(&lines, "charlie").unwrap() < row_of(&lines, "alpha").unwrap());
+
+        // The direction survives new snapshots
+        app.receive_snapshot(collector.collect());
+        assert!(app.snapshot.process_sort_reversed);
+    }
+
+    #[test]
+    fn test_clicking_column_title_sorts_processes() {
+        use crossterm::event::{MouseButton, MouseEventKind};
+
+        let mut collector = MockCollector::new(vec![mock::sample_snapshot()]);
+        let mut app = test_app(&mut collector);
+        render_lines(&mut app);
+        let list = app.column_areas[1];
+        let click = |app: &mut App, column: u16, row: u16| {
+            let event = MouseEvent {
+                kind: MouseEventKind::Down(MouseButton::Left),
+                column,
+                row,
+                modifiers: KeyModifiers::NONE,
+            };
+            if let Some(action) = app.handle_mouse_events(event).unwrap() {
+                app.handle_action(action).unwrap();
+            }
+            render_lines(app)
+        };
+
+        // PID is the first column; its title sits on the first row inside the border
+        let lines = click(&mut app, list.x + 4, list.y + 1);
+        assert_eq!(app.snapshot.process_sort_by, ProcessSortBy::Pid);
+        assert!(row_of(&lines, "charlie").unwrap() < row_of(&lines, "alpha").unwrap());
+
+        // Below the header a click does not touch the sort
+        click(&mut app, list.x + 4, list.y + 3);
+        assert_eq!(app.snapshot.process_sort_by, ProcessSortBy::Pid);
+
+        let lines = click(&mut app, list.right() - 4, list.y + 1);
+        assert_eq!(app.snapshot.process_sort_by, ProcessSortBy::Name);
+        assert!(row_of(&lines, "alpha").unwrap() < row_of(&lines, "bravo").unwrap());
+        assert!(lines.iter().any(|line| line.contains("sort:NAME")));
+    }
+
+    #[test]
+    fn test_selected_process_cpu_history_is_graphed() {
+        let mut collector = MockCollector::new(vec![mock::sample_snapshot()]);
+        let mut app = test_app(&mut collector);
+
+        let mut snapshot = mock::sample_snapshot();
+        let bravo = snapshot
+            .processes
+            .iter_mut()
+            .find(|process| process.name == "bravo")
+            .unwrap();
+        bravo.cpu_history = vec![10.0, 80.0, 20.0];
+        collector.push(snapshot);
+        app.receive_snapshot(collector.collect());
+        settle(&mut app);
+
+        // bravo tops the CPU sort, so it is selected
+        let lines = render_lines(&mut app);
+        assert!(lines.iter().any(|line| line.contains("peak 80.0%")));
+
+        // The detail view starts from the same samples
+        press(&mut app, KeyCode::Enter);
+        let lines = render_lines(&mut app);
+        assert!(lines.iter().any(|line| line.contains("over 3 samples")));
+    }
+
+    #[test]
+    fn test_gpu_column_and_detail_list_nvml_process_usage() {
+        let mut collector = MockCollector::new(vec![mock::sample_snapshot()]);
+        let mut app = test_app(&mut collector);
+        // Without NVML there is no GPU column at all
+        assert!(render_lines(&mut app)
+            .iter()
+            .all(|line| !line.contains("GPU%")));
+
+        let mut snapshot = mock::sample_snapshot();
+        snapshot.process_gpu_stats = true;
+        let usage = |utilization: Option<u32>, memory: u64| ProcessGpuUsage {
+            gpu: "RTX 3060".to_string(),
+            utilization,
+            encoder: 0,
+            decoder: 12,
+            memory: Some(memory),
+        };
+        snapshot.processes[1].gpu = vec![usage(Some(37), 512 * 1024 * 1024)];
+        snapshot.processes[2].gpu = vec![usage(None, 64 * 1024 * 1024)];
+        collector.push(snapshot);
+        app.receive_snapshot(collector.collect());
+        settle(&mut app);
+
+        let lines = render_lines(&mut app);
+        let header = row_of(&lines, "GPU%").unwrap();
+        assert!(lines[header].find("GPU%") < lines[header].find(" S "));
+        assert!(lines
+            .iter()
+            .any(|line| line.contains("bravo") && line.contains("37%")));
+
+        // bravo is selected; the detail view lists both GPU users, busiest first
+        press(&mut app, KeyCode::Enter);
+        let lines = render_lines(&mut app);
+        let section = row_of(&lines, "GPU processes").unwrap();
+        assert!(lines[section + 2].contains("bravo") && lines[section + 2].contains("512"));
+        assert!(lines[section + 3].contains("charlie") && lines[section + 3].contains(" - "));
+    }
+
+    #[test]
+    fn test_followed_process_stays_selected_as_the_list_resorts() {
+        let mut collector = MockCollector::new(vec![mock::sample_snapshot()]);
+        let mut app = test_app(&mut collector);
+        let selected = |app: &App| app.process_component.selected_process().map(|(pid, _)| pid);
+
+        // bravo tops the CPU sort; follow it while it drops to the bottom
+        press(&mut app, KeyCode::Char('F'));
+        assert!(row_of(&render_lines(&mut app), "· follow:").is_some());
+        let mut snapshot = mock::sample_snapshot();
+        snapshot.processes[1].cpu_usage = 1.0;
+        collector.push(snapshot.clone());
+        app.receive_snapshot(collector.collect());
+        settle(&mut app);
+        assert_eq!(selected(&app), Some(200));
+        assert_eq!(app.process_component.selected_index, 2);
+
+        // Moving the selection stops following
+        press(&mut app, KeyCode::Up);
+        snapshot.processes[1].cpu_usage = 90.0;
+        collector.push(snapshot.clone());
+        app.receive_snapshot(collector.collect());
+        settle(&mut app);
+        assert_eq!(selected(&app), Some(300));
+
+        // So does marking, which advances the selection
+        press(&mut app, KeyCode::Char('F'));
+        press(&mut app, KeyCode::Char(' '));
+        settle(&mut app);
+        assert_ne!(selected(&app), Some(300));
+        assert!(row_of(&render_lines(&mut app), "· follow:").is_none());
+        press(&mut app, KeyCode::Up);
+        assert_eq!(selected(&app), Some(300));
+
+        // Following ends when the process exits
+        press(&mut app, KeyCode::Char('F'));
+        snapshot.processes.retain(|process| process.pid != 300);
+        collector.push(snapshot);
+        app.receive_snapshot(collector.collect());
+        settle(&mut app);
+        assert!(row_of(&render_lines(&mut app), "· follow:").is_none());
+    }
+
+    #[test]
+    fn test_mouse_selects_and_scrolls_process_rows() {
+        use crossterm::event::{MouseButton, MouseEventKind};
+
+        let mut collector = MockCollector::new(vec![mock::sample_snapshot()]);
+        let mut app = test_app(&mut collector);
+        let lines = render_lines(&mut app);
+        let list = app.column_areas[1];
+        let mouse = |app: &mut App, kind, row: u16| {
+            let event = MouseEvent {
+                kind,
+                column: list.x + 4,
+                row,
+                modifiers: KeyModifiers::NONE,
+            };
+            if let Some(action) = app.handle_mouse_events(event).unwrap() {
+                app.handle_action(action).unwrap();
+            }
+        };
+
+        let charlie = row_of(&lines, "charlie").unwrap() as u16;
+        mouse(&mut app, MouseEventKind::Down(MouseButton::Left), charlie);
+        let selected = app.process_component.selected_process();
+        assert_eq!(selected.map(|(_, name)| name).as_deref(), Some("charlie"));
+
+        mouse(&mut app, MouseEventKind::ScrollUp, charlie);
+        assert_eq!(app.process_component.selected_index, 0);
+        mouse(&mut app, MouseEventKind::ScrollDown, charlie);
+        assert_eq!(app.process_component.selected_index, 2);
+        // Below the last row a click changes nothing
+        mouse(
+            &mut app,
+            MouseEventKind::Down(MouseButton::Left),
+            charlie + 5,
+        );
+        assert_eq!(app.process_component.selected_index, 2);
+
+        // The wheel over another panel leaves the list alone, and a click there focuses it
+        let (panel, area) = app.panel_areas[0];
+        let event = |kind| MouseEvent {
+            kind,
+            column: area.x + 2,
+            row: area.y + 2,
+            modifiers: KeyModifiers::NONE,
+        };
+        app.handle_mouse_events(event(MouseEventKind::ScrollUp))
+            .unwrap();
+        assert_eq!(app.process_component.selected_index, 2);
+        app.handle_mouse_events(event(MouseEventKind::Down(MouseButton::Left)))
+            .unwrap();
+        assert_eq!(app.focused_panel, Some(panel));
+    }
+
+    #[test]
+    fn test_category_filter_cycles_through_kinds() {
+        let mut collector = MockCollector::new(vec![mock::sample_snapshot()]);
+        let mut app = test_app(&mut collector);
+
+        let mut snapshot = mock::sample_snapshot();
+        snapshot
+            .processes
+            .push(mock::process(400, "firefox", 12.0, 800 << 20));
+        snapshot
+            .processes
+            .push(mock::process(500, "-zsh", 0.0, 8 << 20));
+        collector.push(snapshot);
+        app.receive_snapshot(collector.collect());
+        settle(&mut app);
+        // The TYPE column is among the first dropped, so give the list the whole width
+        for key in '1'..='5' {
+            press(&mut app, KeyCode::Char(key));
+        }
+        let lines = render_lines(&mut app);
+        assert!(lines[row_of(&lines, "firefox").unwrap()].contains(" web "));
+
+        press(&mut app, KeyCode::Char('y'));
+        let lines = render_lines(&mut app);
+        assert!(row_of(&lines, "· browsers").is_some());
+        assert!(row_of(&lines, "firefox").is_some());
+        assert!(row_of(&lines, "alpha").is_none());
+
+        press(&mut app, KeyCode::Char('y'));
+        let lines = render_lines(&mut app);
+        assert!(row_of(&lines, "-zsh").is_some());
+        assert!(row_of(&lines, "firefox").is_none());
+
+        // Past the last category the filter is off again
+        for _ in 0..4 {
+            press(&mut app, KeyCode::Char('y'));
+        }
+        assert_eq!(app.process_component.category, None);
+    }
+
+    #[test]
+    fn test_owner_filter_shows_mine_or_root() {
+        let mut collector = MockCollector::new(vec![mock::sample_snapshot()]);
+        let mut app = test_app(&mut collector);
+        app.process_component.own_uid = Some(1000);
+
+        let mut snapshot = mock::sample_snapshot();
+        for (process, uid) in snapshot.processes.iter_mut().zip([1000, 0, 1001]) {
+            process.uid = Some(uid);
+        }
+        snapshot.processes[0].user = Some("alice".to_string());
+        snapshot.processes[1].user = Some("root".to_string());
+        collector.push(snapshot);
+        app.receive_snapshot(collector.collect());
+        settle(&mut app);
+        let lines = render_lines(&mut app);
+        assert!(lines[row_of(&lines, "alpha").unwrap()].contains(" alice "));
+        assert!(lines[row_of(&lines, "bravo").unwrap()].contains(" root "));
+        // Without a name in the users list the UID is shown
+        assert!(lines[row_of(&lines, "charlie").unwrap()].contains(" 1001 "));
+
+        press(&mut app, KeyCode::Char('u'));
+        let lines = render_lines(&mut app);
+        assert!(row_of(&lines, "· mine").is_some());
+        assert!(row_of(&lines, "alpha").is_some());
+        assert!(row_of(&lines, "bravo").is_none());
+        assert!(row_of(&lines, "charlie").is_none());
+
+        press(&mut app, KeyCode::Char('u'));
+        let lines = render_lines(&mut app);
+        assert!(row_of(&lines, "bravo").is_some());
+        assert!(row_of(&lines, "alpha").is_none());
+
+        press(&mut app, KeyCode::Char('u'));
+        assert_eq!(
+            app.process_component.owner,
+            crate::components::process::ProcessOwner::All
+        );
+    }
+
+    #[test]
+    fn test_pid_table_near_limit_raises_alert() {
+        let mut collector = MockCollector::new(vec![mock::sample_snapshot()]);
+        let mut app = test_app(&mut collector);
+
+        let mut crowded = mock::sample_snapshot();
+        crowded.kernel_limits = Some(KernelLimits {
+            fds_allocated: 1_000,
+            fds_max: 100_000,
+            tasks: 31_500,
+            pid_max: 32_768,
+            ..KernelLimits::default()
+        });
+        collector.push(crowded);
+        app.receive_snapshot(collector.collect());
+        settle(&mut app);
+
+        let alert = app.alerts.headline().expect("pid alert");
+        assert_eq!(alert.key, "pid-exhaustion");
+        assert_eq!(alert.severity, AlertSeverity::Critical);
+        assert!(row_of(&render_lines(&mut app), "pid 31.5k/32.8k").is_some());
+    }
+
+    #[test]
+    fn test_memory_bar_splits_buffers_and_cache() {
+        let mut collector = MockCollector::new(vec![mock::sample_snapshot()]);
+        let mut app = test_app(&mut collector);
+
+        let lines = render_lines(&mut app);
+        // 16 GiB - 7 free - 0.5 buffers - 3 cache, as `free` reports it
+        assert!(row_of(&lines, "RAM: 5.5GB/16.0GB").is_some());
+        let legend = row_of(&lines, "buf 512.0MB").expect("legend");
+        assert!(lines[legend].contains("cache 3.0GB"));
+        assert!(lines[legend].contains("avail 10.0GB"));
+    }
+
+    #[test]
+    fn test_zfs_arc_is_not_counted_as_used() {
+        let mut collector = MockCollector::new(vec![mock::sample_snapshot()]);
+        let mut app = test_app(&mut collector);
+
+        let mut zfs = mock::sample_snapshot();
+        zfs.zfs_arc = Some(ZfsArc {
+            size: 4 * 1024 * 1024 * 1024,
+            target: 6 * 1024 * 1024 * 1024,
+            min: 1024 * 1024 * 1024,
+        });
+        collector.push(zfs);
+        app.receive_snapshot(collector.collect());
+        settle(&mut app);
+
+        let lines = render_lines(&mut app);
+        assert!(row_of(&lines, "RAM: 1.5GB/16.0GB").is_some());
+        assert!(row_of(&lines, "arc 4.0GB/6.0GB").is_some());
+    }
+
+    #[test]
+    fn test_memory_pressure_ignores_cache() {
+        let mut collector = MockCollector::new(vec![mock::sample_snapshot()]);
+        let mut app = test_app(&mut collector);
+
+        // Nearly all RAM "used", but most of it is reclaimable cache
+        let mut cached = mock::sample_snapshot();
+        cached.used_memory = 15 * 1024 * 1024 * 1024;
+        cached.available_memory = 8 * 1024 * 1024 * 1024;
+        collector.push(cached.clone());
+        app.receive_snapshot(collector.collect());
+        settle(&mut app);
+        assert!(app.alerts.headline().is_none());
+
+        cached.available_memory = 512 * 1024 * 1024;
+        collector.push(cached);
+        app.receive_snapshot(collector.collect());
+        settle(&mut app);
+        let alert = app.alerts.headline().expect("pressure alert");
+        assert_eq!(alert.key, "memory-pressure");
+        assert_eq!(alert.severity, AlertSeverity::Critical);
+    }
+
+    #[test]
+    fn test_memory_pressure_discounts_shrinkable_zfs_arc() {
+        let mut collector = MockCollector::new(vec![mock::sample_snapshot()]);
+        let mut app = test_app(&mut collector);
+
+        // MemAvailable leaves out the ARC, which holds most of RAM but can shrink to 1GB
+        let mut zfs = mock::sample_snapshot();
+        zfs.available_memory = 512 * 1024 * 1024;
+        zfs.zfs_arc = Some(ZfsArc {
+            size: 12 * 1024 * 1024 * 1024,
+            target: 12 * 1024 * 1024 * 1024,
+            min: 1024 * 1024 * 1024,
+        });
+        assert!((zfs.memory_pressure_percent() - 28.125).abs() < 0.01);
+        collector.push(zfs.clone());
+        app.receive_snapshot(collector.collect());
+        settle(&mut app);
+        assert!(app.alerts.headline().is_none());
+
+        // An ARC already at its floor has nothing left to give
+        zfs.zfs_arc = Some(ZfsArc {
+            size: 1024 * 1024 * 1024,
+            target: 1024 * 1024 * 1024,
+            min: 1024 * 1024 * 1024,
+        });
+        collector.push(zfs);
+        app.receive_snapshot(collector.collect());
+        settle(&mut app);
+        let alert = app.alerts.headline().expect("pressure alert");
+        assert_eq!(alert.key, "memory-pressure");
+    }
+
+    #[test]
+    fn test_cleared_alert_is_announced_and_logged() {
+        let mut collector = MockCollector::new(vec![mock::sample_snapshot()]);
+        let mut app = test_app(&mut collector);
+
+        let mut tight = mock::sample_snapshot();
+        tight.available_memory = 512 * 1024 * 1024;
+        collector.push(tight);
+        collector.push(mock::sample_snapshot());
+        app.receive_snapshot(collector.collect());
+        settle(&mut app);
+        assert!(row_of(&render_lines(&mut app), "⚠ memory pressure 97%").is_some());
+
+        app.receive_snapshot(collector.collect());
+        settle(&mut app);
+        assert!(app.alerts.headline().is_none());
+        let lines = render_lines(&mut app);
+        assert!(lines[0].contains("✓ cleared after 0s: memory pressure 97%"));
+
+        press(&mut app, KeyCode::Char('E'));
+        let lines = render_lines(&mut app);
+        let raised = row_of(&lines, "⚠ memory pressure 97%").expect("raised event");
+        let cleared = row_of(&lines, "· cleared after 0s").expect("cleared event");
+        assert!(cleared < raised, "newest event first");
+    }
+
+    #[test]
+    fn test_escalated_alert_is_logged() {
+        let mut collector = MockCollector::new(vec![mock::sample_snapshot()]);
+        let mut app = test_app(&mut collector);
+
+        let mut tight = mock::sample_snapshot();
+        tight.available_memory = 2 * 1024 * 1024 * 1024;
+        collector.push(tight.clone());
+        tight.available_memory = 512 * 1024 * 1024;
+        collector.push(tight);
+        app.receive_snapshot(collector.collect());
+        settle(&mut app);
+        app.receive_snapshot(collector.collect());
+        settle(&mut app);
+
+        let events = app.alerts.events();
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].severity, AlertSeverity::Warning);
+        assert_eq!(events[1].severity, AlertSeverity::Critical);
+        assert!(events[1].message.contains("memory pressure 97%"));
+        assert_eq!(events[1].recovered_after, None);
+    }
+
+    #[test]
+    fn test_history_view_charts_hourly_rollups() {
+        let mut collector = MockCollector::new(vec![mock::sample_snapshot()]);
+        let mut app = test_app(&mut collector);
+        app.receive_snapshot(collector.collect());
+        settle(&mut app);
+        assert_eq!(app.rollups.hours.len(), 1);
+        assert_eq!(app.rollups.hours[0].cpu_percent, 25.0);
+
+        press(&mut app, KeyCode::Char('H'));
+        let lines = render_lines(&mut app);
+        assert!(row_of(&lines, "History · last 24 hours").is_some());
+        assert!(row_of(&lines, "Memory pressure").is_some());
+        assert!(row_of(&lines, "Disk I/O").is_some());
+
+        press(&mut app, KeyCode::Tab);
+        assert!(row_of(&render_lines(&mut app), "History · last 7 days").is_some());
+        press(&mut app, KeyCode::Esc);
+        assert!(!app.should_quit);
+        assert!(row_of(&render_lines(&mut app), "History · last").is_none());
+    }
+
+    #[test]
+    fn test_disk_panel_lists_top_io_processes() {
+        let mut collector = MockCollector::new(vec![mock::sample_snapshot()]);
+        let mut app = test_app(&mut collector);
+        assert!(row_of(&render_lines(&mut app), "Top I/O").is_none());
+
+        let mut busy = mock::sample_snapshot();
+        busy.processes[0].disk_write_per_sec = 40 * 1024 * 1024;
+        busy.processes[2].disk_read_per_sec = 2 * 1024 * 1024;
+        collector.push(busy);
+        app.receive_snapshot(collector.collect());
+        settle(&mut app);
+
+        let lines = render_lines(&mut app);
+        let header = row_of(&lines, "Top I/O").expect("top I/O list");
+        assert!(lines[header + 1].contains("alpha"));
+        assert!(lines[header + 1].contains("W  40.0MB/s"));
+        assert!(lines[header + 2].contains("charlie"));
+        assert!(!lines[header + 3].contains("bravo"));
+    }
+
+    #[test]
+    fn test_session_stats_popup_shows_peaks() {
+        let mut collector = MockCollector::new(vec![mock::sample_snapshot()]);
+        let mut app = test_app(&mut collector);
+
+        let mut busy = mock::sample_snapshot();
+        busy.global_cpu_usage = 87.5;
+        collector.push(busy);
+        app.receive_snapshot(collector.collect());
+        collector.push(mock::sample_snapshot());
+        app.receive_snapshot(collector.collect());
+        settle(&mut app);
+
+        press(&mut app, KeyCode::Char('I'));
+        let lines = render_lines(&mut app);
+        let row = row_of(&lines, "PEAK AT").expect("stats popup") + 1;
+        assert!(lines[row].contains("25.0%"));
+        assert!(lines[row].contains("87.5%"));
+        press(&mut app, KeyCode::Esc);
+        assert!(!app.stats_popup.visible);
+    }
+
+    #[test]
+    fn test_session_running_time_and_monitoring_start() {
+        let mut collector = MockCollector::new(vec![mock::sample_snapshot()]);
+        let mut app = test_app(&mut collector);
+        app.session_stats.started = chrono::Local::now() - chrono::Duration::seconds(3725);
+        app.config.status_template = "{hostname}  {session}".to_string();
+        assert!(render_lines(&mut app)[0].contains("host:mockhost  session:1h 2m "));
+
+        // Graphs restored from the previous session start before the statistics do
+        let mut restored = mock::sample_snapshot();
+        let earlier = app.session_stats.started - chrono::Duration::minutes(30);
+        restored.history_times = std::collections::VecDeque::from([earlier.timestamp()]);
+        collector.push(restored);
+        app.receive_snapshot(collector.collect());
+        settle(&mut app);
+
+        press(&mut app, KeyCode::Char('I'));
+        let lines = render_lines(&mut app);
+        let footer = row_of(&lines, "monitoring since").expect("session start");
+        assert!(lines[footer].contains("running 1h 2m"));
+        assert!(lines[footer + 1].contains(&format!(
+            "graphs reach back to {}",
+            earlier.format("%a %H:%M")
+        )));
+    }
+
+    #[test]
+    fn test_filling_disk_raises_alert() {
+        let mut collector = MockCollector::new(vec![mock::sample_snapshot()]);
+        let mut app = test_app(&mut collector);
+        assert!(app.alerts.headline().is_none());
+
+        let mut filling = mock::sample_snapshot();
+        filling.disks[0].fill_rate = Some(1024.0 * 1024.0);
+        filling.disks[0].time_to_full = Some(30 * 60);
+        collector.push(filling);
+        app.receive_snapshot(collector.collect());
+        settle(&mut app);
+
+        let alert = app.alerts.headline().expect("disk alert");
+        assert_eq!(alert.key, "disk-full:/");
+        assert_eq!(alert.severity, AlertSeverity::Critical);
+        assert!(render_lines(&mut app)[0].contains("/ full in ~30m"));
+    }
+
+    #[test]
+    fn test_snapshot_diff_against_bookmark() {
+        let mut collector = MockCollector::new(vec![mock::sample_snapshot()]);
+        let mut app = test_app(&mut collector);
+
+        press(&mut app, KeyCode::Char('d'));
+        assert!(row_of(&render_lines(&mut app), "No bookmark yet").is_some());
+        press(&mut app, KeyCode::Char('v'));
+        press(&mut app, KeyCode::Esc);
+        assert!(!app.compare_popup.visible);
+
+        let mut deployed = mock::sample_snapshot();
+        deployed.processes.retain(|process| process.name != "bravo");
+        deployed
+            .processes
+            .push(mock::process(900, "deploy-agent", 60.0, 200 << 20));
+        collector.push(deployed);
+        app.receive_snapshot(collector.collect());
+        settle(&mut app);
+
+        press(&mut app, KeyCode::Char('d'));
+        let lines = render_lines(&mut app);
+        assert!(row_of(&lines, "Started (1)").is_some());
+        assert!(row_of(&lines, "+     900 deploy-agent").is_some());
+        assert!(row_of(&lines, "Exited (1)").is_some());
+        assert!(row_of(&lines, "-     200 bravo").is_some());
+
+        // An open popup follows new snapshots
+        let mut later = collector.collect();
+        later
+            .processes
+            .push(mock::process(901, "deploy-worker", 10.0, 50 << 20));
+        app.receive_snapshot(later);
+        assert!(row_of(&render_lines(&mut app), "Started (2)").is_some());
+    }
+
+    #[test]
+    fn test_critical_alert_rings_bell_and_flashes_once() {
+        let mut collector = MockCollector::new(vec![mock::sample_snapshot()]);
+        let mut app = test_app(&mut collector);
+        app.config.alert_bell = crate::config::AlertBell::Both;
+
+        let mut filling = mock::sample_snapshot();
+        filling.disks[0].time_to_full = Some(20 * 3600);
+        collector.push(filling.clone());
+        app.receive_snapshot(collector.collect());
+        // A warning does not call for attention
+        assert!(!app.bell_pending && app.flash_until.is_none());
+
+        filling.disks[0].time_to_full = Some(30 * 60);
+        collector.push(filling.clone());
+        app.receive_snapshot(collector.collect());
+        assert!(app.bell_pending && app.flash_until.is_some());
+        settle(&mut app);
+        let mut terminal = Terminal::new(TestBackend::new(160, 48)).unwrap();
+        terminal.draw(|f| app.render(f)).unwrap();
+        assert!(terminal.backend().buffer()[(0, 0)]
+            .modifier
+            .contains(ratatui::style::Modifier::REVERSED));
+
+        // Staying critical does not ring again
+        app.bell_pending = false;
+        app.flash_until = None;
+        collector.push(filling);
+        app.receive_snapshot(collector.collect());
+        assert!(!app.bell_pending && app.flash_until.is_none());
+    }
+
+    #[test]
+    fn test_stale_network_mount_is_marked_and_alerts() {
+        let mut collector = MockCollector::new(vec![mock::sample_snapshot()]);
+        let mut app = test_app(&mut collector);
+
+        let mut hung = mock::sample_snapshot();
+        hung.disks[0].name = "nas:/export".to_string();
+        hung.disks[0].remote = Some(crate::data::snapshot::RemoteMount {
+            latency_ms: Some(12.0),
+            stale_secs: Some(42),
+        });
+        collector.push(hung);
+        app.receive_snapshot(collector.collect());
+        settle(&mut app);
+
+        let alert = app.alerts.headline().expect("stale mount alert");
+        assert_eq!(alert.key, "disk-stale:nas:/export");
+        assert_eq!(alert.severity, AlertSeverity::Critical);
+        assert!(render_lines(&mut app)
+            .iter()
+            .any(|line| line.contains("nas:/export") && line.contains("stale 42s")));
+    }
+
+    #[test]
+    fn test_gpu_temperature_alerts_follow_vendor_and_configured_limits() {
+        let mut collector = MockCollector::new(vec![mock::sample_snapshot()]);
+        let mut app = test_app(&mut collector);
+
+        // 85°C is past NVIDIA's warning limit but fine for an AMD card
+        let mut hot = mock::sample_snapshot();
+        hot.gpus = vec![
+            mock::gpu("GeForce RTX 3080", "NVIDIA", 85.0),
+            mock::gpu("Radeon RX 6800", "AMD", 85.0),
+        ];
+        collector.push(hot.clone());
+        app.receive_snapshot(collector.collect());
+        settle(&mut app);
+        let alert = app.alerts.headline().expect("gpu alert");
+        assert_eq!(alert.key, "gpu-temp:GeForce RTX 3080");
+        assert_eq!(alert.severity, AlertSeverity::Warning);
+
+        app.config.gpu_temp_limits = vec![crate::config::GpuTempLimit {
+            gpu: "radeon".to_string(),
+            warning: 70.0,
+            critical: 80.0,
+        }];
+        collector.push(hot);
+        app.receive_snapshot(collector.collect());
+        settle(&mut app);
+        let alert = app.alerts.headline().expect("gpu alert");
+        assert_eq!(alert.key, "gpu-temp:Radeon RX 6800");
+        assert_eq!(alert.severity, AlertSeverity::Critical);
+        assert!(alert.message.contains("85°C (limit 80°C)"));
+    }
+
+    #[test]
+    fn test_selected_volume_stays_visible_in_a_long_disk_list() {
+        let mut collector = MockCollector::new(vec![mock::sample_snapshot()]);
+        let mut app = test_app(&mut collector);
+
+        let mut snapshot = mock::sample_snapshot();
+        snapshot.disks = (0..40)
+            .map(|i| mock::disk(&format!("/srv/vol{:02}", i), 100, 50))
+            .collect();
+        collector.push(snapshot);
+        app.receive_snapshot(collector.collect());
+        settle(&mut app);
+
+        app.set_focus(Some(Panel::Disk));
+        for _ in 0..39 {
+            press(&mut app, KeyCode::Down);
+        }
+        assert_eq!(
+            app.disk_component.selected_disk().map(|disk| disk.name),
+            Some("/srv/vol39".to_string())
+        );
+        let lines = render_lines(&mut app);
+        assert!(row_of(&lines, "/srv/vol39  50.0%").is_some());
+        assert!(row_of(&lines, "/srv/vol00  50.0%").is_none());
+    }
+
+    #[test]
+    fn test_disk_selection_moves_independently_of_the_process_list() {
+        let mut collector = MockCollector::new(vec![mock::sample_snapshot()]);
+        let mut app = test_app(&mut collector);
+
+        let mut snapshot = mock::sample_snapshot();
+        snapshot.disks = vec![mock::disk("/", 100, 60), mock::disk("/home", 100, 20)];
+        snapshot.disk_usage_history = vec![[(61, 100), (60, 100)].into(), [(20, 100)].into()];
+        collector.push(snapshot);
+        app.receive_snapshot(collector.collect());
+        settle(&mut app);
+
+        // Unfocused, the arrows move the process list and no volume is graphed
+        press(&mut app, KeyCode::Down);
+        assert_eq!(app.process_component.selected_index, 1);
+        assert!(row_of(&render_lines(&mut app), "/ used").is_none());
+
+        app.set_focus(Some(Panel::Disk));
+        let lines = render_lines(&mut app);
+        assert!(row_of(&lines, "/ used 39.00% – 40.00%").is_some());
+        press(&mut app, KeyCode::Down);
+        press(&mut app, KeyCode::Down);
+        assert_eq!(app.disk_component.selected_index, 1);
+        assert_eq!(app.process_component.selected_index, 1);
+        assert!(row_of(&render_lines(&mut app), "/home used 80.00%").is_some());
+
+        press(&mut app, KeyCode::Enter);
+        assert_eq!(app.disk_detail.volume, "/home");
+        press(&mut app, KeyCode::Esc);
+        press(&mut app, KeyCode::Up);
+        assert_eq!(app.disk_component.selected_index, 0);
+    }
+
+    #[test]
+    fn test_enter_on_disk_panel_opens_partition_popup() {
+        let mut collector = MockCollector::new(vec![mock::sample_snapshot()]);
+        let mut app = test_app(&mut collector);
+
+        app.set_focus(Some(Panel::Disk));
+        assert!(render_lines(&mut app)
+            .last()
+            .is_some_and(|line| line.contains("partitions")));
+        press(&mut app, KeyCode::Enter);
+        assert!(app.disk_detail.visible);
+        assert_eq!(app.disk_detail.volume, "/");
+        // The mock volume has no device node behind it
+        assert!(row_of(
+            &render_lines(&mut app),
+            "/ is not backed by a local block device"
+        )
+        .is_some());
+
+        press(&mut app, KeyCode::Esc);
+        assert!(!app.disk_detail.visible);
+    }
+}
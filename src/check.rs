@@ -0,0 +1,231 @@
+use crate::data::{collector::DataCollector, snapshot::SystemSnapshot, Collector};
+use std::fmt;
+use sysinfo::SystemExt;
+
+/// A snapshot value that a check expression can compare
+#[derive(Debug, Clone, PartialEq)]
+enum Metric {
+    /// Global CPU usage in percent
+    Cpu,
+    /// Memory pressure in percent: the share of RAM not in MemAvailable, as the memory panel shows
+    Memory,
+    /// Swap used in percent
+    Swap,
+    /// 1-minute load average
+    Load,
+    /// Used space in percent of the volume mounted at (or named) this path
+    Disk(String),
+    /// Hottest temperature sensor in °C
+    Temp,
+    /// Busiest GPU in percent
+    Gpu,
+    /// Number of processes
+    Procs,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Op {
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+/// One `metric<op>threshold` expression, e.g. `cpu<90` or `disk:/home<80%`
+#[derive(Debug, Clone, PartialEq)]
+pub struct CheckExpr {
+    source: String,
+    metric: Metric,
+    op: Op,
+    threshold: f64,
+}
+
+impl fmt::Display for CheckExpr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.source)
+    }
+}
+
+impl CheckExpr {
+    pub fn parse(source: &str) -> Result<Self, String> {
+        let trimmed = source.trim();
+        let op_at = trimmed
+            .find(['<', '>'])
+            .ok_or_else(|| format!("{}: expected a comparison such as cpu<90", source))?;
+        let (name, rest) = trimmed.split_at(op_at);
+        let (op, value) = if let Some(value) = rest.strip_prefix("<=") {
+            (Op::Le, value)
+        } else if let Some(value) = rest.strip_prefix(">=") {
+            (Op::Ge, value)
+        } else if let Some(value) = rest.strip_prefix('<') {
+            (Op::Lt, value)
+        } else {
+            (Op::Gt, &rest[1..])
+        };
+
+        let name = name.trim();
+        let metric = match name {
+            "cpu" => Metric::Cpu,
+            "mem" | "memory" => Metric::Memory,
+            "swap" => Metric::Swap,
+            "load" => Metric::Load,
+            "temp" => Metric::Temp,
+            "gpu" => Metric::Gpu,
+            "procs" => Metric::Procs,
+            _ => match name.strip_prefix("disk:") {
+                Some(mount) if !mount.is_empty() => Metric::Disk(mount.to_string()),
+                _ => return Err(format!("{}: unknown metric '{}'", source, name)),
+            },
+        };
+
+        let value = value.trim();
+        let threshold = value
+            .strip_suffix('%')
+            .unwrap_or(value)
+            .trim()
+            .parse::<f64>()
+            .map_err(|_| format!("{}: invalid threshold '{}'", source, value))?;
+
+        Ok(Self {
+            source: trimmed.to_string(),
+            metric,
+            op,
+            threshold,
+        })
+    }
+
+    /// Current value of the metric, or why it is not available on this system
+    fn value(&self, snapshot: &SystemSnapshot) -> Result<f64, String> {
+        let percent = |used: u64, total: u64| {
+            if total == 0 {
+                Err("not available".to_string())
+            } else {
+                Ok(used as f64 * 100.0 / total as f64)
+            }
+        };
+
+        match &self.metric {
+            Metric::Cpu => Ok(f64::from(snapshot.global_cpu_usage)),
+            Metric::Memory if snapshot.total_memory == 0 => Err("not available".to_string()),
+            Metric::Memory => Ok(snapshot.memory_pressure_percent()),
+            Metric::Swap => percent(snapshot.used_swap, snapshot.total_swap),
+            Metric::Load => snapshot
+                .load_avg
+                .split_whitespace()
+                .next()
+                .and_then(|load| load.parse::<f64>().ok())
+                .ok_or_else(|| "not available".to_string()),
+            Metric::Disk(mount) => {
+                let disk = snapshot
+                    .disks
+                    .iter()
+                    .find(|disk| disk.mount_points.iter().any(|m| m == mount))
+                    .or_else(|| snapshot.disks.iter().find(|disk| &disk.name == mount))
+                    .ok_or_else(|| format!("no volume mounted at {}", mount))?;
+                percent(
+                    disk.total_space.saturating_sub(disk.available_space),
+                    disk.total_space,
+                )
+            }
+            Metric::Temp => snapshot
+                .temperature_sensors
+                .iter()
+                .map(|sensor| f64::from(sensor.temperature))
+                .reduce(f64::max)
+                .ok_or_else(|| "no temperature sensors".to_string()),
+            Metric::Gpu => snapshot
+                .gpus
+                .iter()
+                .filter_map(|gpu| gpu.usage.map(f64::from))
+                .reduce(f64::max)
+                .ok_or_else(|| "no GPU usage available".to_string()),
+            Metric::Procs => Ok(snapshot.processes.len() as f64),
+        }
+    }
+
+    /// Compare against the snapshot: `Ok((passed, value))`, or `Err` when the metric is missing
+    pub fn evaluate(&self, snapshot: &SystemSnapshot) -> Result<(bool, f64), String> {
+        let value = self.value(snapshot)?;
+        let passed = match self.op {
+            Op::Lt => value < self.threshold,
+            Op::Le => value <= self.threshold,
+            Op::Gt => value > self.threshold,
+            Op::Ge => value >= self.threshold,
+        };
+        Ok((passed, value))
+    }
+}
+
+/// Collect once, print one line per expression and return the process exit code:
+/// 0 when every check passes, 1 when any fails or cannot be evaluated, 2 on bad expressions
+pub fn run_checks(expressions: &[String]) -> i32 {
+    let checks: Result<Vec<CheckExpr>, String> =
+        expressions.iter().map(|e| CheckExpr::parse(e)).collect();
+    let checks = match checks {
+        Ok(checks) => checks,
+        Err(err) => {
+            eprintln!("rtop check: {}", err);
+            return 2;
+        }
+    };
+
+    let mut collector = DataCollector::new();
+    // A hidden sensor's or GPU's reading should not fail a check either
+    let config = crate::config::AppConfig::load().unwrap_or_default();
+    collector.set_sensor_overrides(config.sensors);
+    collector.set_gpu_filter(config.gpus);
+    // CPU usage is a delta between two refreshes, so prime once before the measured collect.
+    Collector::collect(&mut collector);
+    std::thread::sleep(
+        sysinfo::System::MINIMUM_CPU_UPDATE_INTERVAL.max(std::time::Duration::from_millis(250)),
+    );
+    let snapshot = Collector::collect(&mut collector);
+
+    let mut failed = false;
+    for check in &checks {
+        match check.evaluate(&snapshot) {
+            Ok((true, value)) => println!("OK    {}  ({:.1})", check, value),
+            Ok((false, value)) => {
+                failed = true;
+                println!("FAIL  {}  ({:.1})", check, value);
+            }
+            Err(err) => {
+                failed = true;
+                println!("FAIL  {}  ({})", check, err);
+            }
+        }
+    }
+
+    i32::from(failed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::mock;
+
+    #[test]
+    fn test_parse_and_evaluate_expressions() {
+        let snapshot = mock::sample_snapshot();
+
+        let cpu = CheckExpr::parse("cpu<90").unwrap();
+        assert_eq!(cpu.metric, Metric::Cpu);
+        assert_eq!(cpu.evaluate(&snapshot), Ok((true, 25.0)));
+
+        let disk = CheckExpr::parse("disk:/ >= 50%").unwrap();
+        assert_eq!(disk.metric, Metric::Disk("/".to_string()));
+        assert_eq!(disk.op, Op::Ge);
+        assert_eq!(disk.evaluate(&snapshot), Ok((true, 60.0)));
+
+        let mem = CheckExpr::parse("mem<30").unwrap();
+        assert_eq!(mem.evaluate(&snapshot), Ok((false, 37.5)));
+
+        assert!(CheckExpr::parse("disk:/home<80%")
+            .unwrap()
+            .evaluate(&snapshot)
+            .is_err());
+        assert!(CheckExpr::parse("cpu=90").is_err());
+        assert!(CheckExpr::parse("fans<3").is_err());
+        assert!(CheckExpr::parse("cpu<lots").is_err());
+    }
+}
@@ -0,0 +1,91 @@
+/// Command-line options
+#[derive(Debug, Default)]
+pub struct CliArgs {
+    /// Run this many collection cycles without the UI and print timings
+    pub profile_collect: Option<usize>,
+    /// Fewer frames, no animation and flat gradients, for slow links; `None` leaves it to the
+    /// `low_bandwidth` config key
+    pub low_bandwidth: Option<bool>,
+    /// Print a single frame to stdout and exit
+    pub once: bool,
+    /// Refuse every action that signals processes or restarts services
+    pub read_only: bool,
+}
+
+pub const USAGE: &str = "\
+Usage: rtop [OPTIONS]
+       rtop check <EXPR>...
+
+Commands:
+  check <EXPR>...        Collect once, evaluate each expression (e.g. \"cpu<90\" \"disk:/home<80%\")
+                         and exit 1 if any fails. Metrics: cpu, mem, swap, load, temp, gpu,
+                         procs, disk:<mount>; operators: < <= > >=
+
+Options:
+  --profile-collect <N>  Run N collection cycles headless, print per-phase timings and exit
+  --low-bandwidth        Redraw less, without animation or gradients (default over SSH)
+  --no-low-bandwidth     Keep full frame rate, animation and gradients, even over SSH
+  --once                 Print one colored frame to stdout, without the alternate screen, and exit
+  --read-only            Disable killing processes and restarting services, e.g. on shared hosts
+  -h, --help             Print this help and exit
+  -V, --version          Print version and exit";
+
+pub enum CliCommand {
+    Run(CliArgs),
+    /// Headless health check with the given expressions
+    Check(Vec<String>),
+    Help,
+    Version,
+}
+
+impl CliArgs {
+    pub fn parse() -> Result<CliCommand, String> {
+        Self::parse_from(std::env::args().skip(1))
+    }
+
+    fn parse_from(args: impl IntoIterator<Item = String>) -> Result<CliCommand, String> {
+        let mut parsed = CliArgs::default();
+        let mut args = args.into_iter().peekable();
+
+        if args.peek().map(String::as_str) == Some("check") {
+            let expressions: Vec<String> = args.skip(1).collect();
+            if expressions.is_empty() {
+                return Err("check requires at least one expression".to_string());
+            }
+            return Ok(CliCommand::Check(expressions));
+        }
+
+        while let Some(arg) = args.next() {
+            // Accept both `--flag value` and `--flag=value`
+            let (flag, inline_value) = match arg.split_once('=') {
+                Some((flag, value)) if flag.starts_with("--") => {
+                    (flag.to_string(), Some(value.to_string()))
+                }
+                _ => (arg, None),
+            };
+
+            match flag.as_str() {
+                "-h" | "--help" => return Ok(CliCommand::Help),
+                "-V" | "--version" => return Ok(CliCommand::Version),
+                "--profile-collect" => {
+                    let value = inline_value
+                        .or_else(|| args.next())
+                        .ok_or("--profile-collect requires a cycle count")?;
+                    let cycles = value
+                        .parse::<usize>()
+                        .ok()
+                        .filter(|&n| n > 0)
+                        .ok_or_else(|| format!("invalid cycle count: {}", value))?;
+                    parsed.profile_collect = Some(cycles);
+                }
+                "--low-bandwidth" => parsed.low_bandwidth = Some(true),
+                "--no-low-bandwidth" => parsed.low_bandwidth = Some(false),
+                "--once" => parsed.once = true,
+                "--read-only" => parsed.read_only = true,
+                other => return Err(format!("unknown argument: {}", other)),
+            }
+        }
+
+        Ok(CliCommand::Run(parsed))
+    }
+}
@@ -0,0 +1,217 @@
+use crate::data::snapshot::{ProcessInfo, SystemSnapshot};
+use chrono::{DateTime, Local};
+use std::collections::HashMap;
+
+/// A snapshot kept to compare the live one against, e.g. from before a deploy
+#[derive(Debug, Clone)]
+pub struct Bookmark {
+    pub at: DateTime<Local>,
+    pub snapshot: SystemSnapshot,
+}
+
+/// A value at the bookmark and now
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Change<T> {
+    pub before: T,
+    pub after: T,
+}
+
+impl Change<u64> {
+    pub fn delta(&self) -> i64 {
+        self.after as i64 - self.before as i64
+    }
+}
+
+impl Change<f32> {
+    pub fn delta(&self) -> f32 {
+        self.after - self.before
+    }
+}
+
+/// A process by identity; a PID reused by another program counts as a different process
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProcessEntry {
+    pub pid: u32,
+    pub name: String,
+    pub cpu: f32,
+    pub memory: u64,
+}
+
+impl From<&ProcessInfo> for ProcessEntry {
+    fn from(process: &ProcessInfo) -> Self {
+        Self {
+            pid: process.pid,
+            name: process.name.clone(),
+            cpu: process.cpu_usage,
+            memory: process.memory,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProcessChange {
+    pub pid: u32,
+    pub name: String,
+    pub cpu: Change<f32>,
+    pub memory: Change<u64>,
+}
+
+/// What changed between a bookmarked snapshot and a later one
+#[derive(Debug, Clone, PartialEq)]
+pub struct SnapshotDiff {
+    pub cpu: Change<f32>,
+    pub memory: Change<u64>,
+    pub swap: Change<u64>,
+    /// Used space per volume present in both snapshots
+    pub disks: Vec<(String, Change<u64>)>,
+    /// Started since the bookmark, busiest first
+    pub started: Vec<ProcessEntry>,
+    /// Exited since the bookmark, biggest first
+    pub exited: Vec<ProcessEntry>,
+    /// Running in both, largest memory change first
+    pub changed: Vec<ProcessChange>,
+}
+
+impl SnapshotDiff {
+    /// Changes smaller than this are left out of `changed`
+    const MIN_CPU_DELTA: f32 = 1.0;
+    const MIN_MEMORY_DELTA: u64 = 1024 * 1024;
+
+    fn by_pid(snapshot: &SystemSnapshot) -> HashMap<u32, &ProcessInfo> {
+        snapshot.processes.iter().map(|p| (p.pid, p)).collect()
+    }
+
+    /// The same process in the other snapshot: its PID, still running the same program
+    fn matching<'a>(
+        by_pid: &HashMap<u32, &'a ProcessInfo>,
+        process: &ProcessInfo,
+    ) -> Option<&'a ProcessInfo> {
+        by_pid
+            .get(&process.pid)
+            .copied()
+            .filter(|other| other.name == process.name)
+    }
+
+    pub fn between(before: &SystemSnapshot, after: &SystemSnapshot) -> Self {
+        let (old_by_pid, new_by_pid) = (Self::by_pid(before), Self::by_pid(after));
+
+        let mut started: Vec<ProcessEntry> = after
+            .processes
+            .iter()
+            .filter(|process| Self::matching(&old_by_pid, process).is_none())
+            .map(ProcessEntry::from)
+            .collect();
+        started.sort_by(|a, b| b.cpu.total_cmp(&a.cpu).then(b.memory.cmp(&a.memory)));
+
+        let mut exited: Vec<ProcessEntry> = before
+            .processes
+            .iter()
+            .filter(|process| Self::matching(&new_by_pid, process).is_none())
+            .map(ProcessEntry::from)
+            .collect();
+        exited.sort_by_key(|entry| std::cmp::Reverse(entry.memory));
+
+        let mut changed: Vec<ProcessChange> = after
+            .processes
+            .iter()
+            .filter_map(|process| {
+                let old = Self::matching(&old_by_pid, process)?;
+                Some(ProcessChange {
+                    pid: process.pid,
+                    name: process.name.clone(),
+                    cpu: Change {
+                        before: old.cpu_usage,
+                        after: process.cpu_usage,
+                    },
+                    memory: Change {
+                        before: old.memory,
+                        after: process.memory,
+                    },
+                })
+            })
+            .filter(|change| {
+                change.cpu.delta().abs() >= Self::MIN_CPU_DELTA
+                    || change.memory.delta().unsigned_abs() >= Self::MIN_MEMORY_DELTA
+            })
+            .collect();
+        changed.sort_by(|a, b| {
+            b.memory
+                .delta()
+                .unsigned_abs()
+                .cmp(&a.memory.delta().unsigned_abs())
+                .then(b.cpu.delta().abs().total_cmp(&a.cpu.delta().abs()))
+        });
+
+        let disks = after
+            .disks
+            .iter()
+            .filter_map(|disk| {
+                let old = before.disks.iter().find(|old| old.name == disk.name)?;
+                Some((
+                    disk.name.clone(),
+                    Change {
+                        before: old.total_space.saturating_sub(old.available_space),
+                        after: disk.total_space.saturating_sub(disk.available_space),
+                    },
+                ))
+            })
+            .collect();
+
+        Self {
+            cpu: Change {
+                before: before.global_cpu_usage,
+                after: after.global_cpu_usage,
+            },
+            memory: Change {
+                before: before.used_memory,
+                after: after.used_memory,
+            },
+            swap: Change {
+                before: before.used_swap,
+                after: after.used_swap,
+            },
+            disks,
+            started,
+            exited,
+            changed,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::mock;
+
+    #[test]
+    fn test_diff_finds_started_exited_and_changed_processes() {
+        let mut before = mock::sample_snapshot();
+        before.processes = vec![
+            mock::process(1, "steady", 1.0, 100 << 20),
+            mock::process(2, "growing", 5.0, 100 << 20),
+            mock::process(3, "leaving", 0.0, 50 << 20),
+            mock::process(4, "old-name", 0.0, 10 << 20),
+        ];
+        let mut after = before.clone();
+        after.processes = vec![
+            mock::process(1, "steady", 1.2, 100 << 20),
+            mock::process(2, "growing", 30.0, 400 << 20),
+            mock::process(4, "reused", 2.0, 10 << 20),
+            mock::process(5, "deploy", 80.0, 20 << 20),
+        ];
+        after.used_memory = before.used_memory + (1 << 30);
+        after.disks[0].available_space -= 1 << 30;
+
+        let diff = SnapshotDiff::between(&before, &after);
+        let names = |entries: &[ProcessEntry]| -> Vec<String> {
+            entries.iter().map(|entry| entry.name.clone()).collect()
+        };
+        assert_eq!(names(&diff.started), vec!["deploy", "reused"]);
+        assert_eq!(names(&diff.exited), vec!["leaving", "old-name"]);
+        assert_eq!(diff.changed.len(), 1);
+        assert_eq!(diff.changed[0].name, "growing");
+        assert_eq!(diff.changed[0].memory.delta(), 300 << 20);
+        assert_eq!(diff.memory.delta(), 1 << 30);
+        assert_eq!(diff.disks[0].1.delta(), 1 << 30);
+    }
+}
@@ -0,0 +1,268 @@
+use super::Component;
+use crate::action::Action;
+use crate::compare::{Change, ProcessEntry, SnapshotDiff};
+use crate::i18n::{tr, tr_args};
+use crate::theme::Theme;
+use chrono::{DateTime, Local};
+use ratatui::{
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph},
+    Frame,
+};
+
+/// `+1.2GB` / `-300.0MB`
+fn signed_bytes(delta: i64) -> String {
+    let sign = if delta < 0 { "-" } else { "+" };
+    format!("{}{}", sign, crate::format::bytes(delta.unsigned_abs()))
+}
+
+/// Popup comparing the live snapshot with the bookmarked one
+pub struct ComparePopup {
+    pub visible: bool,
+    pub theme: Theme,
+    /// When the bookmark was taken; `None` until one is
+    pub bookmarked_at: Option<DateTime<Local>>,
+    pub diff: Option<SnapshotDiff>,
+}
+
+impl ComparePopup {
+    /// Processes listed per section before the rest are summarized
+    const MAX_ROWS: usize = 6;
+
+    pub fn new(theme: Theme) -> Self {
+        Self {
+            visible: false,
+            theme,
+            bookmarked_at: None,
+            diff: None,
+        }
+    }
+
+    fn delta_color(&self, delta: f64) -> Style {
+        let color = if delta > 0.0 {
+            Color::LightRed
+        } else if delta < 0.0 {
+            Color::LightGreen
+        } else {
+            Color::Gray
+        };
+        Style::default().fg(self.theme.get_color(color))
+    }
+
+    fn system_row(
+        &self,
+        label: &str,
+        before: String,
+        after: String,
+        delta: (String, f64),
+    ) -> Line<'static> {
+        Line::from(vec![
+            Span::styled(format!(" {:<20.20}", label), self.theme.text_style()),
+            Span::styled(
+                format!("{:>10} {:>10}", before, after),
+                Style::default().fg(self.theme.get_color(Color::Gray)),
+            ),
+            Span::styled(format!(" {:>11}", delta.0), self.delta_color(delta.1)),
+        ])
+    }
+
+    fn section_title(&self, title: String) -> Line<'static> {
+        Line::from(Span::styled(
+            format!(" {}", title),
+            Style::default()
+                .fg(self.theme.get_color(Color::Cyan))
+                .add_modifier(Modifier::BOLD),
+        ))
+    }
+
+    fn more_line(&self, total: usize) -> Option<Line<'static>> {
+        (total > Self::MAX_ROWS).then(|| {
+            Line::from(Span::styled(
+                format!(
+                    "   {}",
+                    tr_args("more-count", &[("count", &(total - Self::MAX_ROWS))])
+                ),
+                Style::default().fg(self.theme.get_color(Color::DarkGray)),
+            ))
+        })
+    }
+
+    fn entry_line(&self, marker: &str, entry: &ProcessEntry, color: Color) -> Line<'static> {
+        Line::from(Span::styled(
+            format!(
+                " {} {:>7} {:<20.20} {:>6}% {:>10}",
+                marker,
+                entry.pid,
+                entry.name,
+                crate::format::decimal(f64::from(entry.cpu), 1),
+                crate::format::bytes(entry.memory)
+            ),
+            Style::default().fg(self.theme.get_color(color)),
+        ))
+    }
+
+    fn lines(&self, bookmarked_at: DateTime<Local>, diff: &SnapshotDiff) -> Vec<Line<'static>> {
+        let ago = (Local::now() - bookmarked_at).num_seconds().max(0) as u64;
+        let mut lines = vec![
+            Line::from(Span::styled(
+                format!(
+                    " {}",
+                    tr_args(
+                        "compare-bookmarked",
+                        &[
+                            ("time", &bookmarked_at.format("%H:%M:%S")),
+                            ("ago", &crate::format::duration(ago)),
+                        ],
+                    )
+                ),
+                Style::default().fg(self.theme.get_color(Color::LightYellow)),
+            )),
+            Line::from(""),
+            self.section_title(format!(
+                "{:<20}{:>10} {:>10} {:>11}",
+                tr("compare-system"),
+                tr("compare-bookmark"),
+                tr("compare-now"),
+                tr("compare-change")
+            )),
+        ];
+
+        let percent = |value: f32| format!("{}%", crate::format::decimal(f64::from(value), 1));
+        let bytes = |change: &Change<u64>| {
+            (
+                crate::format::bytes(change.before),
+                crate::format::bytes(change.after),
+                (signed_bytes(change.delta()), change.delta() as f64),
+            )
+        };
+        lines.push(self.system_row(
+            tr("compare-cpu"),
+            percent(diff.cpu.before),
+            percent(diff.cpu.after),
+            (
+                format!("{:+.1}%", diff.cpu.delta()),
+                f64::from(diff.cpu.delta()),
+            ),
+        ));
+        for (label, change) in [
+            (tr("memory"), &diff.memory),
+            (tr("compare-swap"), &diff.swap),
+        ] {
+            let (before, after, delta) = bytes(change);
+            lines.push(self.system_row(label, before, after, delta));
+        }
+        for (name, change) in &diff.disks {
+            let (before, after, delta) = bytes(change);
+            let label = tr_args("compare-disk", &[("name", name)]);
+            lines.push(self.system_row(&label, before, after, delta));
+        }
+
+        lines.push(Line::from(""));
+        let counted = |title: &'static str, count: usize| tr_args(title, &[("count", &count)]);
+        lines.push(self.section_title(counted("compare-started", diff.started.len())));
+        for entry in diff.started.iter().take(Self::MAX_ROWS) {
+            lines.push(self.entry_line("+", entry, Color::LightGreen));
+        }
+        lines.extend(self.more_line(diff.started.len()));
+        lines.push(self.section_title(counted("compare-exited", diff.exited.len())));
+        for entry in diff.exited.iter().take(Self::MAX_ROWS) {
+            lines.push(self.entry_line("-", entry, Color::LightRed));
+        }
+        lines.extend(self.more_line(diff.exited.len()));
+
+        lines.push(self.section_title(counted("compare-changed", diff.changed.len())));
+        for change in diff.changed.iter().take(Self::MAX_ROWS) {
+            lines.push(Line::from(vec![
+                Span::styled(
+                    format!("   {:>7} {:<20.20}", change.pid, change.name),
+                    self.theme.text_style(),
+                ),
+                Span::styled(
+                    format!(
+                        " cpu {}%→{}%",
+                        crate::format::decimal(f64::from(change.cpu.before), 1),
+                        crate::format::decimal(f64::from(change.cpu.after), 1)
+                    ),
+                    self.delta_color(f64::from(change.cpu.delta())),
+                ),
+                Span::styled(
+                    format!(
+                        "  mem {}→{} ({})",
+                        crate::format::bytes(change.memory.before),
+                        crate::format::bytes(change.memory.after),
+                        signed_bytes(change.memory.delta())
+                    ),
+                    self.delta_color(change.memory.delta() as f64),
+                ),
+            ]));
+        }
+        lines.extend(self.more_line(diff.changed.len()));
+        lines
+    }
+
+    pub fn render_in_area(&self, f: &mut Frame, area: Rect) {
+        if !self.visible {
+            return;
+        }
+
+        let dim = Style::default().fg(self.theme.get_color(Color::DarkGray));
+        let mut lines = match (self.bookmarked_at, &self.diff) {
+            (Some(at), Some(diff)) => self.lines(at, diff),
+            _ => vec![Line::from(Span::styled(
+                format!(" {}", tr("compare-no-bookmark")),
+                self.theme.text_style(),
+            ))],
+        };
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            format!(" {}", tr("compare-keys")),
+            dim,
+        )));
+
+        let width = area.width.min(90);
+        let height = area.height.min(lines.len() as u16 + 2);
+        let popup_area = Rect {
+            x: area.x + (area.width - width) / 2,
+            y: area.y + (area.height - height) / 2,
+            width,
+            height,
+        };
+        f.render_widget(Clear, popup_area);
+
+        let popup = Paragraph::new(lines).block(
+            Block::default()
+                .title(Span::styled(
+                    format!(" {} ", tr("compare-title")),
+                    Style::default()
+                        .fg(self.theme.get_color(Color::LightYellow))
+                        .add_modifier(Modifier::BOLD),
+                ))
+                .borders(Borders::ALL)
+                .border_type(BorderType::Rounded)
+                .border_style(dim),
+        );
+        f.render_widget(popup, popup_area);
+    }
+}
+
+impl Component for ComparePopup {
+    fn handle_events(
+        &mut self,
+        _event: crossterm::event::Event,
+    ) -> Result<Option<Action>, Box<dyn std::error::Error>> {
+        Ok(None)
+    }
+
+    fn update(&mut self, action: Action) -> Result<Option<Action>, Box<dyn std::error::Error>> {
+        if let Action::ToggleSnapshotDiff = action {
+            self.visible = !self.visible;
+        }
+        Ok(None)
+    }
+
+    fn render(&mut self, f: &mut Frame) {
+        self.render_in_area(f, f.area());
+    }
+}
@@ -0,0 +1,183 @@
+use super::Component;
+use crate::action::Action;
+use crate::data::snapshot::{ProcessInfo, SystemSnapshot};
+use crate::i18n::{tr, tr_args};
+use crate::theme::Theme;
+use ratatui::{
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph},
+    Frame,
+};
+
+/// Popup listing the processes the scheduler last ran on one core, busiest first, so a pegged
+/// core in the grid can be traced to a culprit
+pub struct CoreProcessesPopup {
+    pub visible: bool,
+    pub theme: Theme,
+    pub core: usize,
+    pub snapshot: SystemSnapshot,
+}
+
+impl CoreProcessesPopup {
+    /// Processes listed before the rest are summarized
+    const MAX_ROWS: usize = 20;
+
+    pub fn new(snapshot: SystemSnapshot, theme: Theme) -> Self {
+        Self {
+            visible: false,
+            theme,
+            core: 0,
+            snapshot,
+        }
+    }
+
+    /// Processes whose last-CPU field is this core, busiest first
+    fn processes_on_core(&self) -> Vec<&ProcessInfo> {
+        let mut processes: Vec<&ProcessInfo> = self
+            .snapshot
+            .processes
+            .iter()
+            .filter(|process| process.last_cpu == Some(self.core as u32))
+            .collect();
+        processes.sort_by(|a, b| {
+            b.cpu_usage
+                .total_cmp(&a.cpu_usage)
+                .then_with(|| a.pid.cmp(&b.pid))
+        });
+        processes
+    }
+
+    /// Share of the recent samples that found the process on this core
+    fn residency(&self, process: &ProcessInfo) -> Option<u32> {
+        let samples: u32 = process.core_samples.iter().map(|&c| u32::from(c)).sum();
+        let here = u32::from(*process.core_samples.get(self.core)?);
+        (samples > 0).then(|| here * 100 / samples)
+    }
+
+    fn lines(&self) -> Vec<Line<'static>> {
+        let gray = Style::default().fg(self.theme.get_color(Color::Gray));
+        let dim = Style::default().fg(self.theme.get_color(Color::DarkGray));
+        let usage = self
+            .snapshot
+            .cpu_history
+            .get(self.core)
+            .and_then(|history| history.back())
+            .copied()
+            .unwrap_or(0.0);
+        let mut summary = format!(" {}%", crate::format::decimal(f64::from(usage), 1));
+        if !self.snapshot.core_online(self.core) {
+            summary = format!(" {}", tr("core-offline"));
+        } else if let Some(freq) = self.snapshot.cpu_frequencies.get(self.core) {
+            summary.push_str(&format!(" · {}MHz", freq));
+        }
+
+        let processes = self.processes_on_core();
+        let mut lines = vec![
+            Line::from(Span::styled(
+                summary,
+                self.theme.text_style().add_modifier(Modifier::BOLD),
+            )),
+            Line::from(""),
+        ];
+        if processes.is_empty() {
+            lines.push(Line::from(Span::styled(
+                format!(" {}", tr_args("core-empty", &[("core", &self.core)])),
+                gray,
+            )));
+        } else {
+            lines.push(Line::from(Span::styled(
+                format!(" {:>7} {:>7} {:>5}  {}", "PID", "CPU%", "HERE", "NAME"),
+                Style::default()
+                    .fg(self.theme.get_color(Color::Cyan))
+                    .add_modifier(Modifier::BOLD),
+            )));
+            for process in processes.iter().take(Self::MAX_ROWS) {
+                let residency = self
+                    .residency(process)
+                    .map_or("-".to_string(), |percent| format!("{}%", percent));
+                lines.push(Line::from(Span::styled(
+                    format!(
+                        " {:>7} {:>6}% {:>5}  {}",
+                        process.pid,
+                        crate::format::decimal(f64::from(process.cpu_usage), 1),
+                        residency,
+                        process.name
+                    ),
+                    gray,
+                )));
+            }
+            if processes.len() > Self::MAX_ROWS {
+                lines.push(Line::from(Span::styled(
+                    format!(
+                        "   {}",
+                        tr_args(
+                            "more-count",
+                            &[("count", &(processes.len() - Self::MAX_ROWS))]
+                        )
+                    ),
+                    dim,
+                )));
+            }
+        }
+
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            format!(" {}", tr("esc-close")),
+            dim,
+        )));
+        lines
+    }
+
+    pub fn render_in_area(&self, f: &mut Frame, area: Rect) {
+        if !self.visible {
+            return;
+        }
+
+        let lines = self.lines();
+        let width = area.width.min(70);
+        let height = area.height.min(lines.len() as u16 + 2);
+        let popup_area = Rect {
+            x: area.x + (area.width - width) / 2,
+            y: area.y + (area.height - height) / 2,
+            width,
+            height,
+        };
+        f.render_widget(Clear, popup_area);
+
+        let popup = Paragraph::new(lines).block(
+            Block::default()
+                .title(Span::styled(
+                    format!(" {} ", tr_args("core-title", &[("core", &self.core)])),
+                    Style::default()
+                        .fg(self.theme.get_color(Color::LightYellow))
+                        .add_modifier(Modifier::BOLD),
+                ))
+                .borders(Borders::ALL)
+                .border_type(BorderType::Rounded)
+                .border_style(Style::default().fg(self.theme.get_color(Color::DarkGray))),
+        );
+        f.render_widget(popup, popup_area);
+    }
+}
+
+impl Component for CoreProcessesPopup {
+    fn handle_events(
+        &mut self,
+        _event: crossterm::event::Event,
+    ) -> Result<Option<Action>, Box<dyn std::error::Error>> {
+        Ok(None)
+    }
+
+    fn update(&mut self, action: Action) -> Result<Option<Action>, Box<dyn std::error::Error>> {
+        if let Action::ToggleCoreProcesses = action {
+            self.visible = !self.visible;
+        }
+        Ok(None)
+    }
+
+    fn render(&mut self, f: &mut Frame) {
+        self.render_in_area(f, f.area());
+    }
+}
@@ -1,6 +1,9 @@
 use crate::{
-    action::Action, components::Component, data::snapshot::SystemSnapshot, theme::Theme,
-    widgets::braille_graph::BrailleGraph,
+    action::Action,
+    components::{cursor::GraphCursor, Component},
+    data::snapshot::SystemSnapshot,
+    theme::Theme,
+    widgets::{braille_graph::BrailleGraph, gauge::GradientBar},
 };
 use ratatui::{
     layout::{Constraint, Direction, Layout},
@@ -13,11 +16,58 @@ use ratatui::{
 pub struct CpuComponent {
     pub snapshot: SystemSnapshot,
     pub theme: Theme,
+    pub cursor: GraphCursor,
+    /// Core picked in the grid with ↑/↓ while the panel is focused
+    pub selected_core: Option<usize>,
 }
 
 impl CpuComponent {
     pub fn new(snapshot: SystemSnapshot, theme: Theme) -> Self {
-        Self { snapshot, theme }
+        Self {
+            snapshot,
+            theme,
+            cursor: GraphCursor::default(),
+            selected_core: None,
+        }
+    }
+
+    /// Step the core selection, starting from the first core
+    fn move_core_selection(&mut self, forward: bool) {
+        let last = self.snapshot.core_slots().saturating_sub(1);
+        self.selected_core = Some(match self.selected_core {
+            None => 0,
+            Some(core) if forward => (core + 1).min(last),
+            Some(core) => core.saturating_sub(1),
+        });
+    }
+
+    /// Average usage across the online cores at each point of the history, in whole percent
+    pub fn average_history(&self) -> Vec<u64> {
+        let mut avg_cpu_data: Vec<u64> = Vec::new();
+        if self.snapshot.cpu_count > 0 {
+            // Assuming all cpu_history VecDeques are of similar length (e.g., 50)
+            let history_len = self.snapshot.cpu_history.first().map_or(0, |h| h.len());
+
+            for i in 0..history_len {
+                let mut sum = 0.0f32;
+                let mut count = 0;
+
+                for (core, core_history) in self.snapshot.cpu_history.iter().enumerate() {
+                    if !self.snapshot.core_online(core) {
+                        continue;
+                    }
+                    if let Some(val) = core_history.get(i) {
+                        sum += val;
+                        count += 1;
+                    }
+                }
+
+                if count > 0 {
+                    avg_cpu_data.push((sum / count as f32) as u64);
+                }
+            }
+        }
+        avg_cpu_data
     }
 
     fn get_cpu_color(&self, cpu_usage: f32) -> Color {
@@ -30,45 +80,6 @@ impl CpuComponent {
         }
     }
 
-    fn calculate_gradient_color(&self, position_ratio: f32) -> Color {
-        // Smooth gradient using hex colors with LERP
-        // 0%->50%: Blend from #00ff87 to #f9ff00
-        // 50%->100%: Blend from #f9ff00 to #ff003c
-        if position_ratio < 0.5 {
-            // From #00ff87 to #f9ff00 (0.0 to 0.5)
-            let t = position_ratio * 2.0; // Normalize to 0.0-1.0 range
-                                          // Interpolate between #00ff87 (0, 255, 135) and #f9ff00 (249, 255, 0)
-            let r = (0.0 + (249.0 - 0.0) * t) as u8;
-            let g = (255.0 + (255.0 - 255.0) * t) as u8;
-            let b = (135.0 + (0.0 - 135.0) * t) as u8;
-            Color::Rgb(r, g, b)
-        } else {
-            // From #f9ff00 to #ff003c (0.5 to 1.0)
-            let t = (position_ratio - 0.5) * 2.0; // Normalize to 0.0-1.0 range
-                                                  // Interpolate between #f9ff00 (249, 255, 0) and #ff003c (255, 0, 60)
-            let r = (249.0 + (255.0 - 249.0) * t) as u8;
-            let g = (255.0 + (0.0 - 255.0) * t) as u8;
-            let b = (0.0 + (60.0 - 0.0) * t) as u8;
-            Color::Rgb(r, g, b)
-        }
-    }
-
-    fn calculate_track_color(&self, position_ratio: f32) -> Color {
-        // Dimmed version of the gradient color for the track (30% brightness)
-        let active_color = self.calculate_gradient_color(position_ratio);
-        match active_color {
-            Color::Rgb(r, g, b) => {
-                // Reduce brightness to 30% for visible track
-                Color::Rgb(
-                    (r as f32 * 0.3) as u8,
-                    (g as f32 * 0.3) as u8,
-                    (b as f32 * 0.3) as u8,
-                )
-            }
-            _ => Color::Rgb(50, 50, 50), // Fallback to medium grey
-        }
-    }
-
     fn get_temperature_color(&self, temp: f32) -> Color {
         // Dynamic temperature color based on LERP with proper thresholds
         // Only turn 'Neon Rose' color when it actually crosses dangerous threshold (85°C+)
@@ -113,12 +124,6 @@ impl CpuComponent {
         }
     }
 
-    fn bar_width_for_area(area_width: u16, prefix_chars: usize, max_width: usize) -> usize {
-        let available = area_width as usize;
-        let room_after_prefix = available.saturating_sub(prefix_chars);
-        room_after_prefix.min(max_width)
-    }
-
     fn cpu_temp_priority(label: &str) -> Option<u8> {
         let lower = label.to_ascii_lowercase();
         if lower.is_empty() {
@@ -147,16 +152,26 @@ impl CpuComponent {
         None
     }
 
-    fn fractional_block(units: usize) -> char {
-        match units {
-            1 => '▏',
-            2 => '▎',
-            3 => '▍',
-            4 => '▌',
-            5 => '▋',
-            6 => '▊',
-            7 => '▉',
-            _ => '░',
+    /// Run-queue wait in µs below a millisecond, ms above
+    fn format_wait(wait_us: f32) -> String {
+        if wait_us < 1000.0 {
+            format!("{:.0}µs", wait_us)
+        } else {
+            format!(
+                "{}ms",
+                crate::format::decimal(f64::from(wait_us / 1000.0), 1)
+            )
+        }
+    }
+
+    /// Waits of a few hundred µs are normal; several ms means tasks are queueing for CPU
+    fn get_wait_color(&self, wait_us: f32) -> Color {
+        if wait_us < 500.0 {
+            self.theme.get_color(Color::Green)
+        } else if wait_us < 4000.0 {
+            self.theme.get_color(Color::Yellow)
+        } else {
+            self.theme.get_color(Color::Red)
         }
     }
 
@@ -170,13 +185,39 @@ impl CpuComponent {
             " CPU ".to_string()
         };
 
-        let block = Block::default()
-            .title(Span::styled(
-                cpu_title,
+        let mut title_spans = vec![Span::styled(
+            cpu_title,
+            Style::default()
+                .fg(self.theme.get_color(Color::LightBlue))
+                .add_modifier(ratatui::style::Modifier::BOLD),
+        )];
+        if let Some(throttle) = self.snapshot.throttle.filter(|t| t.total() > 0) {
+            // Red while events are still arriving, yellow once throttling has stopped
+            let color = if throttle.active {
+                Color::Red
+            } else {
+                Color::Yellow
+            };
+            title_spans.push(Span::styled(
+                format!(" throttled ×{} ", throttle.total()),
                 Style::default()
-                    .fg(self.theme.get_color(Color::LightBlue))
+                    .fg(self.theme.get_color(Color::Black))
+                    .bg(self.theme.get_color(color))
                     .add_modifier(ratatui::style::Modifier::BOLD),
-            ))
+            ));
+        }
+
+        let history = self.average_history();
+        let cursor = self.cursor.index(history.len());
+        let mut block = Block::default().title(Line::from(title_spans));
+        if let Some(index) = cursor {
+            block = block.title_bottom(GraphCursor::readout(
+                &self.theme,
+                self.snapshot.history_time(history.len(), index),
+                format!("avg {}%", history[index]),
+            ));
+        }
+        let block = block
             .borders(Borders::ALL)
             .border_type(BorderType::Rounded)
             .border_style(Style::default().fg(self.theme.get_color(Color::DarkGray)))
@@ -216,17 +257,31 @@ impl CpuComponent {
         let mut total_stats_spans = vec![Line::from(vec![
             Span::styled("Usage: ", self.theme.text_style()),
             Span::styled(
-                format!("{:.1}%", cpu_usage),
+                format!("{}%", crate::format::decimal(f64::from(cpu_usage), 1)),
                 Style::default().fg(self.get_cpu_color(cpu_usage)),
             ),
             Span::raw("   "), // Separator
             Span::styled("Cores: ", self.theme.text_style()),
             Span::styled(format!("{}", cpu_cores), self.theme.text_style()),
         ])];
+        let offline = self.snapshot.core_slots().saturating_sub(cpu_cores);
+        if offline > 0 {
+            total_stats_spans[0].spans.push(Span::styled(
+                format!(" ({} offline)", offline),
+                Style::default().fg(self.theme.get_color(Color::DarkGray)),
+            ));
+        }
 
-        if !self.snapshot.cpu_frequencies.is_empty() {
-            let avg_freq = self.snapshot.cpu_frequencies.iter().sum::<u64>() as f32
-                / self.snapshot.cpu_frequencies.len() as f32;
+        let online_freqs: Vec<u64> = self
+            .snapshot
+            .cpu_frequencies
+            .iter()
+            .enumerate()
+            .filter(|(core, _)| self.snapshot.core_online(*core))
+            .map(|(_, freq)| *freq)
+            .collect();
+        if !online_freqs.is_empty() {
+            let avg_freq = online_freqs.iter().sum::<u64>() as f32 / online_freqs.len() as f32;
             total_stats_spans[0].spans.push(Span::raw("   ")); // Separator
             total_stats_spans[0]
                 .spans
@@ -237,42 +292,36 @@ impl CpuComponent {
             ));
         }
 
+        let waits: Vec<f32> = self
+            .snapshot
+            .run_queue_wait_us
+            .iter()
+            .flatten()
+            .copied()
+            .collect();
+        if !waits.is_empty() {
+            let avg_wait = waits.iter().sum::<f32>() / waits.len() as f32;
+            total_stats_spans[0].spans.push(Span::raw("   ")); // Separator
+            total_stats_spans[0]
+                .spans
+                .push(Span::styled("RQ wait: ", self.theme.text_style()));
+            total_stats_spans[0].spans.push(Span::styled(
+                Self::format_wait(avg_wait),
+                Style::default().fg(self.get_wait_color(avg_wait)),
+            ));
+        }
+
         f.render_widget(Paragraph::new(total_stats_spans), global_stats_area);
 
         // Use fractional block characters for global CPU usage visualization with smooth gradient
-        let usage_percentage = cpu_usage.min(100.0);
-        let usage_prefix = format!("CPU:{:>6.1}% ", cpu_usage);
-        let bar_width = Self::bar_width_for_area(global_gauge_area.width, usage_prefix.len(), 48);
-        let total_units = bar_width * 8; // 8 sub-units per block
-        let filled_units = (usage_percentage / 100.0 * total_units as f32).round() as usize;
-
-        let mut spans = Vec::with_capacity(bar_width + 2); // label + bar cells
-        spans.push(Span::styled(usage_prefix, self.theme.text_style()));
-
-        for i in 0..bar_width {
-            let start_unit = i * 8;
-            let end_unit = start_unit + 8;
-            let position_ratio = i as f32 / (bar_width.saturating_sub(1).max(1)) as f32;
-            let active_color = self.calculate_gradient_color(position_ratio);
-            let track_color = self.calculate_track_color(position_ratio);
-
-            let (char, style) = if filled_units <= start_unit {
-                (' ', Style::default().bg(track_color))
-            } else if filled_units >= end_unit {
-                (' ', Style::default().bg(active_color))
-            } else {
-                let partial_units = filled_units - start_unit;
-                (
-                    Self::fractional_block(partial_units),
-                    Style::default().fg(active_color).bg(track_color),
-                )
-            };
-
-            spans.push(Span::styled(char.to_string(), style));
-        }
-        let gauge_line = Line::from(spans);
-        let paragraph = Paragraph::new(gauge_line);
-        f.render_widget(paragraph, global_gauge_area);
+        let usage_prefix = format!(
+            "CPU:{:>6}% ",
+            crate::format::decimal(f64::from(cpu_usage), 1)
+        );
+        let usage_bar = GradientBar::new(&self.theme, f64::from(cpu_usage))
+            .label(usage_prefix, self.theme.text_style())
+            .max_width(48);
+        f.render_widget(usage_bar, global_gauge_area);
 
         // Render Temperature and Power
         let mut temp_spans = Vec::new();
@@ -283,7 +332,7 @@ impl CpuComponent {
             .temperature_sensors
             .iter()
             .filter_map(|sensor| {
-                Self::cpu_temp_priority(&sensor.label)
+                Self::cpu_temp_priority(&sensor.source)
                     .map(|priority| (priority, sensor.temperature))
             })
             .max_by_key(|(priority, _)| *priority)
@@ -291,7 +340,10 @@ impl CpuComponent {
         if let Some(temperature) = cpu_temp {
             let temp_color = self.get_temperature_color(temperature);
             temp_spans.push(Span::styled(
-                format!("Temp: {:.1}°C", temperature),
+                format!(
+                    "Temp: {}°C",
+                    crate::format::decimal(f64::from(temperature), 1)
+                ),
                 Style::default().fg(temp_color),
             ));
         } else {
@@ -305,7 +357,7 @@ impl CpuComponent {
                 let power_color = self.get_temperature_color(power); // Use same color gradient as temperature
                 temp_spans.push(Span::styled(" | ", self.theme.text_style())); // Separator
                 temp_spans.push(Span::styled(
-                    format!("Power: {:.1}W", power),
+                    format!("Power: {}W", crate::format::decimal(f64::from(power), 1)),
                     Style::default().fg(power_color),
                 ));
             }
@@ -315,42 +367,16 @@ impl CpuComponent {
 
         // Render overall CPU Braille graph (replacing sparkline)
         if !self.snapshot.cpu_history.is_empty() {
-            let mut avg_cpu_data: Vec<u64> = Vec::new();
-            if self.snapshot.cpu_count > 0 {
-                // Assuming all cpu_history VecDeques are of similar length (e.g., 50)
-                let history_len = self.snapshot.cpu_history.get(0).map_or(0, |h| h.len());
-
-                for i in 0..history_len {
-                    let mut sum = 0.0f32;
-                    let mut count = 0;
-
-                    for core_history in &self.snapshot.cpu_history {
-                        if let Some(val) = core_history.get(i) {
-                            sum += val;
-                            count += 1;
-                        }
-                    }
-
-                    if count > 0 {
-                        avg_cpu_data.push((sum / count as f32) as u64);
-                    }
-                }
-            }
-
-            // Convert to percentage values (0-100) for the Braille graph
-            let percentage_data: Vec<u64> = avg_cpu_data
-                .iter()
-                .map(|&val| (val as f64).round() as u64)
-                .collect();
-
-            let braille_graph = BrailleGraph::new(&percentage_data)
+            let braille_graph = BrailleGraph::new(&history)
                 .block(Block::default().borders(Borders::NONE)) // Remove borders for compactness
                 .style(Style::default().fg(self.get_cpu_color(self.snapshot.global_cpu_usage)))
                 .value_range(0.0, 100.0)
                 .smoothing(2)
                 .show_baseline(true)
-                .use_gradient(true)
-                .fill(false);
+                .use_gradient(!self.theme.simple_gradients)
+                .fill(false)
+                .cursor(cursor)
+                .gap(self.snapshot.history_gap_index(history.len()));
             f.render_widget(braille_graph, sparkline_area);
         } else {
             // Show a simple indicator if no history data
@@ -360,7 +386,9 @@ impl CpuComponent {
             );
         }
         // Render per-core grid in core_grid_area
-        let core_width = 18; // Approximate width needed for "0: [==> ] 10% 1234MHz"
+        let show_waits = !self.snapshot.run_queue_wait_us.is_empty();
+        // Approximate width needed for " 0: 10.0% 1234MHz", plus " 120µs" of run-queue wait
+        let core_width = if show_waits { 25 } else { 18 };
         let num_cols = core_grid_area.width / core_width;
 
         if num_cols == 0 {
@@ -374,7 +402,8 @@ impl CpuComponent {
             );
             return;
         }
-        let num_rows_per_col = (self.snapshot.cpu_count as f32 / num_cols as f32).ceil() as usize;
+        let core_slots = self.snapshot.core_slots();
+        let num_rows_per_col = (core_slots as f32 / num_cols as f32).ceil() as usize;
 
         let mut constraints = vec![];
         for _ in 0..num_cols {
@@ -398,7 +427,19 @@ impl CpuComponent {
 
             for row_index in 0..num_rows_per_col {
                 let core_idx = col_index * num_rows_per_col + row_index;
-                if core_idx < self.snapshot.cpu_count {
+                if core_idx < core_slots && !self.snapshot.core_online(core_idx) {
+                    // Parked or hot-unplugged: no usage or clock to speak of
+                    let mut style = Style::default().fg(self.theme.get_color(Color::DarkGray));
+                    if self.selected_core == Some(core_idx) {
+                        style = style
+                            .bg(self.theme.get_color(Color::Blue))
+                            .add_modifier(ratatui::style::Modifier::BOLD);
+                    }
+                    f.render_widget(
+                        Paragraph::new(Span::styled(format!("{:>2}: offline", core_idx), style)),
+                        row_chunks[row_index],
+                    );
+                } else if core_idx < core_slots {
                     let core_usage = if !self.snapshot.cpu_history.is_empty()
                         && core_idx < self.snapshot.cpu_history.len()
                     {
@@ -415,7 +456,11 @@ impl CpuComponent {
                     };
 
                     // Show only text for per-core usage (modern minimalist approach)
-                    let core_text = format!("{:>2}: {:.1}% ", core_idx, core_usage);
+                    let core_text = format!(
+                        "{:>2}: {}% ",
+                        core_idx,
+                        crate::format::decimal(f64::from(core_usage), 1)
+                    );
 
                     // Apply color gradient to frequency based on boost levels
                     let freq_color = self.get_frequency_color(core_freq as f32);
@@ -424,8 +469,21 @@ impl CpuComponent {
 
                     let mut spans = vec![Span::styled(core_text, self.theme.text_style())];
                     spans.push(freq_span);
+                    if let Some(Some(wait)) = self.snapshot.run_queue_wait_us.get(core_idx) {
+                        spans.push(Span::styled(
+                            format!(" {}", Self::format_wait(*wait)),
+                            Style::default().fg(self.get_wait_color(*wait)),
+                        ));
+                    }
 
-                    let paragraph = Paragraph::new(Line::from(spans));
+                    let mut paragraph = Paragraph::new(Line::from(spans));
+                    if self.selected_core == Some(core_idx) {
+                        paragraph = paragraph.style(
+                            Style::default()
+                                .bg(self.theme.get_color(Color::Blue))
+                                .add_modifier(ratatui::style::Modifier::BOLD),
+                        );
+                    }
                     f.render_widget(paragraph, row_chunks[row_index]);
                 }
             }
@@ -442,12 +500,27 @@ impl Component for CpuComponent {
         Ok(None)
     }
 
-    fn update(&mut self, _action: Action) -> Result<Option<Action>, Box<dyn std::error::Error>> {
-        // CPU component doesn't handle actions directly
+    fn update(&mut self, action: Action) -> Result<Option<Action>, Box<dyn std::error::Error>> {
+        match action {
+            Action::MoveUp => self.move_core_selection(false),
+            Action::MoveDown => self.move_core_selection(true),
+            _ => {
+                let len = self.snapshot.cpu_history.first().map_or(0, |h| h.len());
+                self.cursor.update(&action, len);
+            }
+        }
         Ok(None)
     }
 
     fn render(&mut self, f: &mut Frame) {
         self.render_in_area(f, f.area());
     }
+
+    fn hints(&self) -> Vec<(&'static str, &'static str)> {
+        vec![
+            ("←/→", "hint-inspect-graph"),
+            ("↑/↓", "hint-select-core"),
+            ("Enter", "hint-core-processes"),
+        ]
+    }
 }
@@ -0,0 +1,90 @@
+use crate::action::Action;
+use crate::theme::Theme;
+use ratatui::{
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+};
+
+/// Inspection cursor on a history graph. It counts samples back from the newest point, and
+/// `advance` shifts it with every collected sample so it stays on the moment being read.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct GraphCursor {
+    back: Option<usize>,
+}
+
+impl GraphCursor {
+    pub fn is_active(&self) -> bool {
+        self.back.is_some()
+    }
+
+    /// Handle `MoveGraphCursor`/`ClearGraphCursor`; the first move lands on the newest point
+    pub fn update(&mut self, action: &Action, len: usize) {
+        match action {
+            Action::MoveGraphCursor(newer) => {
+                let last = len.saturating_sub(1);
+                self.back = Some(match self.back {
+                    None => 0,
+                    Some(back) if *newer => back.saturating_sub(1),
+                    Some(back) => (back + 1).min(last),
+                });
+            }
+            Action::ClearGraphCursor => self.back = None,
+            _ => {}
+        }
+    }
+
+    /// Keep pointing at the same sample after a new one was appended to a history of `len`
+    pub fn advance(&mut self, len: usize) {
+        if let Some(back) = self.back.as_mut() {
+            *back = (*back + 1).min(len.saturating_sub(1));
+        }
+    }
+
+    /// Index of the inspected point in a history of `len` points
+    pub fn index(&self, len: usize) -> Option<usize> {
+        let back = self.back?;
+        len.checked_sub(1 + back.min(len.checked_sub(1)?))
+    }
+
+    /// `◆ 14:03:22 · <value>` for the bottom border of the inspected panel
+    pub fn readout(theme: &Theme, time: Option<i64>, value: String) -> Line<'static> {
+        let time = time.map_or_else(|| "--:--:--".to_string(), crate::format::clock);
+        Line::from(vec![
+            Span::styled(
+                format!(" ◆ {} ", time),
+                Style::default()
+                    .fg(theme.get_color(Color::Yellow))
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(format!("{} ", value), theme.text_style()),
+        ])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cursor_moves_clamps_and_follows_new_samples() {
+        let mut cursor = GraphCursor::default();
+        assert_eq!(cursor.index(10), None);
+
+        cursor.update(&Action::MoveGraphCursor(false), 10);
+        assert_eq!(cursor.index(10), Some(9));
+        for _ in 0..20 {
+            cursor.update(&Action::MoveGraphCursor(false), 10);
+        }
+        assert_eq!(cursor.index(10), Some(0));
+        cursor.update(&Action::MoveGraphCursor(true), 10);
+        assert_eq!(cursor.index(10), Some(1));
+
+        // A new sample arrives while the history is still filling: same point, one more behind it
+        cursor.advance(11);
+        assert_eq!(cursor.index(11), Some(1));
+        assert_eq!(cursor.index(0), None);
+
+        cursor.update(&Action::ClearGraphCursor, 11);
+        assert!(!cursor.is_active());
+    }
+}
@@ -3,12 +3,16 @@ use crate::{
     components::Component,
     data::snapshot::{DiskInfo, SystemSnapshot},
     theme::Theme,
+    widgets::{
+        braille_graph::BrailleGraph,
+        gauge::{SegmentColor, StackedBar},
+    },
 };
 use ratatui::{
     layout::{Constraint, Direction, Layout},
     style::{Color, Style},
     text::{Line, Span},
-    widgets::{Block, BorderType, Borders, List, ListItem, Paragraph},
+    widgets::{Block, BorderType, Borders, List, ListItem, ListState, Paragraph},
     Frame,
 };
 use std::collections::HashMap;
@@ -17,21 +21,32 @@ pub struct DiskComponent {
     pub snapshot: SystemSnapshot,
     pub theme: Theme,
     pub selected_index: usize,
+    /// The panel has keyboard focus, so the selected volume is highlighted and graphed
+    pub focused: bool,
+    /// Volumes projected to fill within this many seconds are highlighted
+    pub full_horizon_secs: u64,
 }
 
 impl DiskComponent {
+    /// Processes listed under the volumes
+    const TOP_IO_ROWS: usize = 3;
+    /// Label line and graph of the selected volume's usage, while the panel is focused
+    const GRAPH_ROWS: u16 = 3;
+
     pub fn new(snapshot: SystemSnapshot, theme: Theme) -> Self {
         Self {
             snapshot,
             theme,
             selected_index: 0,
+            focused: false,
+            full_horizon_secs: 24 * 3600,
         }
     }
 
     pub fn render_in_area(&self, f: &mut Frame, area: ratatui::layout::Rect) {
         let block = Block::default()
             .title(Span::styled(
-                " Disk ",
+                format!(" {} ", crate::i18n::tr("disk")),
                 Style::default()
                     .fg(self.theme.get_color(Color::LightMagenta))
                     .add_modifier(ratatui::style::Modifier::BOLD),
@@ -59,37 +74,70 @@ impl DiskComponent {
             0.0
         };
 
-        // Split the inner area into summary (top) and list (bottom)
+        let top_io = self.top_io_lines();
+        let top_io_rows = if top_io.is_empty() {
+            0
+        } else {
+            top_io.len() as u16 + 1
+        };
+        let selected = self.selected_index.min(disks.len().saturating_sub(1));
+        let graph_rows = if self.focused && !disks.is_empty() {
+            Self::GRAPH_ROWS
+        } else {
+            0
+        };
+
+        // Split the inner area into summary (top), list and the busiest processes (bottom)
         let chunks = Layout::default()
             .direction(Direction::Vertical)
             .constraints([
-                Constraint::Length(2), // Overall summary
-                Constraint::Min(0),    // Disk list
+                Constraint::Length(2),           // Overall summary
+                Constraint::Min(0),              // Disk list
+                Constraint::Length(graph_rows),  // Usage of the selected volume
+                Constraint::Length(top_io_rows), // Top disk readers/writers
             ])
             .split(inner_area);
 
-        // Render overall summary
-        let summary_spans = vec![
-            Line::from(vec![
-                Span::styled(
-                    "Total: ",
-                    Style::default().fg(self.theme.get_color(Color::White)),
-                ),
-                Span::styled(
-                    format!(
-                        "{}/{}",
-                        crate::utils::bytes_to_human_readable(total_used),
-                        crate::utils::bytes_to_human_readable(total_space)
-                    ),
-                    Style::default().fg(self.theme.get_color(Color::Green)),
-                ),
-                Span::raw("  "),
-                Span::styled(
-                    format!("{:.1}%", total_percent),
-                    Style::default().fg(self.theme.get_color(Color::Yellow)),
+        // Render overall summary: used, reserved for root and free space as one stacked bar
+        let total_reserved: u64 = disks.iter().map(|disk| disk.reserved_space).sum();
+        let total_available: u64 = disks.iter().map(|disk| disk.available_space).sum();
+        let label = Line::from(vec![
+            Span::styled(
+                "Total: ",
+                Style::default().fg(self.theme.get_color(Color::White)),
+            ),
+            Span::styled(
+                format!(
+                    "{}/{}",
+                    crate::format::bytes(total_used),
+                    crate::format::bytes(total_space)
                 ),
-            ]),
-            Line::from(vec![
+                Style::default().fg(self.theme.get_color(Color::Green)),
+            ),
+            Span::raw("  "),
+            Span::styled(
+                format!("{}% ", crate::format::decimal(total_percent, 1)),
+                Style::default().fg(self.theme.get_color(Color::Yellow)),
+            ),
+        ]);
+        let summary = StackedBar::new(&self.theme, total_space as f64)
+            .label(label)
+            .max_width(42)
+            .segment(
+                total_used.saturating_sub(total_reserved) as f64,
+                SegmentColor::Gradient(1.0),
+                "used",
+            )
+            .segment(
+                total_reserved as f64,
+                SegmentColor::Solid(self.theme.get_color(Color::Gray)),
+                format!("reserved {}", crate::format::bytes(total_reserved)),
+            )
+            .track(
+                SegmentColor::Gradient(0.3),
+                format!("free {}", crate::format::bytes(total_available)),
+            )
+            .legend_suffix(vec![
                 Span::styled(
                     "Volumes: ",
                     Style::default().fg(self.theme.get_color(Color::White)),
@@ -98,11 +146,8 @@ impl DiskComponent {
                     format!("{}", disks.len()),
                     Style::default().fg(self.theme.get_color(Color::Cyan)),
                 ),
-            ]),
-        ];
-
-        let summary_paragraph = Paragraph::new(summary_spans).block(Block::default());
-        f.render_widget(summary_paragraph, chunks[0]);
+            ]);
+        f.render_widget(summary, chunks[0]);
 
         // Render disk list (simplified)
         let disk_items: Vec<ListItem> = disks
@@ -116,18 +161,37 @@ impl DiskComponent {
                     0.0
                 };
 
-                let disk_line = format!(
-                    "{}  {:.1}%  {}/{}",
+                let mut disk_line = format!(
+                    "{}  {}%  {}/{}",
                     disk.name,
-                    usage_percent,
-                    crate::utils::bytes_to_human_readable(used_space),
-                    crate::utils::bytes_to_human_readable(disk.total_space)
+                    crate::format::decimal(usage_percent, 1),
+                    crate::format::bytes(used_space),
+                    crate::format::bytes(disk.total_space)
                 );
+                let filling_soon = disk
+                    .time_to_full
+                    .is_some_and(|eta| eta < self.full_horizon_secs);
+                if let Some(eta) = disk.time_to_full {
+                    disk_line.push_str(&format!("  {} to full", crate::format::eta(eta)));
+                }
+                let stale = disk.remote.and_then(|remote| remote.stale_secs);
+                let slow = disk.remote.is_some_and(|remote| remote.is_slow());
+                if let Some(secs) = stale {
+                    disk_line.push_str(&format!("  stale {}", crate::format::duration(secs)));
+                } else if let Some(ms) = disk.remote.and_then(|remote| remote.latency_ms) {
+                    disk_line.push_str(&format!("  {:.0}ms", ms));
+                }
 
-                let style = if i == self.selected_index {
+                let style = if self.focused && i == selected {
                     Style::default()
                         .bg(self.theme.get_color(Color::Blue))
                         .fg(self.theme.get_color(Color::White))
+                } else if filling_soon || stale.is_some() {
+                    Style::default()
+                        .fg(self.theme.get_color(Color::Red))
+                        .add_modifier(ratatui::style::Modifier::BOLD)
+                } else if slow {
+                    Style::default().fg(self.theme.get_color(Color::LightRed))
                 } else {
                     Style::default().fg(self.theme.get_color(Color::Yellow))
                 };
@@ -137,7 +201,121 @@ impl DiskComponent {
             .collect();
 
         let disk_list = List::new(disk_items).block(Block::default()); // No borders
-        f.render_widget(disk_list, chunks[1]);
+                                                                       // Scrolled to keep the selected volume, which Enter opens, on screen
+        let mut state = ListState::default().with_selected(self.focused.then_some(selected));
+        f.render_stateful_widget(disk_list, chunks[1], &mut state);
+
+        if !top_io.is_empty() {
+            let mut lines = vec![Line::from(Span::styled(
+                "Top I/O",
+                Style::default().fg(self.theme.get_color(Color::White)),
+            ))];
+            lines.extend(top_io);
+            f.render_widget(Paragraph::new(lines), chunks[3]);
+        }
+        if let Some(disk) = disks.get(selected).filter(|_| graph_rows > 0) {
+            self.render_usage_graph(f, chunks[2], disk);
+        }
+    }
+
+    /// Used space of `disk` over the collected history, zoomed to the range it moved in so a
+    /// slowly filling volume still shows a slope
+    fn render_usage_graph(&self, f: &mut Frame, area: ratatui::layout::Rect, disk: &DiskInfo) {
+        // The history is kept per collected volume, before duplicates are merged
+        let history = self
+            .snapshot
+            .disks
+            .iter()
+            .position(|d| d.name == disk.name && d.total_space == disk.total_space)
+            .and_then(|index| self.snapshot.disk_usage_history.get(index));
+        // Hundredths of a percent
+        let data: Vec<u64> = history
+            .into_iter()
+            .flatten()
+            .filter(|(_, total)| *total > 0)
+            .map(|&(available, total)| {
+                (total.saturating_sub(available) as f64 / total as f64 * 10_000.0).round() as u64
+            })
+            .collect();
+        let low = data.iter().copied().min().unwrap_or(0);
+        let high = data.iter().copied().max().unwrap_or(0);
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(1), Constraint::Min(0)])
+            .split(area);
+        let range = if data.is_empty() {
+            "no samples yet".to_string()
+        } else {
+            format!(
+                "{}% – {}%",
+                crate::format::decimal(low as f64 / 100.0, 2),
+                crate::format::decimal(high as f64 / 100.0, 2)
+            )
+        };
+        f.render_widget(
+            Paragraph::new(Line::from(vec![
+                Span::styled(
+                    format!("{} used ", disk.name),
+                    Style::default().fg(self.theme.get_color(Color::White)),
+                ),
+                Span::styled(
+                    range,
+                    Style::default().fg(self.theme.get_color(Color::Gray)),
+                ),
+            ])),
+            chunks[0],
+        );
+        // At least a tenth of a percent of range, so sampling noise stays flat
+        let margin = (high - low).max(10) as f64 / 2.0;
+        f.render_widget(
+            BrailleGraph::new(&data)
+                .style(Style::default().fg(self.theme.get_color(Color::LightMagenta)))
+                .value_range((low as f64 - margin).max(0.0), high as f64 + margin)
+                .show_baseline(false)
+                .fill(true),
+            chunks[1],
+        );
+    }
+
+    /// The `TOP_IO_ROWS` processes moving the most bytes to or from storage right now
+    fn top_io_lines(&self) -> Vec<Line<'static>> {
+        let mut busy: Vec<_> = self
+            .snapshot
+            .processes
+            .iter()
+            .filter(|process| process.disk_read_per_sec + process.disk_write_per_sec > 0)
+            .collect();
+        busy.sort_by_key(|process| {
+            std::cmp::Reverse(process.disk_read_per_sec + process.disk_write_per_sec)
+        });
+
+        busy.into_iter()
+            .take(Self::TOP_IO_ROWS)
+            .map(|process| {
+                Line::from(vec![
+                    Span::styled(
+                        format!("{:<12.12} ", process.name),
+                        Style::default().fg(self.theme.get_color(Color::Gray)),
+                    ),
+                    Span::styled(
+                        format!("R {:>9} ", crate::format::rate(process.disk_read_per_sec)),
+                        Style::default().fg(self.theme.get_color(Color::Green)),
+                    ),
+                    Span::styled(
+                        format!("W {:>9}", crate::format::rate(process.disk_write_per_sec)),
+                        Style::default().fg(self.theme.get_color(Color::Red)),
+                    ),
+                ])
+            })
+            .collect()
+    }
+
+    /// The highlighted volume; the last one once volumes were unmounted below the selection
+    pub fn selected_disk(&self) -> Option<DiskInfo> {
+        let disks = Self::deduplicate_disks(&self.snapshot.disks);
+        let index = self.selected_index.min(disks.len().saturating_sub(1));
+        disks.into_iter().nth(index)
     }
 
     fn deduplicate_disks(disks: &[DiskInfo]) -> Vec<DiskInfo> {
@@ -174,9 +352,11 @@ impl Component for DiskComponent {
     fn update(&mut self, action: Action) -> Result<Option<Action>, Box<dyn std::error::Error>> {
         match action {
             Action::MoveUp => {
-                if self.selected_index > 0 {
-                    self.selected_index -= 1;
-                }
+                let disk_count = Self::deduplicate_disks(&self.snapshot.disks).len();
+                self.selected_index = self
+                    .selected_index
+                    .min(disk_count.saturating_sub(1))
+                    .saturating_sub(1);
             }
             Action::MoveDown => {
                 let disk_count = Self::deduplicate_disks(&self.snapshot.disks).len();
@@ -192,4 +372,8 @@ impl Component for DiskComponent {
     fn render(&mut self, f: &mut Frame) {
         self.render_in_area(f, f.area());
     }
+
+    fn hints(&self) -> Vec<(&'static str, &'static str)> {
+        vec![("↑/↓", "hint-select-volume"), ("Enter", "hint-partitions")]
+    }
 }
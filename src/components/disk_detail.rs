@@ -0,0 +1,168 @@
+use super::Component;
+use crate::action::Action;
+use crate::data::blockdev::BlockDevice;
+use crate::theme::Theme;
+use ratatui::{
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph},
+    Frame,
+};
+
+/// Popup with the partition layout of the disk behind the selected volume, like `lsblk -f`
+pub struct DiskDetailPopup {
+    pub visible: bool,
+    pub theme: Theme,
+    /// Volume the popup was opened for, as the disk panel names it
+    pub volume: String,
+    /// `None` when the volume is not backed by a local block device
+    pub device: Option<BlockDevice>,
+}
+
+impl DiskDetailPopup {
+    pub fn new(theme: Theme) -> Self {
+        Self {
+            visible: false,
+            theme,
+            volume: String::new(),
+            device: None,
+        }
+    }
+
+    fn lines(&self) -> Vec<Line<'static>> {
+        let gray = Style::default().fg(self.theme.get_color(Color::Gray));
+        let dim = Style::default().fg(self.theme.get_color(Color::DarkGray));
+        let Some(device) = &self.device else {
+            return vec![Line::from(Span::styled(
+                format!(" {} is not backed by a local block device", self.volume),
+                gray,
+            ))];
+        };
+
+        let mut summary = vec![device.name.clone(), crate::format::bytes(device.size)];
+        summary.extend(device.model.clone());
+        summary.extend(
+            device
+                .rotational
+                .map(|rotational| if rotational { "HDD" } else { "SSD" }.to_string()),
+        );
+        summary.extend(
+            device
+                .scheduler
+                .as_ref()
+                .map(|scheduler| format!("scheduler {}", scheduler)),
+        );
+        let mut lines = vec![
+            Line::from(Span::styled(
+                format!(" {}", summary.join(" · ")),
+                self.theme.text_style().add_modifier(Modifier::BOLD),
+            )),
+            Line::from(""),
+            Line::from(Span::styled(
+                format!(
+                    " {:<14} {:>9}  {:<8} {:<37} {}",
+                    "NAME", "SIZE", "FSTYPE", "UUID", "MOUNTPOINTS"
+                ),
+                Style::default()
+                    .fg(self.theme.get_color(Color::Cyan))
+                    .add_modifier(Modifier::BOLD),
+            )),
+        ];
+
+        for partition in &device.partitions {
+            let style = if partition.selected {
+                Style::default()
+                    .fg(self.theme.get_color(Color::LightYellow))
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                self.theme.text_style()
+            };
+            let mount_points: Vec<&str> = partition
+                .mounts
+                .iter()
+                .map(|(target, _)| target.as_str())
+                .collect();
+            lines.push(Line::from(Span::styled(
+                format!(
+                    "{}{:<14} {:>9}  {:<8} {:<37} {}",
+                    if partition.selected { "▶" } else { " " },
+                    partition.name,
+                    crate::format::bytes(partition.size),
+                    partition.fs_type.as_deref().unwrap_or("-"),
+                    partition.uuid.as_deref().unwrap_or("-"),
+                    mount_points.join(" ")
+                ),
+                style,
+            )));
+            // Bind mounts of one filesystem usually share options; show each set once
+            let mut options: Vec<&str> = partition
+                .mounts
+                .iter()
+                .map(|(_, options)| options.as_str())
+                .collect();
+            options.dedup();
+            for options in options {
+                lines.push(Line::from(Span::styled(format!("   {}", options), gray)));
+            }
+        }
+
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            format!(" {}", crate::i18n::tr("esc-close")),
+            dim,
+        )));
+        lines
+    }
+
+    pub fn render_in_area(&self, f: &mut Frame, area: Rect) {
+        if !self.visible {
+            return;
+        }
+
+        let lines = self.lines();
+        let width = area.width.min(110);
+        let height = area.height.min(lines.len() as u16 + 2);
+        let popup_area = Rect {
+            x: area.x + (area.width - width) / 2,
+            y: area.y + (area.height - height) / 2,
+            width,
+            height,
+        };
+        f.render_widget(Clear, popup_area);
+
+        let popup = Paragraph::new(lines).block(
+            Block::default()
+                .title(Span::styled(
+                    format!(" {} {} ", crate::i18n::tr("disk"), self.volume),
+                    Style::default()
+                        .fg(self.theme.get_color(Color::LightYellow))
+                        .add_modifier(Modifier::BOLD),
+                ))
+                .borders(Borders::ALL)
+                .border_type(BorderType::Rounded)
+                .border_style(Style::default().fg(self.theme.get_color(Color::DarkGray))),
+        );
+        f.render_widget(popup, popup_area);
+    }
+}
+
+impl Component for DiskDetailPopup {
+    fn handle_events(
+        &mut self,
+        _event: crossterm::event::Event,
+    ) -> Result<Option<Action>, Box<dyn std::error::Error>> {
+        Ok(None)
+    }
+
+    fn update(&mut self, action: Action) -> Result<Option<Action>, Box<dyn std::error::Error>> {
+        if let Action::ToggleDiskDetail = action {
+            self.visible = !self.visible;
+        }
+        Ok(None)
+    }
+
+    fn render(&mut self, f: &mut Frame) {
+        self.render_in_area(f, f.area());
+    }
+}
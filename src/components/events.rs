@@ -0,0 +1,126 @@
+use super::Component;
+use crate::action::Action;
+use crate::alerts::{AlertEvent, AlertSeverity};
+use crate::i18n::tr;
+use crate::theme::Theme;
+use ratatui::{
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph},
+    Frame,
+};
+
+/// Popup listing alerts as they were raised and cleared, newest first
+pub struct EventTimeline {
+    pub visible: bool,
+    pub theme: Theme,
+    pub events: Vec<AlertEvent>,
+}
+
+impl EventTimeline {
+    pub fn new(theme: Theme) -> Self {
+        Self {
+            visible: false,
+            theme,
+            events: Vec::new(),
+        }
+    }
+
+    fn line(&self, event: &AlertEvent) -> Line<'static> {
+        let (marker, color) = match (event.recovered_after, event.severity) {
+            (Some(_), _) => ("✓", Color::Green),
+            (None, AlertSeverity::Critical) => ("⚠", Color::Red),
+            (None, AlertSeverity::Warning) => ("⚠", Color::Yellow),
+        };
+        let mut spans = vec![
+            Span::styled(
+                format!(" {} ", event.at.format("%H:%M:%S")),
+                Style::default().fg(self.theme.get_color(Color::Gray)),
+            ),
+            Span::styled(
+                format!("{} ", marker),
+                Style::default()
+                    .fg(self.theme.get_color(color))
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(event.message.clone(), self.theme.text_style()),
+        ];
+        if let Some(lasted) = event.recovered_after {
+            spans.push(Span::styled(
+                format!(" · cleared after {}", crate::format::duration(lasted)),
+                Style::default().fg(self.theme.get_color(Color::Green)),
+            ));
+        }
+        Line::from(spans)
+    }
+
+    pub fn render_in_area(&self, f: &mut Frame, area: Rect) {
+        if !self.visible {
+            return;
+        }
+
+        let dim = Style::default().fg(self.theme.get_color(Color::DarkGray));
+        let mut lines: Vec<Line> = if self.events.is_empty() {
+            vec![Line::from(Span::styled(
+                format!(" {}", tr("events-empty")),
+                dim,
+            ))]
+        } else {
+            self.events
+                .iter()
+                .rev()
+                .map(|event| self.line(event))
+                .collect()
+        };
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            format!(" {}", tr("esc-close")),
+            dim,
+        )));
+
+        let width = area.width.min(90);
+        let height = area.height.min(lines.len() as u16 + 2);
+        let popup_area = Rect {
+            x: area.x + (area.width - width) / 2,
+            y: area.y + (area.height - height) / 2,
+            width,
+            height,
+        };
+        f.render_widget(Clear, popup_area);
+
+        let popup = Paragraph::new(lines).block(
+            Block::default()
+                .title(Span::styled(
+                    format!(" {} ", tr("events-title")),
+                    Style::default()
+                        .fg(self.theme.get_color(Color::LightYellow))
+                        .add_modifier(Modifier::BOLD),
+                ))
+                .borders(Borders::ALL)
+                .border_type(BorderType::Rounded)
+                .border_style(dim),
+        );
+        f.render_widget(popup, popup_area);
+    }
+}
+
+impl Component for EventTimeline {
+    fn handle_events(
+        &mut self,
+        _event: crossterm::event::Event,
+    ) -> Result<Option<Action>, Box<dyn std::error::Error>> {
+        Ok(None)
+    }
+
+    fn update(&mut self, action: Action) -> Result<Option<Action>, Box<dyn std::error::Error>> {
+        if let Action::ToggleEventTimeline = action {
+            self.visible = !self.visible;
+        }
+        Ok(None)
+    }
+
+    fn render(&mut self, f: &mut Frame) {
+        self.render_in_area(f, f.area());
+    }
+}
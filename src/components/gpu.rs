@@ -1,6 +1,13 @@
-use crate::{action::Action, components::Component, data::snapshot::SystemSnapshot, theme::Theme};
+use crate::{
+    action::Action,
+    components::Component,
+    config::GpuTempLimit,
+    data::snapshot::{GpuInfo, SystemSnapshot},
+    theme::Theme,
+    widgets::gauge::{self, GradientBar},
+};
 use ratatui::{
-    layout::{Constraint, Direction, Layout},
+    layout::{Constraint, Direction, Layout, Rect},
     style::{Color, Style},
     text::{Line, Span},
     widgets::{Block, BorderType, Borders, Paragraph},
@@ -10,11 +17,20 @@ use ratatui::{
 pub struct GpuComponent {
     pub snapshot: SystemSnapshot,
     pub theme: Theme,
+    /// Name of the GPU shown with full details; the others get compact rows
+    pub primary_gpu: Option<String>,
+    /// Configured temperature thresholds; see [`GpuTempLimit::for_gpu`]
+    pub temp_limits: Vec<GpuTempLimit>,
 }
 
 impl GpuComponent {
     pub fn new(snapshot: SystemSnapshot, theme: Theme) -> Self {
-        Self { snapshot, theme }
+        Self {
+            snapshot,
+            theme,
+            primary_gpu: None,
+            temp_limits: Vec::new(),
+        }
     }
 
     #[allow(dead_code)]
@@ -28,94 +44,45 @@ impl GpuComponent {
         }
     }
 
-    fn calculate_gradient_color(&self, position_ratio: f32) -> Color {
-        // Smooth gradient using hex colors with LERP
-        // 0%->50%: Blend from #00ff87 to #f9ff00
-        // 50%->100%: Blend from #f9ff00 to #ff003c
-        if position_ratio < 0.5 {
-            // From #00ff87 to #f9ff00 (0.0 to 0.5)
-            let t = position_ratio * 2.0; // Normalize to 0.0-1.0 range
-                                          // Interpolate between #00ff87 (0, 255, 135) and #f9ff00 (249, 255, 0)
-            let r = (0.0 + (249.0 - 0.0) * t) as u8;
-            let g = (255.0 + (255.0 - 255.0) * t) as u8;
-            let b = (135.0 + (0.0 - 135.0) * t) as u8;
-            Color::Rgb(r, g, b)
-        } else {
-            // From #f9ff00 to #ff003c (0.5 to 1.0)
-            let t = (position_ratio - 0.5) * 2.0; // Normalize to 0.0-1.0 range
-                                                  // Interpolate between #f9ff00 (249, 255, 0) and #ff003c (255, 0, 60)
-            let r = (249.0 + (255.0 - 249.0) * t) as u8;
-            let g = (255.0 + (0.0 - 255.0) * t) as u8;
-            let b = (0.0 + (60.0 - 0.0) * t) as u8;
-            Color::Rgb(r, g, b)
-        }
-    }
-
-    fn calculate_track_color(&self, position_ratio: f32) -> Color {
-        // Dimmed version of the gradient color for the track (30% brightness)
-        let active_color = self.calculate_gradient_color(position_ratio);
-        match active_color {
-            Color::Rgb(r, g, b) => {
-                // Reduce brightness to 30% for visible track
-                Color::Rgb(
-                    (r as f32 * 0.3) as u8,
-                    (g as f32 * 0.3) as u8,
-                    (b as f32 * 0.3) as u8,
-                )
-            }
-            _ => Color::Rgb(50, 50, 50), // Fallback to medium grey
-        }
-    }
-
-    fn get_temperature_color(&self, temp: f32) -> Color {
-        // Dynamic temperature color based on LERP with proper thresholds
-        // Only turn 'Neon Rose' color when it actually crosses dangerous threshold (85°C+)
-        if temp < 50.0 {
+    fn get_temperature_color(&self, gpu: &GpuInfo, temp: f32) -> Color {
+        // Green until 25°C below the GPU's warning limit, yellow at the warning limit, orange
+        // just before the critical one and 'Neon Rose' from there on
+        let (warning, critical) = GpuTempLimit::for_gpu(&self.temp_limits, gpu);
+        let cool = warning - 25.0;
+        if temp < cool {
             // Cool: Electric Emerald (#00ff87)
             Color::Rgb(0, 255, 135)
-        } else if temp <= 75.0 {
+        } else if temp <= warning {
             // Moderate: Interpolate between Electric Emerald and Cyber Yellow
-            let t = (temp - 50.0) / 25.0; // Normalize to 0.0-1.0 range
-            let r = (0.0 + (249.0 - 0.0) * t) as u8;
-            let g = (255.0 + (255.0 - 255.0) * t) as u8;
-            let b = (135.0 + (0.0 - 135.0) * t) as u8;
-            Color::Rgb(r, g, b)
-        } else if temp <= 85.0 {
-            // Warm: Interpolate between Cyber Yellow and Orange
-            let t = (temp - 75.0) / 10.0; // Normalize to 0.0-1.0 range
-            let r = (249.0 + (255.0 - 249.0) * t) as u8; // From 249 to 255 (red)
-            let g = (255.0 + (165.0 - 255.0) * t) as u8; // From 255 to 165 (green)
-            let b = (0.0 + (0.0 - 0.0) * t) as u8; // Stay at 0 (blue)
-            Color::Rgb(r, g, b) // Approaching orange: #FFA500
+            let t = (temp - cool) / 25.0;
+            let r = (249.0 * t) as u8;
+            let b = (135.0 - 135.0 * t) as u8;
+            Color::Rgb(r, 255, b)
+        } else if temp < critical {
+            // Warm: Interpolate between Cyber Yellow and Orange (#FFA500)
+            let t = (temp - warning) / (critical - warning).max(1.0);
+            let r = (249.0 + (255.0 - 249.0) * t) as u8;
+            let g = (255.0 + (165.0 - 255.0) * t) as u8;
+            Color::Rgb(r, g, 0)
         } else {
-            // Dangerous: Neon Rose (#ff003c) for temperatures above 85°C
+            // Dangerous: Neon Rose (#ff003c) at the critical limit and above
             Color::Rgb(255, 0, 60)
         }
     }
 
-    fn bar_width_for_area(area_width: u16, prefix_chars: usize, max_width: usize) -> usize {
-        let available = area_width as usize;
-        let room_after_prefix = available.saturating_sub(prefix_chars);
-        room_after_prefix.min(max_width)
-    }
-
-    fn fractional_block(units: usize) -> char {
-        match units {
-            1 => '▏',
-            2 => '▎',
-            3 => '▍',
-            4 => '▌',
-            5 => '▋',
-            6 => '▊',
-            7 => '▉',
-            _ => '░',
-        }
-    }
-
     pub fn render_in_area(&self, f: &mut Frame, area: ratatui::layout::Rect) {
+        let title = if self.snapshot.gpus.len() > 1 {
+            format!(
+                " GPU {}/{} · [g] cycle ",
+                self.primary_index() + 1,
+                self.snapshot.gpus.len()
+            )
+        } else {
+            " GPU ".to_string()
+        };
         let block = Block::default()
             .title(Span::styled(
-                " GPU ",
+                title,
                 self.theme
                     .text_style()
                     .add_modifier(ratatui::style::Modifier::BOLD),
@@ -136,180 +103,197 @@ impl GpuComponent {
             return;
         }
 
-        // Calculate height needed for each GPU - allowing more space for the new layout
-        let mut gpu_heights = Vec::new();
-        for _gpu_info in &self.snapshot.gpus {
-            // Fixed height for the new layout (name, bar, and stats)
-            let height = 4; // Height for name, bar, and stats grid
-
-            gpu_heights.push(height);
-        }
-
-        let gpu_constraints: Vec<Constraint> =
-            gpu_heights.iter().map(|&h| Constraint::Length(h)).collect();
-        let gpu_chunks = Layout::default()
+        let primary = self.primary_index();
+        let compact_rows = self.snapshot.gpus.len().saturating_sub(1) as u16;
+        let chunks = Layout::default()
             .direction(Direction::Vertical)
-            .constraints(gpu_constraints)
+            .constraints([
+                Constraint::Length(Self::DETAIL_HEIGHT), // Primary GPU in full
+                Constraint::Length(compact_rows),        // One row per other GPU
+            ])
             .margin(1) // Add margin between GPU blocks
             .split(inner_area);
 
-        for (i, (gpu_info, &gpu_height)) in self
+        self.render_gpu_detail(f, chunks[0], &self.snapshot.gpus[primary]);
+
+        let compact_lines: Vec<Line> = self
             .snapshot
             .gpus
             .iter()
-            .zip(gpu_heights.iter())
             .enumerate()
-        {
-            if i >= gpu_chunks.len() {
-                break;
-            }
+            .filter(|(i, _)| *i != primary)
+            .map(|(_, gpu_info)| self.compact_gpu_line(gpu_info))
+            .collect();
+        f.render_widget(Paragraph::new(compact_lines), chunks[1]);
+    }
 
-            let gpu_area = gpu_chunks[i];
+    const DETAIL_HEIGHT: u16 = 4; // Name, bar, and stats grid
 
-            // Split GPU area into: Name, Bar, Stats
-            let mut constraints = vec![
-                Constraint::Length(1), // Name header
-                Constraint::Length(1), // Usage bar
-            ];
+    /// Index of the GPU shown in full: the configured one if present, else the first
+    fn primary_index(&self) -> usize {
+        self.primary_gpu
+            .as_deref()
+            .and_then(|name| self.snapshot.gpus.iter().position(|gpu| gpu.name == name))
+            .unwrap_or(0)
+    }
 
-            // Add constraint for stats grid
-            constraints.push(Constraint::Length(gpu_height - 2)); // Remaining space for stats
+    /// Single-row summary for GPUs that are not the primary one
+    fn compact_gpu_line(&self, gpu_info: &GpuInfo) -> Line<'static> {
+        let usage = gpu_info
+            .usage
+            .map(|usage| {
+                format!(
+                    "{:>5}%",
+                    crate::format::decimal(f64::from(usage.clamp(0.0, 100.0)), 1)
+                )
+            })
+            .unwrap_or_else(|| "  N/A ".to_string());
+        let usage_color = gpu_info
+            .usage
+            .map(|usage| gauge::gradient_color(&self.theme, usage.clamp(0.0, 100.0) / 100.0))
+            .unwrap_or(self.theme.get_color(Color::Gray));
+        let temp = gpu_info
+            .temp
+            .map(|temp| {
+                (
+                    format!(" T:{:.0}C", temp),
+                    Style::default().fg(self.get_temperature_color(gpu_info, temp)),
+                )
+            })
+            .unwrap_or_else(|| {
+                (
+                    " T:--".to_string(),
+                    Style::default().fg(self.theme.get_color(Color::Gray)),
+                )
+            });
+        let name: String = gpu_info.name.chars().take(24).collect();
 
-            let per_gpu_chunks = Layout::default()
-                .direction(Direction::Vertical)
-                .constraints(constraints)
-                .split(gpu_area);
+        Line::from(vec![
+            Span::styled(
+                format!("{:<25}", name),
+                Style::default().fg(self.theme.get_color(Color::Cyan)),
+            ),
+            Span::styled(usage, Style::default().fg(usage_color)),
+            Span::styled(temp.0, temp.1),
+        ])
+    }
 
-            let name_area = per_gpu_chunks[0];
-            let bar_area = per_gpu_chunks[1];
-            let stats_area = per_gpu_chunks[2];
+    fn render_gpu_detail(&self, f: &mut Frame, gpu_area: Rect, gpu_info: &GpuInfo) {
+        let gpu_height = Self::DETAIL_HEIGHT;
 
-            // Render GPU Name Header
-            let name_text = format!("{}", gpu_info.name);
-            let name_paragraph = Paragraph::new(Span::styled(
-                name_text,
-                Style::default()
-                    .fg(self.theme.get_color(Color::Cyan))
-                    .add_modifier(ratatui::style::Modifier::BOLD),
-            ))
-            .wrap(ratatui::widgets::Wrap { trim: true });
-            f.render_widget(name_paragraph, name_area);
+        // Split GPU area into: Name, Bar, Stats
+        let mut constraints = vec![
+            Constraint::Length(1), // Name header
+            Constraint::Length(1), // Usage bar
+        ];
 
-            // Render Hero Bar (Usage % and Gradient Bar)
-            let usage_percentage = gpu_info.usage.map(|value| value.clamp(0.0, 100.0));
-            let usage_prefix = match usage_percentage {
-                Some(usage) => format!("Usage:{:>6.1}% ", usage),
-                None => "Usage:  N/A  ".to_string(),
-            };
-            let bar_width = Self::bar_width_for_area(bar_area.width, usage_prefix.len(), 48);
-            let total_units = bar_width * 8; // 8 sub-units per block
-            let filled_units =
-                (usage_percentage.unwrap_or(0.0) / 100.0 * total_units as f32).round() as usize;
+        // Add constraint for stats grid
+        constraints.push(Constraint::Length(gpu_height - 2)); // Remaining space for stats
 
-            let mut spans = Vec::new();
-            spans.push(Span::styled(
-                usage_prefix,
-                if usage_percentage.is_some() {
-                    self.theme.text_style()
-                } else {
-                    Style::default().fg(self.theme.get_color(Color::Gray))
-                },
-            ));
+        let per_gpu_chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints(constraints)
+            .split(gpu_area);
 
-            for i in 0..bar_width {
-                let start_unit = i * 8;
-                let end_unit = start_unit + 8;
-                let position_ratio = i as f32 / (bar_width.saturating_sub(1).max(1)) as f32;
-                let active_color = self.calculate_gradient_color(position_ratio);
-                let track_color = self.calculate_track_color(position_ratio);
+        let name_area = per_gpu_chunks[0];
+        let bar_area = per_gpu_chunks[1];
+        let stats_area = per_gpu_chunks[2];
 
-                let (char, style) = if filled_units <= start_unit {
-                    (' ', Style::default().bg(track_color))
-                } else if filled_units >= end_unit {
-                    (' ', Style::default().bg(active_color))
-                } else {
-                    let partial_units = filled_units - start_unit;
-                    (
-                        Self::fractional_block(partial_units),
-                        Style::default().fg(active_color).bg(track_color),
-                    )
-                };
+        // Render GPU Name Header
+        let name_text = format!("{}", gpu_info.name);
+        let name_paragraph = Paragraph::new(Span::styled(
+            name_text,
+            Style::default()
+                .fg(self.theme.get_color(Color::Cyan))
+                .add_modifier(ratatui::style::Modifier::BOLD),
+        ))
+        .wrap(ratatui::widgets::Wrap { trim: true });
+        f.render_widget(name_paragraph, name_area);
 
-                spans.push(Span::styled(char.to_string(), style));
-            }
-            let usage_line = Line::from(spans);
-            let paragraph = Paragraph::new(usage_line);
-            f.render_widget(paragraph, bar_area);
+        // Render Hero Bar (Usage % and Gradient Bar)
+        let usage_percentage = gpu_info.usage.map(|value| value.clamp(0.0, 100.0));
+        let usage_prefix = match usage_percentage {
+            Some(usage) => format!("Usage:{:>6}% ", crate::format::decimal(f64::from(usage), 1)),
+            None => "Usage:  N/A  ".to_string(),
+        };
+        let label_style = if usage_percentage.is_some() {
+            self.theme.text_style()
+        } else {
+            Style::default().fg(self.theme.get_color(Color::Gray))
+        };
+        let usage_bar = GradientBar::new(&self.theme, f64::from(usage_percentage.unwrap_or(0.0)))
+            .label(usage_prefix, label_style)
+            .max_width(48);
+        f.render_widget(usage_bar, bar_area);
 
-            // Render fixed compact stats with stable field widths.
-            let temp_field = if let Some(temp) = gpu_info.temp {
-                (
-                    format!("T:{:.0}C", temp),
-                    Style::default().fg(self.get_temperature_color(temp)),
-                )
-            } else {
+        // Render fixed compact stats with stable field widths.
+        let temp_field = if let Some(temp) = gpu_info.temp {
+            (
+                format!("T:{:.0}C", temp),
+                Style::default().fg(self.get_temperature_color(gpu_info, temp)),
+            )
+        } else {
+            (
+                "T:--".to_string(),
+                Style::default().fg(self.theme.get_color(Color::Gray)),
+            )
+        };
+        let power_field = if let Some(power) = gpu_info.power_usage {
+            if power > 0.0 {
                 (
-                    "T:--".to_string(),
-                    Style::default().fg(self.theme.get_color(Color::Gray)),
+                    format!("P:{}W", crate::format::decimal(f64::from(power), 1)),
+                    Style::default().fg(self.theme.get_color(Color::Yellow)),
                 )
-            };
-            let power_field = if let Some(power) = gpu_info.power_usage {
-                if power > 0.0 {
-                    (
-                        format!("P:{:.1}W", power),
-                        Style::default().fg(self.theme.get_color(Color::Yellow)),
-                    )
-                } else {
-                    (
-                        "P:--".to_string(),
-                        Style::default().fg(self.theme.get_color(Color::Gray)),
-                    )
-                }
             } else {
                 (
                     "P:--".to_string(),
                     Style::default().fg(self.theme.get_color(Color::Gray)),
                 )
-            };
-            let mem_field = if let (Some(mem_used), Some(mem_total)) =
-                (gpu_info.memory_used, gpu_info.memory_total)
-            {
-                (
-                    format!(
-                        "M:{}/{}",
-                        crate::utils::bytes_to_human_readable(mem_used),
-                        crate::utils::bytes_to_human_readable(mem_total)
-                    ),
-                    self.theme.text_style(),
-                )
-            } else if let Some(mem_used) = gpu_info.memory_used {
-                (
-                    format!("M:{}", crate::utils::bytes_to_human_readable(mem_used)),
-                    self.theme.text_style(),
-                )
-            } else if let Some(mem_total) = gpu_info.memory_total {
-                (
-                    format!("M:--/{}", crate::utils::bytes_to_human_readable(mem_total)),
-                    Style::default().fg(self.theme.get_color(Color::Gray)),
-                )
-            } else {
-                (
-                    "M:--".to_string(),
-                    Style::default().fg(self.theme.get_color(Color::Gray)),
-                )
-            };
-            let stats_line = Line::from(vec![
-                Span::styled(temp_field.0, temp_field.1),
-                Span::raw("  "),
-                Span::styled(power_field.0, power_field.1),
-                Span::raw("  "),
-                Span::styled(mem_field.0, mem_field.1),
-            ]);
-            f.render_widget(
-                Paragraph::new(stats_line).wrap(ratatui::widgets::Wrap { trim: true }),
-                stats_area,
-            );
-        }
+            }
+        } else {
+            (
+                "P:--".to_string(),
+                Style::default().fg(self.theme.get_color(Color::Gray)),
+            )
+        };
+        let mem_field = if let (Some(mem_used), Some(mem_total)) =
+            (gpu_info.memory_used, gpu_info.memory_total)
+        {
+            (
+                format!(
+                    "M:{}/{}",
+                    crate::format::bytes(mem_used),
+                    crate::format::bytes(mem_total)
+                ),
+                self.theme.text_style(),
+            )
+        } else if let Some(mem_used) = gpu_info.memory_used {
+            (
+                format!("M:{}", crate::format::bytes(mem_used)),
+                self.theme.text_style(),
+            )
+        } else if let Some(mem_total) = gpu_info.memory_total {
+            (
+                format!("M:--/{}", crate::format::bytes(mem_total)),
+                Style::default().fg(self.theme.get_color(Color::Gray)),
+            )
+        } else {
+            (
+                "M:--".to_string(),
+                Style::default().fg(self.theme.get_color(Color::Gray)),
+            )
+        };
+        let stats_line = Line::from(vec![
+            Span::styled(temp_field.0, temp_field.1),
+            Span::raw("  "),
+            Span::styled(power_field.0, power_field.1),
+            Span::raw("  "),
+            Span::styled(mem_field.0, mem_field.1),
+        ]);
+        f.render_widget(
+            Paragraph::new(stats_line).wrap(ratatui::widgets::Wrap { trim: true }),
+            stats_area,
+        );
     }
 
     // Use the shared utility function instead of duplicating code
@@ -331,4 +315,8 @@ impl Component for GpuComponent {
     fn render(&mut self, f: &mut Frame) {
         self.render_in_area(f, f.area());
     }
+
+    fn hints(&self) -> Vec<(&'static str, &'static str)> {
+        vec![("g", "hint-next-gpu")]
+    }
 }
@@ -0,0 +1,224 @@
+use super::Component;
+use crate::action::Action;
+use crate::rollups::HourRollup;
+use crate::theme::Theme;
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Axis, Block, BorderType, Borders, Chart, Clear, Dataset, GraphType, Paragraph},
+    Frame,
+};
+
+/// Renders an axis label for a chart's values (`50%`, `1.2MB/s`)
+type Formatter = fn(f64) -> String;
+
+/// Full-screen charts of the persisted hourly rollups over the last day or week
+pub struct HistoryView {
+    pub visible: bool,
+    pub theme: Theme,
+    pub hours: Vec<HourRollup>,
+    /// Show the last 7 days instead of the last 24 hours
+    pub week: bool,
+    /// Why earlier sessions' rollups were not restored
+    pub warning: Option<String>,
+}
+
+impl HistoryView {
+    pub fn new(theme: Theme) -> Self {
+        Self {
+            visible: false,
+            theme,
+            hours: Vec::new(),
+            week: false,
+            warning: None,
+        }
+    }
+
+    fn span_hours(&self) -> i64 {
+        if self.week {
+            7 * 24
+        } else {
+            24
+        }
+    }
+
+    /// One metric as `(hours before now, value)` points over the selected range
+    fn series(&self, now_hour: i64, value: fn(&HourRollup) -> f64) -> Vec<(f64, f64)> {
+        self.hours
+            .iter()
+            .map(|hour| ((hour.hour - now_hour) as f64 / 3600.0, value(hour)))
+            .filter(|(x, _)| *x > -(self.span_hours() as f64))
+            .collect()
+    }
+
+    fn render_chart(
+        &self,
+        f: &mut Frame,
+        area: Rect,
+        (title, color): (&str, Color),
+        data: &[(f64, f64)],
+        (max, format): (Option<f64>, Formatter),
+    ) {
+        let gray = Style::default().fg(self.theme.get_color(Color::Gray));
+        let y_max = max.unwrap_or_else(|| {
+            data.iter()
+                .map(|(_, value)| *value)
+                .fold(0.0_f64, f64::max)
+                .max(1.0)
+                * 1.15
+        });
+        let span = self.span_hours() as f64;
+        let x_label = |hours_ago: f64| {
+            if hours_ago == 0.0 {
+                "now".to_string()
+            } else if self.week {
+                format!("-{:.0}d", hours_ago / 24.0)
+            } else {
+                format!("-{:.0}h", hours_ago)
+            }
+        };
+
+        let dataset = Dataset::default()
+            .data(data)
+            .graph_type(GraphType::Line)
+            .marker(ratatui::symbols::Marker::Braille)
+            .style(Style::default().fg(self.theme.get_color(color)));
+        let chart = Chart::new(vec![dataset])
+            .block(
+                Block::default()
+                    .title(Span::styled(
+                        format!(" {} ", title),
+                        Style::default()
+                            .fg(self.theme.get_color(color))
+                            .add_modifier(Modifier::BOLD),
+                    ))
+                    .borders(Borders::ALL)
+                    .border_type(BorderType::Rounded)
+                    .border_style(Style::default().fg(self.theme.get_color(Color::DarkGray))),
+            )
+            .x_axis(
+                Axis::default()
+                    .bounds([-(span - 1.0), 0.0])
+                    .style(gray)
+                    .labels(vec![
+                        Span::styled(x_label(span - 1.0), gray),
+                        Span::styled(x_label((span / 2.0).round()), gray),
+                        Span::styled(x_label(0.0), gray),
+                    ]),
+            )
+            .y_axis(
+                Axis::default()
+                    .bounds([0.0, y_max])
+                    .style(gray)
+                    .labels(vec![
+                        Span::styled(format(0.0), gray),
+                        Span::styled(format(y_max / 2.0), gray),
+                        Span::styled(format(y_max), gray),
+                    ]),
+            );
+        f.render_widget(chart, area);
+    }
+
+    pub fn render_in_area(&self, f: &mut Frame, area: Rect) {
+        if !self.visible {
+            return;
+        }
+        f.render_widget(Clear, area);
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(1), Constraint::Min(0)])
+            .split(area);
+        let range = if self.week { "7 days" } else { "24 hours" };
+        let mut spans = vec![
+            Span::styled(
+                format!(" History · last {} ", range),
+                Style::default()
+                    .fg(self.theme.get_color(Color::LightYellow))
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(
+                " hourly averages, kept across restarts · Tab 24h/7d · Esc close",
+                Style::default().fg(self.theme.get_color(Color::DarkGray)),
+            ),
+        ];
+        if let Some(warning) = &self.warning {
+            spans.push(Span::styled(
+                format!(" · ⚠ {}", warning),
+                Style::default().fg(self.theme.get_color(Color::Yellow)),
+            ));
+        }
+        let header = Line::from(spans);
+        f.render_widget(Paragraph::new(header), chunks[0]);
+
+        let rows = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+            .split(chunks[1]);
+        let cells: Vec<Rect> = rows
+            .iter()
+            .flat_map(|row| {
+                Layout::default()
+                    .direction(Direction::Horizontal)
+                    .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+                    .split(*row)
+                    .to_vec()
+            })
+            .collect();
+
+        let now_hour = self.hours.last().map_or(0, |hour| hour.hour);
+        let percent: Formatter = |value| format!("{:.0}%", value);
+        let rate: Formatter = |value| crate::format::rate(value as u64);
+        self.render_chart(
+            f,
+            cells[0],
+            ("CPU", Color::LightBlue),
+            &self.series(now_hour, |hour| hour.cpu_percent),
+            (Some(100.0), percent),
+        );
+        self.render_chart(
+            f,
+            cells[1],
+            ("Memory pressure", Color::LightMagenta),
+            &self.series(now_hour, |hour| hour.memory_percent),
+            (Some(100.0), percent),
+        );
+        self.render_chart(
+            f,
+            cells[2],
+            ("Network RX+TX", Color::LightCyan),
+            &self.series(now_hour, |hour| hour.net_bytes_per_sec),
+            (None, rate),
+        );
+        self.render_chart(
+            f,
+            cells[3],
+            ("Disk I/O", Color::LightGreen),
+            &self.series(now_hour, |hour| hour.disk_bytes_per_sec),
+            (None, rate),
+        );
+    }
+}
+
+impl Component for HistoryView {
+    fn handle_events(
+        &mut self,
+        _event: crossterm::event::Event,
+    ) -> Result<Option<Action>, Box<dyn std::error::Error>> {
+        Ok(None)
+    }
+
+    fn update(&mut self, action: Action) -> Result<Option<Action>, Box<dyn std::error::Error>> {
+        match action {
+            Action::ToggleHistoryView => self.visible = !self.visible,
+            Action::CycleHistoryRange => self.week = !self.week,
+            _ => {}
+        }
+        Ok(None)
+    }
+
+    fn render(&mut self, f: &mut Frame) {
+        self.render_in_area(f, f.area());
+    }
+}
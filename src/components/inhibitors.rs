@@ -0,0 +1,228 @@
+use super::Component;
+use crate::action::Action;
+use crate::data::inhibitors::Inhibitor;
+use crate::data::snapshot::SystemSnapshot;
+use crate::i18n::{tr, tr_args};
+use crate::theme::Theme;
+use ratatui::{
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph},
+    Frame,
+};
+use std::collections::HashMap;
+use std::sync::mpsc;
+
+/// Popup listing the logind inhibitor locks and the processes holding them, for "why won't
+/// this laptop suspend"
+pub struct InhibitorsPopup {
+    pub visible: bool,
+    pub theme: Theme,
+    /// `None` when logind could not be asked
+    pub inhibitors: Option<Vec<Inhibitor>>,
+    /// Name and owner of each holder, looked up when the popup was opened
+    holders: HashMap<u32, (String, Option<String>)>,
+    /// The locks being asked for in the background; see `poll`
+    pending: Option<mpsc::Receiver<Option<Vec<Inhibitor>>>>,
+}
+
+impl InhibitorsPopup {
+    pub fn new(theme: Theme) -> Self {
+        Self {
+            visible: false,
+            theme,
+            inhibitors: None,
+            holders: HashMap::new(),
+            pending: None,
+        }
+    }
+
+    /// Show the popup and ask logind for the locks on a thread, as busctl can take a while
+    /// to answer (or to time out) on a busy bus
+    pub fn request(&mut self) {
+        let (tx, rx) = mpsc::channel();
+        std::thread::spawn(move || {
+            let _ = tx.send(crate::data::inhibitors::list());
+        });
+        self.pending = Some(rx);
+        self.inhibitors = None;
+        self.visible = true;
+    }
+
+    /// Take the locks once the background request has them
+    pub fn poll(&mut self, snapshot: &SystemSnapshot) {
+        let Some(rx) = &self.pending else {
+            return;
+        };
+        match rx.try_recv() {
+            Ok(inhibitors) => {
+                self.pending = None;
+                if self.visible {
+                    self.open(inhibitors, snapshot);
+                }
+            }
+            Err(mpsc::TryRecvError::Disconnected) => self.pending = None,
+            Err(mpsc::TryRecvError::Empty) => {}
+        }
+    }
+
+    /// Show `inhibitors`, naming their holders from `snapshot`
+    pub fn open(&mut self, inhibitors: Option<Vec<Inhibitor>>, snapshot: &SystemSnapshot) {
+        self.holders = inhibitors
+            .iter()
+            .flatten()
+            .filter_map(|inhibitor| {
+                let process = snapshot
+                    .processes
+                    .iter()
+                    .find(|process| process.pid == inhibitor.pid)?;
+                Some((process.pid, (process.name.clone(), process.user.clone())))
+            })
+            .collect();
+        self.inhibitors = inhibitors;
+        self.visible = true;
+    }
+
+    fn lines(&self) -> Vec<Line<'static>> {
+        let gray = Style::default().fg(self.theme.get_color(Color::Gray));
+        let dim = Style::default().fg(self.theme.get_color(Color::DarkGray));
+        let mut lines = Vec::new();
+        match &self.inhibitors {
+            None if self.pending.is_some() => lines.push(Line::from(Span::styled(
+                format!(" {}", tr("inhibitors-loading")),
+                gray,
+            ))),
+            None => lines.push(Line::from(Span::styled(
+                format!(" {}", tr("inhibitors-unavailable")),
+                gray,
+            ))),
+            Some(inhibitors) if inhibitors.is_empty() => lines.push(Line::from(Span::styled(
+                format!(" {}", tr("inhibitors-empty")),
+                gray,
+            ))),
+            Some(inhibitors) => {
+                let blocking = inhibitors.iter().filter(|lock| lock.blocks()).count();
+                lines.push(Line::from(Span::styled(
+                    format!(
+                        " {}",
+                        tr_args(
+                            "inhibitors-summary",
+                            &[
+                                ("blocking", &blocking),
+                                ("delaying", &(inhibitors.len() - blocking)),
+                            ],
+                        )
+                    ),
+                    self.theme.text_style().add_modifier(Modifier::BOLD),
+                )));
+                lines.push(Line::from(""));
+                lines.push(Line::from(Span::styled(
+                    format!(
+                        " {:<5} {:<22} {:>7} {:<15} {:<10} {}",
+                        tr("inhibitors-mode"),
+                        tr("inhibitors-what"),
+                        tr("inhibitors-pid"),
+                        tr("inhibitors-process"),
+                        tr("inhibitors-user"),
+                        tr("inhibitors-who")
+                    ),
+                    Style::default()
+                        .fg(self.theme.get_color(Color::Cyan))
+                        .add_modifier(Modifier::BOLD),
+                )));
+                for inhibitor in inhibitors {
+                    let (name, user) = match self.holders.get(&inhibitor.pid) {
+                        Some((name, user)) => (name.clone(), user.clone()),
+                        None => ("-".to_string(), None),
+                    };
+                    let user = user.unwrap_or_else(|| inhibitor.uid.to_string());
+                    let style = if inhibitor.blocks() {
+                        Style::default().fg(self.theme.get_color(Color::LightRed))
+                    } else {
+                        gray
+                    };
+                    lines.push(Line::from(Span::styled(
+                        format!(
+                            " {:<5} {:<22} {:>7} {:<15} {:<10} {}",
+                            inhibitor.mode,
+                            inhibitor.what,
+                            inhibitor.pid,
+                            name,
+                            user,
+                            inhibitor.who
+                        ),
+                        style,
+                    )));
+                    if !inhibitor.why.is_empty() {
+                        lines.push(Line::from(Span::styled(
+                            format!("       {}", inhibitor.why),
+                            dim,
+                        )));
+                    }
+                }
+            }
+        }
+
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            format!(" {}", tr("esc-close")),
+            dim,
+        )));
+        lines
+    }
+
+    pub fn render_in_area(&self, f: &mut Frame, area: Rect) {
+        if !self.visible {
+            return;
+        }
+
+        let lines = self.lines();
+        let width = area.width.min(100);
+        let height = area.height.min(lines.len() as u16 + 2);
+        let popup_area = Rect {
+            x: area.x + (area.width - width) / 2,
+            y: area.y + (area.height - height) / 2,
+            width,
+            height,
+        };
+        f.render_widget(Clear, popup_area);
+
+        let popup = Paragraph::new(lines).block(
+            Block::default()
+                .title(Span::styled(
+                    format!(" {} ", tr("inhibitors-title")),
+                    Style::default()
+                        .fg(self.theme.get_color(Color::LightYellow))
+                        .add_modifier(Modifier::BOLD),
+                ))
+                .borders(Borders::ALL)
+                .border_type(BorderType::Rounded)
+                .border_style(Style::default().fg(self.theme.get_color(Color::DarkGray))),
+        );
+        f.render_widget(popup, popup_area);
+    }
+}
+
+impl Component for InhibitorsPopup {
+    fn handle_events(
+        &mut self,
+        _event: crossterm::event::Event,
+    ) -> Result<Option<Action>, Box<dyn std::error::Error>> {
+        Ok(None)
+    }
+
+    fn update(&mut self, action: Action) -> Result<Option<Action>, Box<dyn std::error::Error>> {
+        if let Action::ToggleInhibitors = action {
+            self.visible = !self.visible;
+            if !self.visible {
+                self.pending = None;
+            }
+        }
+        Ok(None)
+    }
+
+    fn render(&mut self, f: &mut Frame) {
+        self.render_in_area(f, f.area());
+    }
+}
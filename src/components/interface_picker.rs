@@ -0,0 +1,230 @@
+use super::Component;
+use crate::action::Action;
+use crate::data::snapshot::{NetworkInfo, SystemSnapshot};
+use crate::i18n::tr;
+use crate::theme::Theme;
+use ratatui::{
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph},
+    Frame,
+};
+use std::collections::BTreeMap;
+
+/// Popup listing every network interface with its rates and link state, to pick the one the
+/// network panel shows (or all of them) without cycling through veths and bridges blindly
+pub struct InterfacePicker {
+    pub visible: bool,
+    pub theme: Theme,
+    pub snapshot: SystemSnapshot,
+    pub nicknames: BTreeMap<String, String>,
+    /// Show rates in bits per second instead of bytes
+    pub show_bits: bool,
+    /// Highlighted interface; `None` is the "All" entry. Kept by name so the highlight stays
+    /// put while interfaces come and go.
+    selected: Option<String>,
+}
+
+impl InterfacePicker {
+    pub fn new(snapshot: SystemSnapshot, theme: Theme) -> Self {
+        Self {
+            visible: false,
+            theme,
+            snapshot,
+            nicknames: BTreeMap::new(),
+            show_bits: false,
+            selected: None,
+        }
+    }
+
+    /// Show the interfaces, highlighting the one the network panel shows
+    pub fn open(&mut self, snapshot: &SystemSnapshot) {
+        self.snapshot = snapshot.clone();
+        self.selected = snapshot.selected_network_interface.clone();
+        self.visible = true;
+    }
+
+    /// Interfaces by name, after the "All" entry (`None`)
+    fn entries(&self) -> Vec<Option<&NetworkInfo>> {
+        let mut networks: Vec<&NetworkInfo> = self.snapshot.networks.iter().collect();
+        networks.sort_by(|a, b| a.name.cmp(&b.name));
+        networks.dedup_by(|a, b| a.name == b.name);
+        std::iter::once(None)
+            .chain(networks.into_iter().map(Some))
+            .collect()
+    }
+
+    /// Index of the highlighted entry, or "All" once its interface is gone
+    fn selected_index(&self, entries: &[Option<&NetworkInfo>]) -> usize {
+        entries
+            .iter()
+            .position(|entry| entry.map(|net| &net.name) == self.selected.as_ref())
+            .unwrap_or(0)
+    }
+
+    /// The interface to show, `None` for all of them
+    pub fn selected_interface(&self) -> Option<String> {
+        let entries = self.entries();
+        entries[self.selected_index(&entries)].map(|net| net.name.clone())
+    }
+
+    fn move_selection(&mut self, forward: bool) {
+        let entries = self.entries();
+        let at = self.selected_index(&entries);
+        let next = if forward {
+            (at + 1).min(entries.len() - 1)
+        } else {
+            at.saturating_sub(1)
+        };
+        self.selected = entries[next].map(|net| net.name.clone());
+    }
+
+    fn format_rate(&self, bytes_per_sec: u64) -> String {
+        if self.show_bits {
+            crate::format::bits_rate(bytes_per_sec)
+        } else {
+            crate::format::rate(bytes_per_sec)
+        }
+    }
+
+    fn truncate(text: &str, width: usize) -> String {
+        if text.chars().count() <= width {
+            text.to_string()
+        } else {
+            let mut cut: String = text.chars().take(width - 1).collect();
+            cut.push('…');
+            cut
+        }
+    }
+
+    /// The header, the `visible_rows` entries around the selection, and the key hint
+    fn lines(&self, visible_rows: usize) -> Vec<Line<'static>> {
+        let dim = Style::default().fg(self.theme.get_color(Color::DarkGray));
+        let entries = self.entries();
+        let selected = self.selected_index(&entries);
+        let start = super::window_start(selected, visible_rows);
+        let mut lines = vec![Line::from(Span::styled(
+            format!(
+                " {:<28} {:<8} {:>11} {:>11}",
+                "INTERFACE", "STATE", "RX", "TX"
+            ),
+            Style::default()
+                .fg(self.theme.get_color(Color::Cyan))
+                .add_modifier(Modifier::BOLD),
+        ))];
+        for (index, entry) in entries.iter().enumerate().skip(start).take(visible_rows) {
+            let (name, state, rx, tx) = match entry {
+                // Members of a bond/bridge and VLANs are already counted on their parent
+                None => {
+                    let top_level = self
+                        .snapshot
+                        .networks
+                        .iter()
+                        .filter(|net| net.parent.is_none());
+                    let (rx, tx) = top_level.fold((0, 0), |acc, net| {
+                        (
+                            acc.0 + net.received_per_sec,
+                            acc.1 + net.transmitted_per_sec,
+                        )
+                    });
+                    (tr("interfaces-all").to_string(), String::new(), rx, tx)
+                }
+                Some(net) => {
+                    let mut name = match self.nicknames.get(&net.name) {
+                        Some(nickname) => format!("{} ({})", nickname, net.name),
+                        None => net.name.clone(),
+                    };
+                    if net.parent.is_some() {
+                        name = format!("└ {}", name);
+                    }
+                    let state = net.oper_state.clone().unwrap_or_else(|| "-".to_string());
+                    (name, state, net.received_per_sec, net.transmitted_per_sec)
+                }
+            };
+            let style = if index == selected {
+                Style::default()
+                    .bg(self.theme.get_color(Color::Blue))
+                    .fg(self.theme.get_color(Color::White))
+            } else if entry.is_some_and(|net| net.oper_state.as_deref() == Some("down")) {
+                dim
+            } else {
+                self.theme.text_style()
+            };
+            lines.push(Line::from(Span::styled(
+                format!(
+                    " {:<28} {:<8} {:>11} {:>11}",
+                    Self::truncate(&name, 28),
+                    state,
+                    self.format_rate(rx),
+                    self.format_rate(tx)
+                ),
+                style,
+            )));
+        }
+
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            format!(" {}", tr("interfaces-keys")),
+            dim,
+        )));
+        lines
+    }
+
+    pub fn render_in_area(&self, f: &mut Frame, area: Rect) {
+        if !self.visible {
+            return;
+        }
+
+        // Borders, the header, and the blank line and hint below the entries
+        let visible_rows = area.height.saturating_sub(5) as usize;
+        let lines = self.lines(visible_rows);
+        let width = area.width.min(66);
+        let height = area.height.min(lines.len() as u16 + 2);
+        let popup_area = Rect {
+            x: area.x + (area.width - width) / 2,
+            y: area.y + (area.height - height) / 2,
+            width,
+            height,
+        };
+        f.render_widget(Clear, popup_area);
+
+        let popup = Paragraph::new(lines).block(
+            Block::default()
+                .title(Span::styled(
+                    format!(" {} ", tr("interfaces-title")),
+                    Style::default()
+                        .fg(self.theme.get_color(Color::LightYellow))
+                        .add_modifier(Modifier::BOLD),
+                ))
+                .borders(Borders::ALL)
+                .border_type(BorderType::Rounded)
+                .border_style(Style::default().fg(self.theme.get_color(Color::DarkGray))),
+        );
+        f.render_widget(popup, popup_area);
+    }
+}
+
+impl Component for InterfacePicker {
+    fn handle_events(
+        &mut self,
+        _event: crossterm::event::Event,
+    ) -> Result<Option<Action>, Box<dyn std::error::Error>> {
+        Ok(None)
+    }
+
+    fn update(&mut self, action: Action) -> Result<Option<Action>, Box<dyn std::error::Error>> {
+        match action {
+            Action::MoveInterfaceSelection(forward) => self.move_selection(forward),
+            Action::ToggleInterfacePicker | Action::SelectNetworkInterface => {
+                self.visible = false;
+            }
+            _ => {}
+        }
+        Ok(None)
+    }
+
+    fn render(&mut self, f: &mut Frame) {
+        self.render_in_area(f, f.area());
+    }
+}
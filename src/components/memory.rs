@@ -1,6 +1,12 @@
 use crate::{
-    action::Action, components::Component, data::snapshot::SystemSnapshot, theme::Theme,
-    widgets::braille_graph::BrailleGraph,
+    action::Action,
+    components::{cursor::GraphCursor, Component},
+    data::snapshot::{KernelLimits, SystemSnapshot},
+    theme::Theme,
+    widgets::{
+        braille_graph::BrailleGraph,
+        gauge::{GradientBar, SegmentColor, StackedBar},
+    },
 };
 use ratatui::{
     layout::{Constraint, Direction, Layout},
@@ -13,11 +19,33 @@ use ratatui::{
 pub struct MemoryComponent {
     pub snapshot: SystemSnapshot,
     pub theme: Theme,
+    pub cursor: GraphCursor,
 }
 
 impl MemoryComponent {
     pub fn new(snapshot: SystemSnapshot, theme: Theme) -> Self {
-        Self { snapshot, theme }
+        Self {
+            snapshot,
+            theme,
+            cursor: GraphCursor::default(),
+        }
+    }
+
+    /// Memory pressure at each point of the history, in tenths of a percent
+    fn pressure_history(&self) -> Vec<u64> {
+        self.snapshot
+            .memory_history
+            .iter()
+            .map(|&(used, total)| {
+                let percent = if total > 0 {
+                    (used as f64 / total as f64) * 100.0
+                } else {
+                    0.0
+                };
+                // Keep one decimal point precision for a smoother graph line.
+                (percent * 10.0).round() as u64
+            })
+            .collect()
     }
 
     fn get_memory_color(&self, memory_usage: f64) -> Color {
@@ -30,89 +58,30 @@ impl MemoryComponent {
         }
     }
 
-    fn calculate_gradient_color(&self, position_ratio: f32) -> Color {
-        // Smooth gradient using hex colors with LERP
-        // 0%->50%: Blend from #00ff87 to #f9ff00
-        // 50%->100%: Blend from #f9ff00 to #ff003c
-        if position_ratio < 0.5 {
-            // From #00ff87 to #f9ff00 (0.0 to 0.5)
-            let t = position_ratio * 2.0; // Normalize to 0.0-1.0 range
-                                          // Interpolate between #00ff87 (0, 255, 135) and #f9ff00 (249, 255, 0)
-            let r = (0.0 + (249.0 - 0.0) * t) as u8;
-            let g = (255.0 + (255.0 - 255.0) * t) as u8;
-            let b = (135.0 + (0.0 - 135.0) * t) as u8;
-            Color::Rgb(r, g, b)
-        } else {
-            // From #f9ff00 to #ff003c (0.5 to 1.0)
-            let t = (position_ratio - 0.5) * 2.0; // Normalize to 0.0-1.0 range
-                                                  // Interpolate between #f9ff00 (249, 255, 0) and #ff003c (255, 0, 60)
-            let r = (249.0 + (255.0 - 249.0) * t) as u8;
-            let g = (255.0 + (0.0 - 255.0) * t) as u8;
-            let b = (0.0 + (60.0 - 0.0) * t) as u8;
-            Color::Rgb(r, g, b)
-        }
-    }
-
-    fn calculate_track_color(&self, position_ratio: f32) -> Color {
-        // Dimmed version of the gradient color for the track (30% brightness)
-        let active_color = self.calculate_gradient_color(position_ratio);
-        match active_color {
-            Color::Rgb(r, g, b) => {
-                // Reduce brightness to 30% for visible track
-                Color::Rgb(
-                    (r as f32 * 0.3) as u8,
-                    (g as f32 * 0.3) as u8,
-                    (b as f32 * 0.3) as u8,
-                )
-            }
-            _ => Color::Rgb(50, 50, 50), // Fallback to medium grey
-        }
-    }
-
-    fn calculate_cached_color(&self, position_ratio: f32) -> Color {
-        // Keep cached RAM in the same palette as used RAM, just dimmed.
-        match self.calculate_gradient_color(position_ratio) {
-            Color::Rgb(r, g, b) => Color::Rgb(
-                (r as f32 * 0.7) as u8,
-                (g as f32 * 0.7) as u8,
-                (b as f32 * 0.7) as u8,
-            ),
-            _ => self.theme.get_color(Color::Yellow),
-        }
-    }
-
-    fn bar_width_for_area(area_width: u16, prefix_chars: usize, max_width: usize) -> usize {
-        let available = area_width as usize;
-        let room_after_prefix = available.saturating_sub(prefix_chars);
-        room_after_prefix.min(max_width)
-    }
-
-    fn fractional_block(units: usize) -> char {
-        match units {
-            1 => '▏',
-            2 => '▎',
-            3 => '▍',
-            4 => '▌',
-            5 => '▋',
-            6 => '▊',
-            7 => '▉',
-            _ => '░',
-        }
-    }
-
     pub fn render_in_area(&self, f: &mut Frame, area: ratatui::layout::Rect) {
-        let used_memory = self.snapshot.used_memory;
         let total_memory = self.snapshot.total_memory;
         let used_swap = self.snapshot.used_swap;
         let total_swap = self.snapshot.total_swap;
 
-        let block = Block::default()
-            .title(Span::styled(
-                " Memory ",
-                self.theme
-                    .text_style()
-                    .add_modifier(ratatui::style::Modifier::BOLD),
-            ))
+        let history = self.pressure_history();
+        let cursor = self.cursor.index(history.len());
+        let mut block = Block::default().title(Span::styled(
+            format!(" {} ", crate::i18n::tr("memory")),
+            self.theme
+                .text_style()
+                .add_modifier(ratatui::style::Modifier::BOLD),
+        ));
+        if let Some(index) = cursor {
+            block = block.title_bottom(GraphCursor::readout(
+                &self.theme,
+                self.snapshot.history_time(history.len(), index),
+                format!(
+                    "pressure {}%",
+                    crate::format::decimal(history[index] as f64 / 10.0, 1)
+                ),
+            ));
+        }
+        let block = block
             .borders(Borders::ALL)
             .border_type(BorderType::Rounded)
             .border_style(Style::default().fg(self.theme.get_color(Color::DarkGray)))
@@ -121,17 +90,24 @@ impl MemoryComponent {
 
         let inner_area = block.inner(area);
 
-        // Split inner area into top (bars) and bottom (sparkline)
+        // Split inner area into top (bars), kernel resources and bottom (sparkline)
         let main_chunks = Layout::default()
             .direction(Direction::Vertical)
             .constraints([
                 Constraint::Length(4), // RAM + SWAP bars
-                Constraint::Min(0),    // Sparkline
+                Constraint::Length(u16::from(self.snapshot.kernel_limits.is_some())),
+                Constraint::Min(0), // Sparkline
             ])
             .split(inner_area);
 
         let bars_area = main_chunks[0];
-        let sparkline_area = main_chunks[1];
+        let sparkline_area = main_chunks[2];
+        if let Some(limits) = &self.snapshot.kernel_limits {
+            f.render_widget(
+                Paragraph::new(self.kernel_limits_line(limits)),
+                main_chunks[1],
+            );
+        }
 
         // Split bars area into RAM and SWAP sections
         let bar_chunks = Layout::default()
@@ -145,97 +121,74 @@ impl MemoryComponent {
         let ram_area = bar_chunks[0];
         let swap_area = bar_chunks[1];
 
-        // Render RAM with stacked bar for Used and Cached memory
+        // Render RAM as a stacked bar of used, ZFS ARC, buffers and cache, matching `free`
         let cached_memory = self.snapshot.cached_memory;
-        let used_memory_actual = used_memory.saturating_sub(cached_memory); // Actual used memory excluding cache
-        let total_used_and_cached = used_memory.min(total_memory); // Total used + cached, capped at total
+        let buffers_memory = self.snapshot.buffers_memory;
+        // The kernel counts ARC as used memory even though ZFS gives it back under pressure
+        let arc_memory = self.snapshot.zfs_arc.map_or(0, |arc| arc.size);
+        let used_memory_actual = total_memory
+            .saturating_sub(self.snapshot.free_memory)
+            .saturating_sub(buffers_memory)
+            .saturating_sub(cached_memory)
+            .saturating_sub(arc_memory);
+        // Pressure from MemAvailable drives the colors; raw used/cache numbers stay as labels
+        let pressure = self.snapshot.memory_pressure_percent();
 
         let ram_label = format!(
-            "RAM: {}/{}/{}",
-            Self::bytes_to_human_readable(used_memory_actual),
-            Self::bytes_to_human_readable(cached_memory),
-            Self::bytes_to_human_readable(total_memory)
+            "RAM: {}/{} ",
+            crate::format::bytes(used_memory_actual),
+            crate::format::bytes(total_memory)
         );
-        let ram_label_len = ram_label.len();
-
-        let mut spans = Vec::new();
-        spans.push(Span::styled(ram_label, self.theme.text_style()));
-
-        // Create stacked bar visualization with consistent gradient style
-        let ram_bar_width = Self::bar_width_for_area(ram_area.width, ram_label_len, 42);
-        let total_units = ram_bar_width * 8; // 8 sub-units per block
-
-        // Draw the stacked bar
-        let used_ratio = if total_memory > 0 {
-            used_memory_actual as f64 / total_memory as f64
-        } else {
-            0.0
-        };
-        let cached_ratio = if total_memory > 0 {
-            cached_memory as f64 / total_memory as f64
-        } else {
-            0.0
-        };
-
-        let used_units = (used_ratio * total_units as f64) as usize;
-        let cached_units = (cached_ratio * total_units as f64) as usize;
+        let pressure_label = format!("{:.0}% ", pressure);
 
-        for i in 0..ram_bar_width {
-            let start_unit = i * 8;
-            let end_unit = start_unit + 8;
-
-            // Determine what type of memory is in this segment
-            if start_unit >= used_units + cached_units {
-                // Empty space - use track color based on position
-                let position_ratio = i as f32 / (ram_bar_width.saturating_sub(1).max(1)) as f32;
-                let track_color = self.calculate_track_color(position_ratio);
-                spans.push(Span::styled(
-                    ' '.to_string(),
-                    Style::default().bg(track_color),
-                ));
-            } else if start_unit >= used_units {
-                // Cached memory segment
-                let is_partial = end_unit > used_units + cached_units;
-                let position_ratio = i as f32 / (ram_bar_width.saturating_sub(1).max(1)) as f32;
-                let track_color = self.calculate_track_color(position_ratio);
-                let cached_color = self.calculate_cached_color(position_ratio);
-                let char = if !is_partial {
-                    ' ' // Fully filled with cached
-                } else {
-                    // Partially filled - determine the fractional character
-                    let partial_units = (used_units + cached_units).saturating_sub(start_unit);
-                    Self::fractional_block(partial_units)
-                };
-
-                let mut style = Style::default().bg(cached_color);
-                if is_partial {
-                    style = Style::default().fg(cached_color).bg(track_color);
-                }
-                spans.push(Span::styled(char.to_string(), style));
-            } else {
-                // Used memory segment
-                let is_partial = end_unit > used_units;
-                let position_ratio = i as f32 / (ram_bar_width.saturating_sub(1).max(1)) as f32;
-                let track_color = self.calculate_track_color(position_ratio);
-                let used_color = self.calculate_gradient_color(position_ratio);
-                let char = if !is_partial {
-                    ' ' // Fully filled with used
-                } else {
-                    // Partially filled - determine the fractional character
-                    let partial_units = used_units.saturating_sub(start_unit);
-                    Self::fractional_block(partial_units)
-                };
-
-                let mut style = Style::default().bg(used_color);
-                if is_partial {
-                    style = Style::default().fg(used_color).bg(track_color);
-                }
-                spans.push(Span::styled(char.to_string(), style));
-            }
+        let label = Line::from(vec![
+            Span::styled(ram_label, self.theme.text_style()),
+            Span::styled(
+                pressure_label,
+                Style::default().fg(self.get_memory_color(pressure)),
+            ),
+        ]);
+        let mut ram_bar = StackedBar::new(&self.theme, total_memory as f64)
+            .label(label)
+            .max_width(42)
+            .segment(
+                used_memory_actual as f64,
+                SegmentColor::Gradient(1.0),
+                "used",
+            );
+        if let Some(arc) = self.snapshot.zfs_arc {
+            // ZFS ARC behaves like cache but is sized by ZFS, not the page cache; show it in teal.
+            ram_bar = ram_bar.segment(
+                arc.size as f64,
+                SegmentColor::Solid(self.theme.get_color(Color::Rgb(60, 180, 170))),
+                format!(
+                    "arc {}/{}",
+                    crate::format::bytes(arc.size),
+                    crate::format::bytes(arc.target)
+                ),
+            );
         }
-        let ram_line = Line::from(spans);
-        let ram_paragraph = Paragraph::new(ram_line);
-        f.render_widget(ram_paragraph, ram_area);
+        let ram_bar = ram_bar
+            .segment(
+                buffers_memory as f64,
+                // Buffers are kernel-owned like cache but tied to block devices; keep them apart in blue.
+                SegmentColor::Solid(self.theme.get_color(Color::Rgb(80, 140, 220))),
+                format!("buf {}", crate::format::bytes(buffers_memory)),
+            )
+            .segment(
+                cached_memory as f64,
+                // Keep cached RAM in the same palette as used RAM, just dimmed.
+                SegmentColor::Gradient(0.7),
+                format!("cache {}", crate::format::bytes(cached_memory)),
+            )
+            .legend_suffix(vec![Span::styled(
+                format!(
+                    "avail {}",
+                    crate::format::bytes(self.snapshot.available_memory)
+                ),
+                Style::default().fg(self.theme.get_color(Color::Green)),
+            )]);
+        f.render_widget(ram_bar, ram_area);
 
         // Render SWAP
         let swap_percent = if total_swap > 0 {
@@ -244,78 +197,29 @@ impl MemoryComponent {
             0.0
         };
         let swap_label = format!(
-            "SWAP: {}/{} ({:.1}%)",
-            Self::bytes_to_human_readable(used_swap),
-            Self::bytes_to_human_readable(total_swap),
-            swap_percent
+            "SWAP: {}/{} ({}%)",
+            crate::format::bytes(used_swap),
+            crate::format::bytes(total_swap),
+            crate::format::decimal(swap_percent, 1)
         );
         // Use fractional block characters for SWAP usage visualization with smooth gradient
-        let swap_usage_percentage = swap_percent.min(100.0);
-        let swap_bar_width = Self::bar_width_for_area(swap_area.width, swap_label.len(), 42);
-        let total_units = swap_bar_width * 8; // 8 sub-units per block
-        let filled_units = (swap_usage_percentage / 100.0 * total_units as f64).round() as usize;
-
-        let mut spans = Vec::new();
-        spans.push(Span::styled(swap_label, self.theme.text_style()));
-
-        for i in 0..swap_bar_width {
-            let start_unit = i * 8;
-            let end_unit = start_unit + 8;
-
-            let position_ratio = i as f32 / (swap_bar_width.saturating_sub(1).max(1)) as f32;
-            let active_color = self.calculate_gradient_color(position_ratio);
-            let track_color = self.calculate_track_color(position_ratio);
-
-            let (char, style) = if filled_units <= start_unit {
-                (' ', Style::default().bg(track_color))
-            } else if filled_units >= end_unit {
-                (' ', Style::default().bg(active_color))
-            } else {
-                let partial_units = filled_units - start_unit;
-                (
-                    Self::fractional_block(partial_units),
-                    Style::default().fg(active_color).bg(track_color),
-                )
-            };
-
-            spans.push(Span::styled(char.to_string(), style));
-        }
-        let swap_line = Line::from(spans);
-        let swap_paragraph = Paragraph::new(swap_line);
-        f.render_widget(swap_paragraph, swap_area);
+        let swap_gauge = GradientBar::new(&self.theme, swap_percent)
+            .label(swap_label, self.theme.text_style())
+            .max_width(42);
+        f.render_widget(swap_gauge, swap_area);
 
         // Render memory Braille graph
-        if !self.snapshot.memory_history.is_empty() {
-            let mut mem_history_data: Vec<u64> = Vec::new();
-            // Assuming all history VecDeques are of similar length (e.g., 50)
-            let history_len = self.snapshot.memory_history.len();
-
-            for i in 0..history_len {
-                if let Some((used, total)) = self.snapshot.memory_history.get(i) {
-                    let percent = if *total > 0 {
-                        (*used as f64 / *total as f64) * 100.0
-                    } else {
-                        0.0
-                    };
-                    // Keep one decimal point precision for a smoother graph line.
-                    mem_history_data.push((percent * 10.0).round() as u64);
-                }
-            }
-
-            let ram_percent = if total_memory > 0 {
-                (total_used_and_cached as f64 / total_memory as f64) * 100.0
-            } else {
-                0.0
-            };
-
-            let braille_graph = BrailleGraph::new(&mem_history_data)
+        if !history.is_empty() {
+            let braille_graph = BrailleGraph::new(&history)
                 .block(Block::default().borders(Borders::NONE))
-                .style(Style::default().fg(self.get_memory_color(ram_percent))) // Use RAM percent for graph color
+                .style(Style::default().fg(self.get_memory_color(pressure))) // Use memory pressure for graph color
                 .value_range(0.0, 1000.0)
                 .smoothing(3)
                 .show_baseline(true)
-                .use_gradient(true)
-                .fill(false);
+                .use_gradient(!self.theme.simple_gradients)
+                .fill(false)
+                .cursor(cursor)
+                .gap(self.snapshot.history_gap_index(history.len()));
             f.render_widget(braille_graph, sparkline_area);
         } else {
             // Show a simple indicator if no history data
@@ -326,17 +230,83 @@ impl MemoryComponent {
         }
     }
 
-    fn bytes_to_human_readable(bytes: u64) -> String {
-        const UNITS: [&str; 6] = ["B", "KB", "MB", "GB", "TB", "PB"];
-        let mut size = bytes as f64;
-        let mut unit_idx = 0;
+    /// `fd 1.2%  pty 4/4096  pid 812/4.2M  entropy 256/256`, each colored by how close it is to its limit
+    fn kernel_limits_line(&self, limits: &KernelLimits) -> Line<'static> {
+        let usage_color = |used: u64, limit: u64| {
+            let ratio = if limit > 0 {
+                used as f64 / limit as f64
+            } else {
+                0.0
+            };
+            if ratio >= 0.9 {
+                self.theme.get_color(Color::Red)
+            } else if ratio >= 0.7 {
+                self.theme.get_color(Color::Yellow)
+            } else {
+                self.theme.get_color(Color::Green)
+            }
+        };
+        let percent = |used: u64, limit: u64| {
+            if limit > 0 {
+                used as f64 * 100.0 / limit as f64
+            } else {
+                0.0
+            }
+        };
 
-        while size >= 1024.0 && unit_idx < UNITS.len() - 1 {
-            size /= 1024.0;
-            unit_idx += 1;
+        let mut spans = vec![
+            Span::styled("Kernel: ", self.theme.text_style()),
+            Span::styled("fd ", self.theme.text_style()),
+            Span::styled(
+                format!(
+                    "{}%",
+                    crate::format::decimal(percent(limits.fds_allocated, limits.fds_max), 1)
+                ),
+                Style::default().fg(usage_color(limits.fds_allocated, limits.fds_max)),
+            ),
+        ];
+        if limits.ptys_max > 0 {
+            spans.push(Span::styled("  pty ", self.theme.text_style()));
+            spans.push(Span::styled(
+                format!(
+                    "{}/{}",
+                    Self::compact_count(limits.ptys),
+                    Self::compact_count(limits.ptys_max)
+                ),
+                Style::default().fg(usage_color(limits.ptys, limits.ptys_max)),
+            ));
+        }
+        if limits.pid_max > 0 {
+            spans.push(Span::styled("  pid ", self.theme.text_style()));
+            spans.push(Span::styled(
+                format!(
+                    "{}/{}",
+                    Self::compact_count(limits.tasks),
+                    Self::compact_count(limits.pid_max)
+                ),
+                Style::default().fg(usage_color(limits.tasks, limits.pid_max)),
+            ));
+        }
+        if let (Some(avail), Some(pool)) = (limits.entropy_avail, limits.entropy_pool) {
+            // A starved pool is the problem here, so invert the usage scale
+            spans.push(Span::styled("  entropy ", self.theme.text_style()));
+            spans.push(Span::styled(
+                format!("{}/{}", avail, pool),
+                Style::default().fg(usage_color(pool.saturating_sub(avail), pool)),
+            ));
         }
+        Line::from(spans)
+    }
 
-        format!("{:.1}{}", size, UNITS[unit_idx])
+    /// Short count: 812, 32.8k, 4.2M
+    fn compact_count(value: u64) -> String {
+        if value >= 1_000_000 {
+            format!("{}M", crate::format::decimal(value as f64 / 1_000_000.0, 1))
+        } else if value >= 10_000 {
+            format!("{}k", crate::format::decimal(value as f64 / 1_000.0, 1))
+        } else {
+            value.to_string()
+        }
     }
 }
 
@@ -349,12 +319,17 @@ impl Component for MemoryComponent {
         Ok(None)
     }
 
-    fn update(&mut self, _action: Action) -> Result<Option<Action>, Box<dyn std::error::Error>> {
-        // Memory component doesn't handle actions directly
+    fn update(&mut self, action: Action) -> Result<Option<Action>, Box<dyn std::error::Error>> {
+        self.cursor
+            .update(&action, self.snapshot.memory_history.len());
         Ok(None)
     }
 
     fn render(&mut self, f: &mut Frame) {
         self.render_in_area(f, f.area());
     }
+
+    fn hints(&self) -> Vec<(&'static str, &'static str)> {
+        vec![("←/→", "hint-inspect-graph")]
+    }
 }
@@ -1,12 +1,33 @@
 use crate::action::Action;
 use ratatui::Frame;
 
+pub mod compare;
+pub mod core_detail;
 pub mod cpu;
+pub mod cursor;
 pub mod disk;
+pub mod disk_detail;
+pub mod events;
 pub mod gpu;
+pub mod history;
+pub mod inhibitors;
+pub mod interface_picker;
 pub mod memory;
 pub mod network;
+#[cfg(feature = "ebpf")]
+pub mod offenders;
 pub mod process;
+pub mod stats;
+pub mod tools;
+
+/// First row shown when `visible_rows` rows fit, scrolled just enough to show `selected`
+pub fn window_start(selected: usize, visible_rows: usize) -> usize {
+    if visible_rows > 0 && selected >= visible_rows {
+        selected + 1 - visible_rows
+    } else {
+        0
+    }
+}
 
 /// The Component trait defines the interface that all UI components must implement
 #[allow(dead_code)]
@@ -22,4 +43,10 @@ pub trait Component {
 
     /// Render the component to the terminal frame
     fn render(&mut self, f: &mut Frame);
+
+    /// Panel-local `(key, description message id)` pairs shown in the bottom bar while this panel
+    /// has focus
+    fn hints(&self) -> Vec<(&'static str, &'static str)> {
+        Vec::new()
+    }
 }
@@ -1,16 +1,31 @@
-use crate::{action::Action, components::Component, data::snapshot::SystemSnapshot, theme::Theme};
+use crate::{
+    action::Action,
+    components::{cursor::GraphCursor, Component},
+    data::{
+        snapshot::{InterfaceKind, NetworkInfo, ProtocolStats, SocketStats, SystemSnapshot},
+        speedtest::SpeedTestStatus,
+    },
+    theme::Theme,
+};
 use ratatui::{
     layout::{Constraint, Direction, Layout},
     style::{Color, Style},
     text::{Line, Span},
-    widgets::{Axis, Block, BorderType, Borders, Chart, Dataset, GraphType, Paragraph},
+    widgets::{Axis, Block, BorderType, Borders, Chart, Dataset, GraphType, Paragraph, Sparkline},
     Frame,
 };
+use std::collections::BTreeMap;
 
 pub struct NetworkComponent {
     pub snapshot: SystemSnapshot,
     pub theme: Theme,
     pub show_graphs: bool,
+    /// Show rates in bits per second instead of bytes
+    pub show_bits: bool,
+    pub speed_test: SpeedTestStatus,
+    pub cursor: GraphCursor,
+    /// Friendly names shown in place of raw interface names, e.g. `wlp3s0` → `WiFi`
+    pub nicknames: BTreeMap<String, String>,
 }
 
 impl NetworkComponent {
@@ -19,13 +34,31 @@ impl NetworkComponent {
             snapshot,
             theme,
             show_graphs: true,
+            show_bits: false,
+            speed_test: SpeedTestStatus::Idle,
+            cursor: GraphCursor::default(),
+            nicknames: BTreeMap::new(),
+        }
+    }
+
+    /// The configured nickname of an interface, or its raw name
+    fn display_name<'a>(&'a self, name: &'a str) -> &'a str {
+        self.nicknames.get(name).map_or(name, String::as_str)
+    }
+
+    /// A per-second rate in bytes (`1.2MB/s`) or, in bits mode, bits (`9.6Mb/s`)
+    fn format_rate(&self, bytes_per_sec: u64) -> String {
+        if self.show_bits {
+            crate::format::bits_rate(bytes_per_sec)
+        } else {
+            crate::format::rate(bytes_per_sec)
         }
     }
 
     pub fn render_in_area(&self, f: &mut Frame, area: ratatui::layout::Rect) {
         let block = Block::default()
             .title(Span::styled(
-                " Network ",
+                format!(" {} ", crate::i18n::tr("network")),
                 Style::default()
                     .fg(self.theme.get_color(Color::LightCyan))
                     .add_modifier(ratatui::style::Modifier::BOLD),
@@ -37,12 +70,29 @@ impl NetworkComponent {
 
         let inner_area = block.inner(area);
 
-        // Split the inner area into two parts: summary (top) and chart (bottom)
+        // Split the inner area into summary (top), latency probes and chart (bottom)
+        let group_lines = self.interface_group_lines();
+        let group_rows = group_lines.len().min(Self::MAX_GROUP_ROWS) as u16;
+        let probe_rows = self.snapshot.probes.len().min(Self::MAX_PROBE_ROWS) as u16;
+        let speed_test_line = self.speed_test_line();
+        let speed_test_rows = u16::from(speed_test_line.is_some());
+        let link_line = self.link_line();
+        let summary_rows = 3
+            + u16::from(link_line.is_some())
+            + if self.snapshot.protocol_stats.is_some() {
+                2
+            } else {
+                0
+            }
+            + u16::from(self.snapshot.socket_stats.is_some());
         let chunks = Layout::default()
             .direction(Direction::Vertical)
             .constraints([
-                Constraint::Length(3), // Summary lines
-                Constraint::Min(0),    // Chart
+                Constraint::Length(summary_rows),    // Summary lines
+                Constraint::Length(group_rows),      // Bond/bridge/VLAN groups
+                Constraint::Length(probe_rows),      // Latency monitor
+                Constraint::Length(speed_test_rows), // Last on-demand speed test
+                Constraint::Min(0),                  // Chart
             ])
             .split(inner_area);
 
@@ -62,42 +112,31 @@ impl NetworkComponent {
                     )
                 })
                 .unwrap_or((0, 0, 0, 0)),
-            None => (
-                self.snapshot
-                    .networks
-                    .iter()
-                    .map(|net| net.received_per_sec)
-                    .sum(),
-                self.snapshot
-                    .networks
-                    .iter()
-                    .map(|net| net.transmitted_per_sec)
-                    .sum(),
-                self.snapshot
-                    .networks
-                    .iter()
-                    .map(|net| net.total_received)
-                    .sum(),
-                self.snapshot
-                    .networks
-                    .iter()
-                    .map(|net| net.total_transmitted)
-                    .sum(),
-            ),
+            // Bond/bridge members and VLANs are already counted on their parent.
+            None => self
+                .snapshot
+                .networks
+                .iter()
+                .filter(|net| net.parent.is_none())
+                .fold((0, 0, 0, 0), |acc, net| {
+                    (
+                        acc.0 + net.received_per_sec,
+                        acc.1 + net.transmitted_per_sec,
+                        acc.2 + net.total_received,
+                        acc.3 + net.total_transmitted,
+                    )
+                }),
         };
 
         // Render summary
-        let summary_spans = vec![
+        let mut summary_spans = vec![
             Line::from(vec![
                 Span::styled(
                     "RX: ",
                     Style::default().fg(self.theme.get_color(Color::White)),
                 ),
                 Span::styled(
-                    format!(
-                        "{}/s",
-                        crate::utils::bytes_to_human_readable(current_rx_rate)
-                    ),
+                    self.format_rate(current_rx_rate),
                     Style::default().fg(self.theme.get_color(Color::Green)),
                 ),
             ]),
@@ -107,10 +146,7 @@ impl NetworkComponent {
                     Style::default().fg(self.theme.get_color(Color::White)),
                 ),
                 Span::styled(
-                    format!(
-                        "{}/s",
-                        crate::utils::bytes_to_human_readable(current_tx_rate)
-                    ),
+                    self.format_rate(current_tx_rate),
                     Style::default().fg(self.theme.get_color(Color::Red)),
                 ),
             ]),
@@ -122,24 +158,41 @@ impl NetworkComponent {
                 Span::styled(
                     self.snapshot
                         .selected_network_interface
-                        .clone()
-                        .unwrap_or_else(|| "All".to_string()),
+                        .as_deref()
+                        .map_or("All".to_string(), |name| {
+                            self.display_name(name).to_string()
+                        }),
                     Style::default().fg(self.theme.get_color(Color::Blue)),
                 ),
                 Span::raw("  "),
                 Span::styled(
                     format!(
                         "{} / {}",
-                        crate::utils::bytes_to_human_readable(total_rx),
-                        crate::utils::bytes_to_human_readable(total_tx)
+                        crate::format::bytes(total_rx),
+                        crate::format::bytes(total_tx)
                     ),
                     Style::default().fg(self.theme.get_color(Color::Gray)),
                 ),
             ]),
         ];
 
+        if let Some(line) = link_line {
+            summary_spans.push(line);
+        }
+        if let Some(stats) = &self.snapshot.protocol_stats {
+            summary_spans.extend(self.protocol_lines(stats));
+        }
+        if let Some(sockets) = &self.snapshot.socket_stats {
+            summary_spans.push(self.socket_line(sockets));
+        }
+
         let summary_paragraph = Paragraph::new(summary_spans).block(Block::default());
         f.render_widget(summary_paragraph, chunks[0]);
+        f.render_widget(Paragraph::new(group_lines), chunks[1]);
+        self.render_probes(f, chunks[2]);
+        if let Some(line) = speed_test_line {
+            f.render_widget(Paragraph::new(line), chunks[3]);
+        }
 
         // Render chart
         if self.show_graphs && !self.snapshot.network_history.is_empty() {
@@ -190,21 +243,32 @@ impl NetworkComponent {
                 .fold(0.0_f64, f64::max)
                 .max(1.0);
 
-            let x_max = (self.snapshot.network_history.len().saturating_sub(1)) as f64;
+            let history_len = self.snapshot.network_history.len();
+            let x_max = (history_len.saturating_sub(1)) as f64;
             let x_bound = x_max.max(1.0);
             let x_mid = (x_bound / 2.0).round();
+            // Wall-clock time of each labelled sample, or its index before any are recorded
+            let x_label = |x: f64| {
+                self.snapshot
+                    .history_time(history_len, x as usize)
+                    .map(crate::format::clock)
+                    .unwrap_or_else(|| format!("{:.0}", x))
+            };
 
             let x_axis = Axis::default()
                 .bounds([0.0, x_bound])
                 .style(Style::default().fg(self.theme.get_color(Color::DarkGray)))
                 .labels(vec![
-                    Span::styled("0", Style::default().fg(self.theme.get_color(Color::Gray))),
                     Span::styled(
-                        format!("{:.0}", x_mid),
+                        x_label(0.0),
                         Style::default().fg(self.theme.get_color(Color::Gray)),
                     ),
                     Span::styled(
-                        format!("{:.0}", x_bound),
+                        x_label(x_mid),
+                        Style::default().fg(self.theme.get_color(Color::Gray)),
+                    ),
+                    Span::styled(
+                        x_label(x_bound),
                         Style::default().fg(self.theme.get_color(Color::Gray)),
                     ),
                 ]);
@@ -217,30 +281,371 @@ impl NetworkComponent {
                 .labels(vec![
                     Span::styled("0", Style::default().fg(self.theme.get_color(Color::Gray))),
                     Span::styled(
-                        crate::utils::bytes_to_human_readable(y_mid_bound as u64),
+                        crate::format::bytes(y_mid_bound as u64),
                         Style::default().fg(self.theme.get_color(Color::Gray)),
                     ),
                     Span::styled(
-                        crate::utils::bytes_to_human_readable(y_max_bound as u64),
+                        crate::format::bytes(y_max_bound as u64),
                         Style::default().fg(self.theme.get_color(Color::Gray)),
                     ),
                 ]);
 
-            let chart = Chart::new(vec![rx_dataset, tx_dataset])
-                .block(
-                    Block::default()
-                        .borders(Borders::TOP)
-                        .border_style(Style::default().fg(self.theme.get_color(Color::DarkGray))),
-                )
+            // Inspected point: a vertical marker, with its exact rates in the bottom border
+            let cursor = self.cursor.index(history_len);
+            let marker: Vec<(f64, f64)> = cursor
+                .map(|index| vec![(index as f64, 0.0), (index as f64, y_max_bound)])
+                .unwrap_or_default();
+            let marker_dataset = Dataset::default()
+                .data(&marker)
+                .graph_type(GraphType::Line)
+                .style(Style::default().fg(self.theme.get_color(Color::Yellow)));
+            // Where the history restored from the previous session ends
+            let gap: Vec<(f64, f64)> = self
+                .snapshot
+                .history_gap_index(history_len)
+                .map(|index| {
+                    let x = index as f64 - 0.5;
+                    vec![(x, 0.0), (x, y_max_bound)]
+                })
+                .unwrap_or_default();
+            let gap_dataset = Dataset::default()
+                .data(&gap)
+                .graph_type(GraphType::Line)
+                .style(Style::default().fg(self.theme.get_color(Color::DarkGray)));
+            let mut chart_block = Block::default()
+                .borders(Borders::TOP)
+                .border_style(Style::default().fg(self.theme.get_color(Color::DarkGray)));
+            if let Some(index) = cursor {
+                let (rx, tx) = self.snapshot.network_history[index];
+                chart_block = chart_block.title_bottom(GraphCursor::readout(
+                    &self.theme,
+                    self.snapshot.history_time(history_len, index),
+                    format!("RX {}  TX {}", self.format_rate(rx), self.format_rate(tx)),
+                ));
+            }
+
+            let chart = Chart::new(vec![gap_dataset, marker_dataset, rx_dataset, tx_dataset])
+                .block(chart_block)
                 .x_axis(x_axis)
                 .y_axis(y_axis);
 
-            f.render_widget(chart, chunks[1]);
+            f.render_widget(chart, chunks[4]);
         } else if !self.show_graphs {
             let info_block = Paragraph::new("Graphs disabled")
                 .block(Block::default())
                 .style(Style::default().fg(self.theme.get_color(Color::Gray)));
-            f.render_widget(info_block, chunks[1]);
+            f.render_widget(info_block, chunks[4]);
+        }
+    }
+
+    const MAX_PROBE_ROWS: usize = 4;
+    const MAX_GROUP_ROWS: usize = 6;
+
+    /// Bonds, bridges and VLAN parents with their members indented below, each with its rates
+    fn interface_group_lines(&self) -> Vec<Line<'static>> {
+        let networks = &self.snapshot.networks;
+        let mut parents: Vec<&NetworkInfo> = networks
+            .iter()
+            .filter(|net| {
+                net.parent.is_none()
+                    && networks
+                        .iter()
+                        .any(|member| member.parent.as_deref() == Some(net.name.as_str()))
+            })
+            .collect();
+        parents.sort_by(|a, b| a.name.cmp(&b.name));
+
+        let mut lines = Vec::new();
+        for parent in parents {
+            self.push_group_line(&mut lines, parent, 0);
+        }
+        lines
+    }
+
+    fn push_group_line(&self, lines: &mut Vec<Line<'static>>, net: &NetworkInfo, depth: usize) {
+        let kind = match net.kind {
+            InterfaceKind::Bond => " bond",
+            InterfaceKind::Bridge => " bridge",
+            InterfaceKind::Vlan => " vlan",
+            InterfaceKind::Plain => "",
+        };
+        let prefix = if depth == 0 {
+            String::new()
+        } else {
+            format!("{}└ ", "  ".repeat(depth - 1))
+        };
+        let name_color = if depth == 0 {
+            Color::LightCyan
+        } else {
+            Color::Gray
+        };
+        lines.push(Line::from(vec![
+            Span::styled(
+                format!("{}{}", prefix, self.display_name(&net.name)),
+                Style::default().fg(self.theme.get_color(name_color)),
+            ),
+            Span::styled(
+                kind.to_string(),
+                Style::default().fg(self.theme.get_color(Color::DarkGray)),
+            ),
+            Span::styled(
+                format!(
+                    "  ↓{} ↑{}",
+                    self.format_rate(net.received_per_sec),
+                    self.format_rate(net.transmitted_per_sec)
+                ),
+                Style::default().fg(self.theme.get_color(Color::Gray)),
+            ),
+        ]));
+
+        let mut members: Vec<&NetworkInfo> = self
+            .snapshot
+            .networks
+            .iter()
+            .filter(|member| member.parent.as_deref() == Some(net.name.as_str()))
+            .collect();
+        members.sort_by(|a, b| a.name.cmp(&b.name));
+        for member in members {
+            self.push_group_line(lines, member, depth + 1);
+        }
+    }
+    /// Retransmitted share of TCP segments that suggests a lossy path
+    const RETRANS_WARN_PERCENT: f64 = 1.0;
+    const RETRANS_CRIT_PERCENT: f64 = 5.0;
+
+    /// TCP connection counts by state
+    fn socket_line(&self, sockets: &SocketStats) -> Line<'static> {
+        let label = Style::default().fg(self.theme.get_color(Color::White));
+        let value = Style::default().fg(self.theme.get_color(Color::Gray));
+        // Many CLOSE_WAIT sockets usually mean an application is not closing connections.
+        let close_wait_style = if sockets.close_wait > 100 {
+            Style::default().fg(self.theme.get_color(Color::Yellow))
+        } else {
+            value
+        };
+
+        Line::from(vec![
+            Span::styled("Sock ", label),
+            Span::styled(format!("est {} ", sockets.established), value),
+            Span::styled(format!("tw {} ", sockets.time_wait), value),
+            Span::styled(format!("cw {} ", sockets.close_wait), close_wait_style),
+            Span::styled(format!("listen {} ", sockets.listen), value),
+            Span::styled(format!("all {}", sockets.sockets_used), value),
+        ])
+    }
+
+    /// Per-protocol packet rates, IP byte rates and TCP retransmissions
+    fn protocol_lines(&self, stats: &ProtocolStats) -> Vec<Line<'static>> {
+        let label = Style::default().fg(self.theme.get_color(Color::White));
+        let value = Style::default().fg(self.theme.get_color(Color::Gray));
+        let pair = |rx: f64, tx: f64| format!("{:.0}/{:.0} ", rx, tx);
+
+        let retrans_color = if stats.tcp_retrans_percent >= Self::RETRANS_CRIT_PERCENT {
+            Color::Red
+        } else if stats.tcp_retrans_percent >= Self::RETRANS_WARN_PERCENT {
+            Color::Yellow
+        } else {
+            Color::Gray
+        };
+        let mut retrans_style = Style::default().fg(self.theme.get_color(retrans_color));
+        if retrans_color != Color::Gray {
+            retrans_style = retrans_style.add_modifier(ratatui::style::Modifier::BOLD);
+        }
+
+        vec![
+            Line::from(vec![
+                Span::styled("TCP ", label),
+                Span::styled(pair(stats.tcp_in_per_sec, stats.tcp_out_per_sec), value),
+                Span::styled("UDP ", label),
+                Span::styled(pair(stats.udp_in_per_sec, stats.udp_out_per_sec), value),
+                Span::styled("ICMP ", label),
+                Span::styled(pair(stats.icmp_in_per_sec, stats.icmp_out_per_sec), value),
+                Span::styled("pkt/s", value),
+            ]),
+            Line::from(vec![
+                Span::styled("IP ", label),
+                Span::styled(
+                    format!(
+                        "{} / {}  ",
+                        self.format_rate(stats.ip_in_bytes_per_sec as u64),
+                        self.format_rate(stats.ip_out_bytes_per_sec as u64)
+                    ),
+                    value,
+                ),
+                Span::styled("retx ", label),
+                Span::styled(
+                    format!(
+                        "{:.0}/s ({}%)",
+                        stats.tcp_retrans_per_sec,
+                        crate::format::decimal(stats.tcp_retrans_percent, 1)
+                    ),
+                    retrans_style,
+                ),
+            ]),
+        ]
+    }
+
+    /// `wlan0 1500 psave  eth0 9000`: MTU and Wi-Fi power saving of the shown interfaces, with
+    /// the raw name after a nickname (`WiFi (wlp3s0) 1500`). Power saving turns red while a
+    /// latency target is losing probes, as it is a common cause.
+    fn link_line(&self) -> Option<Line<'static>> {
+        let selected = self.snapshot.selected_network_interface.as_deref();
+        let shown: Vec<&NetworkInfo> = self
+            .snapshot
+            .networks
+            .iter()
+            .filter(|net| match selected {
+                Some(name) => net.name == name,
+                None => net.parent.is_none() && net.name != "lo",
+            })
+            .filter(|net| net.mtu.is_some())
+            .collect();
+        if shown.is_empty() {
+            return None;
+        }
+
+        let latency_trouble = self.snapshot.probes.iter().any(|probe| probe.is_degraded());
+        let mut spans = vec![Span::styled(
+            "MTU: ",
+            Style::default().fg(self.theme.get_color(Color::White)),
+        )];
+        for (i, net) in shown.iter().enumerate() {
+            if i > 0 {
+                spans.push(Span::raw("  "));
+            }
+            let name = match self.nicknames.get(&net.name) {
+                Some(nickname) => format!("{} ({})", nickname, net.name),
+                None => net.name.clone(),
+            };
+            spans.push(Span::styled(
+                format!("{} {}", name, net.mtu.unwrap_or_default()),
+                Style::default().fg(self.theme.get_color(Color::Gray)),
+            ));
+            if net.power_save == Some(true) {
+                let style = if latency_trouble {
+                    Style::default()
+                        .fg(self.theme.get_color(Color::Red))
+                        .add_modifier(ratatui::style::Modifier::BOLD)
+                } else {
+                    Style::default().fg(self.theme.get_color(Color::Yellow))
+                };
+                spans.push(Span::styled(" psave", style));
+            }
+        }
+        Some(Line::from(spans))
+    }
+
+    /// `Speed test ↓ 94.1MB/s ↑ 11.8MB/s (3m ago)`, labelled so it is not read as live traffic
+    fn speed_test_line(&self) -> Option<Line<'static>> {
+        let label = Span::styled(
+            "Speed test ",
+            Style::default().fg(self.theme.get_color(Color::LightYellow)),
+        );
+        let dim = Style::default().fg(self.theme.get_color(Color::Gray));
+        let spans = match &self.speed_test {
+            SpeedTestStatus::Idle => return None,
+            SpeedTestStatus::Running { phase, started } => vec![
+                label,
+                Span::styled(
+                    format!("measuring {}… {}s", phase, started.elapsed().as_secs()),
+                    dim,
+                ),
+            ],
+            SpeedTestStatus::Done {
+                download,
+                upload,
+                finished,
+            } => vec![
+                label,
+                Span::styled(
+                    format!("↓ {}", self.format_rate(*download)),
+                    Style::default().fg(self.theme.get_color(Color::Green)),
+                ),
+                Span::raw(" "),
+                Span::styled(
+                    format!("↑ {}", self.format_rate(*upload)),
+                    Style::default().fg(self.theme.get_color(Color::Red)),
+                ),
+                Span::styled(
+                    format!(
+                        " ({} ago)",
+                        crate::format::duration(finished.elapsed().as_secs())
+                    ),
+                    dim,
+                ),
+            ],
+            SpeedTestStatus::Failed(err) => vec![
+                label,
+                Span::styled(
+                    err.clone(),
+                    Style::default().fg(self.theme.get_color(Color::Red)),
+                ),
+            ],
+        };
+        Some(Line::from(spans))
+    }
+
+    /// One line per latency target: RTT and loss, followed by an RTT sparkline
+    fn render_probes(&self, f: &mut Frame, area: ratatui::layout::Rect) {
+        for (i, probe) in self
+            .snapshot
+            .probes
+            .iter()
+            .take(Self::MAX_PROBE_ROWS)
+            .enumerate()
+        {
+            if i as u16 >= area.height {
+                break;
+            }
+            let row = ratatui::layout::Rect {
+                y: area.y + i as u16,
+                height: 1,
+                ..area
+            };
+            let columns = Layout::default()
+                .direction(Direction::Horizontal)
+                .constraints([Constraint::Length(30), Constraint::Min(0)])
+                .split(row);
+
+            let loss = probe.loss_percent();
+            let (summary, color) = if let Some(error) = &probe.error {
+                (error.clone(), Color::Yellow)
+            } else {
+                let rtt = probe
+                    .last_rtt()
+                    .map(|rtt| format!("{}ms", crate::format::decimal(rtt, 1)))
+                    .unwrap_or_else(|| "--".to_string());
+                let color = if loss >= 10.0 {
+                    Color::Red
+                } else if loss > 0.0 {
+                    Color::Yellow
+                } else {
+                    Color::Green
+                };
+                (format!("{:>8} loss {:>3.0}%", rtt, loss), color)
+            };
+
+            let label: String = probe.label.chars().take(12).collect();
+            let line = Line::from(vec![
+                Span::styled(
+                    format!("{:<13}", label),
+                    Style::default().fg(self.theme.get_color(Color::White)),
+                ),
+                Span::styled(summary, Style::default().fg(self.theme.get_color(color))),
+            ]);
+            f.render_widget(Paragraph::new(line), columns[0]);
+
+            // Lost probes are drawn as gaps.
+            let data: Vec<u64> = probe
+                .history
+                .iter()
+                .map(|rtt| rtt.map_or(0, |rtt| rtt.ceil() as u64))
+                .collect();
+            let visible = data.len().saturating_sub(columns[1].width as usize);
+            let sparkline = Sparkline::default()
+                .data(&data[visible..])
+                .style(Style::default().fg(self.theme.get_color(Color::LightCyan)));
+            f.render_widget(sparkline, columns[1]);
         }
     }
 
@@ -294,7 +699,12 @@ impl Component for NetworkComponent {
             Action::ToggleGraphs => {
                 self.show_graphs = !self.show_graphs;
             }
-            _ => {}
+            Action::ToggleNetworkBits => {
+                self.show_bits = !self.show_bits;
+            }
+            _ => self
+                .cursor
+                .update(&action, self.snapshot.network_history.len()),
         }
         Ok(None)
     }
@@ -302,4 +712,13 @@ impl Component for NetworkComponent {
     fn render(&mut self, f: &mut Frame) {
         self.render_in_area(f, f.area());
     }
+
+    fn hints(&self) -> Vec<(&'static str, &'static str)> {
+        vec![
+            ("i", "hint-interface"),
+            ("b", "hint-bits-bytes"),
+            ("B", "hint-speed-test"),
+            ("←/→", "hint-inspect-graph"),
+        ]
+    }
 }
@@ -0,0 +1,144 @@
+use super::Component;
+use crate::action::Action;
+use crate::data::offcpu::{self, SharedOffenders};
+use crate::theme::Theme;
+use ratatui::{
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph},
+    Frame,
+};
+use std::time::Duration;
+
+/// Seconds of tracing summarized per refresh of the popup
+const SAMPLE_WINDOW_SECS: u64 = 2;
+
+/// Popup listing the processes that spent the most time blocked or waiting on disk
+pub struct OffendersPopup {
+    pub visible: bool,
+    pub theme: Theme,
+    /// Started on first open: tracing has a cost and needs root
+    source: Option<SharedOffenders>,
+}
+
+impl OffendersPopup {
+    pub fn new(theme: Theme) -> Self {
+        Self {
+            visible: false,
+            theme,
+            source: None,
+        }
+    }
+
+    pub fn toggle(&mut self) {
+        self.visible = !self.visible;
+        if self.visible && self.source.is_none() {
+            self.source = Some(offcpu::spawn_sampler(Duration::from_secs(
+                SAMPLE_WINDOW_SECS,
+            )));
+        }
+    }
+
+    fn lines(&self) -> Vec<Line<'static>> {
+        let dim = Style::default().fg(self.theme.get_color(Color::DarkGray));
+        let report = self
+            .source
+            .as_ref()
+            .and_then(|source| source.lock().ok().map(|report| report.clone()))
+            .unwrap_or_default();
+
+        let mut lines = vec![Line::from(Span::styled(
+            format!(
+                "{:>7} {:<16} {:>10} {:>10} {:>7}",
+                "PID", "NAME", "OFFCPU ms", "IO ms", "IOs"
+            ),
+            Style::default()
+                .fg(self.theme.get_color(Color::Cyan))
+                .add_modifier(Modifier::BOLD),
+        ))];
+
+        if let Some(err) = &report.error {
+            lines.push(Line::from(Span::styled(
+                format!("sampler stopped: {}", err),
+                Style::default().fg(self.theme.get_color(Color::LightRed)),
+            )));
+        } else if report.offenders.is_empty() {
+            lines.push(Line::from(Span::styled("sampling...", dim)));
+        }
+
+        for offender in &report.offenders {
+            let name: String = offender.name.chars().take(16).collect();
+            lines.push(Line::from(Span::styled(
+                format!(
+                    "{:>7} {:<16} {:>10} {:>10} {:>7}",
+                    offender.pid,
+                    name,
+                    crate::format::decimal(offender.off_cpu_ms, 1),
+                    crate::format::decimal(offender.io_ms, 1),
+                    offender.io_requests
+                ),
+                self.theme.text_style(),
+            )));
+        }
+
+        lines.push(Line::from(Span::styled(
+            format!(
+                "ms per second over {}s windows · Esc/O: close",
+                report.window_secs
+            ),
+            dim,
+        )));
+        lines
+    }
+
+    pub fn render_in_area(&self, f: &mut Frame, area: Rect) {
+        if !self.visible {
+            return;
+        }
+
+        let width = area.width.min(60);
+        let height = area.height.min(20);
+        let popup_area = Rect {
+            x: area.x + (area.width - width) / 2,
+            y: area.y + (area.height - height) / 2,
+            width,
+            height,
+        };
+        f.render_widget(Clear, popup_area);
+
+        let popup = Paragraph::new(self.lines()).block(
+            Block::default()
+                .title(Span::styled(
+                    " Top Offenders (off-CPU / block I/O) ",
+                    Style::default()
+                        .fg(self.theme.get_color(Color::LightYellow))
+                        .add_modifier(Modifier::BOLD),
+                ))
+                .borders(Borders::ALL)
+                .border_type(BorderType::Rounded)
+                .border_style(Style::default().fg(self.theme.get_color(Color::DarkGray))),
+        );
+        f.render_widget(popup, popup_area);
+    }
+}
+
+impl Component for OffendersPopup {
+    fn handle_events(
+        &mut self,
+        _event: crossterm::event::Event,
+    ) -> Result<Option<Action>, Box<dyn std::error::Error>> {
+        Ok(None)
+    }
+
+    fn update(&mut self, action: Action) -> Result<Option<Action>, Box<dyn std::error::Error>> {
+        if let Action::ToggleOffenders = action {
+            self.toggle();
+        }
+        Ok(None)
+    }
+
+    fn render(&mut self, f: &mut Frame) {
+        self.render_in_area(f, f.area());
+    }
+}
@@ -1,30 +1,519 @@
 use crate::{
     action::Action,
     components::Component,
-    data::snapshot::{ProcessInfo, SystemSnapshot},
+    data::snapshot::{ProcessInfo, ProcessSortBy, ProcessState, SchedClass, SystemSnapshot},
+    i18n::{tr, tr_args},
+    search::{MatchMode, Matcher},
     theme::Theme,
+    widgets::braille_graph::BrailleGraph,
 };
 use ratatui::{
-    layout::{Constraint, Direction, Layout, Rect},
-    style::{Color, Style},
+    layout::{Constraint, Direction, Layout, Position, Rect},
+    style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, BorderType, Borders, Clear, List, ListItem, Paragraph, Wrap},
+    widgets::{Block, BorderType, Borders, Cell, Clear, Paragraph, Row, Sparkline, Table, Wrap},
     Frame,
 };
+use std::collections::{HashMap, VecDeque};
 use std::process::Command;
+use std::sync::mpsc;
+
+pub use crate::signal::Signal;
 
 #[derive(Clone)]
 struct ProcessRow {
     pid: u32,
     name: String,
-    line: String,
+    /// CPU% already in the displayed scale (cumulative, Irix/Solaris)
+    process: ProcessInfo,
+    /// Tree indent or leak growth, before the name
+    prefix: String,
+    /// Subtree total, after the name
+    suffix: String,
+    /// Ancestor kept only to show a matching descendant in context
+    dimmed: bool,
+    kernel_thread: bool,
+    realtime: bool,
+}
+
+/// Fixed-width process list columns, in display order; NAME takes the width left over
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Column {
+    Pid,
+    User,
+    Mem,
+    Cpu,
+    /// Only while NVML reports per-process GPU use
+    Gpu,
+    State,
+    Elapsed,
+    Write,
+    Class,
+    Category,
+    MajorFaults,
+    Swap,
+}
+
+impl Column {
+    /// Dropped one by one, in this order, until NAME gets at least `NAME_MIN_WIDTH`
+    const DROP_ORDER: [Column; 10] = [
+        Column::Swap,
+        Column::MajorFaults,
+        Column::Category,
+        Column::Class,
+        Column::Elapsed,
+        Column::Write,
+        Column::User,
+        Column::Gpu,
+        Column::State,
+        Column::Mem,
+    ];
+    const NAME_MIN_WIDTH: usize = 12;
+
+    fn width(self) -> usize {
+        match self {
+            Column::Pid => 7,
+            Column::User => 8,
+            // Room for `1023.9MiB`
+            Column::Mem | Column::Swap => 9,
+            Column::MajorFaults => 8,
+            Column::Cpu => 7,
+            Column::Gpu => 5,
+            Column::State => 1,
+            // Room for `3:25:07` and `12d03h`
+            Column::Elapsed => 8,
+            Column::Write => 9,
+            Column::Class => 4,
+            Column::Category => 5,
+        }
+    }
+
+    fn header(self, cumulative_cpu: bool) -> &'static str {
+        match self {
+            Column::Pid => "PID",
+            Column::User => "USER",
+            Column::Mem => "MEM",
+            Column::Cpu if cumulative_cpu => "AVG%",
+            Column::Cpu => "CPU%",
+            Column::Gpu => "GPU%",
+            Column::State => "S",
+            Column::Elapsed => "ELAPSED",
+            Column::Write => "WRITE",
+            Column::Class => "CLS",
+            Column::Category => "TYPE",
+            Column::MajorFaults => "MAJFLT/s",
+            Column::Swap => "SWAP",
+        }
+    }
+
+    /// What clicking the column's title sorts by
+    fn sort_key(self) -> Option<ProcessSortBy> {
+        match self {
+            Column::Pid => Some(ProcessSortBy::Pid),
+            Column::Mem => Some(ProcessSortBy::Memory),
+            Column::Cpu => Some(ProcessSortBy::CpuUsage),
+            Column::Elapsed => Some(ProcessSortBy::StartTime),
+            Column::Write => Some(ProcessSortBy::DiskIo),
+            Column::User
+            | Column::Gpu
+            | Column::State
+            | Column::Class
+            | Column::Category
+            | Column::MajorFaults
+            | Column::Swap => None,
+        }
+    }
+
+    /// Cell text padded to the column width; USER, CLS and TYPE are left-aligned
+    fn cell(self, process: &ProcessInfo) -> String {
+        let width = self.width();
+        match self {
+            Column::Pid => format!("{:>width$}", process.pid),
+            Column::User => {
+                let owner = match (&process.user, process.uid) {
+                    (Some(user), _) => user.clone(),
+                    (None, Some(uid)) => uid.to_string(),
+                    (None, None) => "-".to_string(),
+                };
+                format!("{:<width$}", ProcessComponent::truncate(&owner, width))
+            }
+            Column::Mem => format!("{:>width$}", crate::format::bytes(process.memory)),
+            Column::Cpu => format!(
+                "{:>w$}%",
+                crate::format::decimal(f64::from(process.cpu_usage), 2),
+                w = width - 1
+            ),
+            Column::Gpu => match process.gpu_usage() {
+                Some(usage) => format!("{:>w$.0}%", usage, w = width - 1),
+                None => format!("{:>width$}", "-"),
+            },
+            Column::State => process.state.code().to_string(),
+            Column::Elapsed => format!("{:>width$}", crate::format::elapsed(process.run_time)),
+            Column::Write => format!("{:>width$}", crate::format::bytes(process.disk_usage)),
+            Column::Class if process.rt_priority > 0 => format!(
+                "{:<width$}",
+                format!("{}{}", process.sched_class.label(), process.rt_priority)
+            ),
+            Column::Class => format!("{:<width$}", process.sched_class.label()),
+            Column::Category => format!("{:<width$}", ProcessCategory::of(process).tag()),
+            Column::MajorFaults => format!("{:>width$.1}", process.major_faults_per_sec),
+            Column::Swap => format!("{:>width$}", crate::format::bytes(process.swap)),
+        }
+    }
+}
+
+/// Title of the dialog confirming `signal`
+fn confirm_title(signal: Signal) -> String {
+    match signal {
+        Signal::Term => tr("kill-title").to_string(),
+        signal => tr_args("signal-title", &[("signal", &signal.name())]),
+    }
 }
 
 #[derive(Clone)]
 struct KillDialog {
     pid: u32,
     name: String,
-    yes_selected: bool,
+    choice: KillChoice,
+    /// Sent when the process is terminated rather than its unit restarted
+    signal: Signal,
+    /// Service the process runs in, offered for a restart instead of the kill
+    unit: Option<SystemdUnit>,
+    /// On the protected list: confirmed by typing "yes" instead of choosing Yes
+    protected: bool,
+    typed: String,
+    /// Every marked process the signal goes to; empty for a single process
+    batch: Vec<(u32, String)>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum KillChoice {
+    RestartUnit,
+    Terminate,
+    Cancel,
+}
+
+/// A systemd service, restarted with `systemctl` (`--user` for a user's own services)
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SystemdUnit {
+    pub name: String,
+    pub user: bool,
+}
+
+/// The systemd service `pid` runs in, from its cgroup
+#[cfg(target_os = "linux")]
+fn systemd_unit(pid: u32) -> Option<SystemdUnit> {
+    let mut content = String::new();
+    if !crate::data::procfs::read_pid_file(pid, "cgroup", &mut content) {
+        return None;
+    }
+    crate::data::procfs::parse_systemd_unit(&content).map(|(name, user)| SystemdUnit { name, user })
+}
+
+#[cfg(not(target_os = "linux"))]
+fn systemd_unit(_pid: u32) -> Option<SystemdUnit> {
+    None
+}
+
+/// Color of the CPU% cell by how the scheduler treats the task, so a busy niced batch job reads
+/// as background work and a busy real-time task stands out; `None` keeps the row color
+fn priority_color(process: &ProcessInfo) -> Option<Color> {
+    if process.sched_class.is_realtime() {
+        Some(Color::LightMagenta)
+    } else if process.nice > 0
+        || matches!(process.sched_class, SchedClass::Batch | SchedClass::Idle)
+    {
+        Some(Color::Green)
+    } else if process.nice < 0 {
+        Some(Color::Yellow)
+    } else {
+        None
+    }
+}
+
+/// Warning color of the state cell for processes stuck in uninterruptible sleep (usually on
+/// I/O) or left unreaped as zombies; `None` keeps the row color
+fn state_color(state: ProcessState) -> Option<Color> {
+    match state {
+        ProcessState::Zombie => Some(Color::LightRed),
+        ProcessState::DiskSleep => Some(Color::Yellow),
+        _ => None,
+    }
+}
+
+/// Set the nice value of `pid`; only on Linux, where the current value is read from procfs
+#[cfg(target_os = "linux")]
+fn set_nice(pid: u32, nice: i32) -> std::io::Result<()> {
+    // SAFETY: setpriority only reads its arguments
+    if unsafe { libc::setpriority(libc::PRIO_PROCESS, pid as libc::id_t, nice) } == 0 {
+        Ok(())
+    } else {
+        Err(std::io::Error::last_os_error())
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+fn set_nice(_pid: u32, _nice: i32) -> std::io::Result<()> {
+    Err(std::io::Error::new(
+        std::io::ErrorKind::Unsupported,
+        "renicing is only supported on Linux",
+    ))
+}
+
+/// Restrict every thread of `pid` to `core`, or with `None` allow all cores again. Affinity is
+/// per thread, so like `taskset -a` each task under `/proc/<pid>/task` is set.
+#[cfg(target_os = "linux")]
+fn set_affinity(pid: u32, core: Option<u32>) -> std::io::Result<()> {
+    // SAFETY: cpu_set_t is a plain bit mask, and the CPU_* helpers stay inside it
+    let mut set: libc::cpu_set_t = unsafe { std::mem::zeroed() };
+    let cores = match core {
+        Some(core) => core as usize..core as usize + 1,
+        None => 0..libc::CPU_SETSIZE as usize,
+    };
+    for core in cores {
+        if core >= libc::CPU_SETSIZE as usize {
+            return Err(std::io::Error::from(std::io::ErrorKind::InvalidInput));
+        }
+        // SAFETY: core is below CPU_SETSIZE
+        unsafe { libc::CPU_SET(core, &mut set) };
+    }
+    let mut tasks: Vec<libc::pid_t> = std::fs::read_dir(format!("/proc/{}/task", pid))
+        .map(|entries| {
+            entries
+                .flatten()
+                .filter_map(|entry| entry.file_name().to_str()?.parse().ok())
+                .collect()
+        })
+        .unwrap_or_default();
+    if tasks.is_empty() {
+        tasks.push(pid as libc::pid_t);
+    }
+    for task in tasks {
+        // SAFETY: set outlives the call, which is told its size
+        let result = unsafe { libc::sched_setaffinity(task, std::mem::size_of_val(&set), &set) };
+        if result != 0 {
+            return Err(std::io::Error::last_os_error());
+        }
+    }
+    Ok(())
+}
+
+#[cfg(not(target_os = "linux"))]
+fn set_affinity(_pid: u32, _core: Option<u32>) -> std::io::Result<()> {
+    Err(std::io::Error::new(
+        std::io::ErrorKind::Unsupported,
+        "setting CPU affinity is only supported on Linux",
+    ))
+}
+
+/// Whether `name` matches a protected-process pattern, where `*` matches any run of characters
+fn matches_pattern(pattern: &str, name: &str) -> bool {
+    let pattern = pattern.to_lowercase();
+    let name = name.to_lowercase();
+    let mut parts = pattern.split('*');
+    let first = parts.next().unwrap_or_default();
+    let Some(mut rest) = name.strip_prefix(first) else {
+        return false;
+    };
+    let mut parts: Vec<&str> = parts.collect();
+    let Some(last) = parts.pop() else {
+        return rest.is_empty();
+    };
+    for part in parts {
+        match rest.find(part) {
+            Some(at) => rest = &rest[at + part.len()..],
+            None => return false,
+        }
+    }
+    rest.ends_with(last)
+}
+
+/// Which processes the list shows by where they run
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ProcessOrigin {
+    #[default]
+    All,
+    Host,
+    Containers,
+}
+
+impl ProcessOrigin {
+    fn next(self) -> Self {
+        match self {
+            ProcessOrigin::All => ProcessOrigin::Host,
+            ProcessOrigin::Host => ProcessOrigin::Containers,
+            ProcessOrigin::Containers => ProcessOrigin::All,
+        }
+    }
+
+    fn includes(self, process: &ProcessInfo) -> bool {
+        match self {
+            ProcessOrigin::All => true,
+            ProcessOrigin::Host => !process.container,
+            ProcessOrigin::Containers => process.container,
+        }
+    }
+}
+
+/// Which processes the list shows by who owns them
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ProcessOwner {
+    #[default]
+    All,
+    /// Owned by the user running rtop
+    Mine,
+    Root,
+}
+
+impl ProcessOwner {
+    fn next(self) -> Self {
+        match self {
+            ProcessOwner::All => ProcessOwner::Mine,
+            ProcessOwner::Mine => ProcessOwner::Root,
+            ProcessOwner::Root => ProcessOwner::All,
+        }
+    }
+
+    fn includes(self, process: &ProcessInfo, own_uid: Option<u32>) -> bool {
+        match self {
+            ProcessOwner::All => true,
+            ProcessOwner::Mine => own_uid.is_some() && process.uid == own_uid,
+            ProcessOwner::Root => process.uid == Some(0),
+        }
+    }
+}
+
+/// Broad kind of program, guessed from its name and executable path
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ProcessCategory {
+    Browser,
+    Shell,
+    Daemon,
+    Kernel,
+    Other,
+}
+
+impl ProcessCategory {
+    /// Order the category filter cycles through
+    const ALL: [ProcessCategory; 5] = [
+        ProcessCategory::Browser,
+        ProcessCategory::Shell,
+        ProcessCategory::Daemon,
+        ProcessCategory::Kernel,
+        ProcessCategory::Other,
+    ];
+    /// Browser executables and the names their helper processes take (Firefox's are cut to
+    /// the kernel's 15-character limit)
+    const BROWSERS: [&'static str; 18] = [
+        "firefox",
+        "firefox-esr",
+        "librewolf",
+        "chrome",
+        "chromium",
+        "chromium-browser",
+        "google-chrome",
+        "brave",
+        "msedge",
+        "opera",
+        "vivaldi",
+        "epiphany",
+        "safari",
+        "web content",
+        "webextensions",
+        "isolated web co",
+        "rdd process",
+        "socket process",
+    ];
+    const SHELLS: [&'static str; 14] = [
+        "sh", "bash", "zsh", "fish", "dash", "ksh", "mksh", "tcsh", "csh", "nu", "elvish", "xonsh",
+        "pwsh", "cmd.exe",
+    ];
+    const DAEMONS: [&'static str; 16] = [
+        "init",
+        "systemd",
+        "cron",
+        "dbus-daemon",
+        "dbus-broker",
+        "networkmanager",
+        "pipewire",
+        "wireplumber",
+        "pulseaudio",
+        "containerd",
+        "dockerd",
+        "launchd",
+        "kernel_task",
+        "svchost.exe",
+        "services.exe",
+        "lsass.exe",
+    ];
+    /// Where system services are installed
+    const DAEMON_DIRS: [&'static str; 5] = [
+        "/sbin/",
+        "/usr/sbin/",
+        "/usr/libexec/",
+        "/usr/lib/systemd/",
+        "/lib/systemd/",
+    ];
+
+    pub fn of(process: &ProcessInfo) -> Self {
+        if process.kernel_thread {
+            return ProcessCategory::Kernel;
+        }
+        let name = process.name.to_lowercase();
+        let exe = process.exe.as_deref().unwrap_or_default().to_lowercase();
+        if Self::BROWSERS.contains(&name.as_str())
+            || ["/firefox/", "/chromium/", "/google/chrome/", "/brave"]
+                .iter()
+                .any(|dir| exe.contains(dir))
+        {
+            ProcessCategory::Browser
+        } else if Self::SHELLS.contains(&name.trim_start_matches('-')) {
+            // A leading dash marks a login shell
+            ProcessCategory::Shell
+        } else if Self::DAEMONS.contains(&name.as_str())
+            || name.starts_with("systemd-")
+            || Self::DAEMON_DIRS.iter().any(|dir| exe.starts_with(dir))
+            // sshd, crond, udevd: the traditional suffix, on something init started
+            || (name.len() > 2 && name.ends_with('d') && process.parent_pid == Some(1))
+        {
+            ProcessCategory::Daemon
+        } else {
+            ProcessCategory::Other
+        }
+    }
+
+    /// TYPE column text
+    fn tag(self) -> &'static str {
+        match self {
+            ProcessCategory::Browser => "web",
+            ProcessCategory::Shell => "shell",
+            ProcessCategory::Daemon => "svc",
+            ProcessCategory::Kernel => "krnl",
+            ProcessCategory::Other => "",
+        }
+    }
+
+    /// Title text while the list is filtered to this category
+    fn label(self) -> &'static str {
+        match self {
+            ProcessCategory::Browser => "browsers",
+            ProcessCategory::Shell => "shells",
+            ProcessCategory::Daemon => "daemons",
+            ProcessCategory::Kernel => "kernel",
+            ProcessCategory::Other => "other",
+        }
+    }
+
+    /// The category filter after `current`: each category in turn, then none
+    fn cycle(current: Option<Self>) -> Option<Self> {
+        match current {
+            None => Some(Self::ALL[0]),
+            Some(category) => {
+                let at = Self::ALL.iter().position(|&c| c == category).unwrap_or(0);
+                Self::ALL.get(at + 1).copied()
+            }
+        }
+    }
 }
 
 pub struct ProcessComponent {
@@ -32,12 +521,141 @@ pub struct ProcessComponent {
     pub theme: Theme,
     pub selected_index: usize,
     pub show_tree: bool,
+    /// Show the MAJFLT/s and SWAP columns
+    pub show_fault_columns: bool,
+    /// Show each process's command line instead of its name
+    pub show_full_cmd: bool,
+    /// Mask command-line arguments
+    pub privacy_mode: bool,
+    /// In tree mode, order siblings by the usage of their whole subtree
+    pub subtree_sort: bool,
+    /// Show and sort by average CPU% since rtop started
+    pub cumulative_cpu: bool,
+    /// Process CPU% per core; off divides by the core count so the column sums to 100%
+    pub irix_mode: bool,
+    /// Leave out processes using less than both idle thresholds
+    pub hide_idle: bool,
+    pub idle_cpu_threshold: f32,
+    /// Bytes
+    pub idle_memory_threshold: u64,
+    /// Only list processes whose memory keeps growing
+    pub leak_view: bool,
+    /// Host processes only, container processes only, or both
+    pub origin: ProcessOrigin,
+    /// Only list processes of this category
+    pub category: Option<ProcessCategory>,
+    /// Everyone's processes, only ours, or only root's
+    pub owner: ProcessOwner,
+    /// UID rtop runs as, for the "mine" owner filter
+    pub own_uid: Option<u32>,
+    /// Process open in the detail view; pinned so re-sorting the list cannot swap it out
+    detail_pid: Option<u32>,
+    /// Where the panel was last drawn, for mouse clicks and the wheel
+    pub area: Rect,
+    /// CPU% of the process in the detail view, one sample per collection since it was opened
+    detail_cpu_history: VecDeque<f32>,
     filter_query: String,
+    /// How the search text is matched; cycled with Tab while searching
+    match_mode: MatchMode,
     search_mode: bool,
     search_input: String,
     search_prev_filter: String,
     kill_dialog: Option<KillDialog>,
+    /// Processes marked with Space, in the order they were marked, for a batch kill
+    marked: Vec<(u32, String)>,
+    /// Tree branches folded away, by PID, with the name to notice the PID being reused
+    collapsed: HashMap<u32, String>,
+    /// Process the selection stays on while rows move around it; moving the selection stops
+    /// following
+    followed: Option<(u32, String)>,
+    /// The rows may have moved since the followed process was last looked up: a new snapshot
+    /// arrived or the view changed
+    rows_moved: bool,
     status_message: Option<String>,
+    /// `pkexec` or `sudo`; empty disables retrying a refused kill with privileges
+    pub elevate_with: String,
+    /// Signal refused with EPERM and its process, offered for an elevated retry
+    elevation_offer: Option<(u32, String, Signal)>,
+    /// Name patterns whose kill must be confirmed by typing "yes"
+    pub protected_processes: Vec<String>,
+    /// `--read-only`: leave the kill and mark keys out of the hints
+    pub read_only: bool,
+    /// Finds the systemd service a PID runs in; replaced in tests
+    pub unit_of: fn(u32) -> Option<SystemdUnit>,
+    /// Outcome of a `systemctl restart` still running on its own thread
+    unit_restart: Option<mpsc::Receiver<String>>,
+    /// Outcome of an elevated kill still waiting on its own thread, e.g. for a polkit password
+    elevated_kill: Option<mpsc::Receiver<ElevatedKill>>,
+}
+
+/// A signal sent through `elevate_with`, and how it went
+struct ElevatedKill {
+    pid: u32,
+    name: String,
+    signal: Signal,
+    program: &'static str,
+    result: Result<(), KillFailure>,
+}
+
+/// Why a process could not be signalled
+#[derive(Debug, PartialEq, Eq)]
+enum KillFailure {
+    /// EPERM: the process belongs to another user or is protected
+    NotPermitted,
+    /// ESRCH: the process exited before the signal was sent
+    NoSuchProcess,
+    Other(String),
+}
+
+impl KillDialog {
+    /// What Tab cycles through; the protected dialog is cancelled with Esc only
+    fn choices(&self) -> Vec<KillChoice> {
+        let mut choices = Vec::new();
+        if self.unit.is_some() {
+            choices.push(KillChoice::RestartUnit);
+        }
+        choices.push(KillChoice::Terminate);
+        if !self.protected {
+            choices.push(KillChoice::Cancel);
+        }
+        choices
+    }
+}
+
+impl KillFailure {
+    fn from_io_error(err: &std::io::Error) -> Self {
+        if err.kind() == std::io::ErrorKind::PermissionDenied {
+            KillFailure::NotPermitted
+        } else if crate::signal::no_such_process(err) {
+            KillFailure::NoSuchProcess
+        } else {
+            KillFailure::Other(err.to_string())
+        }
+    }
+
+    /// From how an elevated `kill` exited: pkexec has its own exit statuses for refused
+    /// authorization, and everything else is told apart by `kill`'s error output
+    fn from_exit(program: &str, code: Option<i32>, stderr: &str) -> Self {
+        match (program, code) {
+            // 127: not authorized, or no agent could ask
+            ("pkexec", Some(127)) => KillFailure::NotPermitted,
+            ("pkexec", Some(126)) => {
+                KillFailure::Other("pkexec authentication was dismissed".to_string())
+            }
+            _ => Self::from_stderr(stderr),
+        }
+    }
+
+    /// From the error output of `kill` run through sudo or doas, in the C locale
+    fn from_stderr(stderr: &str) -> Self {
+        if stderr.contains("Operation not permitted") {
+            KillFailure::NotPermitted
+        } else if stderr.contains("No such process") {
+            KillFailure::NoSuchProcess
+        } else {
+            KillFailure::Other(stderr.trim().to_string())
+        }
+    }
 }
 
 impl ProcessComponent {
@@ -47,12 +665,45 @@ impl ProcessComponent {
             theme,
             selected_index: 0,
             show_tree: false,
+            show_fault_columns: false,
+            show_full_cmd: false,
+            privacy_mode: false,
+            subtree_sort: false,
+            cumulative_cpu: false,
+            irix_mode: true,
+            hide_idle: false,
+            idle_cpu_threshold: 0.1,
+            idle_memory_threshold: 10 * 1024 * 1024,
+            leak_view: false,
+            origin: ProcessOrigin::All,
+            category: None,
+            owner: ProcessOwner::All,
+            #[cfg(unix)]
+            // SAFETY: getuid has no preconditions and cannot fail
+            own_uid: Some(unsafe { libc::getuid() }),
+            #[cfg(not(unix))]
+            own_uid: None,
+            detail_pid: None,
+            area: Rect::default(),
+            detail_cpu_history: VecDeque::new(),
             filter_query: String::new(),
+            match_mode: MatchMode::default(),
             search_mode: false,
             search_input: String::new(),
             search_prev_filter: String::new(),
             kill_dialog: None,
+            marked: Vec::new(),
+            collapsed: HashMap::new(),
+            followed: None,
+            rows_moved: false,
             status_message: None,
+            elevate_with: String::new(),
+            elevation_offer: None,
+            protected_processes: Vec::new(),
+            read_only: false,
+            unit_of: systemd_unit,
+            unit_restart: None,
+            elevated_kill: None,
         }
     }
 
@@ -64,6 +715,84 @@ impl ProcessComponent {
         self.kill_dialog.is_some()
     }
 
+    /// The kill dialog is waiting for "yes" to be typed
+    pub fn is_typed_kill_confirm_active(&self) -> bool {
+        self.kill_dialog
+            .as_ref()
+            .is_some_and(|dialog| dialog.protected)
+    }
+
+    fn is_protected(&self, name: &str) -> bool {
+        self.protected_processes
+            .iter()
+            .any(|pattern| matches_pattern(pattern, name))
+    }
+
+    fn open_kill_dialog(&mut self, pid: u32, name: String) {
+        let unit = (self.unit_of)(pid);
+        self.kill_dialog = Some(KillDialog {
+            pid,
+            protected: self.is_protected(&name),
+            name,
+            choice: if unit.is_some() {
+                KillChoice::RestartUnit
+            } else {
+                KillChoice::Terminate
+            },
+            unit,
+            signal: Signal::Term,
+            typed: String::new(),
+            batch: Vec::new(),
+        });
+        self.status_message = None;
+    }
+
+    /// The kill dialog for every marked process; a protected one among them makes it the
+    /// typed-confirmation dialog, naming that process
+    fn open_batch_kill_dialog(&mut self) {
+        let Some((pid, name)) = self
+            .marked
+            .iter()
+            .find(|(_, name)| self.is_protected(name))
+            .or(self.marked.first())
+            .cloned()
+        else {
+            return;
+        };
+        self.kill_dialog = Some(KillDialog {
+            pid,
+            protected: self.is_protected(&name),
+            name,
+            choice: KillChoice::Terminate,
+            unit: None,
+            signal: Signal::Term,
+            typed: String::new(),
+            batch: self.marked.clone(),
+        });
+        self.status_message = None;
+    }
+
+    pub fn marked_count(&self) -> usize {
+        self.marked.len()
+    }
+
+    fn is_marked(&self, pid: u32) -> bool {
+        self.marked.iter().any(|&(marked, _)| marked == pid)
+    }
+
+    /// Mark the highlighted process for a batch kill, or unmark it
+    fn toggle_mark(&mut self) {
+        let Some(row) = self.selected_row() else {
+            return;
+        };
+        match self.marked.iter().position(|&(pid, _)| pid == row.pid) {
+            Some(at) => {
+                self.marked.remove(at);
+            }
+            None => self.marked.push((row.pid, row.name)),
+        }
+    }
+
     fn current_filter(&self) -> &str {
         if self.search_mode {
             &self.search_input
@@ -72,155 +801,468 @@ impl ProcessComponent {
         }
     }
 
-    fn normalized_filter(&self) -> Option<String> {
+    /// Matcher for the current search, if any; a regex that does not compile matches nothing
+    /// until it is fixed
+    fn filter_matcher(&self) -> Option<Matcher> {
         let raw = self.current_filter().trim();
         if raw.is_empty() {
             None
         } else {
-            Some(raw.to_lowercase())
+            Some(Matcher::new(self.match_mode, raw).unwrap_or_else(|_| Matcher::never()))
         }
     }
 
-    fn process_matches_filter(process: &ProcessInfo, needle: Option<&str>) -> bool {
-        let Some(needle) = needle else {
-            return true;
-        };
-        if process.pid.to_string().contains(needle) || process.name.to_lowercase().contains(needle)
-        {
-            return true;
+    /// Why the current search does not compile, for the search prompt
+    fn filter_error(&self) -> Option<String> {
+        let raw = self.current_filter().trim();
+        if raw.is_empty() {
+            return None;
         }
-        if process
-            .cmd
-            .iter()
-            .any(|part| part.to_lowercase().contains(needle))
-        {
+        Matcher::new(self.match_mode, raw).err()
+    }
+
+    fn process_matches_filter(process: &ProcessInfo, matcher: Option<&Matcher>) -> bool {
+        let Some(matcher) = matcher else {
             return true;
+        };
+        matcher.is_match(&process.pid.to_string())
+            || matcher.is_match(&process.name)
+            || process.cmd.iter().any(|part| matcher.is_match(part))
+            || process
+                .exe
+                .as_ref()
+                .is_some_and(|exe| matcher.is_match(exe))
+    }
+
+    fn compare_processes(
+        sort_by: ProcessSortBy,
+        a: &ProcessInfo,
+        b: &ProcessInfo,
+    ) -> std::cmp::Ordering {
+        match sort_by {
+            ProcessSortBy::CpuUsage => b
+                .cpu_usage
+                .partial_cmp(&a.cpu_usage)
+                .unwrap_or(std::cmp::Ordering::Equal)
+                .then_with(|| b.memory.cmp(&a.memory))
+                .then_with(|| a.pid.cmp(&b.pid)),
+            ProcessSortBy::Memory => b.memory.cmp(&a.memory).then_with(|| a.pid.cmp(&b.pid)),
+            ProcessSortBy::Pid => b.pid.cmp(&a.pid),
+            ProcessSortBy::Name => a.name.cmp(&b.name).then_with(|| a.pid.cmp(&b.pid)),
+            ProcessSortBy::DiskIo => (b.disk_read_per_sec + b.disk_write_per_sec)
+                .cmp(&(a.disk_read_per_sec + a.disk_write_per_sec))
+                .then_with(|| a.pid.cmp(&b.pid)),
+            // Newest first
+            ProcessSortBy::StartTime => b
+                .start_time
+                .cmp(&a.start_time)
+                .then_with(|| b.pid.cmp(&a.pid)),
         }
-        if let Some(exe) = &process.exe {
-            if exe.to_lowercase().contains(needle) {
-                return true;
+    }
+
+    /// Processes with `cpu_usage` replaced by the average since start in cumulative mode,
+    /// scaled to the whole machine outside Irix mode
+    fn displayed_processes(&self) -> Vec<ProcessInfo> {
+        let mut processes = self.snapshot.processes.clone();
+        let scale = if self.irix_mode {
+            1.0
+        } else {
+            self.snapshot.cpu_count.max(1) as f32
+        };
+        for process in &mut processes {
+            if self.cumulative_cpu {
+                process.cpu_usage = process.cumulative_cpu;
             }
+            process.cpu_usage /= scale;
         }
-        false
+        processes
     }
 
     fn get_sorted_processes(&self) -> Vec<ProcessInfo> {
-        let mut processes: Vec<ProcessInfo> = self.snapshot.processes.clone();
-
-        match self.snapshot.process_sort_by {
-            crate::data::snapshot::ProcessSortBy::CpuUsage => {
-                processes.sort_by(|a, b| {
-                    b.cpu_usage
-                        .partial_cmp(&a.cpu_usage)
-                        .unwrap_or(std::cmp::Ordering::Equal)
-                        .then_with(|| b.memory.cmp(&a.memory))
-                        .then_with(|| a.pid.cmp(&b.pid))
-                });
-            }
-            crate::data::snapshot::ProcessSortBy::Memory => {
-                processes.sort_by(|a, b| b.memory.cmp(&a.memory).then_with(|| a.pid.cmp(&b.pid)));
-            }
-            crate::data::snapshot::ProcessSortBy::Pid => {
-                processes.sort_by(|a, b| b.pid.cmp(&a.pid));
-            }
-            crate::data::snapshot::ProcessSortBy::Name => {
-                processes.sort_by(|a, b| a.name.cmp(&b.name).then_with(|| a.pid.cmp(&b.pid)));
+        let mut processes = self.displayed_processes();
+        let sort_by = self.snapshot.process_sort_by;
+        let reversed = self.snapshot.process_sort_reversed;
+        processes.sort_by(|a, b| {
+            let ordering = Self::compare_processes(sort_by, a, b);
+            if reversed {
+                ordering.reverse()
+            } else {
+                ordering
             }
-        }
-
+        });
         processes
     }
 
     fn get_filtered_sorted_processes(&self) -> Vec<ProcessInfo> {
         let processes = self.get_sorted_processes();
-        let filter = self.normalized_filter();
+        let filter = self.filter_matcher();
         processes
             .into_iter()
-            .filter(|process| Self::process_matches_filter(process, filter.as_deref()))
+            .filter(|process| {
+                Self::process_matches_filter(process, filter.as_ref())
+                    && self.includes_kind(process)
+                    && !self.hidden_idle(process)
+            })
             .collect()
     }
 
+    /// Whether `process` passes the origin, owner and category filters
+    fn includes_kind(&self, process: &ProcessInfo) -> bool {
+        self.origin.includes(process)
+            && self.owner.includes(process, self.own_uid)
+            && self
+                .category
+                .is_none_or(|category| ProcessCategory::of(process) == category)
+    }
+
+    /// Whether idle hiding leaves `process` out; takes CPU% as displayed
+    fn hidden_idle(&self, process: &ProcessInfo) -> bool {
+        self.hide_idle
+            && process.cpu_usage < self.idle_cpu_threshold
+            && process.memory < self.idle_memory_threshold
+    }
+
+    /// Processes matching the search that idle hiding leaves out, for the title
+    fn hidden_idle_count(&self) -> usize {
+        if !self.hide_idle {
+            return 0;
+        }
+        let filter = self.filter_matcher();
+        self.displayed_processes()
+            .iter()
+            .filter(|process| {
+                Self::process_matches_filter(process, filter.as_ref())
+                    && self.includes_kind(process)
+                    && self.hidden_idle(process)
+            })
+            .count()
+    }
+
+    /// Enabled columns that fit in `width` next to a NAME column of at least
+    /// `Column::NAME_MIN_WIDTH`, dropping the least important ones first
+    fn columns_for_width(&self, width: usize) -> Vec<Column> {
+        let mut columns = vec![
+            Column::Pid,
+            Column::User,
+            Column::Mem,
+            Column::Cpu,
+            Column::State,
+            Column::Elapsed,
+            Column::Write,
+            Column::Class,
+            Column::Category,
+        ];
+        if self.snapshot.process_gpu_stats {
+            columns.insert(4, Column::Gpu);
+        }
+        if self.show_fault_columns {
+            columns.extend([Column::MajorFaults, Column::Swap]);
+        }
+        let used = |columns: &[Column]| columns.iter().map(|c| c.width() + 1).sum::<usize>();
+        for drop in Column::DROP_ORDER {
+            if used(&columns) + Column::NAME_MIN_WIDTH <= width {
+                break;
+            }
+            columns.retain(|&column| column != drop);
+        }
+        columns
+    }
+
+    /// Cell text for the given columns and NAME; NAME is cut to what is left of `width`,
+    /// keeping the subtree total after it
+    fn row_cells(&self, row: &ProcessRow, columns: &[Column], width: usize) -> Vec<String> {
+        let mut cells: Vec<String> = columns
+            .iter()
+            .map(|column| column.cell(&row.process))
+            .collect();
+        let used: usize = columns.iter().map(|column| column.width() + 1).sum();
+        let name_width = width
+            .saturating_sub(used)
+            .saturating_sub(row.suffix.chars().count());
+        let name = format!("{}{}", row.prefix, self.display_name(&row.process));
+        cells.push(format!(
+            "{}{}",
+            Self::truncate(&name, name_width),
+            row.suffix
+        ));
+        cells
+    }
+
+    /// The NAME cell with the characters the search matched highlighted; the tree indent and
+    /// subtree total around the name are left alone
+    fn highlighted_name(
+        &self,
+        row: &ProcessRow,
+        cell: String,
+        matcher: Option<&Matcher>,
+    ) -> Line<'static> {
+        let chars: Vec<char> = cell.chars().collect();
+        let start = row.prefix.chars().count().min(chars.len());
+        let end = chars
+            .len()
+            .saturating_sub(row.suffix.chars().count())
+            .max(start);
+        let name: String = chars[start..end].iter().collect();
+        let Some(positions) = matcher.and_then(|matcher| matcher.find(&name)) else {
+            return Line::from(cell);
+        };
+        let highlight = Style::default()
+            .fg(self.theme.get_color(Color::Yellow))
+            .add_modifier(Modifier::BOLD | Modifier::UNDERLINED);
+        let mut spans = Vec::new();
+        let mut run = String::new();
+        let mut run_matched = false;
+        for (i, &c) in chars.iter().enumerate() {
+            let matched = i >= start && positions.contains(&(i - start));
+            if matched != run_matched && !run.is_empty() {
+                let text = std::mem::take(&mut run);
+                spans.push(if run_matched {
+                    Span::styled(text, highlight)
+                } else {
+                    Span::raw(text)
+                });
+            }
+            run_matched = matched;
+            run.push(c);
+        }
+        spans.push(if run_matched {
+            Span::styled(run, highlight)
+        } else {
+            Span::raw(run)
+        });
+        Line::from(spans)
+    }
+
+    /// `text` cut to `width` characters, marking the cut with `…`
+    fn truncate(text: &str, width: usize) -> String {
+        if text.chars().count() <= width {
+            return text.to_string();
+        }
+        let mut cut: String = text.chars().take(width.saturating_sub(1)).collect();
+        if width > 0 {
+            cut.push('…');
+        }
+        cut
+    }
+
+    /// NAME column text: the process name, or its command line when enabled
+    fn display_name(&self, process: &ProcessInfo) -> String {
+        if process.kernel_thread {
+            return format!("[{}]", process.name);
+        }
+        if !self.show_full_cmd || process.cmd.is_empty() {
+            return process.name.clone();
+        }
+        if self.privacy_mode {
+            let program = process.cmd[0].as_str();
+            if process.cmd.len() > 1 {
+                format!("{} ***", program)
+            } else {
+                program.to_string()
+            }
+        } else {
+            process.cmd.join(" ")
+        }
+    }
+
+    /// Titles aligned like their cells, NAME last
+    fn header_titles(&self, columns: &[Column]) -> Vec<String> {
+        let mut titles: Vec<String> = columns
+            .iter()
+            .map(|&column| {
+                let title = column.header(self.cumulative_cpu);
+                let width = column.width();
+                if matches!(column, Column::User | Column::Class | Column::Category) {
+                    format!("{:<width$}", title)
+                } else {
+                    format!("{:>width$}", title)
+                }
+            })
+            .collect();
+        titles.push("NAME".to_string());
+        titles
+    }
+
+    fn process_row(&self, process: &ProcessInfo, prefix: &str, dimmed: bool) -> ProcessRow {
+        ProcessRow {
+            pid: process.pid,
+            name: process.name.clone(),
+            process: process.clone(),
+            prefix: prefix.to_string(),
+            suffix: String::new(),
+            dimmed,
+            kernel_thread: process.kernel_thread,
+            realtime: process.sched_class.is_realtime(),
+        }
+    }
+
     fn get_flat_process_rows(&self) -> Vec<ProcessRow> {
         self.get_filtered_sorted_processes()
             .into_iter()
-            .map(|process| ProcessRow {
-                pid: process.pid,
-                name: process.name.clone(),
-                line: format!(
-                    "{:>7} {:>8} {:>6.2}% {:>9} {}",
-                    process.pid,
-                    Self::bytes_to_human_readable(process.memory),
-                    process.cpu_usage,
-                    Self::bytes_to_human_readable(process.disk_usage),
-                    process.name
-                ),
-            })
+            .map(|process| self.process_row(&process, "", false))
             .collect()
     }
 
     fn get_process_tree_rows(&self) -> Vec<ProcessRow> {
         let mut rows = Vec::new();
-        let mut processes_map: std::collections::HashMap<u32, Vec<&ProcessInfo>> =
-            std::collections::HashMap::new();
+        let mut processes_map: HashMap<u32, Vec<&ProcessInfo>> = HashMap::new();
 
-        for process in &self.snapshot.processes {
+        let processes = self.displayed_processes();
+        for process in &processes {
             let parent_pid = process.parent_pid.unwrap_or(0);
             processes_map.entry(parent_pid).or_default().push(process);
         }
 
-        let filter = self.normalized_filter();
-        Self::build_tree_recursive(0, &processes_map, 0, &mut rows, filter.as_deref());
-        Self::build_tree_recursive(1, &processes_map, 0, &mut rows, filter.as_deref());
+        let mut totals = HashMap::new();
+        if self.subtree_sort {
+            for root in [0, 1] {
+                for process in processes_map.get(&root).into_iter().flatten() {
+                    Self::accumulate_subtree(process, &processes_map, &mut totals);
+                }
+            }
+        }
+
+        let filter = self.filter_matcher();
+        for root in [0, 1] {
+            self.build_tree_recursive(root, &processes_map, &totals, 0, &mut rows, filter.as_ref());
+        }
         rows
     }
 
+    /// Record the CPU% and memory of `process` plus all its descendants in `totals`
+    fn accumulate_subtree(
+        process: &ProcessInfo,
+        processes_map: &HashMap<u32, Vec<&ProcessInfo>>,
+        totals: &mut HashMap<u32, (f32, u64)>,
+    ) -> (f32, u64) {
+        let mut total = (process.cpu_usage, process.memory);
+        for child in processes_map.get(&process.pid).into_iter().flatten() {
+            if child.pid == process.pid {
+                continue;
+            }
+            let (cpu, memory) = Self::accumulate_subtree(child, processes_map, totals);
+            total.0 += cpu;
+            total.1 += memory;
+        }
+        totals.insert(process.pid, total);
+        total
+    }
+
+    /// Append the subtree under `parent_pid`, returning whether any row in it matched the filter.
+    /// Non-matching processes are kept (dimmed) when one of their descendants matches.
     fn build_tree_recursive(
+        &self,
         parent_pid: u32,
-        processes_map: &std::collections::HashMap<u32, Vec<&ProcessInfo>>,
+        processes_map: &HashMap<u32, Vec<&ProcessInfo>>,
+        totals: &HashMap<u32, (f32, u64)>,
         depth: usize,
         rows: &mut Vec<ProcessRow>,
-        filter: Option<&str>,
-    ) {
+        filter: Option<&Matcher>,
+    ) -> bool {
+        let sort_by = self.snapshot.process_sort_by;
+        let reversed = self.snapshot.process_sort_reversed;
+        let mut any_match = false;
         if let Some(children) = processes_map.get(&parent_pid) {
             let mut ordered_children = children.clone();
             ordered_children.sort_by(|a, b| {
-                b.cpu_usage
-                    .partial_cmp(&a.cpu_usage)
-                    .unwrap_or(std::cmp::Ordering::Equal)
-                    .then_with(|| a.pid.cmp(&b.pid))
+                let ordering = match (totals.get(&a.pid), totals.get(&b.pid)) {
+                    (Some(ta), Some(tb)) => match sort_by {
+                        ProcessSortBy::CpuUsage => tb.0.total_cmp(&ta.0),
+                        ProcessSortBy::Memory => tb.1.cmp(&ta.1),
+                        _ => std::cmp::Ordering::Equal,
+                    }
+                    .then_with(|| Self::compare_processes(sort_by, a, b)),
+                    _ => Self::compare_processes(sort_by, a, b),
+                };
+                if reversed {
+                    ordering.reverse()
+                } else {
+                    ordering
+                }
             });
             for process in ordered_children {
-                if Self::process_matches_filter(process, filter) {
-                    let indent = "  ".repeat(depth);
-                    rows.push(ProcessRow {
-                        pid: process.pid,
-                        name: process.name.clone(),
-                        line: format!(
-                            "{}{} [{}] {:.2}% {}",
-                            indent,
-                            process.name,
-                            process.pid,
-                            process.cpu_usage,
-                            Self::bytes_to_human_readable(process.memory)
-                        ),
-                    });
+                // Idle parents of busy processes stay, dimmed, to keep the tree intact, and
+                // so do the host-side runtimes (shims) that containers hang under
+                let matches = Self::process_matches_filter(process, filter)
+                    && self.includes_kind(process)
+                    && !self.hidden_idle(process);
+                let row_index = rows.len();
+                let indent = if depth == 0 {
+                    String::new()
+                } else {
+                    format!("{}└ ", "  ".repeat(depth - 1))
+                };
+                let mut row = self.process_row(process, &indent, !matches);
+                if processes_map.contains_key(&process.pid) {
+                    // Only the total being sorted on, to stay visible in narrow panels.
+                    if let Some((cpu, memory)) = totals.get(&process.pid) {
+                        let total = match sort_by {
+                            ProcessSortBy::Memory => crate::format::bytes(*memory),
+                            _ => format!("{}%", crate::format::decimal(f64::from(*cpu), 1)),
+                        };
+                        row.suffix = format!(" Σ{}", total);
+                    }
                 }
+                rows.push(row);
 
-                Self::build_tree_recursive(process.pid, processes_map, depth + 1, rows, filter);
+                // A folded branch is still searched, so a match inside keeps its root listed
+                let collapsed = self.collapsed.contains_key(&process.pid);
+                let mut hidden = Vec::new();
+                let descendant_matches = self.build_tree_recursive(
+                    process.pid,
+                    processes_map,
+                    totals,
+                    depth + 1,
+                    if collapsed { &mut hidden } else { rows },
+                    filter,
+                );
+                if collapsed && !hidden.is_empty() {
+                    let row = &mut rows[row_index];
+                    row.prefix.push_str("▸ ");
+                    row.suffix.push_str(&format!(" (+{})", hidden.len()));
+                }
+                if !matches && !descendant_matches {
+                    rows.truncate(row_index);
+                }
+                any_match |= matches || descendant_matches;
             }
         }
+        any_match
+    }
+
+    /// Suspected leaks, fastest growing first, with the growth rate before the name
+    fn get_leak_rows(&self) -> Vec<ProcessRow> {
+        let mut leaks: Vec<(f64, ProcessInfo)> = self
+            .get_filtered_sorted_processes()
+            .into_iter()
+            .filter_map(|process| Some((process.leak_rate?, process)))
+            .collect();
+        leaks.sort_by(|a, b| b.0.total_cmp(&a.0));
+        leaks
+            .into_iter()
+            .map(|(rate, process)| {
+                let growth = format!("+{}/min ", crate::format::bytes((rate * 60.0) as u64));
+                self.process_row(&process, &growth, false)
+            })
+            .collect()
     }
 
     fn get_process_rows(&self) -> Vec<ProcessRow> {
-        if self.show_tree {
+        if self.leak_view {
+            self.get_leak_rows()
+        } else if self.show_tree {
             self.get_process_tree_rows()
         } else {
             self.get_flat_process_rows()
         }
     }
 
+    /// The highlighted row, or the process in the detail view while it is open
     fn selected_row(&self) -> Option<ProcessRow> {
         let rows = self.get_process_rows();
+        if let Some(pid) = self.detail_pid {
+            return rows.into_iter().find(|row| row.pid == pid);
+        }
         if rows.is_empty() {
             None
         } else {
@@ -229,45 +1271,1051 @@ impl ProcessComponent {
         }
     }
 
-    fn clamp_selected_index(&mut self) {
-        let count = self.get_process_rows().len();
-        if count == 0 {
-            self.selected_index = 0;
-        } else if self.selected_index >= count {
-            self.selected_index = count - 1;
+    /// One shade per core by how often the task was last seen there: `·` never, `█` always
+    fn occupancy_strip(samples: &[u8]) -> String {
+        let total: u32 = samples.iter().map(|&count| u32::from(count)).sum();
+        samples
+            .iter()
+            .map(|&count| {
+                if count == 0 || total == 0 {
+                    return '·';
+                }
+                match u32::from(count) * 4 / total {
+                    0 => '░',
+                    1 => '▒',
+                    2 => '▓',
+                    _ => '█',
+                }
+            })
+            .collect()
+    }
+
+    /// Drill-down for one process: everything the list columns leave out, and its recent CPU
+    fn render_detail_view(&self, f: &mut Frame, area: Rect, pid: u32) {
+        let label_style = Style::default().fg(self.theme.get_color(Color::Cyan));
+        let value_style = Style::default().fg(self.theme.get_color(Color::Gray));
+        let dim = Style::default().fg(self.theme.get_color(Color::DarkGray));
+
+        let Some(process) = self.snapshot.processes.iter().find(|p| p.pid == pid) else {
+            f.render_widget(
+                Paragraph::new(Line::from(Span::styled(
+                    tr_args("detail-exited", &[("pid", &pid)]),
+                    value_style,
+                ))),
+                area,
+            );
+            return;
+        };
+        // Filled in by the collector from the next collection on
+        let detail = self
+            .snapshot
+            .inspected_process
+            .as_ref()
+            .filter(|detail| detail.pid == pid);
+        let field = |label: &str, value: String| {
+            Line::from(vec![
+                Span::styled(format!("{:<9}", label), label_style),
+                Span::styled(value, value_style),
+            ])
+        };
+        let pending = || "…".to_string();
+
+        let mut header = format!(
+            "{} · {} · {}",
+            process.name,
+            process.state.label(),
+            process.sched_class.label()
+        );
+        if process.kernel_thread {
+            header.push_str(&format!(" · {}", tr("detail-kernel-thread")));
+        }
+        if process.container {
+            header.push_str(&format!(" · {}", tr("detail-container")));
+        }
+        let mut lines = vec![Line::from(vec![
+            Span::styled(format!("{} ", process.pid), label_style),
+            Span::styled(
+                header,
+                Style::default()
+                    .fg(self.theme.get_color(Color::White))
+                    .add_modifier(Modifier::BOLD),
+            ),
+        ])];
+
+        let parent = match process.parent_pid {
+            Some(ppid) => {
+                let name = self
+                    .snapshot
+                    .processes
+                    .iter()
+                    .find(|p| p.pid == ppid)
+                    .map_or("?", |p| p.name.as_str());
+                format!("{} ({})", ppid, name)
+            }
+            None => tr("detail-no-parent").to_string(),
+        };
+        lines.push(field(tr("detail-parent"), parent));
+        let unknown = || tr("detail-unknown").to_string();
+        let owner = match (&process.user, process.uid) {
+            (Some(user), Some(uid)) => format!("{} ({})", user, uid),
+            (None, Some(uid)) => uid.to_string(),
+            (_, None) => unknown(),
+        };
+        lines.push(field(tr("detail-user"), owner));
+        let class = if process.rt_priority > 0 {
+            format!("{}{}", process.sched_class.label(), process.rt_priority)
+        } else {
+            process.sched_class.label().to_string()
+        };
+        lines.push(field(
+            tr("detail-priority"),
+            format!("nice {} · {}", process.nice, class),
+        ));
+
+        let cmd = detail.map_or(&process.cmd, |detail| &detail.cmd);
+        let command = match cmd.split_first() {
+            None => format!("[{}]", process.name),
+            Some((program, args)) if self.privacy_mode && !args.is_empty() => {
+                format!("{} ***", program)
+            }
+            Some(_) => cmd.join(" "),
+        };
+        lines.push(field(tr("detail-command"), command));
+        let exe = detail
+            .and_then(|detail| detail.exe.clone())
+            .or_else(|| process.exe.clone());
+        lines.push(field(tr("detail-exe"), exe.unwrap_or_else(unknown)));
+        lines.push(field(
+            tr("detail-cwd"),
+            match detail {
+                Some(detail) => detail.cwd.clone().unwrap_or_else(unknown),
+                None => pending(),
+            },
+        ));
+
+        let started = match detail.filter(|detail| detail.start_time > 0) {
+            Some(detail) => {
+                let now = chrono::Local::now().timestamp().max(0) as u64;
+                tr_args(
+                    "detail-started-at",
+                    &[
+                        ("time", &crate::format::date_time(detail.start_time as i64)),
+                        (
+                            "uptime",
+                            &crate::format::duration(now.saturating_sub(detail.start_time)),
+                        ),
+                    ],
+                )
+            }
+            None => pending(),
+        };
+        lines.push(field(tr("detail-started"), started));
+
+        let mut memory = format!("rss {}", crate::format::bytes(process.memory));
+        if let Some(detail) = detail {
+            for (label, value) in [
+                ("anon", detail.rss_anon),
+                ("file", detail.rss_file),
+                ("shmem", detail.rss_shmem),
+            ] {
+                if let Some(value) = value {
+                    memory.push_str(&format!(" · {} {}", label, crate::format::bytes(value)));
+                }
+            }
+        }
+        memory.push_str(&format!(" · swap {}", crate::format::bytes(process.swap)));
+        if let Some(detail) = detail {
+            memory.push_str(&format!(
+                " · virtual {}",
+                crate::format::bytes(detail.virtual_memory)
+            ));
+        }
+        lines.push(field(tr("detail-memory"), memory));
+
+        let samples: u32 = process.core_samples.iter().map(|&c| u32::from(c)).sum();
+        if samples == 0 {
+            lines.push(field(
+                tr("detail-cores"),
+                tr("detail-no-samples").to_string(),
+            ));
+        } else {
+            let used = process.core_samples.iter().filter(|&&c| c > 0).count();
+            lines.push(Line::from(vec![
+                Span::styled(format!("{:<9}", tr("detail-cores")), label_style),
+                Span::styled(
+                    Self::occupancy_strip(&process.core_samples),
+                    Style::default().fg(self.theme.get_color(Color::LightYellow)),
+                ),
+                Span::styled(
+                    format!(
+                        " {}",
+                        tr_args(
+                            "detail-cores-used",
+                            &[
+                                ("used", &used),
+                                ("cores", &process.core_samples.len()),
+                                ("samples", &samples),
+                            ],
+                        )
+                    ),
+                    value_style,
+                ),
+            ]));
+        }
+        if let Some(line) = self.open_files_line(pid) {
+            lines.push(line);
+        }
+        if self.snapshot.process_gpu_stats {
+            lines.extend(self.gpu_process_lines(pid));
+        }
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Min(0),
+                Constraint::Length(1),
+                Constraint::Length(3),
+            ])
+            .split(area);
+        f.render_widget(Paragraph::new(lines).wrap(Wrap { trim: false }), chunks[0]);
+
+        let scale = self.cpu_scale();
+        let history: Vec<f32> = self
+            .detail_cpu_history
+            .iter()
+            .map(|cpu| cpu / scale)
+            .collect();
+        let peak = history.iter().copied().fold(0.0, f32::max);
+        f.render_widget(
+            Paragraph::new(Line::from(vec![
+                Span::styled(format!("{:<9}", tr("detail-cpu")), label_style),
+                Span::styled(
+                    tr_args(
+                        "detail-cpu-usage",
+                        &[
+                            (
+                                "now",
+                                &crate::format::decimal(f64::from(process.cpu_usage / scale), 1),
+                            ),
+                            ("peak", &crate::format::decimal(f64::from(peak), 1)),
+                            ("samples", &history.len()),
+                        ],
+                    ),
+                    value_style,
+                ),
+                Span::styled(format!("  · {}", tr("detail-esc-back")), dim),
+            ])),
+            chunks[1],
+        );
+        // Tenths of a percent, scaled to at least a full core so idle noise stays flat
+        let data: Vec<u64> = history
+            .iter()
+            .map(|cpu| (cpu * 10.0).round() as u64)
+            .collect();
+        let visible = data.len().saturating_sub(chunks[2].width as usize);
+        f.render_widget(
+            Sparkline::default()
+                .data(&data[visible..])
+                .max((peak.max(100.0) * 10.0).round() as u64)
+                .style(Style::default().fg(self.theme.get_color(Color::LightGreen))),
+            chunks[2],
+        );
+    }
+
+    /// Every process on the NVIDIA GPUs, busiest first, with `pid` highlighted, so a process's
+    /// GPU use can be seen next to what it shares the card with
+    fn gpu_process_lines(&self, pid: u32) -> Vec<Line<'static>> {
+        const MAX_ROWS: usize = 6;
+        let label_style = Style::default().fg(self.theme.get_color(Color::Cyan));
+        let value_style = Style::default().fg(self.theme.get_color(Color::Gray));
+
+        let mut users: Vec<(&ProcessInfo, &crate::data::snapshot::ProcessGpuUsage)> = self
+            .snapshot
+            .processes
+            .iter()
+            .flat_map(|process| process.gpu.iter().map(move |usage| (process, usage)))
+            .collect();
+        users.sort_by(|a, b| {
+            b.1.utilization
+                .cmp(&a.1.utilization)
+                .then_with(|| b.1.memory.cmp(&a.1.memory))
+                .then_with(|| a.0.pid.cmp(&b.0.pid))
+        });
+
+        let mut lines = vec![
+            Line::from(""),
+            Line::from(Span::styled(
+                tr("gpu-processes-title"),
+                label_style.add_modifier(Modifier::BOLD),
+            )),
+        ];
+        if users.is_empty() {
+            lines.push(Line::from(Span::styled(
+                tr("gpu-processes-none"),
+                value_style,
+            )));
+            return lines;
+        }
+        // Which GPU only matters with more than one in use
+        let several = users.iter().any(|(_, usage)| usage.gpu != users[0].1.gpu);
+        lines.push(Line::from(Span::styled(
+            format!(
+                "{:>7} {:<12} {:>5} {:>9} {:>4} {:>4}{}",
+                "PID",
+                "NAME",
+                "GPU%",
+                "MEM",
+                "ENC",
+                "DEC",
+                if several { "  GPU" } else { "" }
+            ),
+            label_style,
+        )));
+        // The inspected process is listed even when it is not among the busiest
+        let shown: Vec<_> = users
+            .iter()
+            .enumerate()
+            .filter(|(index, (process, _))| *index < MAX_ROWS || process.pid == pid)
+            .map(|(_, user)| user)
+            .collect();
+        for (process, usage) in &shown {
+            let style = if process.pid == pid {
+                Style::default()
+                    .fg(self.theme.get_color(Color::White))
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                value_style
+            };
+            let utilization = usage
+                .utilization
+                .map_or("-".to_string(), |percent| format!("{}%", percent));
+            let memory = usage.memory.map_or("-".to_string(), crate::format::bytes);
+            lines.push(Line::from(Span::styled(
+                format!(
+                    "{:>7} {:<12} {:>5} {:>9} {:>3}% {:>3}%{}",
+                    process.pid,
+                    Self::truncate(&process.name, 12),
+                    utilization,
+                    memory,
+                    usage.encoder,
+                    usage.decoder,
+                    if several {
+                        format!("  {}", usage.gpu)
+                    } else {
+                        String::new()
+                    }
+                ),
+                style,
+            )));
+        }
+        if users.len() > shown.len() {
+            lines.push(Line::from(Span::styled(
+                format!(
+                    "   {}",
+                    tr_args("more-count", &[("count", &(users.len() - shown.len()))])
+                ),
+                Style::default().fg(self.theme.get_color(Color::DarkGray)),
+            )));
+        }
+        lines
+    }
+
+    /// `files /home 12 (3 w) · / 4`: where the process has files open, writers highlighted
+    fn open_files_line(&self, pid: u32) -> Option<Line<'static>> {
+        let files = self
+            .snapshot
+            .inspected_files
+            .as_ref()
+            .filter(|files| files.pid == pid)?;
+        let label_style = Style::default().fg(self.theme.get_color(Color::Cyan));
+        let value_style = Style::default().fg(self.theme.get_color(Color::Gray));
+
+        let mut spans = vec![Span::styled(
+            format!("{} ", tr("detail-files")),
+            label_style,
+        )];
+        if !files.accessible {
+            spans.push(Span::styled(tr("detail-files-denied"), value_style));
+        } else if files.mounts.is_empty() {
+            spans.push(Span::styled(tr("detail-files-none"), value_style));
+        }
+        for (i, mount) in files.mounts.iter().enumerate() {
+            if i > 0 {
+                spans.push(Span::styled(" · ", value_style));
+            }
+            spans.push(Span::styled(
+                format!("{} {}", mount.mount, mount.open),
+                value_style,
+            ));
+            if mount.writable > 0 {
+                spans.push(Span::styled(
+                    format!(" ({} w)", mount.writable),
+                    Style::default().fg(self.theme.get_color(Color::LightRed)),
+                ));
+            }
+        }
+        Some(Line::from(spans))
+    }
+
+    /// PID shown in the detail view, when it is open
+    pub fn details_pid(&self) -> Option<u32> {
+        self.detail_pid
+    }
+
+    pub fn is_detail_view_open(&self) -> bool {
+        self.detail_pid.is_some()
+    }
+
+    /// Record the CPU% of the process in the detail view from a new collection
+    pub fn observe(&mut self, snapshot: &SystemSnapshot) {
+        const HISTORY_LEN: usize = 240;
+        self.rows_moved = true;
+        // A marked process that exited, or whose PID was reused, is no longer a target
+        self.marked.retain(|(pid, name)| {
+            snapshot
+                .processes
+                .iter()
+                .any(|p| p.pid == *pid && p.name == *name)
+        });
+        self.collapsed.retain(|pid, name| {
+            snapshot
+                .processes
+                .iter()
+                .any(|p| p.pid == *pid && p.name == *name)
+        });
+        let Some(pid) = self.detail_pid else {
+            return;
+        };
+        if let Some(process) = snapshot.processes.iter().find(|p| p.pid == pid) {
+            if self.detail_cpu_history.len() >= HISTORY_LEN {
+                self.detail_cpu_history.pop_front();
+            }
+            self.detail_cpu_history.push_back(process.cpu_usage);
+        }
+    }
+
+    fn open_detail_view(&mut self) {
+        let Some(row) = self.selected_row() else {
+            return;
+        };
+        // Start from the samples the collector already kept, so the graph is not empty
+        self.detail_cpu_history = row.process.cpu_history.iter().copied().collect();
+        if self.detail_cpu_history.is_empty() {
+            self.detail_cpu_history.push_back(row.process.cpu_usage);
+        }
+        self.detail_pid = Some(row.pid);
+    }
+
+    /// PID and name of the highlighted process
+    pub fn selected_process(&self) -> Option<(u32, String)> {
+        self.selected_row().map(|row| (row.pid, row.name))
+    }
+
+    /// PID and command line of the highlighted process, as copied to the clipboard; just the
+    /// name in privacy mode
+    pub fn selected_summary(&self) -> Option<String> {
+        let process = self.selected_row()?.process;
+        let command = if self.privacy_mode || process.cmd.is_empty() {
+            process.name.clone()
+        } else {
+            process.cmd.join(" ")
+        };
+        Some(format!("{} {}", process.pid, command))
+    }
+
+    /// Highlight `pid` if it is in the list, e.g. after the list re-sorted under a menu
+    pub fn select_pid(&mut self, pid: u32) -> bool {
+        match self
+            .get_process_rows()
+            .iter()
+            .position(|row| row.pid == pid)
+        {
+            Some(index) => {
+                // Jumping to another process stops following
+                if self
+                    .followed
+                    .as_ref()
+                    .is_some_and(|(followed, _)| *followed != pid)
+                {
+                    self.followed = None;
+                }
+                self.selected_index = index;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Highlight the process drawn at `row` when the list is drawn in `area`
+    pub fn select_at(&mut self, area: Rect, column: u16, row: u16) -> bool {
+        if self.detail_pid.is_some() || self.search_mode || self.kill_dialog.is_some() {
+            return false;
+        }
+        let inner = Block::default().borders(Borders::ALL).inner(area);
+        let (_, list, _) = Self::list_areas(inner);
+        let top = list.y;
+        if row < top || row >= list.bottom() || column < list.x || column >= list.right() {
+            return false;
+        }
+        let count = self.get_process_rows().len();
+        let selected = self.selected_index.min(count.saturating_sub(1));
+        let index = super::window_start(selected, list.height as usize) + (row - top) as usize;
+        if index >= count {
+            return false;
+        }
+        self.selected_index = index;
+        self.followed = None;
+        true
+    }
+
+    /// Fold or unfold the tree branch under the selected process. Folding a leaf or an already
+    /// folded branch moves up to the parent instead, as file trees do.
+    fn set_branch_collapsed(&mut self, collapse: bool) {
+        let rows = self.get_process_rows();
+        let Some(row) = rows.get(self.selected_index) else {
+            return;
+        };
+        if !collapse {
+            self.collapsed.remove(&row.pid);
+            return;
+        }
+        let has_children = self
+            .snapshot
+            .processes
+            .iter()
+            .any(|process| process.parent_pid == Some(row.pid) && process.pid != row.pid);
+        if has_children && !self.collapsed.contains_key(&row.pid) {
+            self.collapsed.insert(row.pid, row.process.name.clone());
+        } else if let Some(parent) = row
+            .process
+            .parent_pid
+            .and_then(|parent| rows.iter().position(|other| other.pid == parent))
+        {
+            self.followed = None;
+            self.selected_index = parent;
+        }
+    }
+
+    /// Move the selection by a wheel notch
+    fn scroll(&mut self, down: bool) {
+        const SCROLL_ROWS: usize = 3;
+        if self.detail_pid.is_some() || self.search_mode || self.kill_dialog.is_some() {
+            return;
+        }
+        let last = self.get_process_rows().len().saturating_sub(1);
+        self.followed = None;
+        self.selected_index = if down {
+            (self.selected_index + SCROLL_ROWS).min(last)
+        } else {
+            self.selected_index.saturating_sub(SCROLL_ROWS)
+        };
+    }
+
+    /// The list needs at least this many lines before one is given to the CPU history graph
+    const CPU_HISTORY_MIN_HEIGHT: u16 = 14;
+    const CPU_HISTORY_ROWS: u16 = 2;
+
+    /// Header line, rows and CPU history strip of the list drawn in `inner`. The strip has no
+    /// height when the list is too short to spare it.
+    fn list_areas(inner: Rect) -> (Rect, Rect, Rect) {
+        let history_rows = if inner.height >= Self::CPU_HISTORY_MIN_HEIGHT {
+            Self::CPU_HISTORY_ROWS
+        } else {
+            0
+        };
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(1),
+                Constraint::Min(0),
+                Constraint::Length(history_rows),
+            ])
+            .split(inner);
+        (chunks[0], chunks[1], chunks[2])
+    }
+
+    /// Divisor that puts a CPU% on the list's scale: 1 in Irix mode, the core count otherwise
+    fn cpu_scale(&self) -> f32 {
+        if self.irix_mode {
+            1.0
+        } else {
+            self.snapshot.cpu_count.max(1) as f32
+        }
+    }
+
+    /// Current and peak CPU% of the selected process beside a graph of its recent samples, to
+    /// tell a sustained load from a momentary spike
+    fn render_cpu_history(&self, f: &mut Frame, area: Rect, process: &ProcessInfo) {
+        if area.height == 0 {
+            return;
+        }
+        let scale = self.cpu_scale();
+        let history: Vec<f32> = process.cpu_history.iter().map(|cpu| cpu / scale).collect();
+        let peak = history.iter().copied().fold(0.0, f32::max);
+        let label_style = Style::default().fg(self.theme.get_color(Color::DarkGray));
+        let value_style = Style::default().fg(self.theme.get_color(Color::Gray));
+        let chunks = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Length(14), Constraint::Min(0)])
+            .split(area);
+        f.render_widget(
+            Paragraph::new(vec![
+                Line::from(vec![
+                    Span::styled(" cpu  ", label_style),
+                    Span::styled(
+                        format!(
+                            "{}%",
+                            crate::format::decimal(f64::from(process.cpu_usage / scale), 1)
+                        ),
+                        value_style,
+                    ),
+                ]),
+                Line::from(vec![
+                    Span::styled(" peak ", label_style),
+                    Span::styled(
+                        format!("{}%", crate::format::decimal(f64::from(peak), 1)),
+                        value_style,
+                    ),
+                ]),
+            ]),
+            chunks[0],
+        );
+        // Tenths of a percent, scaled to at least a full core so idle noise stays flat
+        let data: Vec<u64> = history
+            .iter()
+            .map(|cpu| (cpu * 10.0).round() as u64)
+            .collect();
+        f.render_widget(
+            BrailleGraph::new(&data)
+                .style(Style::default().fg(self.theme.get_color(Color::LightGreen)))
+                .value_range(0.0, f64::from(peak.max(100.0) * 10.0))
+                .show_baseline(true)
+                .fill(false),
+            chunks[1],
+        );
+    }
+
+    /// Start or stop following the selected process
+    fn toggle_follow(&mut self) {
+        if let Some((pid, name)) = self.followed.take() {
+            self.status_message = Some(format!("Stopped following {} ({})", name, pid));
+        } else if let Some((pid, name)) = self.selected_process() {
+            self.status_message = Some(format!("Following {} ({})", name, pid));
+            self.followed = Some((pid, name));
+        }
+    }
+
+    /// Move the selection to the followed process wherever the latest sort put it. Following
+    /// ends when the process exits; while a filter hides it the selection stays put.
+    pub fn follow_selection(&mut self) {
+        if !std::mem::take(&mut self.rows_moved) {
+            return;
+        }
+        let Some((pid, name)) = self.followed.clone() else {
+            return;
+        };
+        let alive = self
+            .snapshot
+            .processes
+            .iter()
+            .any(|process| process.pid == pid && process.name == name);
+        if !alive {
+            self.followed = None;
+            self.status_message = Some(format!("{} ({}) exited", name, pid));
+            return;
+        }
+        self.select_pid(pid);
+    }
+
+    /// The sort order changed outside the component, so the followed process may have moved
+    pub fn resorted(&mut self) {
+        self.rows_moved = true;
+    }
+
+    /// Show a one-off message in the header, like the result of a kill
+    pub fn set_status(&mut self, message: String) {
+        self.status_message = Some(message);
+    }
+
+    fn clamp_selected_index(&mut self) {
+        let count = self.get_process_rows().len();
+        if count == 0 {
+            self.selected_index = 0;
+        } else if self.selected_index >= count {
+            self.selected_index = count - 1;
+        }
+    }
+
+    /// Program and arguments that prefix `kill` for an elevated retry, if one is configured
+    fn elevation_prefix(&self) -> Option<Vec<&'static str>> {
+        match self.elevate_with.trim() {
+            "pkexec" => Some(vec!["pkexec"]),
+            // -n: fail instead of prompting for a password inside the TUI
+            "sudo" => Some(vec!["sudo", "-n"]),
+            _ => None,
+        }
+    }
+
+    /// `systemctl restart` the unit, so systemd stops and starts the whole service cleanly.
+    /// A restart waits for the service to stop and start again, so it runs on its own thread
+    /// and reports through `poll_unit_restart`.
+    fn restart_unit(&mut self, unit: &SystemdUnit) {
+        self.elevation_offer = None;
+        let mut command = Command::new("systemctl");
+        if unit.user {
+            command.arg("--user");
+        }
+        // Fail instead of waiting on a polkit password prompt the TUI cannot show
+        command.args(["--no-ask-password", "restart", &unit.name]);
+        self.status_message = Some(format!("Restarting {}…", unit.name));
+
+        let (tx, rx) = mpsc::channel();
+        let name = unit.name.clone();
+        std::thread::spawn(move || {
+            let message = match command.output() {
+                Ok(output) if output.status.success() => format!("Restarted {}", name),
+                Ok(output) => {
+                    let stderr = String::from_utf8_lossy(&output.stderr);
+                    match stderr.lines().next().map(str::trim) {
+                        Some(message) if !message.is_empty() => {
+                            format!("Failed to restart {}: {}", name, message)
+                        }
+                        _ => format!("Failed to restart {}", name),
+                    }
+                }
+                Err(err) => format!("Failed to run systemctl: {}", err),
+            };
+            let _ = tx.send(message);
+        });
+        self.unit_restart = Some(rx);
+    }
+
+    /// Show the outcome of a unit restart once it has finished
+    pub fn poll_unit_restart(&mut self) {
+        let Some(rx) = &self.unit_restart else {
+            return;
+        };
+        match rx.try_recv() {
+            Ok(message) => {
+                self.status_message = Some(message);
+                self.unit_restart = None;
+            }
+            Err(mpsc::TryRecvError::Disconnected) => self.unit_restart = None,
+            Err(mpsc::TryRecvError::Empty) => {}
+        }
+    }
+
+    /// Buttons for the kill dialog's choices, the selected one highlighted
+    fn choice_line(&self, dialog: &KillDialog) -> Line<'static> {
+        let mut spans = vec![Span::styled(" ", self.theme.text_style())];
+        for choice in dialog.choices() {
+            let (label, color) = match choice {
+                KillChoice::RestartUnit => (tr("unit-restart"), Color::Green),
+                KillChoice::Terminate if dialog.protected || dialog.unit.is_some() => {
+                    (tr("unit-terminate"), Color::Red)
+                }
+                KillChoice::Terminate => (tr("kill-yes"), Color::Green),
+                KillChoice::Cancel => (tr("kill-no"), Color::Red),
+            };
+            let style = if choice == dialog.choice {
+                Style::default()
+                    .fg(self.theme.get_color(Color::Black))
+                    .bg(self.theme.get_color(color))
+                    .add_modifier(ratatui::style::Modifier::BOLD)
+            } else {
+                Style::default().fg(self.theme.get_color(Color::Gray))
+            };
+            spans.push(Span::styled(format!(" {} ", label), style));
+            spans.push(Span::styled("   ", self.theme.text_style()));
+        }
+        spans.pop();
+        Line::from(spans)
+    }
+
+    /// The signal picker row, the selected signal highlighted
+    fn signal_line(&self, dialog: &KillDialog) -> Line<'static> {
+        let mut spans = vec![Span::styled(
+            format!(" {} ", tr("signal-label")),
+            Style::default().fg(self.theme.get_color(Color::White)),
+        )];
+        for signal in Signal::ALL {
+            let style = if signal == dialog.signal {
+                Style::default()
+                    .fg(self.theme.get_color(Color::Black))
+                    .bg(self.theme.get_color(Color::Yellow))
+                    .add_modifier(ratatui::style::Modifier::BOLD)
+            } else {
+                Style::default().fg(self.theme.get_color(Color::Gray))
+            };
+            spans.push(Span::styled(format!(" {} ", signal.name()), style));
+        }
+        Line::from(spans)
+    }
+
+    /// Move the nice value of `pid` by `delta`, reporting the outcome in the status message
+    fn renice(&mut self, pid: u32, name: &str, delta: i32) {
+        let Some(current) = self
+            .snapshot
+            .processes
+            .iter()
+            .find(|process| process.pid == pid)
+            .map(|process| process.nice)
+        else {
+            return;
+        };
+        let nice = (current + delta).clamp(-20, 19);
+        if nice == current {
+            self.status_message = Some(format!("{} ({}) is already at nice {}", name, pid, nice));
+            return;
+        }
+        self.status_message = Some(match set_nice(pid, nice) {
+            Ok(()) => format!("{} ({}) reniced from {} to {}", name, pid, current, nice),
+            Err(err) if err.kind() == std::io::ErrorKind::PermissionDenied && delta < 0 => {
+                format!(
+                    "Not permitted to raise the priority of {} ({}): lowering nice needs root",
+                    name, pid
+                )
+            }
+            Err(err) if err.kind() == std::io::ErrorKind::PermissionDenied => format!(
+                "Not permitted to renice {} ({}): owned by another user",
+                name, pid
+            ),
+            Err(err) => format!("Failed to renice {} ({}): {}", name, pid, err),
+        });
+    }
+
+    /// Pin `pid` to the core it last ran on, or with `pin` false free it to run anywhere
+    fn pin(&mut self, pid: u32, name: &str, pin: bool) {
+        let core = self
+            .snapshot
+            .processes
+            .iter()
+            .find(|process| process.pid == pid)
+            .and_then(|process| process.last_cpu);
+        let core = match (pin, core) {
+            (true, None) => {
+                self.status_message = Some(format!(
+                    "{} ({}) has no recorded core to pin it to",
+                    name, pid
+                ));
+                return;
+            }
+            (true, core) => core,
+            (false, _) => None,
+        };
+        self.status_message = Some(match (set_affinity(pid, core), core) {
+            (Ok(()), Some(core)) => format!("{} ({}) pinned to core {}", name, pid, core),
+            (Ok(()), None) => format!("{} ({}) may run on every core", name, pid),
+            (Err(err), _) if err.kind() == std::io::ErrorKind::PermissionDenied => format!(
+                "Not permitted to change the affinity of {} ({}): owned by another user",
+                name, pid
+            ),
+            (Err(err), _) => format!("Failed to set the affinity of {} ({}): {}", name, pid, err),
+        });
+    }
+
+    fn kill_process(&mut self, pid: u32, name: &str, signal: Signal) {
+        self.send_signal(pid, name, signal);
+    }
+
+    /// Signal every process in `targets`, summarizing the result in one status message
+    fn kill_batch(&mut self, targets: &[(u32, String)], signal: Signal) {
+        let mut sent = 0;
+        let mut last_failure = None;
+        for (pid, name) in targets {
+            if self.send_signal(*pid, name, signal) {
+                sent += 1;
+            } else {
+                last_failure = self.status_message.take();
+            }
+        }
+        // An elevated retry covers one process, which would be misleading after a batch
+        self.elevation_offer = None;
+        self.status_message = Some(match last_failure {
+            None => format!("SIG{} sent to {} processes", signal.name(), sent),
+            Some(failure) => format!(
+                "SIG{} sent to {} of {} processes · {}",
+                signal.name(),
+                sent,
+                targets.len(),
+                failure
+            ),
+        });
+    }
+
+    /// `kill` run through `prefix` (e.g. `sudo -n`): the signal syscall itself cannot be
+    /// elevated
+    fn elevated_kill(pid: u32, signal: Signal, prefix: &[&str]) -> Result<(), KillFailure> {
+        let mut command = Command::new(prefix[0]);
+        command
+            .args(&prefix[1..])
+            .args(["kill", &format!("-{}", signal.name()), &pid.to_string()])
+            // Untranslated messages, so the failure can be classified in any locale
+            .env("LC_ALL", "C");
+        #[cfg(unix)]
+        {
+            use std::os::unix::process::CommandExt;
+            // A new session has no controlling terminal, so pkexec cannot fall back to its text
+            // agent and draw over the TUI; without a graphical agent it fails instead
+            // SAFETY: setsid is async-signal-safe and touches no memory of the parent
+            unsafe {
+                command.pre_exec(|| {
+                    if libc::setsid() == -1 {
+                        Err(std::io::Error::last_os_error())
+                    } else {
+                        Ok(())
+                    }
+                });
+            }
+        }
+        let output = command
+            .output()
+            .map_err(|err| KillFailure::Other(format!("could not run {}: {}", prefix[0], err)))?;
+        if output.status.success() {
+            return Ok(());
+        }
+        // sudo -n reports a needed password on stderr rather than EPERM
+        match KillFailure::from_exit(
+            prefix[0],
+            output.status.code(),
+            &String::from_utf8_lossy(&output.stderr),
+        ) {
+            KillFailure::Other(message) if message.is_empty() => Err(KillFailure::Other(format!(
+                "{} exited with {}",
+                prefix[0], output.status
+            ))),
+            failure => Err(failure),
+        }
+    }
+
+    /// Send `signal` to `pid`, reporting the outcome in the status message; true if it was sent
+    fn send_signal(&mut self, pid: u32, name: &str, signal: Signal) -> bool {
+        self.elevation_offer = None;
+        let sent = crate::signal::send(pid, signal).map_err(|err| KillFailure::from_io_error(&err));
+        self.report_signal(pid, name, signal, None, sent)
+    }
+
+    /// Retry a refused signal through `prefix`. The helper may wait on a password prompt, so it
+    /// runs on its own thread and reports through `poll_elevated_kill`.
+    fn send_signal_elevated(
+        &mut self,
+        pid: u32,
+        name: String,
+        signal: Signal,
+        prefix: Vec<&'static str>,
+    ) {
+        self.elevation_offer = None;
+        self.status_message = Some(format!(
+            "Asking {} to send SIG{} to {} ({})…",
+            prefix[0],
+            signal.name(),
+            name,
+            pid
+        ));
+        let (tx, rx) = mpsc::channel();
+        std::thread::spawn(move || {
+            let result = Self::elevated_kill(pid, signal, &prefix);
+            let _ = tx.send(ElevatedKill {
+                pid,
+                name,
+                signal,
+                program: prefix[0],
+                result,
+            });
+        });
+        self.elevated_kill = Some(rx);
+    }
+
+    /// Show the outcome of an elevated kill once the helper has exited
+    pub fn poll_elevated_kill(&mut self) {
+        let Some(rx) = &self.elevated_kill else {
+            return;
+        };
+        match rx.try_recv() {
+            Ok(kill) => {
+                self.elevated_kill = None;
+                self.report_signal(
+                    kill.pid,
+                    &kill.name,
+                    kill.signal,
+                    Some(kill.program),
+                    kill.result,
+                );
+            }
+            Err(mpsc::TryRecvError::Disconnected) => self.elevated_kill = None,
+            Err(mpsc::TryRecvError::Empty) => {}
         }
     }
 
-    fn kill_process(&mut self, pid: u32, name: &str) {
-        match Command::new("kill")
-            .arg("-TERM")
-            .arg(pid.to_string())
-            .status()
-        {
-            Ok(status) if status.success() => {
-                self.status_message = Some(format!("SIGTERM sent to {} ({})", name, pid));
-            }
-            Ok(status) => {
+    /// Put the outcome of a signal, sent directly or through the `elevated_with` helper, in the
+    /// status message; true if it was sent
+    fn report_signal(
+        &mut self,
+        pid: u32,
+        name: &str,
+        signal: Signal,
+        elevated_with: Option<&str>,
+        sent: Result<(), KillFailure>,
+    ) -> bool {
+        let failure = match sent {
+            Ok(()) => {
                 self.status_message =
-                    Some(format!("Failed to terminate PID {} (exit {})", pid, status));
+                    Some(format!("SIG{} sent to {} ({})", signal.name(), name, pid));
+                return true;
             }
-            Err(err) => {
-                self.status_message = Some(format!("Failed to run kill for PID {}: {}", pid, err));
+            Err(failure) => failure,
+        };
+
+        self.status_message = Some(match (failure, elevated_with) {
+            (KillFailure::NotPermitted, Some(program)) => {
+                format!("{} could not signal {} ({})", program, name, pid)
             }
-        }
+            (KillFailure::NotPermitted, None) => match self.elevation_prefix() {
+                Some(elevate) => {
+                    self.elevation_offer = Some((pid, name.to_string(), signal));
+                    format!(
+                        "Not permitted to signal {} ({}) · [e] retry with {}",
+                        name,
+                        pid,
+                        elevate.join(" ")
+                    )
+                }
+                None => format!(
+                    "Not permitted to signal {} ({}): owned by another user (set elevate_with to retry)",
+                    name, pid
+                ),
+            },
+            (KillFailure::NoSuchProcess, _) => format!("{} ({}) already exited", name, pid),
+            (KillFailure::Other(message), _) => {
+                format!("Failed to signal PID {}: {}", pid, message)
+            }
+        });
+        false
     }
 
-    fn bytes_to_human_readable(bytes: u64) -> String {
-        const UNITS: [&str; 6] = ["B", "KB", "MB", "GB", "TB", "PB"];
-        let mut size = bytes as f64;
-        let mut unit_idx = 0;
-
-        while size >= 1024.0 && unit_idx < UNITS.len() - 1 {
-            size /= 1024.0;
-            unit_idx += 1;
+    /// The processes a batch kill goes to, as dialog lines
+    fn batch_lines(&self, dialog: &KillDialog) -> Vec<Line<'static>> {
+        const MAX_LISTED: usize = 8;
+        let style = Style::default().fg(self.theme.get_color(Color::Gray));
+        let mut lines: Vec<Line> = dialog
+            .batch
+            .iter()
+            .take(MAX_LISTED)
+            .map(|(pid, name)| Line::from(Span::styled(format!("  {:>7}  {}", pid, name), style)))
+            .collect();
+        if dialog.batch.len() > MAX_LISTED {
+            lines.push(Line::from(Span::styled(
+                format!(
+                    "  {}",
+                    tr_args(
+                        "more-count",
+                        &[("count", &(dialog.batch.len() - MAX_LISTED))]
+                    )
+                ),
+                style,
+            )));
         }
-
-        format!("{:.1}{}", size, UNITS[unit_idx])
+        lines
     }
 
     fn centered_rect(percent_x: u16, percent_y: u16, area: Rect) -> Rect {
@@ -294,198 +2342,454 @@ impl ProcessComponent {
 impl ProcessComponent {
     pub fn render_in_area(&self, f: &mut Frame, area: ratatui::layout::Rect) {
         let sort_label = match self.snapshot.process_sort_by {
-            crate::data::snapshot::ProcessSortBy::CpuUsage => "CPU",
-            crate::data::snapshot::ProcessSortBy::Memory => "MEM",
-            crate::data::snapshot::ProcessSortBy::Pid => "PID",
-            crate::data::snapshot::ProcessSortBy::Name => "NAME",
+            ProcessSortBy::CpuUsage if self.cumulative_cpu => "AVG CPU",
+            ProcessSortBy::CpuUsage => "CPU",
+            ProcessSortBy::Memory => "MEM",
+            ProcessSortBy::Pid => "PID",
+            ProcessSortBy::Name => "NAME",
+            ProcessSortBy::DiskIo => "IO",
+            ProcessSortBy::StartTime => "START",
+        };
+        // Names sort A to Z; every other key starts with the largest or newest
+        let ascending = (self.snapshot.process_sort_by == ProcessSortBy::Name)
+            != self.snapshot.process_sort_reversed;
+        let sort_arrow = if ascending { "▲" } else { "▼" };
+        let mode_label = match (self.leak_view, self.show_tree, self.subtree_sort) {
+            _ if self.detail_pid.is_some() => "detail",
+            (true, _, _) => "suspected leaks",
+            (false, true, true) => "tree Σ",
+            (false, true, false) => "tree",
+            (false, false, _) => "list",
         };
-        let mode_label = if self.show_tree { "tree" } else { "list" };
 
-        let filter_suffix = if self.filter_query.trim().is_empty() {
+        let mut filter_suffix = if self.filter_query.trim().is_empty() {
             String::new()
         } else {
-            format!(" · filter:{}", self.filter_query)
+            let kind = match self.match_mode {
+                MatchMode::Substring => "filter",
+                mode => mode.label(),
+            };
+            format!(" · {}:{}", kind, self.filter_query)
         };
+        match self.origin {
+            ProcessOrigin::All => {}
+            ProcessOrigin::Host => filter_suffix.push_str(" · host"),
+            ProcessOrigin::Containers => filter_suffix.push_str(" · containers"),
+        }
+        match self.owner {
+            ProcessOwner::All => {}
+            ProcessOwner::Mine => filter_suffix.push_str(" · mine"),
+            ProcessOwner::Root => filter_suffix.push_str(" · root"),
+        }
+        if let Some(category) = self.category {
+            filter_suffix.push_str(&format!(" · {}", category.label()));
+        }
+        if self.privacy_mode {
+            filter_suffix.push_str(" · private");
+        }
+        if !self.marked.is_empty() {
+            filter_suffix.push_str(&format!(" · {} marked", self.marked.len()));
+        }
+        if let Some((pid, _)) = &self.followed {
+            filter_suffix.push_str(&format!(" · follow:{}", pid));
+        }
+        let cpu_scale = if self.irix_mode { "irix" } else { "solaris" };
         let title = format!(
-            " Processes · {} · sort:{}{} ",
-            mode_label, sort_label, filter_suffix
+            " Processes · {} · sort:{}{} · cpu:{}{} ",
+            mode_label, sort_label, sort_arrow, cpu_scale, filter_suffix
         );
 
-        let rows = self.get_process_rows();
-
-        let block = Block::default()
-            .title(Span::styled(
-                title,
-                Style::default()
-                    .fg(self.theme.get_color(Color::LightGreen))
-                    .add_modifier(ratatui::style::Modifier::BOLD),
-            ))
+        let mut block = Block::default().title(Span::styled(
+            title,
+            Style::default()
+                .fg(self.theme.get_color(Color::LightGreen))
+                .add_modifier(ratatui::style::Modifier::BOLD),
+        ));
+        if self.hide_idle {
+            block = block.title_bottom(Span::styled(
+                format!(" {} idle hidden · h shows all ", self.hidden_idle_count()),
+                Style::default().fg(self.theme.get_color(Color::Gray)),
+            ));
+        }
+        let block = block
             .borders(Borders::ALL)
             .border_type(BorderType::Rounded)
             .border_style(Style::default().fg(self.theme.get_color(Color::DarkGray)));
         f.render_widget(&block, area);
 
         let inner = block.inner(area);
-        let body_chunks = Layout::default()
-            .direction(Direction::Vertical)
-            .constraints([Constraint::Length(1), Constraint::Min(0)])
-            .split(inner);
+        match self.detail_pid {
+            Some(pid) => self.render_detail_view(f, inner, pid),
+            None => self.render_list(f, inner),
+        }
+
+        if let Some(dialog) = self.kill_dialog.as_ref().filter(|dialog| dialog.protected) {
+            self.render_protected_kill_dialog(f, area, dialog);
+        } else if let Some(dialog) = &self.kill_dialog {
+            let height = if dialog.batch.is_empty() { 36 } else { 60 };
+            let popup_area = Self::centered_rect(66, height, area);
+            f.render_widget(Clear, popup_area);
+
+            let question = match &dialog.unit {
+                _ if !dialog.batch.is_empty() => {
+                    tr_args("batch-confirm", &[("count", &dialog.batch.len())])
+                }
+                Some(unit) => tr_args(
+                    "unit-confirm",
+                    &[
+                        ("name", &dialog.name),
+                        ("pid", &dialog.pid),
+                        ("unit", &unit.name),
+                    ],
+                ),
+                None if dialog.signal == Signal::Term => tr_args(
+                    "kill-confirm",
+                    &[("name", &dialog.name), ("pid", &dialog.pid)],
+                ),
+                None => tr_args(
+                    "signal-confirm",
+                    &[
+                        ("signal", &dialog.signal.name()),
+                        ("name", &dialog.name),
+                        ("pid", &dialog.pid),
+                    ],
+                ),
+            };
+            let mut lines = vec![Line::from(Span::styled(
+                question,
+                Style::default().fg(self.theme.get_color(Color::White)),
+            ))];
+            lines.extend(self.batch_lines(dialog));
+            lines.extend([
+                self.choice_line(dialog),
+                self.signal_line(dialog),
+                Line::from(Span::styled(
+                    tr("signal-keys"),
+                    Style::default().fg(self.theme.get_color(Color::DarkGray)),
+                )),
+            ]);
+
+            let dialog_widget = Paragraph::new(lines)
+                .block(
+                    Block::default()
+                        .title(Span::styled(
+                            format!(" {} ", confirm_title(dialog.signal)),
+                            Style::default()
+                                .fg(self.theme.get_color(Color::LightRed))
+                                .add_modifier(ratatui::style::Modifier::BOLD),
+                        ))
+                        .borders(Borders::ALL)
+                        .border_type(BorderType::Rounded)
+                        .border_style(Style::default().fg(self.theme.get_color(Color::DarkGray))),
+                )
+                .wrap(Wrap { trim: true });
+
+            f.render_widget(dialog_widget, popup_area);
+        }
+    }
+
+    /// Column header, status line and the visible window of rows
+    fn render_list(&self, f: &mut Frame, inner: Rect) {
+        let rows = self.get_process_rows();
+        let (header_area, list_area, history_area) = Self::list_areas(inner);
 
-        let visible_rows = body_chunks[1].height as usize;
+        let visible_rows = list_area.height as usize;
         let selected = if rows.is_empty() {
             0
         } else {
             self.selected_index.min(rows.len() - 1)
         };
-        let window_start = if visible_rows > 0 && selected >= visible_rows {
-            selected + 1 - visible_rows
-        } else {
-            0
-        };
+        let window_start = super::window_start(selected, visible_rows);
         let window_end = if visible_rows == 0 {
             0
         } else {
             (window_start + visible_rows).min(rows.len())
         };
 
-        let mut header_line = if self.search_mode {
-            format!(
-                " search: {}_  [enter] apply [esc] cancel",
-                self.search_input
-            )
-        } else if self.show_tree {
-            format!(
-                " tree entries: {}  selected: {}/{}",
-                rows.len(),
-                if rows.is_empty() { 0 } else { selected + 1 },
-                rows.len()
-            )
+        let width = list_area.width as usize;
+        let columns = self.columns_for_width(width);
+        let widths: Vec<Constraint> = columns
+            .iter()
+            .map(|column| Constraint::Length(column.width() as u16))
+            .chain([Constraint::Min(0)])
+            .collect();
+
+        if self.search_mode {
+            let yellow = Style::default().fg(self.theme.get_color(Color::Yellow));
+            let mut spans = vec![Span::styled(
+                format!(
+                    " search ({}): {}_",
+                    self.match_mode.label(),
+                    self.search_input
+                ),
+                yellow,
+            )];
+            if let Some(error) = self.filter_error() {
+                spans.push(Span::styled(
+                    format!("  {}", error),
+                    Style::default().fg(self.theme.get_color(Color::LightRed)),
+                ));
+            }
+            spans.push(Span::styled(
+                format!(
+                    "  [tab] {} [enter] apply [esc] cancel",
+                    self.match_mode.next().label()
+                ),
+                yellow,
+            ));
+            let header = Paragraph::new(Line::from(spans)).wrap(Wrap { trim: true });
+            f.render_widget(header, header_area);
         } else {
-            format!(
-                " {:>7} {:>8} {:>6} {:>9}  {}   [{}/{}]",
-                "PID",
-                "MEM",
-                "CPU%",
-                "WRITE",
-                "NAME",
+            let mut status = format!(
+                "   [{}/{}]",
                 if rows.is_empty() { 0 } else { selected + 1 },
                 rows.len()
-            )
-        };
-
-        if let Some(msg) = &self.status_message {
-            header_line.push_str("  ·  ");
-            header_line.push_str(msg);
+            );
+            if let Some(msg) = &self.status_message {
+                status.push_str("  ·  ");
+                status.push_str(msg);
+            }
+            let header = Table::new(Vec::<Row>::new(), widths.clone())
+                .header(self.header_row(&columns, status))
+                .column_spacing(1);
+            f.render_widget(header, header_area);
         }
 
-        let header_style = if self.search_mode {
-            Style::default().fg(self.theme.get_color(Color::Yellow))
-        } else {
-            Style::default().fg(self.theme.get_color(Color::Cyan))
-        };
+        let gray = Style::default().fg(self.theme.get_color(Color::Gray));
+        if rows.is_empty() {
+            let empty = if self.leak_view {
+                "No suspected leaks: no process has only grown over the leak window"
+            } else {
+                "No processes"
+            };
+            f.render_widget(Paragraph::new(empty).style(gray), list_area);
+            return;
+        }
 
-        let header = Paragraph::new(Line::from(Span::styled(header_line, header_style)))
-            .wrap(Wrap { trim: true });
-        f.render_widget(header, body_chunks[0]);
+        let matcher = self.filter_matcher();
+        let table_rows: Vec<Row> = rows[window_start..window_end]
+            .iter()
+            .enumerate()
+            .map(|(idx, row)| {
+                let global_index = window_start + idx;
+                let marked = self.is_marked(row.pid);
+                let style = if global_index == selected {
+                    Style::default()
+                        .bg(self.theme.get_color(Color::Blue))
+                        .fg(self.theme.get_color(if marked {
+                            Color::LightYellow
+                        } else {
+                            Color::White
+                        }))
+                } else if marked {
+                    Style::default()
+                        .fg(self.theme.get_color(Color::LightYellow))
+                        .add_modifier(Modifier::BOLD)
+                } else if row.dimmed {
+                    Style::default().fg(self.theme.get_color(Color::DarkGray))
+                } else if row.realtime {
+                    Style::default()
+                        .fg(self.theme.get_color(Color::LightMagenta))
+                        .add_modifier(Modifier::BOLD)
+                } else if row.kernel_thread {
+                    Style::default().fg(self.theme.get_color(Color::LightBlue))
+                } else {
+                    gray
+                };
+                let mut texts = self.row_cells(row, &columns, width);
+                let name = texts.pop().unwrap_or_default();
+                let cpu_color = priority_color(&row.process).filter(|_| !row.dimmed);
+                let state_color = state_color(row.process.state).filter(|_| !row.dimmed);
+                let mut cells: Vec<Cell> = texts
+                    .into_iter()
+                    .zip(&columns)
+                    .map(|(text, column)| match (column, cpu_color, state_color) {
+                        (Column::Cpu, Some(color), _) => {
+                            Cell::from(text).style(Style::default().fg(self.theme.get_color(color)))
+                        }
+                        (Column::State, _, Some(color)) => Cell::from(text).style(
+                            Style::default()
+                                .fg(self.theme.get_color(color))
+                                .add_modifier(Modifier::BOLD),
+                        ),
+                        _ => Cell::from(text),
+                    })
+                    .collect();
+                cells.push(Cell::from(self.highlighted_name(
+                    row,
+                    name,
+                    matcher.as_ref(),
+                )));
+                Row::new(cells).style(style)
+            })
+            .collect();
+        f.render_widget(Table::new(table_rows, widths).column_spacing(1), list_area);
+        self.render_cpu_history(f, history_area, &rows[selected].process);
+    }
 
-        let process_items: Vec<ListItem> = if rows.is_empty() {
-            vec![ListItem::new("No processes")
-                .style(Style::default().fg(self.theme.get_color(Color::Gray)))]
-        } else {
-            rows[window_start..window_end]
-                .iter()
-                .enumerate()
-                .map(|(idx, row)| {
-                    let global_index = window_start + idx;
-                    let style = if global_index == selected {
-                        Style::default()
-                            .bg(self.theme.get_color(Color::Blue))
-                            .fg(self.theme.get_color(Color::White))
-                    } else {
-                        Style::default().fg(self.theme.get_color(Color::Gray))
-                    };
-                    ListItem::new(row.line.clone()).style(style)
-                })
-                .collect()
+    /// Column titles, the one the list is sorted by highlighted, then the position and status
+    fn header_row(&self, columns: &[Column], status: String) -> Row<'static> {
+        let sort_by = self.snapshot.process_sort_by;
+        let title_style = |sorted: bool| {
+            let style = Style::default().fg(self.theme.get_color(Color::Cyan));
+            if sorted {
+                style
+                    .fg(self.theme.get_color(Color::Yellow))
+                    .add_modifier(Modifier::BOLD | Modifier::UNDERLINED)
+            } else {
+                style
+            }
         };
-        let process_list = List::new(process_items);
-        f.render_widget(process_list, body_chunks[1]);
+        let titles = self.header_titles(columns);
+        let (name, titles) = titles.split_last().expect("NAME is always a column");
+        let mut cells: Vec<Cell> = titles
+            .iter()
+            .zip(columns)
+            .map(|(title, column)| {
+                Cell::from(Span::styled(
+                    title.clone(),
+                    title_style(column.sort_key() == Some(sort_by)),
+                ))
+            })
+            .collect();
+        cells.push(Cell::from(Line::from(vec![
+            Span::styled(name.clone(), title_style(sort_by == ProcessSortBy::Name)),
+            Span::styled(status, title_style(false)),
+        ])));
+        Row::new(cells)
+    }
 
-        if let Some(dialog) = &self.kill_dialog {
-            let popup_area = Self::centered_rect(66, 32, area);
-            f.render_widget(Clear, popup_area);
+    /// The sort key whose header is at (`column`, `row`) when the list is drawn in `area`, so
+    /// a click on a column title sorts by it
+    pub fn sort_at(&self, area: Rect, column: u16, row: u16) -> Option<ProcessSortBy> {
+        if self.detail_pid.is_some() || self.search_mode {
+            return None;
+        }
+        let inner = Block::default().borders(Borders::ALL).inner(area);
+        if row != inner.y || column < inner.x || column >= inner.right() {
+            return None;
+        }
+        let mut x = inner.x;
+        for table_column in self.columns_for_width(inner.width as usize) {
+            let end = x + table_column.width() as u16;
+            if column < end {
+                return table_column.sort_key();
+            }
+            x = end + 1;
+        }
+        (column >= x).then_some(ProcessSortBy::Name)
+    }
 
-            let yes_style = if dialog.yes_selected {
-                Style::default()
-                    .fg(self.theme.get_color(Color::Black))
-                    .bg(self.theme.get_color(Color::Green))
-                    .add_modifier(ratatui::style::Modifier::BOLD)
-            } else {
-                Style::default().fg(self.theme.get_color(Color::Gray))
-            };
-            let no_style = if dialog.yes_selected {
-                Style::default().fg(self.theme.get_color(Color::Gray))
-            } else {
-                Style::default()
-                    .fg(self.theme.get_color(Color::Black))
-                    .bg(self.theme.get_color(Color::Red))
-                    .add_modifier(ratatui::style::Modifier::BOLD)
-            };
+    /// The kill dialog for a protected process: a stern warning and a typed "yes"
+    fn render_protected_kill_dialog(&self, f: &mut Frame, area: Rect, dialog: &KillDialog) {
+        let height = if dialog.batch.is_empty() { 40 } else { 64 };
+        let popup_area = Self::centered_rect(66, height, area);
+        f.render_widget(Clear, popup_area);
 
-            let lines = vec![
-                Line::from(Span::styled(
-                    format!("Terminate '{}' (PID {})?", dialog.name, dialog.pid),
+        let red = Style::default()
+            .fg(self.theme.get_color(Color::LightRed))
+            .add_modifier(ratatui::style::Modifier::BOLD);
+        let confirmed = dialog.typed.trim().eq_ignore_ascii_case("yes");
+        let mut lines = vec![
+            Line::from(Span::styled(
+                tr_args(
+                    "protected-warning",
+                    &[("name", &dialog.name), ("pid", &dialog.pid)],
+                ),
+                red,
+            )),
+            Line::from(Span::styled(
+                tr("protected-consequence"),
+                Style::default().fg(self.theme.get_color(Color::White)),
+            )),
+        ];
+        if !dialog.batch.is_empty() {
+            lines.push(Line::from(Span::styled(
+                tr_args("batch-protected", &[("count", &dialog.batch.len())]),
+                Style::default().fg(self.theme.get_color(Color::White)),
+            )));
+            lines.extend(self.batch_lines(dialog));
+        }
+        if let Some(unit) = &dialog.unit {
+            lines.push(Line::from(Span::styled(
+                tr_args("unit-choice", &[("unit", &unit.name)]),
+                Style::default().fg(self.theme.get_color(Color::White)),
+            )));
+            lines.push(self.choice_line(dialog));
+        }
+        lines.extend([self.signal_line(dialog), Line::from("")]);
+        lines.extend([
+            Line::from(vec![
+                Span::styled(
+                    format!("{} ", tr("protected-type-yes")),
                     Style::default().fg(self.theme.get_color(Color::White)),
-                )),
-                Line::from(vec![
-                    Span::styled(" ", self.theme.text_style()),
-                    Span::styled(" Yes ", yes_style),
-                    Span::styled("   ", self.theme.text_style()),
-                    Span::styled(" No ", no_style),
-                ]),
-                Line::from(Span::styled(
-                    "Enter: confirm  Esc: cancel",
-                    Style::default().fg(self.theme.get_color(Color::DarkGray)),
-                )),
-            ];
+                ),
+                Span::styled(
+                    format!("{}█", dialog.typed),
+                    Style::default().fg(self.theme.get_color(if confirmed {
+                        Color::LightRed
+                    } else {
+                        Color::Yellow
+                    })),
+                ),
+            ]),
+            Line::from(Span::styled(
+                tr("signal-keys"),
+                Style::default().fg(self.theme.get_color(Color::DarkGray)),
+            )),
+        ]);
 
-            let dialog_widget = Paragraph::new(lines)
-                .block(
-                    Block::default()
-                        .title(Span::styled(
-                            " Confirm Termination ",
-                            Style::default()
-                                .fg(self.theme.get_color(Color::LightRed))
-                                .add_modifier(ratatui::style::Modifier::BOLD),
-                        ))
-                        .borders(Borders::ALL)
-                        .border_type(BorderType::Rounded)
-                        .border_style(Style::default().fg(self.theme.get_color(Color::DarkGray))),
-                )
-                .wrap(Wrap { trim: true });
+        let dialog_widget = Paragraph::new(lines)
+            .block(
+                Block::default()
+                    .title(Span::styled(format!(" {} ", tr("protected-title")), red))
+                    .borders(Borders::ALL)
+                    .border_type(BorderType::Thick)
+                    .border_style(Style::default().fg(self.theme.get_color(Color::Red))),
+            )
+            .wrap(Wrap { trim: true });
 
-            f.render_widget(dialog_widget, popup_area);
-        }
+        f.render_widget(dialog_widget, popup_area);
     }
 }
 
 impl Component for ProcessComponent {
     fn handle_events(
         &mut self,
-        _event: crossterm::event::Event,
+        event: crossterm::event::Event,
     ) -> Result<Option<Action>, Box<dyn std::error::Error>> {
+        use crossterm::event::{Event, MouseButton, MouseEventKind};
+
+        let Event::Mouse(mouse) = event else {
+            return Ok(None);
+        };
+        if !self.area.contains(Position::new(mouse.column, mouse.row)) {
+            return Ok(None);
+        }
+        match mouse.kind {
+            MouseEventKind::ScrollUp => self.scroll(false),
+            MouseEventKind::ScrollDown => self.scroll(true),
+            MouseEventKind::Down(MouseButton::Left) => {
+                self.select_at(self.area, mouse.column, mouse.row);
+            }
+            _ => {}
+        }
         Ok(None)
     }
 
     fn update(&mut self, action: Action) -> Result<Option<Action>, Box<dyn std::error::Error>> {
+        // Tree, filter and view toggles reorder the rows too
+        self.rows_moved = true;
         match action {
             Action::MoveUp => {
                 if self.kill_dialog.is_none() && !self.search_mode && self.selected_index > 0 {
                     self.selected_index -= 1;
+                    self.followed = None;
                 }
             }
             Action::MoveDown => {
                 if self.kill_dialog.is_none() && !self.search_mode {
+                    self.followed = None;
                     let process_count = self.get_process_rows().len();
                     if process_count > 0 && self.selected_index < process_count - 1 {
                         self.selected_index += 1;
@@ -498,6 +2802,64 @@ impl Component for ProcessComponent {
                     self.clamp_selected_index();
                 }
             }
+            Action::CollapseProcessBranch(collapse)
+                if self.show_tree
+                    && !self.leak_view
+                    && self.detail_pid.is_none()
+                    && self.kill_dialog.is_none()
+                    && !self.search_mode =>
+            {
+                self.set_branch_collapsed(collapse);
+            }
+            Action::ToggleCumulativeCpu if self.kill_dialog.is_none() && !self.search_mode => {
+                self.cumulative_cpu = !self.cumulative_cpu;
+            }
+            Action::ToggleLeakView if self.kill_dialog.is_none() && !self.search_mode => {
+                self.leak_view = !self.leak_view;
+                self.clamp_selected_index();
+            }
+            Action::Enter if self.kill_dialog.is_none() && !self.search_mode => {
+                if self.detail_pid.is_some() {
+                    self.detail_pid = None;
+                } else {
+                    self.open_detail_view();
+                }
+            }
+            Action::Back if self.kill_dialog.is_none() => {
+                self.detail_pid = None;
+            }
+            Action::ToggleSubtreeSort if self.kill_dialog.is_none() && !self.search_mode => {
+                self.subtree_sort = !self.subtree_sort;
+            }
+            Action::ToggleFaultColumns if self.kill_dialog.is_none() && !self.search_mode => {
+                self.show_fault_columns = !self.show_fault_columns;
+            }
+            Action::ToggleFullCommandLine if self.kill_dialog.is_none() && !self.search_mode => {
+                self.show_full_cmd = !self.show_full_cmd;
+            }
+            Action::TogglePrivacyMode if self.kill_dialog.is_none() && !self.search_mode => {
+                self.privacy_mode = !self.privacy_mode;
+            }
+            Action::ToggleIrixMode if self.kill_dialog.is_none() && !self.search_mode => {
+                self.irix_mode = !self.irix_mode;
+                self.clamp_selected_index();
+            }
+            Action::ToggleHideIdle if self.kill_dialog.is_none() && !self.search_mode => {
+                self.hide_idle = !self.hide_idle;
+                self.clamp_selected_index();
+            }
+            Action::CycleProcessOrigin if self.kill_dialog.is_none() && !self.search_mode => {
+                self.origin = self.origin.next();
+                self.clamp_selected_index();
+            }
+            Action::CycleProcessOwner if self.kill_dialog.is_none() && !self.search_mode => {
+                self.owner = self.owner.next();
+                self.clamp_selected_index();
+            }
+            Action::CycleProcessCategory if self.kill_dialog.is_none() && !self.search_mode => {
+                self.category = ProcessCategory::cycle(self.category);
+                self.clamp_selected_index();
+            }
             Action::StartProcessSearch => {
                 if self.kill_dialog.is_none() {
                     self.search_prev_filter = self.filter_query.clone();
@@ -507,6 +2869,11 @@ impl Component for ProcessComponent {
                     self.clamp_selected_index();
                 }
             }
+            Action::CycleSearchMatchMode if self.search_mode => {
+                self.match_mode = self.match_mode.next();
+                self.selected_index = 0;
+                self.clamp_selected_index();
+            }
             Action::UpdateProcessSearch(c) => {
                 if self.search_mode {
                     self.search_input.push(c);
@@ -543,15 +2910,25 @@ impl Component for ProcessComponent {
                     self.clamp_selected_index();
                 }
             }
+            Action::ToggleProcessMark
+                if self.kill_dialog.is_none() && !self.search_mode && self.detail_pid.is_none() =>
+            {
+                self.toggle_mark();
+                self.followed = None;
+                self.selected_index += 1;
+            }
+            Action::ClearProcessMarks => {
+                self.marked.clear();
+            }
+            Action::ToggleFollowProcess if self.kill_dialog.is_none() && !self.search_mode => {
+                self.toggle_follow();
+            }
             Action::RequestProcessKill => {
                 if !self.search_mode {
-                    if let Some(row) = self.selected_row() {
-                        self.kill_dialog = Some(KillDialog {
-                            pid: row.pid,
-                            name: row.name,
-                            yes_selected: true,
-                        });
-                        self.status_message = None;
+                    if !self.marked.is_empty() {
+                        self.open_batch_kill_dialog();
+                    } else if let Some(row) = self.selected_row() {
+                        self.open_kill_dialog(row.pid, row.name);
                     } else {
                         self.status_message = Some("No process selected".to_string());
                     }
@@ -559,23 +2936,86 @@ impl Component for ProcessComponent {
             }
             Action::ToggleProcessKillChoice => {
                 if let Some(dialog) = self.kill_dialog.as_mut() {
-                    dialog.yes_selected = !dialog.yes_selected;
+                    let choices = dialog.choices();
+                    let at = choices
+                        .iter()
+                        .position(|choice| *choice == dialog.choice)
+                        .unwrap_or(0);
+                    dialog.choice = choices[(at + 1) % choices.len()];
+                }
+            }
+            Action::UpdateKillConfirmation(c) => {
+                if let Some(dialog) = self.kill_dialog.as_mut() {
+                    if dialog.typed.len() < 8 {
+                        dialog.typed.push(c);
+                    }
+                }
+            }
+            Action::BackspaceKillConfirmation => {
+                if let Some(dialog) = self.kill_dialog.as_mut() {
+                    dialog.typed.pop();
                 }
             }
             Action::ConfirmProcessKill => {
+                let unconfirmed = self.kill_dialog.as_ref().is_some_and(|dialog| {
+                    dialog.protected && !dialog.typed.trim().eq_ignore_ascii_case("yes")
+                });
+                if unconfirmed {
+                    return Ok(None);
+                }
                 if let Some(dialog) = self.kill_dialog.take() {
-                    if dialog.yes_selected {
-                        self.kill_process(dialog.pid, &dialog.name);
-                    } else {
-                        self.status_message = Some("Termination canceled".to_string());
+                    match (dialog.choice, &dialog.unit) {
+                        (KillChoice::RestartUnit, Some(unit)) => self.restart_unit(unit),
+                        (KillChoice::Cancel, _) => {
+                            self.status_message = Some("Termination canceled".to_string())
+                        }
+                        _ if !dialog.batch.is_empty() => {
+                            self.kill_batch(&dialog.batch, dialog.signal);
+                            self.marked.clear();
+                        }
+                        _ => self.kill_process(dialog.pid, &dialog.name, dialog.signal),
                     }
                 }
             }
+            Action::CycleKillSignal(forward) => {
+                if let Some(dialog) = self.kill_dialog.as_mut() {
+                    dialog.signal = dialog.signal.cycle(forward);
+                }
+            }
             Action::CancelProcessKill => {
                 if self.kill_dialog.take().is_some() {
                     self.status_message = Some("Termination canceled".to_string());
                 }
             }
+            Action::QuickKillProcess if self.kill_dialog.is_none() && !self.search_mode => {
+                match self.selected_row() {
+                    // Protected processes always get the dialog, even from the quick kill key
+                    Some(row) if self.is_protected(&row.name) => {
+                        self.open_kill_dialog(row.pid, row.name)
+                    }
+                    Some(row) => self.kill_process(row.pid, &row.name, Signal::Term),
+                    None => self.status_message = Some("No process selected".to_string()),
+                }
+            }
+            Action::ReniceProcess(delta) if self.kill_dialog.is_none() && !self.search_mode => {
+                match self.selected_row() {
+                    Some(row) => self.renice(row.pid, &row.name, delta),
+                    None => self.status_message = Some("No process selected".to_string()),
+                }
+            }
+            Action::PinProcess(pin) if self.kill_dialog.is_none() && !self.search_mode => {
+                match self.selected_row() {
+                    Some(row) => self.pin(row.pid, &row.name, pin),
+                    None => self.status_message = Some("No process selected".to_string()),
+                }
+            }
+            Action::RetryKillElevated => {
+                if let (Some((pid, name, signal)), Some(prefix)) =
+                    (self.elevation_offer.take(), self.elevation_prefix())
+                {
+                    self.send_signal_elevated(pid, name, signal, prefix);
+                }
+            }
             _ => {}
         }
 
@@ -586,4 +3026,232 @@ impl Component for ProcessComponent {
     fn render(&mut self, f: &mut Frame) {
         self.render_in_area(f, f.area());
     }
+
+    fn hints(&self) -> Vec<(&'static str, &'static str)> {
+        if self.elevation_offer.is_some() {
+            return vec![("e", "hint-retry-kill-elevated"), ("↑/↓", "hint-move")];
+        }
+        let mut hints = vec![
+            ("↑/↓", "hint-move"),
+            ("s", "hint-sort"),
+            ("S", "hint-search"),
+            ("k", "hint-kill"),
+            ("Space", "hint-mark"),
+            ("m", "hint-menu"),
+            ("T", "hint-tree"),
+            ("Enter", "hint-details"),
+        ];
+        if self.show_tree && !self.leak_view {
+            hints.insert(7, ("←/→", "hint-fold"));
+        }
+        if self.read_only {
+            hints.retain(|&(key, _)| key != "k" && key != "Space");
+        }
+        hints
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_set_affinity_frees_every_thread() {
+        // Allowing every core never narrows what the test process may use
+        assert!(set_affinity(std::process::id(), None).is_ok());
+        assert_eq!(
+            set_affinity(std::process::id(), Some(libc::CPU_SETSIZE as u32))
+                .unwrap_err()
+                .kind(),
+            std::io::ErrorKind::InvalidInput
+        );
+    }
+
+    #[test]
+    fn test_kill_dialog_choices() {
+        let mut dialog = KillDialog {
+            pid: 1,
+            name: "sshd".to_string(),
+            choice: KillChoice::RestartUnit,
+            unit: Some(SystemdUnit {
+                name: "sshd.service".to_string(),
+                user: false,
+            }),
+            signal: Signal::Term,
+            protected: false,
+            typed: String::new(),
+            batch: Vec::new(),
+        };
+        assert_eq!(
+            dialog.choices(),
+            vec![
+                KillChoice::RestartUnit,
+                KillChoice::Terminate,
+                KillChoice::Cancel
+            ]
+        );
+        dialog.protected = true;
+        assert_eq!(
+            dialog.choices(),
+            vec![KillChoice::RestartUnit, KillChoice::Terminate]
+        );
+        dialog.unit = None;
+        assert_eq!(dialog.choices(), vec![KillChoice::Terminate]);
+
+        assert_eq!(Signal::Term.cycle(true), Signal::Kill);
+        assert_eq!(Signal::Term.cycle(false), Signal::Int);
+        assert_eq!(Signal::Int.cycle(true), Signal::Term);
+    }
+
+    #[test]
+    fn test_process_categories() {
+        let category = |name: &str, exe: Option<&str>, parent: Option<u32>| {
+            let mut process = crate::data::mock::process(10, name, 0.0, 0);
+            process.exe = exe.map(str::to_string);
+            process.parent_pid = parent;
+            ProcessCategory::of(&process)
+        };
+        assert_eq!(
+            category("Web Content", None, None),
+            ProcessCategory::Browser
+        );
+        assert_eq!(
+            category("helper", Some("/opt/google/chrome/chrome"), None),
+            ProcessCategory::Browser
+        );
+        assert_eq!(category("-bash", None, None), ProcessCategory::Shell);
+        assert_eq!(category("sshd", None, Some(1)), ProcessCategory::Daemon);
+        assert_eq!(
+            category("polkit", Some("/usr/libexec/polkitd"), None),
+            ProcessCategory::Daemon
+        );
+        // Without init as its parent a trailing "d" means nothing
+        assert_eq!(category("sshd", None, Some(900)), ProcessCategory::Other);
+
+        let mut kthread = crate::data::mock::process(2, "kthreadd", 0.0, 0);
+        kthread.kernel_thread = true;
+        assert_eq!(ProcessCategory::of(&kthread), ProcessCategory::Kernel);
+        assert_eq!(ProcessCategory::cycle(Some(ProcessCategory::Other)), None);
+    }
+
+    #[test]
+    fn test_priority_color() {
+        let mut process = crate::data::mock::process(10, "make", 90.0, 0);
+        assert_eq!(priority_color(&process), None);
+        process.nice = 19;
+        assert_eq!(priority_color(&process), Some(Color::Green));
+        process.nice = -10;
+        assert_eq!(priority_color(&process), Some(Color::Yellow));
+        process.nice = 0;
+        process.sched_class = SchedClass::Idle;
+        assert_eq!(priority_color(&process), Some(Color::Green));
+        process.sched_class = SchedClass::Fifo;
+        assert_eq!(priority_color(&process), Some(Color::LightMagenta));
+    }
+
+    #[test]
+    fn test_state_column_flags_stuck_processes() {
+        let mut process = crate::data::mock::process(10, "nfs-reader", 0.0, 0);
+        process.state = ProcessState::from_status(sysinfo::ProcessStatus::UninterruptibleDiskSleep);
+        assert_eq!(Column::State.cell(&process), "D");
+        assert_eq!(state_color(process.state), Some(Color::Yellow));
+        process.state = ProcessState::from_status(sysinfo::ProcessStatus::Zombie);
+        assert_eq!(Column::State.cell(&process), "Z");
+        assert_eq!(state_color(process.state), Some(Color::LightRed));
+        process.state = ProcessState::from_status(sysinfo::ProcessStatus::Run);
+        assert_eq!(Column::State.cell(&process), "R");
+        assert_eq!(state_color(process.state), None);
+    }
+
+    #[test]
+    fn test_protected_patterns() {
+        assert!(matches_pattern("sshd", "sshd"));
+        assert!(matches_pattern("Xorg", "xorg"));
+        assert!(!matches_pattern("sshd", "sshd-session"));
+        assert!(matches_pattern("gdm*", "gdm-wayland-session"));
+        assert!(matches_pattern("kwin_*", "kwin_x11"));
+        assert!(matches_pattern("*shell", "gnome-shell"));
+        assert!(matches_pattern("a*b*c", "axxbyyc"));
+        assert!(!matches_pattern("a*b*c", "axxcyyb"));
+    }
+
+    #[test]
+    fn test_classify_kill_failures() {
+        assert_eq!(
+            KillFailure::from_stderr("kill: (1) - Operation not permitted\n"),
+            KillFailure::NotPermitted
+        );
+        assert_eq!(
+            KillFailure::from_stderr("kill: sending signal to 99999 failed: No such process\n"),
+            KillFailure::NoSuchProcess
+        );
+        assert_eq!(
+            KillFailure::from_stderr("sudo: a password is required\n"),
+            KillFailure::Other("sudo: a password is required".to_string())
+        );
+        assert_eq!(
+            KillFailure::from_exit(
+                "pkexec",
+                Some(127),
+                "Error executing command as another user: Not authorized\n"
+            ),
+            KillFailure::NotPermitted
+        );
+        assert!(matches!(
+            KillFailure::from_exit("pkexec", Some(126), ""),
+            KillFailure::Other(_)
+        ));
+        assert_eq!(
+            KillFailure::from_exit("sudo", Some(1), "kill: (1) - Operation not permitted\n"),
+            KillFailure::NotPermitted
+        );
+
+        #[cfg(unix)]
+        {
+            let err = std::io::Error::from_raw_os_error(libc::EPERM);
+            assert_eq!(KillFailure::from_io_error(&err), KillFailure::NotPermitted);
+            let err = std::io::Error::from_raw_os_error(libc::ESRCH);
+            assert_eq!(KillFailure::from_io_error(&err), KillFailure::NoSuchProcess);
+        }
+    }
+
+    #[test]
+    fn test_narrow_widths_drop_columns_before_truncating_name() {
+        let mut component = ProcessComponent::new(
+            SystemSnapshot::default(),
+            Theme::new(crate::data::snapshot::ColorScheme::Default),
+        );
+        component.show_fault_columns = true;
+        assert_eq!(component.columns_for_width(120).len(), 11);
+        assert_eq!(
+            component.columns_for_width(50),
+            vec![
+                Column::Pid,
+                Column::User,
+                Column::Mem,
+                Column::Cpu,
+                Column::State
+            ]
+        );
+        // PID and CPU% stay even when NAME cannot get its minimum
+        assert_eq!(
+            component.columns_for_width(10),
+            vec![Column::Pid, Column::Cpu]
+        );
+
+        let row = component.process_row(
+            &crate::data::mock::process(42, "a-very-long-process-name", 12.5, 0),
+            "",
+            false,
+        );
+        let columns = component.columns_for_width(30);
+        let line = component.row_cells(&row, &columns, 30).join(" ");
+        assert_eq!(line, "     42  12.50% a-very-long-p…");
+        assert_eq!(line.chars().count(), 30);
+        assert_eq!(
+            component.header_titles(&columns).join(" "),
+            "    PID    CPU% NAME"
+        );
+    }
 }
@@ -0,0 +1,182 @@
+use super::Component;
+use crate::action::Action;
+use crate::i18n::tr_args;
+use crate::stats::{MetricStats, SessionStats};
+use crate::theme::Theme;
+use chrono::{DateTime, Local};
+use ratatui::{
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph},
+    Frame,
+};
+
+/// Renders one statistic (`42.0%`, `1.2MB/s`, `65.0°C`)
+type Formatter = fn(f64) -> String;
+
+/// Popup with session min/avg/max per metric and when each peak happened
+pub struct StatsPopup {
+    pub visible: bool,
+    pub theme: Theme,
+    pub stats: Option<SessionStats>,
+    /// Result of the last export, shown under the table
+    pub status: Option<String>,
+    /// Oldest graph sample, which predates the session when history was restored
+    pub history_since: Option<DateTime<Local>>,
+}
+
+impl StatsPopup {
+    pub fn new(theme: Theme) -> Self {
+        Self {
+            visible: false,
+            theme,
+            stats: None,
+            status: None,
+            history_since: None,
+        }
+    }
+
+    fn row(&self, label: &str, stats: &MetricStats, format: Formatter) -> Line<'static> {
+        Line::from(vec![
+            Span::styled(format!(" {:<18.18}", label), self.theme.text_style()),
+            Span::styled(
+                format!(
+                    "{:>10} {:>10} {:>10}",
+                    format(stats.min),
+                    format(stats.avg),
+                    format(stats.max)
+                ),
+                Style::default().fg(self.theme.get_color(Color::Gray)),
+            ),
+            Span::styled(
+                format!("  {}", stats.max_at.format("%a %H:%M:%S")),
+                Style::default().fg(self.theme.get_color(Color::LightYellow)),
+            ),
+        ])
+    }
+
+    fn lines(&self, stats: &SessionStats) -> Vec<Line<'static>> {
+        let percent = |value: f64| format!("{}%", crate::format::decimal(value, 1));
+        let rate = |value: f64| crate::format::rate(value as u64);
+        let celsius = |value: f64| format!("{}°C", crate::format::decimal(value, 1));
+
+        let mut lines = vec![Line::from(Span::styled(
+            format!(
+                " {:<18}{:>10} {:>10} {:>10}  {}",
+                "METRIC", "MIN", "AVG", "MAX", "PEAK AT"
+            ),
+            Style::default()
+                .fg(self.theme.get_color(Color::Cyan))
+                .add_modifier(Modifier::BOLD),
+        ))];
+        let metrics: [(&str, &str, Formatter); 4] = [
+            (SessionStats::CPU, "CPU", percent),
+            (SessionStats::MEMORY, "Memory pressure", percent),
+            (SessionStats::NET_RX, "Network RX", rate),
+            (SessionStats::NET_TX, "Network TX", rate),
+        ];
+        for (key, label, format) in metrics {
+            if let Some(metric) = stats.metrics.get(key) {
+                lines.push(self.row(label, metric, format));
+            }
+        }
+        for (label, metric) in &stats.temperatures {
+            lines.push(self.row(label, metric, celsius));
+        }
+
+        let dim = Style::default().fg(self.theme.get_color(Color::DarkGray));
+        lines.push(Line::from(""));
+        if let Some(status) = &self.status {
+            lines.push(Line::from(Span::styled(
+                format!(" {}", status),
+                Style::default().fg(self.theme.get_color(Color::Green)),
+            )));
+        }
+        lines.push(Line::from(Span::styled(
+            format!(
+                " {}",
+                tr_args(
+                    "stats-monitoring",
+                    &[
+                        ("since", &stats.started.format("%a %d %b %H:%M")),
+                        (
+                            "running",
+                            &crate::format::duration(stats.running_secs(Local::now())),
+                        ),
+                    ],
+                )
+            ),
+            dim,
+        )));
+        if let Some(since) = self.history_since.filter(|since| *since < stats.started) {
+            lines.push(Line::from(Span::styled(
+                format!(
+                    " {}",
+                    tr_args(
+                        "stats-restored-since",
+                        &[("since", &since.format("%a %H:%M"))],
+                    )
+                ),
+                dim,
+            )));
+        }
+        lines.push(Line::from(Span::styled(" e export JSON · Esc close", dim)));
+        lines
+    }
+
+    pub fn render_in_area(&self, f: &mut Frame, area: Rect) {
+        if !self.visible {
+            return;
+        }
+        let Some(stats) = &self.stats else {
+            return;
+        };
+
+        let lines = self.lines(stats);
+        let width = area.width.min(74);
+        let height = area.height.min(lines.len() as u16 + 2);
+        let popup_area = Rect {
+            x: area.x + (area.width - width) / 2,
+            y: area.y + (area.height - height) / 2,
+            width,
+            height,
+        };
+        f.render_widget(Clear, popup_area);
+
+        let popup = Paragraph::new(lines).block(
+            Block::default()
+                .title(Span::styled(
+                    format!(" {} ", crate::i18n::tr("stats-title")),
+                    Style::default()
+                        .fg(self.theme.get_color(Color::LightYellow))
+                        .add_modifier(Modifier::BOLD),
+                ))
+                .borders(Borders::ALL)
+                .border_type(BorderType::Rounded)
+                .border_style(Style::default().fg(self.theme.get_color(Color::DarkGray))),
+        );
+        f.render_widget(popup, popup_area);
+    }
+}
+
+impl Component for StatsPopup {
+    fn handle_events(
+        &mut self,
+        _event: crossterm::event::Event,
+    ) -> Result<Option<Action>, Box<dyn std::error::Error>> {
+        Ok(None)
+    }
+
+    fn update(&mut self, action: Action) -> Result<Option<Action>, Box<dyn std::error::Error>> {
+        if let Action::ToggleSessionStats = action {
+            self.visible = !self.visible;
+            self.status = None;
+        }
+        Ok(None)
+    }
+
+    fn render(&mut self, f: &mut Frame) {
+        self.render_in_area(f, f.area());
+    }
+}
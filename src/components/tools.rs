@@ -0,0 +1,241 @@
+use super::Component;
+use crate::action::Action;
+use crate::config::ExternalTool;
+use crate::i18n::tr;
+use crate::theme::Theme;
+use ratatui::{
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph},
+    Frame,
+};
+
+/// Context menu for the selected process: the built-in process actions, so they can be found
+/// without knowing their keys, then the configured external commands
+pub struct ToolMenu {
+    pub visible: bool,
+    pub theme: Theme,
+    /// Leave out the entries that change the process, and the external tools (a debugger can
+    /// change it as much as a kill)
+    pub read_only: bool,
+    tools: Vec<ExternalTool>,
+    entries: Vec<MenuEntry>,
+    selected: usize,
+    target: Option<(u32, String)>,
+}
+
+/// A row of the process menu
+#[derive(Debug, Clone)]
+pub enum MenuEntry {
+    /// A built-in action on the process, with the key that does the same outside the menu
+    Builtin {
+        label: &'static str,
+        key: &'static str,
+        action: Action,
+    },
+    /// A configured external command
+    Tool(ExternalTool),
+}
+
+impl MenuEntry {
+    /// The built-in rows, listed above the tools
+    fn builtins() -> Vec<Self> {
+        [
+            ("menu-details", "Enter", Action::Enter),
+            ("menu-kill", "k", Action::RequestProcessKill),
+            ("menu-mark", "Space", Action::ToggleProcessMark),
+            ("menu-nice-lower", "", Action::ReniceProcess(5)),
+            ("menu-nice-raise", "", Action::ReniceProcess(-5)),
+            ("menu-pin", "", Action::PinProcess(true)),
+            ("menu-unpin", "", Action::PinProcess(false)),
+            ("menu-copy", "", Action::CopyProcessInfo),
+            ("menu-watch", "F", Action::ToggleFollowProcess),
+        ]
+        .into_iter()
+        .map(|(id, key, action)| Self::Builtin {
+            label: tr(id),
+            key,
+            action,
+        })
+        .collect()
+    }
+}
+
+/// Substitute `{pid}` and `{name}` in a tool command and split it into program and arguments.
+/// Words are split on whitespace without a shell, so a process name cannot inject commands.
+pub fn expand_command(command: &str, pid: u32, name: &str) -> Vec<String> {
+    command
+        .split_whitespace()
+        .map(|word| {
+            word.replace("{pid}", &pid.to_string())
+                .replace("{name}", name)
+        })
+        .collect()
+}
+
+impl ToolMenu {
+    pub fn new(tools: Vec<ExternalTool>, theme: Theme) -> Self {
+        Self {
+            visible: false,
+            theme,
+            read_only: false,
+            tools,
+            entries: Vec::new(),
+            selected: 0,
+            target: None,
+        }
+    }
+
+    /// Open the menu for `pid`
+    pub fn open(&mut self, pid: u32, name: String) {
+        let read_only = self.read_only;
+        self.entries = MenuEntry::builtins()
+            .into_iter()
+            .filter(|entry| {
+                !matches!(entry, MenuEntry::Builtin { action, .. }
+                    if read_only && action.is_destructive())
+            })
+            .chain(
+                self.tools
+                    .iter()
+                    .filter(|_| !read_only)
+                    .cloned()
+                    .map(MenuEntry::Tool),
+            )
+            .collect();
+        self.visible = true;
+        self.selected = 0;
+        self.target = Some((pid, name));
+    }
+
+    /// PID the menu was opened for
+    pub fn target_pid(&self) -> Option<u32> {
+        self.target.as_ref().map(|(pid, _)| *pid)
+    }
+
+    pub fn selected_entry(&self) -> Option<&MenuEntry> {
+        self.entries.get(self.selected)
+    }
+
+    /// Program and arguments of the highlighted tool for the target process
+    pub fn selected_command(&self) -> Option<Vec<String>> {
+        let (pid, name) = self.target.as_ref()?;
+        let Some(MenuEntry::Tool(tool)) = self.selected_entry() else {
+            return None;
+        };
+        let argv = expand_command(&tool.command, *pid, name);
+        (!argv.is_empty()).then_some(argv)
+    }
+
+    pub fn render_in_area(&self, f: &mut Frame, area: Rect) {
+        if !self.visible {
+            return;
+        }
+        let Some((pid, name)) = &self.target else {
+            return;
+        };
+
+        let width = area.width.min(64);
+        let height = area.height.min(self.entries.len() as u16 + 3);
+        let popup_area = Rect {
+            x: area.x + (area.width - width) / 2,
+            y: area.y + (area.height - height) / 2,
+            width,
+            height,
+        };
+        f.render_widget(Clear, popup_area);
+
+        let mut lines: Vec<Line> = self
+            .entries
+            .iter()
+            .enumerate()
+            .map(|(idx, entry)| {
+                let style = if idx == self.selected {
+                    Style::default()
+                        .bg(self.theme.get_color(Color::Blue))
+                        .fg(self.theme.get_color(Color::White))
+                } else {
+                    self.theme.text_style()
+                };
+                let text = match entry {
+                    MenuEntry::Builtin { label, key, .. } => format!(" {:<30} {}", label, key),
+                    MenuEntry::Tool(tool) => format!(
+                        " {:<30} {}",
+                        format!("Open in {}", tool.name),
+                        expand_command(&tool.command, *pid, name).join(" ")
+                    ),
+                };
+                Line::from(Span::styled(text, style))
+            })
+            .collect();
+        lines.push(Line::from(Span::styled(
+            "↑/↓ select · Enter run · Esc close",
+            Style::default().fg(self.theme.get_color(Color::DarkGray)),
+        )));
+
+        let popup = Paragraph::new(lines).block(
+            Block::default()
+                .title(Span::styled(
+                    format!(" {} ({}) ", name, pid),
+                    Style::default()
+                        .fg(self.theme.get_color(Color::LightYellow))
+                        .add_modifier(Modifier::BOLD),
+                ))
+                .borders(Borders::ALL)
+                .border_type(BorderType::Rounded)
+                .border_style(Style::default().fg(self.theme.get_color(Color::DarkGray))),
+        );
+        f.render_widget(popup, popup_area);
+    }
+}
+
+impl Component for ToolMenu {
+    fn handle_events(
+        &mut self,
+        _event: crossterm::event::Event,
+    ) -> Result<Option<Action>, Box<dyn std::error::Error>> {
+        Ok(None)
+    }
+
+    fn update(&mut self, action: Action) -> Result<Option<Action>, Box<dyn std::error::Error>> {
+        match action {
+            Action::MoveToolSelection(forward) => {
+                let last = self.entries.len().saturating_sub(1);
+                self.selected = if forward {
+                    (self.selected + 1).min(last)
+                } else {
+                    self.selected.saturating_sub(1)
+                };
+            }
+            Action::CloseToolMenu | Action::LaunchTool => {
+                self.visible = false;
+            }
+            _ => {}
+        }
+        Ok(None)
+    }
+
+    fn render(&mut self, f: &mut Frame) {
+        self.render_in_area(f, f.area());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_expand_command_without_shell() {
+        assert_eq!(
+            expand_command("strace -f -p {pid}", 42, "nginx"),
+            vec!["strace", "-f", "-p", "42"]
+        );
+        // A hostile process name stays a single argument
+        assert_eq!(
+            expand_command("echo {name}", 7, "x;rm"),
+            vec!["echo", "x;rm"]
+        );
+        assert!(expand_command("   ", 1, "a").is_empty());
+    }
+}
@@ -1,25 +1,362 @@
-use crate::data::snapshot::ColorScheme;
+use crate::data::snapshot::{ColorScheme, GpuInfo, TemperatureInfo};
+use crate::format::UnitBase;
+use crate::layout::{Panel, PanelLayout};
 use serde::{Deserialize, Serialize};
-use std::{fs, path::PathBuf};
+use std::{collections::BTreeMap, fs, path::PathBuf};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct AppConfig {
     pub color_scheme: ColorScheme,
+    /// Interface language, e.g. `es`; empty follows the locale
+    pub language: String,
+    /// Byte units: `jedec` (1024, `KB`), `iec` (1024, `KiB`) or `si` (1000, `kB`)
+    pub unit_base: UnitBase,
+    /// `.` or `,` between integer and fraction; empty follows the locale
+    pub decimal_separator: String,
+    /// Top status bar content: `{hostname}`, `{uptime}`, `{load}`, `{tasks}`, `{session}`,
+    /// `{time}`, `{theme}`, `{battery}`, `{ip}` and `{self}` between any text; unknown or
+    /// unavailable fields are skipped
+    pub status_template: String,
+    /// strftime format of the status bar clock
+    pub clock_format: String,
+    /// Show the date before the clock
+    pub clock_show_date: bool,
+    /// Show the UTC offset after the clock
+    pub clock_show_timezone: bool,
+    /// Alert when a volume is projected to fill up within this many hours
+    pub disk_full_horizon_hours: u64,
+    /// Ring the terminal bell and/or flash the screen when an alert turns critical
+    pub alert_bell: AlertBell,
+    /// Fewer frames, no animation and flat gradients: `auto` (over SSH), `on` or `off`
+    pub low_bandwidth: LowBandwidth,
+    /// Stretch polling and rendering intervals by this factor while on battery power (1 to 60)
+    pub battery_poll_multiplier: f64,
+    /// Stretch polling and rendering intervals by this factor after `idle_after_secs` without
+    /// input (1 to 60)
+    pub idle_poll_multiplier: f64,
+    /// Seconds without keyboard or mouse input before rtop is considered idle (0 disables)
+    pub idle_after_secs: u64,
+    /// Command-line arguments kept per process, including the program (0 keeps all)
+    pub cmd_max_args: usize,
+    /// Executable paths longer than this are dropped (0 keeps all)
+    pub exe_max_len: usize,
+    /// Mask process arguments, e.g. for demos and screenshots
+    pub privacy_mode: bool,
+    /// Process CPU% relative to one core, so busy multi-threaded processes exceed 100% (top's
+    /// Irix mode); `false` divides by the core count (Solaris mode)
+    pub irix_mode: bool,
+    /// Start with idle processes hidden from the process list
+    pub hide_idle_processes: bool,
+    /// A process is idle below this CPU% (in the current Irix/Solaris scale)...
+    pub idle_process_cpu_percent: f32,
+    /// ...and this much memory, in MiB
+    pub idle_process_memory_mb: u64,
+    /// Hosts to ping for the latency monitor; `gateway` means the default route (empty disables)
+    pub latency_targets: Vec<String>,
+    /// Hostname resolved periodically to measure DNS response time (empty disables)
+    pub dns_probe_host: String,
+    /// Seconds between latency and DNS probes
+    pub latency_interval_secs: u64,
+    /// Name of the GPU shown with full details when several are present
+    pub primary_gpu: Option<String>,
+    /// Temperature alert thresholds per GPU; GPUs without an entry use their vendor's defaults
+    pub gpu_temp_limits: Vec<GpuTempLimit>,
+    /// Friendly names for temperature sensors, and sensors to leave out
+    pub sensors: SensorOverrides,
+    /// GPUs to leave out, and whether a discrete GPU comes first
+    pub gpus: GpuFilter,
+    /// Friendly names for network interfaces, keyed by the interface name
+    pub interface_names: BTreeMap<String, String>,
+    /// Minutes of steady memory growth before a process is a suspected leak (0 disables)
+    pub leak_window_mins: u64,
+    /// Save the CPU, memory and network graphs on exit and continue them on the next start
+    pub persist_history: bool,
+    /// Side panel order: the first three stack left of the process list, the rest right
+    pub panel_order: Vec<Panel>,
+    /// Percent of the width for the left panels, the process list and the right panels
+    pub column_widths: Vec<u16>,
+    /// Percent of its column's height for each panel slot, in `panel_order` order
+    pub panel_heights: Vec<u16>,
+    /// Side panels switched off, e.g. `["gpu"]` on a headless VM
+    pub hidden_panels: Vec<Panel>,
+    /// Commands offered for the selected process; `{pid}` and `{name}` are substituted
+    pub external_tools: Vec<ExternalTool>,
+    /// `pkexec` or `sudo` to offer retrying kills refused with EPERM (empty disables)
+    pub elevate_with: String,
+    /// Process names (`*` matches any run of characters) whose kill must be confirmed by
+    /// typing "yes", like the init system, sshd and the display manager
+    pub protected_processes: Vec<String>,
+    /// Server for the on-demand speed test: `iperf3://host[:port]` or an `http(s)://` URL
+    pub speedtest_endpoint: String,
+}
+
+/// How a newly critical alert calls attention to a background pane
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AlertBell {
+    #[default]
+    Off,
+    /// Terminal bell (BEL), which terminals may turn into a sound or an urgency hint
+    Bell,
+    /// Briefly invert the whole UI
+    Flash,
+    Both,
+}
+
+impl AlertBell {
+    pub fn rings(self) -> bool {
+        matches!(self, AlertBell::Bell | AlertBell::Both)
+    }
+
+    pub fn flashes(self) -> bool {
+        matches!(self, AlertBell::Flash | AlertBell::Both)
+    }
+}
+
+/// Whether to trade smoothness for fewer bytes on the wire
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LowBandwidth {
+    /// On when `SSH_CONNECTION` is set
+    #[default]
+    Auto,
+    On,
+    Off,
+}
+
+impl LowBandwidth {
+    pub fn enabled(self, over_ssh: bool) -> bool {
+        match self {
+            LowBandwidth::Auto => over_ssh,
+            LowBandwidth::On => true,
+            LowBandwidth::Off => false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExternalTool {
+    pub name: String,
+    pub command: String,
+}
+
+impl ExternalTool {
+    fn new(name: &str, command: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            command: command.to_string(),
+        }
+    }
+}
+
+/// Warning and critical temperatures, in °C, for the GPUs `gpu` matches
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GpuTempLimit {
+    /// Case-insensitive part of the GPU's name, or its vendor
+    pub gpu: String,
+    pub warning: f32,
+    pub critical: f32,
+}
+
+impl GpuTempLimit {
+    /// The first configured limit matching `gpu`, otherwise where its vendor's parts throttle
+    pub fn for_gpu(limits: &[GpuTempLimit], gpu: &GpuInfo) -> (f32, f32) {
+        let name = gpu.name.to_lowercase();
+        let vendor = gpu.vendor.to_lowercase();
+        limits
+            .iter()
+            .find(|limit| {
+                let pattern = limit.gpu.to_lowercase();
+                !pattern.is_empty() && (name.contains(&pattern) || vendor == pattern)
+            })
+            .map(|limit| (limit.warning, limit.critical))
+            .unwrap_or_else(|| Self::vendor_default(&vendor))
+    }
+
+    fn vendor_default(vendor: &str) -> (f32, f32) {
+        match vendor {
+            // GeForce and Quadro cards start slowing down in the high 80s
+            "nvidia" => (83.0, 90.0),
+            // Radeon edge temperature; the hotspot runs well above it
+            "amd" => (90.0, 100.0),
+            // Integrated graphics share the CPU package's limit
+            "intel" => (95.0, 100.0),
+            _ => (80.0, 90.0),
+        }
+    }
+}
+
+/// Temperature sensor overrides, keyed by the name a sensor is found under: its hwmon chip and
+/// channel label, e.g. `acpitz temp1` or `coretemp Package id 0`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SensorOverrides {
+    /// Name shown instead, e.g. `"acpitz temp1" = "Motherboard"` (keys are case-insensitive)
+    pub names: BTreeMap<String, String>,
+    /// Sensors left out everywhere, e.g. a stuck or meaningless reading (case-insensitive)
+    pub hidden: Vec<String>,
+}
+
+impl SensorOverrides {
+    pub fn apply(&self, sensors: &mut Vec<TemperatureInfo>) {
+        sensors.retain(|sensor| {
+            !self
+                .hidden
+                .iter()
+                .any(|hidden| hidden.eq_ignore_ascii_case(&sensor.source))
+        });
+        for sensor in sensors {
+            let name = self
+                .names
+                .iter()
+                .find(|(source, _)| source.eq_ignore_ascii_case(&sensor.source));
+            if let Some((_, name)) = name {
+                sensor.label = name.clone();
+            }
+        }
+    }
+}
+
+/// Which GPUs are collected and in what order, for hybrid laptops whose integrated GPU only
+/// takes up room in the panel
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GpuFilter {
+    /// GPUs left out, matched like `gpu_temp_limits`: part of the name or the vendor
+    pub hidden: Vec<String>,
+    /// Leave out integrated GPUs; see [`GpuInfo::is_integrated`]
+    pub hide_integrated: bool,
+    /// List discrete GPUs first, so one is primary unless `primary_gpu` names another
+    pub prefer_discrete: bool,
+}
+
+impl GpuFilter {
+    /// Whether every GPU of `vendor` is left out, so its sources need not be read at all
+    pub fn hides_vendor(&self, vendor: &str) -> bool {
+        self.hidden
+            .iter()
+            .any(|hidden| hidden.eq_ignore_ascii_case(vendor))
+    }
+
+    fn hides(&self, gpu: &GpuInfo) -> bool {
+        let name = gpu.name.to_lowercase();
+        (self.hide_integrated && gpu.is_integrated())
+            || self.hidden.iter().any(|hidden| {
+                let pattern = hidden.to_lowercase();
+                !pattern.is_empty()
+                    && (name.contains(&pattern) || gpu.vendor.to_lowercase() == pattern)
+            })
+    }
+
+    pub fn apply(&self, gpus: &mut Vec<GpuInfo>) {
+        gpus.retain(|gpu| !self.hides(gpu));
+        if self.prefer_discrete {
+            gpus.sort_by_key(GpuInfo::is_integrated);
+        }
+    }
 }
 
 impl Default for AppConfig {
     fn default() -> Self {
         Self {
             color_scheme: ColorScheme::Default,
+            language: String::new(),
+            unit_base: UnitBase::Jedec,
+            decimal_separator: String::new(),
+            status_template: crate::status_line::DEFAULT_TEMPLATE.to_string(),
+            clock_format: crate::status_line::DEFAULT_CLOCK_FORMAT.to_string(),
+            clock_show_date: false,
+            clock_show_timezone: false,
+            disk_full_horizon_hours: 24,
+            alert_bell: AlertBell::Off,
+            low_bandwidth: LowBandwidth::Auto,
+            battery_poll_multiplier: 2.0,
+            idle_poll_multiplier: 3.0,
+            idle_after_secs: 120,
+            cmd_max_args: 3,
+            exe_max_len: 200,
+            privacy_mode: false,
+            irix_mode: true,
+            hide_idle_processes: false,
+            idle_process_cpu_percent: 0.1,
+            idle_process_memory_mb: 10,
+            latency_targets: Vec::new(),
+            dns_probe_host: String::new(),
+            latency_interval_secs: 5,
+            primary_gpu: None,
+            gpu_temp_limits: Vec::new(),
+            sensors: SensorOverrides::default(),
+            gpus: GpuFilter::default(),
+            interface_names: BTreeMap::new(),
+            leak_window_mins: 10,
+            persist_history: false,
+            panel_order: Panel::ALL.to_vec(),
+            column_widths: PanelLayout::DEFAULT_COLUMNS.to_vec(),
+            panel_heights: PanelLayout::DEFAULT_HEIGHTS.to_vec(),
+            hidden_panels: Vec::new(),
+            external_tools: vec![
+                ExternalTool::new("strace", "strace -f -p {pid}"),
+                ExternalTool::new("gdb", "gdb -p {pid}"),
+                ExternalTool::new("lsof", "lsof -p {pid}"),
+            ],
+            elevate_with: String::new(),
+            protected_processes: [
+                "init",
+                "systemd",
+                "sshd",
+                "login",
+                "dbus-daemon",
+                "Xorg",
+                "Xwayland",
+                "gdm*",
+                "sddm*",
+                "lightdm",
+                "gnome-shell",
+                "kwin_*",
+                "plasmashell",
+                "launchd",
+                "loginwindow",
+                "WindowServer",
+                "winlogon.exe",
+                "csrss.exe",
+            ]
+            .iter()
+            .map(|name| name.to_string())
+            .collect(),
+            speedtest_endpoint: String::new(),
         }
     }
 }
 
 impl AppConfig {
+    /// Bounds of the poll multipliers; they scale a `Duration`, which panics on values outside
+    /// its range, NaN and infinity included
+    const POLL_MULTIPLIER_RANGE: (f64, f64) = (1.0, 60.0);
+
     pub fn load() -> Option<Self> {
         let path = Self::config_path()?;
         let content = fs::read_to_string(path).ok()?;
-        toml::from_str(&content).ok()
+        toml::from_str(&content).ok().map(Self::sanitized)
+    }
+
+    /// The config with values the rest of rtop cannot take brought into range
+    fn sanitized(mut self) -> Self {
+        let defaults = Self::default();
+        let (low, high) = Self::POLL_MULTIPLIER_RANGE;
+        let clamp = |value: f64, default: f64| {
+            if value.is_nan() {
+                default
+            } else {
+                value.clamp(low, high)
+            }
+        };
+        self.battery_poll_multiplier = clamp(
+            self.battery_poll_multiplier,
+            defaults.battery_poll_multiplier,
+        );
+        self.idle_poll_multiplier = clamp(self.idle_poll_multiplier, defaults.idle_poll_multiplier);
+        self
     }
 
     pub fn save(&self) -> Result<(), Box<dyn std::error::Error>> {
@@ -37,3 +374,99 @@ impl AppConfig {
         dirs::config_dir().map(|dir| dir.join("rtop").join("config.toml"))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_poll_multipliers_are_clamped_on_load() {
+        let load = |text: &str| toml::from_str::<AppConfig>(text).unwrap().sanitized();
+        let config = load("battery_poll_multiplier = inf\nidle_poll_multiplier = -2.0");
+        assert_eq!(config.battery_poll_multiplier, 60.0);
+        assert_eq!(config.idle_poll_multiplier, 1.0);
+        let config = load("battery_poll_multiplier = nan\nidle_poll_multiplier = 1e300");
+        assert_eq!(config.battery_poll_multiplier, 2.0);
+        assert_eq!(config.idle_poll_multiplier, 60.0);
+        let config = load("battery_poll_multiplier = 4.5");
+        assert_eq!(config.battery_poll_multiplier, 4.5);
+    }
+
+    #[test]
+    fn test_gpu_filter_hides_and_orders() {
+        let gpu = |name: &str, vendor: &str| crate::data::mock::gpu(name, vendor, 50.0);
+        let mut gpus = vec![
+            gpu("Intel UHD Graphics 620", "Intel"),
+            gpu("AMD Radeon Graphics", "AMD"),
+            gpu("NVIDIA GeForce RTX 3060 Laptop GPU", "NVIDIA"),
+            gpu("Intel Arc A770", "Intel"),
+        ];
+        assert!(gpus[0].is_integrated() && gpus[1].is_integrated());
+        assert!(!gpus[2].is_integrated() && !gpus[3].is_integrated());
+        assert!(gpu("AMD Radeon 780M", "AMD").is_integrated());
+        assert!(!gpu("AMD Radeon RX 7900 XTX", "AMD").is_integrated());
+        assert!(!gpu("Intel Arc Pro A60", "Intel").is_integrated());
+        assert!(gpu("Intel Arc Graphics", "Intel").is_integrated());
+        assert!(gpu("Intel Arc 140V GPU", "Intel").is_integrated());
+        assert!(!gpu("AMD Radeon Pro Vega 56", "AMD").is_integrated());
+        assert!(!gpu("AMD Radeon Vega 64", "AMD").is_integrated());
+        assert!(gpu("AMD Radeon Vega 8 Graphics", "AMD").is_integrated());
+
+        let mut ordered = gpus.clone();
+        GpuFilter {
+            prefer_discrete: true,
+            ..GpuFilter::default()
+        }
+        .apply(&mut ordered);
+        assert_eq!(ordered[0].vendor, "NVIDIA");
+        assert_eq!(ordered[1].name, "Intel Arc A770");
+
+        let filter: AppConfig = toml::from_str(
+            r#"
+            [gpus]
+            hidden = ["nvidia"]
+            hide_integrated = true
+            "#,
+        )
+        .unwrap();
+        assert!(filter.gpus.hides_vendor("NVIDIA"));
+        assert!(!filter.gpus.hides_vendor("Intel"));
+        filter.gpus.apply(&mut gpus);
+        let names: Vec<&str> = gpus.iter().map(|gpu| gpu.name.as_str()).collect();
+        assert_eq!(names, ["Intel Arc A770"]);
+    }
+
+    #[test]
+    fn test_sensor_overrides_rename_and_hide() {
+        let config: AppConfig = toml::from_str(
+            r#"
+            [sensors]
+            hidden = ["IWLWIFI_1 TEMP1"]
+
+            [sensors.names]
+            "ACPITZ temp1" = "Motherboard"
+            "#,
+        )
+        .unwrap();
+        let sensor = |source: &str| TemperatureInfo {
+            label: source.to_string(),
+            source: source.to_string(),
+            temperature: 40.0,
+        };
+        let mut sensors = vec![
+            sensor("acpitz temp1"),
+            sensor("iwlwifi_1 temp1"),
+            sensor("coretemp Package id 0"),
+        ];
+        config.sensors.apply(&mut sensors);
+        let labels: Vec<&str> = sensors.iter().map(|s| s.label.as_str()).collect();
+        assert_eq!(labels, ["Motherboard", "coretemp Package id 0"]);
+        assert_eq!(sensors[0].source, "acpitz temp1");
+
+        // The overrides survive a save
+        let saved = toml::to_string_pretty(&config).unwrap();
+        let reloaded: AppConfig = toml::from_str(&saved).unwrap();
+        assert_eq!(reloaded.sensors.names["ACPITZ temp1"], "Motherboard");
+        assert_eq!(reloaded.sensors.hidden, ["IWLWIFI_1 TEMP1"]);
+    }
+}
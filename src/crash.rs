@@ -0,0 +1,79 @@
+use crate::data::snapshot::SystemSnapshot;
+use serde::Serialize;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+/// The most recent snapshot received by the UI, written out if rtop panics so the collector
+/// state that led to the crash can be reproduced
+static LAST_SNAPSHOT: Mutex<Option<SystemSnapshot>> = Mutex::new(None);
+
+#[derive(Serialize)]
+struct CrashReport<'a> {
+    version: &'static str,
+    timestamp: String,
+    message: String,
+    location: Option<String>,
+    backtrace: String,
+    snapshot: Option<&'a SystemSnapshot>,
+}
+
+/// Keep a copy of the latest snapshot so a crash report can include it; called once per
+/// collected snapshot, not per frame
+pub fn record_snapshot(snapshot: &SystemSnapshot) {
+    let snapshot = snapshot.clone();
+    if let Ok(mut last) = LAST_SNAPSHOT.lock() {
+        *last = Some(snapshot);
+    }
+}
+
+/// Write a crash report, then restore the terminal and print the panic
+pub fn install_panic_hook() {
+    std::panic::set_hook(Box::new(|panic_info| {
+        use crossterm::{
+            execute,
+            terminal::{disable_raw_mode, LeaveAlternateScreen},
+        };
+
+        let report_path = write_crash_report(panic_info);
+
+        // Restore terminal
+        let _ = disable_raw_mode();
+        let mut stderr = std::io::stderr();
+        execute!(stderr, LeaveAlternateScreen).ok();
+
+        // Print panic info
+        eprintln!("{}", panic_info);
+        if let Some(path) = report_path {
+            eprintln!("crash report written to {}", path.display());
+        }
+    }));
+}
+
+fn write_crash_report(panic_info: &std::panic::PanicHookInfo) -> Option<PathBuf> {
+    let message = if let Some(message) = panic_info.payload().downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = panic_info.payload().downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "unknown panic payload".to_string()
+    };
+
+    // The panic may have happened while the lock was held; never block inside the hook.
+    let last = LAST_SNAPSHOT.try_lock().ok();
+    let now = chrono::Local::now();
+    let report = CrashReport {
+        version: env!("CARGO_PKG_VERSION"),
+        timestamp: now.to_rfc3339(),
+        message,
+        location: panic_info.location().map(|location| location.to_string()),
+        backtrace: std::backtrace::Backtrace::force_capture().to_string(),
+        snapshot: last.as_ref().and_then(|last| last.as_ref()),
+    };
+
+    let dir = dirs::cache_dir()?.join("rtop");
+    std::fs::create_dir_all(&dir).ok()?;
+    let path = dir.join(format!("crash-{}.json", now.format("%Y%m%d-%H%M%S")));
+    let json = serde_json::to_string_pretty(&report).ok()?;
+    std::fs::write(&path, json).ok()?;
+    Some(path)
+}
@@ -0,0 +1,230 @@
+//! lsblk-style description of the block device behind a mounted volume: the whole disk, its
+//! partitions, their filesystems, UUIDs and mounts, read from sysfs and `/proc/mounts`.
+
+use std::path::{Path, PathBuf};
+
+/// A whole disk and its partitions
+#[derive(Debug, Clone, Default)]
+pub struct BlockDevice {
+    /// Kernel name, e.g. `nvme0n1` or `dm-0`
+    pub name: String,
+    pub model: Option<String>,
+    /// Bytes
+    pub size: u64,
+    /// Spinning disk (`true`) or solid state
+    pub rotational: Option<bool>,
+    /// Active I/O scheduler, e.g. `mq-deadline`
+    pub scheduler: Option<String>,
+    /// In partition-number order; a disk without a partition table lists itself
+    pub partitions: Vec<Partition>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Partition {
+    pub name: String,
+    /// Bytes
+    pub size: u64,
+    pub fs_type: Option<String>,
+    pub uuid: Option<String>,
+    /// Where it is mounted, with the options of each mount
+    pub mounts: Vec<(String, String)>,
+    /// Backs the volume the popup was opened for
+    pub selected: bool,
+}
+
+/// The device behind `device` (a `/dev` path as the disk panel shows it). `None` when it is
+/// not a block device, e.g. a network or overlay filesystem, or off Linux.
+pub fn describe(device: &str) -> Option<BlockDevice> {
+    describe_in(Path::new("/"), device)
+}
+
+#[cfg(target_os = "linux")]
+fn describe_in(root: &Path, device: &str) -> Option<BlockDevice> {
+    let read = |path: &Path| {
+        std::fs::read_to_string(root.join(path.strip_prefix("/").unwrap_or(path)))
+            .ok()
+            .map(|text| text.trim().to_string())
+    };
+    let resolve = |path: &Path| {
+        std::fs::canonicalize(root.join(path.strip_prefix("/").unwrap_or(path)))
+            .ok()
+            .and_then(|path| Some(path.file_name()?.to_string_lossy().into_owned()))
+    };
+
+    let name = resolve(Path::new(device))?;
+    let class = root.join("sys/class/block").join(&name);
+    let sys = std::fs::canonicalize(&class).ok()?;
+    let disk_dir = if sys.join("partition").exists() {
+        sys.parent()?.to_path_buf()
+    } else {
+        sys
+    };
+    let disk_name = disk_dir.file_name()?.to_string_lossy().into_owned();
+    let sectors = |dir: &Path| {
+        std::fs::read_to_string(dir.join("size"))
+            .ok()
+            .and_then(|text| text.trim().parse::<u64>().ok())
+            .map_or(0, |sectors| sectors * 512)
+    };
+    let attribute = |file: &str| {
+        std::fs::read_to_string(disk_dir.join(file))
+            .ok()
+            .map(|text| text.trim().to_string())
+            .filter(|text| !text.is_empty())
+    };
+
+    let mut partition_dirs: Vec<(u32, PathBuf)> = std::fs::read_dir(&disk_dir)
+        .map(|entries| {
+            entries
+                .flatten()
+                .map(|entry| entry.path())
+                .filter_map(|path| {
+                    let number = std::fs::read_to_string(path.join("partition")).ok()?;
+                    Some((number.trim().parse().ok()?, path))
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    partition_dirs.sort();
+    if partition_dirs.is_empty() {
+        partition_dirs.push((0, disk_dir.clone()));
+    }
+
+    let mounts = read(Path::new("/proc/self/mounts"))
+        .map(|text| crate::data::procfs::parse_mounts(&text))
+        .unwrap_or_default();
+    let uuids: Vec<(String, String)> = std::fs::read_dir(root.join("dev/disk/by-uuid"))
+        .map(|entries| {
+            entries
+                .flatten()
+                .filter_map(|entry| {
+                    let target = std::fs::canonicalize(entry.path()).ok()?;
+                    Some((
+                        target.file_name()?.to_string_lossy().into_owned(),
+                        entry.file_name().to_string_lossy().into_owned(),
+                    ))
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let partitions = partition_dirs
+        .into_iter()
+        .filter_map(|(_, dir)| {
+            let part_name = dir.file_name()?.to_string_lossy().into_owned();
+            let part_mounts: Vec<_> = mounts
+                .iter()
+                .filter(|mount| {
+                    mount.source.starts_with("/dev/")
+                        && resolve(Path::new(&mount.source)).as_deref() == Some(part_name.as_str())
+                })
+                .collect();
+            Some(Partition {
+                size: sectors(&dir),
+                fs_type: part_mounts.first().map(|mount| mount.fs_type.clone()),
+                uuid: uuids
+                    .iter()
+                    .find(|(target, _)| *target == part_name)
+                    .map(|(_, uuid)| uuid.clone()),
+                mounts: part_mounts
+                    .iter()
+                    .map(|mount| (mount.target.clone(), mount.options.clone()))
+                    .collect(),
+                selected: part_name == name,
+                name: part_name,
+            })
+        })
+        .collect();
+
+    Some(BlockDevice {
+        name: disk_name,
+        model: attribute("device/model"),
+        size: sectors(&disk_dir),
+        rotational: attribute("queue/rotational").map(|value| value == "1"),
+        scheduler: attribute("queue/scheduler").and_then(|text| parse_scheduler(&text)),
+        partitions,
+    })
+}
+
+#[cfg(not(target_os = "linux"))]
+fn describe_in(_root: &Path, _device: &str) -> Option<BlockDevice> {
+    None
+}
+
+/// The active scheduler from `queue/scheduler`, which lists them all with it in brackets
+fn parse_scheduler(text: &str) -> Option<String> {
+    let words: Vec<&str> = text.split_whitespace().collect();
+    words
+        .iter()
+        .find_map(|word| word.strip_prefix('[')?.strip_suffix(']'))
+        .or(match words.as_slice() {
+            [only] => Some(*only),
+            _ => None,
+        })
+        .map(str::to_string)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_scheduler() {
+        assert_eq!(
+            parse_scheduler("none [mq-deadline] kyber bfq").as_deref(),
+            Some("mq-deadline")
+        );
+        assert_eq!(parse_scheduler("none").as_deref(), Some("none"));
+        assert_eq!(parse_scheduler(""), None);
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_describe_walks_a_fake_sysfs() {
+        let root = std::env::temp_dir().join(format!("rtop-blockdev-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        let disk = root.join("sys/devices/pci0000:00/nvme/block/nvme0n1");
+        let write = |path: PathBuf, text: &str| {
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, text).unwrap();
+        };
+        write(disk.join("size"), "2000\n");
+        write(disk.join("device/model"), "Fast SSD   \n");
+        write(disk.join("queue/rotational"), "0\n");
+        write(disk.join("queue/scheduler"), "[none] mq-deadline\n");
+        for (number, size) in [(1, "200"), (2, "1800")] {
+            let part = disk.join(format!("nvme0n1p{}", number));
+            write(part.join("partition"), &number.to_string());
+            write(part.join("size"), size);
+        }
+        let link = |target: &Path, link: PathBuf| {
+            std::fs::create_dir_all(link.parent().unwrap()).unwrap();
+            std::os::unix::fs::symlink(target, link).unwrap();
+        };
+        for name in ["nvme0n1", "nvme0n1p1", "nvme0n1p2"] {
+            let sys = if name == "nvme0n1" {
+                disk.clone()
+            } else {
+                disk.join(name)
+            };
+            link(&sys, root.join("sys/class/block").join(name));
+            write(root.join("dev").join(name), "");
+        }
+        link(
+            &root.join("dev/nvme0n1p2"),
+            root.join("dev/disk/by-uuid/1234-abcd"),
+        );
+
+        let device = describe_in(&root, "/dev/nvme0n1p2").expect("device");
+        let _ = std::fs::remove_dir_all(&root);
+        assert_eq!(device.name, "nvme0n1");
+        assert_eq!(device.model.as_deref(), Some("Fast SSD"));
+        assert_eq!(device.size, 2000 * 512);
+        assert_eq!(device.rotational, Some(false));
+        assert_eq!(device.scheduler.as_deref(), Some("none"));
+        let names: Vec<&str> = device.partitions.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, ["nvme0n1p1", "nvme0n1p2"]);
+        assert!(device.partitions[1].selected);
+        assert_eq!(device.partitions[1].uuid.as_deref(), Some("1234-abcd"));
+    }
+}
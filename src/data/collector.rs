@@ -1,10 +1,26 @@
+use crate::config::{GpuFilter, SensorOverrides};
+use crate::data::command::CommandRunner;
+use crate::data::leaks::LeakTracker;
+#[cfg(target_os = "linux")]
+use crate::data::mounts::MountProbes;
+use crate::data::mounts::{self, FsUsage};
+#[cfg(target_os = "linux")]
+use crate::data::procfs::CpuTimes;
 use crate::data::snapshot::{
-    BatteryInfo, DiskInfo, NetworkInfo, ProcessInfo, SystemSnapshot, TemperatureInfo,
+    BatteryInfo, DiskInfo, InterfaceKind, KernelLimits, NetworkInfo, OpenFilesByMount,
+    ProcessDetail, ProcessGpuUsage, ProcessInfo, ProcessState, ProtocolStats, RemoteMount,
+    SchedClass, SelfUsage, SocketStats, SystemSnapshot, TaskTotals, TemperatureInfo, ThrottleStats,
+    ZfsArc,
 };
+#[cfg(target_os = "linux")]
+use crate::data::sysfs::SysfsReader;
+use crate::profile::PhaseProfiler;
 use nvml_wrapper::Nvml;
 use std::collections::{HashMap, VecDeque};
+#[cfg(not(target_os = "linux"))]
+use sysinfo::DiskExt;
 use sysinfo::{
-    ComponentExt, CpuExt, DiskExt, NetworkExt, NetworksExt, PidExt, ProcessExt, System, SystemExt,
+    ComponentExt, CpuExt, NetworkExt, NetworksExt, PidExt, ProcessExt, System, SystemExt,
 };
 
 pub struct DataCollector {
@@ -13,14 +29,24 @@ pub struct DataCollector {
     last_update_time: std::time::Instant,
     cpu_history: Vec<VecDeque<f32>>,
     memory_history: VecDeque<(u64, u64)>,
+    history_times: VecDeque<i64>,
+    /// Time of the last sample restored from a previous session
+    restored_until: Option<i64>,
     swap_history: VecDeque<(u64, u64)>,
     network_history: VecDeque<(u64, u64)>,
     disk_usage_history: Vec<VecDeque<(u64, u64)>>,
+    disk_history_times: VecDeque<std::time::Instant>,
     nvml: Option<Nvml>,
+    nvml_sample_seen: HashMap<u32, u64>, // Newest per-process utilization sample read, per device
+    #[cfg(target_os = "linux")]
+    reader: SysfsReader,
     #[cfg(target_os = "linux")]
     lspci_gpu_candidates: Vec<(String, String)>, // (name, vendor)
     #[cfg(target_os = "linux")]
     intel_drm_card_path: Option<String>,
+    /// Whether that card is the one on the CPU package; `None` until a card is found
+    #[cfg(target_os = "linux")]
+    intel_integrated: Option<bool>,
     #[cfg(target_os = "linux")]
     intel_rc6_paths: Vec<String>,
     #[cfg(target_os = "linux")]
@@ -36,6 +62,12 @@ pub struct DataCollector {
     #[cfg(target_os = "linux")]
     intel_debugfs_mem_path: Option<String>,
     #[cfg(target_os = "linux")]
+    drm_fdinfo_paths: Vec<String>, // fdinfo of open /dev/dri fds, refreshed by a periodic scan
+    #[cfg(target_os = "linux")]
+    drm_fd_scan_countdown: u32,
+    #[cfg(target_os = "linux")]
+    drm_fd_scan_complete: bool,
+    #[cfg(target_os = "linux")]
     previous_intel_rc6_by_path: HashMap<String, (u64, std::time::Instant)>,
     #[cfg(target_os = "linux")]
     intel_gpu_rapl_energy_path: Option<String>,
@@ -49,7 +81,67 @@ pub struct DataCollector {
     previous_rapl_energy: Option<f64>,
     #[cfg(target_os = "linux")]
     previous_rapl_time: Option<std::time::Instant>,
+    /// RAPL energy counters that can be read, and whether any others are root-only; probed once
+    #[cfg(target_os = "linux")]
+    rapl_paths: Option<(Vec<&'static str>, bool)>,
+    #[cfg(target_os = "linux")]
+    throttle_baseline: Option<ThrottleStats>, // Raw counters at startup and at the last collection
+    #[cfg(target_os = "linux")]
+    throttle_previous: ThrottleStats,
+    #[cfg(target_os = "linux")]
+    previous_schedstat: HashMap<usize, (u64, u64)>, // (wait ns, timeslices) per CPU
+    #[cfg(target_os = "linux")]
+    previous_cpu_times: (Option<CpuTimes>, HashMap<usize, CpuTimes>), // (all CPUs, per online CPU)
     process_cpu_ema: HashMap<u32, f32>,
+    process_cpu_history: HashMap<u32, VecDeque<f32>>, // Recent smoothed CPU% per process
+    process_cpu_accumulated: HashMap<u32, (f64, std::time::Instant)>, // (CPU%·seconds, first seen)
+    leak_tracker: LeakTracker,
+    sensor_overrides: SensorOverrides,
+    gpu_filter: GpuFilter,
+    interface_topology: HashMap<String, (InterfaceKind, Option<String>)>,
+    /// Process shown in the detail view, whose open files and details are collected
+    inspected_pid: Option<u32>,
+    /// Highlighted process; only it and the inspected one carry their CPU% history
+    selected_pid: Option<u32>,
+    /// Data sources refused with EPERM during the current collection
+    restricted_sources: Vec<&'static str>,
+    /// Wi-Fi power-save state per wireless interface and when `iw` was last asked
+    #[cfg(target_os = "linux")]
+    wifi_power_save: HashMap<String, (std::time::Instant, Option<bool>)>,
+    #[cfg(target_os = "linux")]
+    previous_page_faults: HashMap<u32, (u64, u64)>, // (minor, major) per PID
+    #[cfg(target_os = "linux")]
+    last_cpu_history: HashMap<u32, VecDeque<u16>>, // Recent last-ran-on cores per process
+    /// Source address of the default route and when it was last looked up
+    primary_ip: Option<(std::time::Instant, Option<String>)>,
+    /// Login name per UID; `None` once the users list was re-read without finding it
+    user_names: HashMap<u32, Option<String>>,
+    /// Whether each PID runs in a container; fixed for the life of a process, so read once
+    #[cfg(target_os = "linux")]
+    container_pids: HashMap<u32, bool>,
+    #[cfg(target_os = "linux")]
+    previous_snmp: Option<(HashMap<String, i64>, std::time::Instant)>,
+    cmd_max_args: usize,
+    exe_max_len: usize,
+    full_command_lines: bool,
+    probes: Option<crate::data::probes::SharedProbes>,
+    profiler: Option<PhaseProfiler>,
+    own_pid: Option<u32>,
+    /// Background statvfs probes of network mounts
+    #[cfg(target_os = "linux")]
+    mount_probes: MountProbes,
+    /// Timeouts and circuit breaking for the external commands collection runs
+    commands: CommandRunner,
+}
+
+/// A mounted filesystem before duplicates are merged
+struct MountedVolume {
+    /// Device or remote source, e.g. `/dev/sda1` or `server:/export`
+    name: String,
+    fs: String,
+    mount_point: String,
+    usage: FsUsage,
+    remote: Option<RemoteMount>,
 }
 
 impl DataCollector {
@@ -57,6 +149,14 @@ impl DataCollector {
         // Initialize System with only essential components to reduce startup time
         let mut system = System::new();
         system.refresh_system(); // Only get system-level info initially
+        #[cfg(target_os = "linux")]
+        let (commands, lspci_gpu_candidates) = {
+            let mut commands = CommandRunner::new();
+            let gpus = Self::detect_lspci_gpus(&mut commands);
+            (commands, gpus)
+        };
+        #[cfg(not(target_os = "linux"))]
+        let commands = CommandRunner::new();
 
         Self {
             system,
@@ -64,15 +164,23 @@ impl DataCollector {
             last_update_time: std::time::Instant::now(),
             cpu_history: Vec::new(),
             memory_history: VecDeque::with_capacity(Self::HISTORY_LEN),
+            history_times: VecDeque::with_capacity(Self::HISTORY_LEN),
+            restored_until: None,
             swap_history: VecDeque::with_capacity(Self::HISTORY_LEN),
             network_history: VecDeque::with_capacity(Self::HISTORY_LEN),
             disk_usage_history: Vec::new(),
+            disk_history_times: VecDeque::with_capacity(Self::HISTORY_LEN),
             nvml: Self::initialize_nvml(),
+            nvml_sample_seen: HashMap::new(),
             #[cfg(target_os = "linux")]
-            lspci_gpu_candidates: Self::detect_lspci_gpus(),
+            reader: SysfsReader::new(),
+            #[cfg(target_os = "linux")]
+            lspci_gpu_candidates,
             #[cfg(target_os = "linux")]
             intel_drm_card_path: Self::detect_intel_drm_card_path(),
             #[cfg(target_os = "linux")]
+            intel_integrated: None,
+            #[cfg(target_os = "linux")]
             intel_rc6_paths: Vec::new(),
             #[cfg(target_os = "linux")]
             intel_gt_cur_freq_paths: Vec::new(),
@@ -87,6 +195,12 @@ impl DataCollector {
             #[cfg(target_os = "linux")]
             intel_debugfs_mem_path: None,
             #[cfg(target_os = "linux")]
+            drm_fdinfo_paths: Vec::new(),
+            #[cfg(target_os = "linux")]
+            drm_fd_scan_countdown: 0,
+            #[cfg(target_os = "linux")]
+            drm_fd_scan_complete: false,
+            #[cfg(target_os = "linux")]
             previous_intel_rc6_by_path: HashMap::new(),
             #[cfg(target_os = "linux")]
             intel_gpu_rapl_energy_path: None,
@@ -100,11 +214,129 @@ impl DataCollector {
             previous_rapl_energy: None,
             #[cfg(target_os = "linux")]
             previous_rapl_time: None,
+            #[cfg(target_os = "linux")]
+            rapl_paths: None,
+            #[cfg(target_os = "linux")]
+            throttle_baseline: None,
+            #[cfg(target_os = "linux")]
+            throttle_previous: ThrottleStats::default(),
+            #[cfg(target_os = "linux")]
+            previous_schedstat: HashMap::new(),
+            #[cfg(target_os = "linux")]
+            previous_cpu_times: (None, HashMap::new()),
             process_cpu_ema: HashMap::new(),
+            process_cpu_history: HashMap::new(),
+            process_cpu_accumulated: HashMap::new(),
+            leak_tracker: LeakTracker::new(std::time::Duration::from_secs(600)),
+            sensor_overrides: SensorOverrides::default(),
+            gpu_filter: GpuFilter::default(),
+            interface_topology: HashMap::new(),
+            inspected_pid: None,
+            selected_pid: None,
+            restricted_sources: Vec::new(),
+            #[cfg(target_os = "linux")]
+            wifi_power_save: HashMap::new(),
+            #[cfg(target_os = "linux")]
+            previous_page_faults: HashMap::new(),
+            #[cfg(target_os = "linux")]
+            last_cpu_history: HashMap::new(),
+            primary_ip: None,
+            user_names: HashMap::new(),
+            #[cfg(target_os = "linux")]
+            container_pids: HashMap::new(),
+            #[cfg(target_os = "linux")]
+            previous_snmp: None,
+            cmd_max_args: 3,
+            exe_max_len: 200,
+            full_command_lines: false,
+            probes: None,
+            profiler: None,
+            own_pid: sysinfo::get_current_pid().ok().map(|pid| pid.as_u32()),
+            #[cfg(target_os = "linux")]
+            mount_probes: MountProbes::new(MountProbes::STALE_AFTER),
+            commands,
+        }
+    }
+
+    /// Limit how much of each process command line and executable path is kept (0 = unlimited)
+    pub fn set_command_limits(&mut self, cmd_max_args: usize, exe_max_len: usize) {
+        self.cmd_max_args = cmd_max_args;
+        self.exe_max_len = exe_max_len;
+    }
+
+    /// Keep complete command lines regardless of the configured limits
+    pub fn set_full_command_lines(&mut self, enabled: bool) {
+        self.full_command_lines = enabled;
+    }
+
+    /// Break down the open files of `pid` by mount point (`None` stops)
+    pub fn set_inspected_process(&mut self, pid: Option<u32>) {
+        self.inspected_pid = pid;
+    }
+
+    /// Send the recent CPU% samples of `pid` with the snapshots; the other processes only keep
+    /// theirs in the collector, so every snapshot does not carry a series per process
+    pub fn set_selected_process(&mut self, pid: Option<u32>) {
+        self.selected_pid = pid;
+    }
+
+    /// Rename or leave out temperature sensors, everywhere they are shown
+    pub fn set_sensor_overrides(&mut self, overrides: SensorOverrides) {
+        self.sensor_overrides = overrides;
+    }
+
+    /// Leave out GPUs, skipping the sources of hidden vendors, and order the rest
+    pub fn set_gpu_filter(&mut self, filter: GpuFilter) {
+        self.gpu_filter = filter;
+    }
+
+    /// How long memory must grow without shrinking before a process is flagged (zero disables)
+    pub fn set_leak_window(&mut self, window: std::time::Duration) {
+        self.leak_tracker.set_window(window);
+    }
+
+    /// Continue the CPU, memory and network graphs of a previous session. CPU history is only
+    /// kept when the core count still matches.
+    pub fn restore_history(&mut self, saved: crate::saved_history::SavedHistory) {
+        let saved = saved.capped(Self::HISTORY_LEN);
+        let Some(&last) = saved.times.back() else {
+            return;
+        };
+        self.cpu_history = saved.cpu;
+        self.memory_history = saved.memory;
+        self.swap_history = saved.swap;
+        self.network_history = saved.network;
+        self.history_times = saved.times;
+        self.restored_until = Some(last);
+    }
+
+    /// Include results from a background probe task (latency monitor) in each snapshot
+    pub fn set_probe_source(&mut self, probes: crate::data::probes::SharedProbes) {
+        self.probes = Some(probes);
+    }
+
+    /// Record per-phase timings on every following `collect` call
+    pub fn enable_profiling(&mut self) {
+        self.profiler = Some(PhaseProfiler::new());
+    }
+
+    pub fn take_profiler(&mut self) -> Option<PhaseProfiler> {
+        self.profiler.take()
+    }
+
+    fn profile_mark(&mut self, phase: &'static str) {
+        if let Some(profiler) = self.profiler.as_mut() {
+            profiler.mark(phase);
         }
     }
 
     const HISTORY_LEN: usize = 120;
+    /// Last-CPU observations kept per process for the core occupancy strip (fits in a u8 count)
+    #[cfg(target_os = "linux")]
+    const AFFINITY_SAMPLES: usize = 60;
+    /// CPU% samples kept per process for the selected process's graph
+    const PROCESS_CPU_SAMPLES: usize = 60;
+    const DISK_FILL_MIN_SAMPLES: usize = 10;
 
     fn initialize_nvml() -> Option<Nvml> {
         // Initialize NVML but catch any errors that might occur during initialization
@@ -129,40 +361,105 @@ impl DataCollector {
             }
         }
     }
+
+    /// Per-process use of one NVIDIA GPU, keyed by PID: memory from the running compute and
+    /// graphics process lists, utilization from the samples newer than `last_seen`
+    fn nvml_process_usage(
+        device: &nvml_wrapper::Device,
+        gpu: &str,
+        last_seen: &mut u64,
+    ) -> HashMap<u32, ProcessGpuUsage> {
+        let mut usage: HashMap<u32, ProcessGpuUsage> = HashMap::new();
+        let unsampled = || ProcessGpuUsage {
+            gpu: gpu.to_string(),
+            utilization: None,
+            encoder: 0,
+            decoder: 0,
+            memory: None,
+        };
+
+        let running = device
+            .running_compute_processes()
+            .unwrap_or_default()
+            .into_iter()
+            .chain(device.running_graphics_processes().unwrap_or_default());
+        for process in running {
+            let used = usage.entry(process.pid).or_insert_with(unsampled);
+            // A process doing both compute and graphics is listed twice with the same memory
+            if let nvml_wrapper::enums::device::UsedGpuMemory::Used(bytes) = process.used_gpu_memory
+            {
+                used.memory = Some(used.memory.unwrap_or(0).max(bytes));
+            }
+        }
+
+        // Fails with NotFound when no process ran since `last_seen`; keep the newest sample
+        // of each process when the window holds several
+        let mut samples = device
+            .process_utilization_stats(*last_seen)
+            .unwrap_or_default();
+        samples.sort_by_key(|sample| sample.timestamp);
+        for sample in samples {
+            *last_seen = (*last_seen).max(sample.timestamp);
+            let used = usage.entry(sample.pid).or_insert_with(unsampled);
+            used.utilization = Some(sample.sm_util);
+            used.encoder = sample.enc_util;
+            used.decoder = sample.dec_util;
+        }
+        usage
+    }
+
     pub fn collect(&mut self) -> SystemSnapshot {
-        // More granular refreshes to improve performance
+        let collect_started = std::time::Instant::now();
+        if let Some(profiler) = self.profiler.as_mut() {
+            profiler.begin();
+        }
+        self.restricted_sources.clear();
+
+        // More granular refreshes to improve performance. On Linux per-core usage is read from
+        // /proc/stat directly, as sysinfo sizes its core list once and cannot follow hot-plugging
+        #[cfg(not(target_os = "linux"))]
         self.system.refresh_cpu();
         self.system.refresh_memory();
         self.system.refresh_networks_list(); // Refresh network list separately
-        self.system.refresh_disks_list();
-        self.system.refresh_disks();
         self.system.refresh_components(); // Refresh components separately
                                           // Only refresh processes if needed (configurable)
         self.system.refresh_processes(); // Refresh processes separately
+        self.profile_mark("refresh");
 
         let elapsed = self.last_update_time.elapsed().as_secs_f64();
         self.last_update_time = std::time::Instant::now();
 
-        let cpu_count = self.system.cpus().len();
+        // One slot per present core; offline cores keep theirs so core numbers stay put
+        let (global_cpu_usage, core_usage) = self.collect_core_usage();
+        let cpu_count = core_usage.len();
+        let online_cpus = core_usage.iter().flatten().count();
         if self.cpu_history.len() != cpu_count {
             self.cpu_history = (0..cpu_count)
                 .map(|_| VecDeque::with_capacity(Self::HISTORY_LEN))
                 .collect();
         }
 
-        for (i, cpu) in self.system.cpus().iter().enumerate() {
-            Self::push_history_point(&mut self.cpu_history[i], cpu.cpu_usage());
+        for (history, usage) in self.cpu_history.iter_mut().zip(&core_usage) {
+            // Offline cores read 0% so every series stays aligned with the timestamps
+            Self::push_history_point(history, usage.unwrap_or(0.0));
         }
 
+        Self::push_history_point(&mut self.history_times, chrono::Local::now().timestamp());
+
+        // Memory history tracks pressure (total minus MemAvailable), not used/total
+        let memory = self.collect_memory_breakdown();
+        let total_memory = self.system.total_memory();
         Self::push_history_point(
             &mut self.memory_history,
-            (self.system.used_memory(), self.system.total_memory()),
+            (total_memory.saturating_sub(memory.available), total_memory),
         );
         Self::push_history_point(
             &mut self.swap_history,
             (self.system.used_swap(), self.system.total_swap()),
         );
 
+        self.profile_mark("history");
+
         // Update network interfaces and calculate rates
         self.system.refresh_networks();
         // Collect network data to get count and iterate
@@ -182,6 +479,18 @@ impl DataCollector {
         let mut network_interfaces = HashMap::with_capacity(network_data.len());
         let mut networks = Vec::with_capacity(network_data.len());
 
+        // Bond/bridge/VLAN relationships only change when interfaces come and go.
+        let topology_stale = network_data.len() != self.interface_topology.len()
+            || network_data
+                .iter()
+                .any(|(name, _, _)| !self.interface_topology.contains_key(name));
+        if topology_stale {
+            self.interface_topology = network_data
+                .iter()
+                .map(|(name, _, _)| (name.clone(), Self::detect_interface_topology(name)))
+                .collect();
+        }
+
         for (interface_name, current_received, current_transmitted) in network_data {
             let current_data = (current_received, current_transmitted);
             network_interfaces.insert(interface_name.to_string(), current_data);
@@ -217,75 +526,272 @@ impl DataCollector {
                 (current_received, current_transmitted),
             );
 
+            let (kind, parent) = self
+                .interface_topology
+                .get(&interface_name)
+                .cloned()
+                .unwrap_or((InterfaceKind::Plain, None));
+            let (mtu, power_save, oper_state) = self.interface_link_settings(&interface_name);
             networks.push(NetworkInfo {
                 name: interface_name.to_string(),
                 total_received: current_received,
                 total_transmitted: current_transmitted,
                 received_per_sec,
                 transmitted_per_sec,
+                kind,
+                parent,
+                mtu,
+                power_save,
+                oper_state,
             });
         }
         self.previous_network_values
             .retain(|name, _| network_interfaces.contains_key(name));
+        #[cfg(target_os = "linux")]
+        self.wifi_power_save
+            .retain(|name, _| network_interfaces.contains_key(name));
 
-        let total_rx_rate = networks.iter().map(|n| n.received_per_sec).sum::<u64>();
-        let total_tx_rate = networks.iter().map(|n| n.transmitted_per_sec).sum::<u64>();
+        // Members of a bond/bridge and VLANs are already counted on their parent.
+        let top_level = || networks.iter().filter(|n| n.parent.is_none());
+        let total_rx_rate = top_level().map(|n| n.received_per_sec).sum::<u64>();
+        let total_tx_rate = top_level().map(|n| n.transmitted_per_sec).sum::<u64>();
         Self::push_history_point(&mut self.network_history, (total_rx_rate, total_tx_rate));
+        let protocol_stats = self.collect_protocol_stats();
+        let socket_stats = self.collect_socket_stats();
+        let kernel_limits = self.collect_kernel_limits();
+        self.profile_mark("network");
 
         // Update temperature sensors
         let temperature_sensors = self.update_temperature_sensors();
 
         // Update CPU frequencies with kernel-file fallbacks for systems where sysinfo reports 0.
         let cpu_frequencies = self.collect_cpu_frequencies(cpu_count);
+        self.profile_mark("sensors");
 
-        // sysinfo process CPU semantics differ across platforms/versions:
-        // some report 0..100, others 0..(cores*100). Normalize only when needed.
-        let cpu_normalization = cpu_count.max(1) as f32;
+        // sysinfo reports process CPU relative to one core; the process list decides whether to
+        // show that (Irix mode) or divide by the core count (Solaris mode).
+        let max_process_cpu = 100.0 * online_cpus.max(1) as f32;
+        // sysinfo divides by the cores it found at startup; rescale once some went offline
+        let process_cpu_scale = online_cpus.max(1) as f32 / self.system.cpus().len().max(1) as f32;
         let smoothing_alpha = (elapsed as f32 / 1.5).clamp(0.35, 1.0);
         let mut next_process_cpu_ema: HashMap<u32, f32> =
             HashMap::with_capacity(self.system.processes().len());
+        let mut next_process_cpu_accumulated: HashMap<u32, (f64, std::time::Instant)> =
+            HashMap::with_capacity(self.system.processes().len());
+        let mut previous_cpu_history = std::mem::take(&mut self.process_cpu_history);
+        let mut next_cpu_history: HashMap<u32, VecDeque<f32>> =
+            HashMap::with_capacity(self.system.processes().len());
+        let now = std::time::Instant::now();
+        #[cfg(target_os = "linux")]
+        let mut next_page_faults: HashMap<u32, (u64, u64)> =
+            HashMap::with_capacity(self.system.processes().len());
+        #[cfg(target_os = "linux")]
+        let mut proc_buf = String::new();
+        #[cfg(target_os = "linux")]
+        let mut previous_last_cpu = std::mem::take(&mut self.last_cpu_history);
+        #[cfg(target_os = "linux")]
+        let mut next_last_cpu: HashMap<u32, VecDeque<u16>> =
+            HashMap::with_capacity(self.system.processes().len());
+        #[cfg(target_os = "linux")]
+        let previous_containers = std::mem::take(&mut self.container_pids);
+        #[cfg(target_os = "linux")]
+        let mut next_containers: HashMap<u32, bool> =
+            HashMap::with_capacity(self.system.processes().len());
+        #[cfg(target_os = "linux")]
+        let own_namespaces = Self::namespaces("self");
+        let (cmd_max_args, exe_max_len) = if self.full_command_lines {
+            (usize::MAX, usize::MAX)
+        } else {
+            (
+                Self::limit_or_unbounded(self.cmd_max_args),
+                Self::limit_or_unbounded(self.exe_max_len),
+            )
+        };
+
+        #[cfg(unix)]
+        self.resolve_user_names();
+
+        self.leak_tracker.observe(
+            now,
+            self.system
+                .processes()
+                .values()
+                .map(|process| (process.pid().as_u32(), process.memory())),
+        );
+
+        // sysinfo reports I/O bytes since the previous refresh
+        let per_second = |bytes: u64| {
+            if elapsed > 0.0 {
+                (bytes as f64 / elapsed) as u64
+            } else {
+                0
+            }
+        };
 
         // Create process info - only collect essential information to reduce memory usage
-        let processes: Vec<ProcessInfo> = self
+        let mut processes: Vec<ProcessInfo> = self
             .system
             .processes()
             .values()
             .map(|process| {
                 let pid = process.pid().as_u32();
-                let raw_cpu = process.cpu_usage();
-                let normalized_cpu = if raw_cpu > 100.0 {
-                    (raw_cpu / cpu_normalization).clamp(0.0, 100.0)
-                } else {
-                    raw_cpu.clamp(0.0, 100.0)
-                };
+                #[cfg(unix)]
+                let uid = process.user_id().map(|uid| **uid);
+                #[cfg(not(unix))]
+                let uid = None;
+                let normalized_cpu =
+                    (process.cpu_usage() * process_cpu_scale).clamp(0.0, max_process_cpu);
                 let smoothed_cpu = self
                     .process_cpu_ema
                     .get(&pid)
                     .map(|prev| prev + (normalized_cpu - prev) * smoothing_alpha)
                     .unwrap_or(normalized_cpu);
                 next_process_cpu_ema.insert(pid, smoothed_cpu);
+                let mut cpu_history = previous_cpu_history.remove(&pid).unwrap_or_default();
+                if cpu_history.len() >= Self::PROCESS_CPU_SAMPLES {
+                    cpu_history.pop_front();
+                }
+                cpu_history.push_back(smoothed_cpu);
+                let cpu_samples: Vec<f32> =
+                    if self.selected_pid == Some(pid) || self.inspected_pid == Some(pid) {
+                        cpu_history.iter().copied().collect()
+                    } else {
+                        Vec::new()
+                    };
+                next_cpu_history.insert(pid, cpu_history);
+
+                // Integrating each interval's usage gives CPU time consumed since first seen.
+                let (accumulated, first_seen) = match self.process_cpu_accumulated.get(&pid) {
+                    Some(&(accumulated, first_seen)) => (
+                        accumulated + f64::from(normalized_cpu) * elapsed,
+                        first_seen,
+                    ),
+                    None => (0.0, now),
+                };
+                next_process_cpu_accumulated.insert(pid, (accumulated, first_seen));
+                let observed = now.duration_since(first_seen).as_secs_f64();
+                let cumulative_cpu = if observed > 0.0 {
+                    (accumulated / observed) as f32
+                } else {
+                    normalized_cpu
+                };
+
+                // Page faults and swap are not exposed by sysinfo; read them from procfs.
+                #[cfg(target_os = "linux")]
+                let (
+                    minor_faults_per_sec,
+                    major_faults_per_sec,
+                    swap,
+                    sched,
+                    core_samples,
+                    last_cpu,
+                ) = {
+                    use crate::data::procfs;
+                    let mut rates = (0.0, 0.0);
+                    let mut sched = (SchedClass::Normal, 0, false, 0);
+                    let mut core_samples = Vec::new();
+                    let mut last_cpu = None;
+                    if procfs::read_pid_file(pid, "stat", &mut proc_buf) {
+                        if let Some(stat) = procfs::parse_stat(&proc_buf) {
+                            if let Some(processor) = stat.processor {
+                                let mut history =
+                                    previous_last_cpu.remove(&pid).unwrap_or_default();
+                                if history.len() >= Self::AFFINITY_SAMPLES {
+                                    history.pop_front();
+                                }
+                                history.push_back(processor as u16);
+                                core_samples = vec![0u8; cpu_count];
+                                for &core in &history {
+                                    if let Some(count) = core_samples.get_mut(core as usize) {
+                                        *count += 1;
+                                    }
+                                }
+                                next_last_cpu.insert(pid, history);
+                                last_cpu = Some(processor);
+                            }
+                            sched = (
+                                SchedClass::from_policy(stat.policy),
+                                stat.rt_priority as u32,
+                                stat.is_kernel_thread(),
+                                stat.nice,
+                            );
+                            if let Some(&(prev_minor, prev_major)) =
+                                self.previous_page_faults.get(&pid)
+                            {
+                                if elapsed > 0.0 {
+                                    rates = (
+                                        (stat.minor_faults.saturating_sub(prev_minor) as f64
+                                            / elapsed)
+                                            as f32,
+                                        (stat.major_faults.saturating_sub(prev_major) as f64
+                                            / elapsed)
+                                            as f32,
+                                    );
+                                }
+                            }
+                            next_page_faults.insert(pid, (stat.minor_faults, stat.major_faults));
+                        }
+                    }
+                    let swap = if procfs::read_pid_file(pid, "status", &mut proc_buf) {
+                        procfs::parse_status_kb(&proc_buf, "VmSwap").unwrap_or(0)
+                    } else {
+                        0
+                    };
+                    (rates.0, rates.1, swap, sched, core_samples, last_cpu)
+                };
+                #[cfg(not(target_os = "linux"))]
+                let (
+                    minor_faults_per_sec,
+                    major_faults_per_sec,
+                    swap,
+                    sched,
+                    core_samples,
+                    last_cpu,
+                ) = (
+                    0.0,
+                    0.0,
+                    0,
+                    (SchedClass::Normal, 0, false, 0),
+                    Vec::new(),
+                    None,
+                );
+
+                #[cfg(target_os = "linux")]
+                let container = {
+                    let container = previous_containers.get(&pid).copied().unwrap_or_else(|| {
+                        Self::detect_container(pid, own_namespaces.as_deref(), &mut proc_buf)
+                    });
+                    next_containers.insert(pid, container);
+                    container
+                };
+                #[cfg(not(target_os = "linux"))]
+                let container = false;
 
                 ProcessInfo {
                     pid,
                     name: process.name().to_string(),
                     memory: process.memory(),
                     cpu_usage: smoothed_cpu,
+                    cumulative_cpu,
                     disk_usage: process.disk_usage().total_written_bytes,
+                    disk_read_per_sec: per_second(process.disk_usage().read_bytes),
+                    disk_write_per_sec: per_second(process.disk_usage().written_bytes),
                     parent_pid: process.parent().map(|pid| pid.as_u32()),
-                    cmd: {
-                        let cmd = process.cmd();
-                        if cmd.len() > 3 {
-                            // Only keep first few command args to save memory
-                            cmd.iter().take(3).map(|s| s.to_string()).collect()
-                        } else {
-                            cmd.iter().map(|s| s.to_string()).collect()
-                        }
-                    },
+                    start_time: process.start_time(),
+                    run_time: process.run_time(),
+                    uid,
+                    user: uid.and_then(|uid| self.user_names.get(&uid).cloned().flatten()),
+                    // Only keep the first few command args to save memory
+                    cmd: process
+                        .cmd()
+                        .iter()
+                        .take(cmd_max_args)
+                        .map(|s| s.to_string())
+                        .collect(),
                     exe: {
                         // Only store exe path if it's reasonably short to save memory
                         let exe_path = process.exe().to_string_lossy();
-                        if exe_path.len() < 200 {
-                            // Limit path length
+                        if exe_path.len() < exe_max_len {
                             Some(exe_path.to_string())
                         } else {
                             None // Skip storing very long paths
@@ -293,45 +799,98 @@ impl DataCollector {
                     },
                     root: None, // Skip root path to save memory
                     cwd: None,  // Skip current working directory to save memory
-                    status: format!("{:?}", process.status()),
+                    state: ProcessState::from_status(process.status()),
+                    major_faults_per_sec,
+                    minor_faults_per_sec,
+                    swap,
+                    leak_rate: self.leak_tracker.growth_rate(pid),
+                    core_samples,
+                    cpu_history: cpu_samples,
+                    last_cpu,
+                    sched_class: sched.0,
+                    rt_priority: sched.1,
+                    nice: sched.3,
+                    kernel_thread: sched.2,
+                    container,
+                    gpu: Vec::new(),
                 }
             })
             .collect();
         self.process_cpu_ema = next_process_cpu_ema;
+        self.process_cpu_history = next_cpu_history;
+        self.process_cpu_accumulated = next_process_cpu_accumulated;
+        #[cfg(target_os = "linux")]
+        {
+            self.previous_page_faults = next_page_faults;
+            self.last_cpu_history = next_last_cpu;
+            self.container_pids = next_containers;
+        }
+        self.profile_mark("processes");
 
         // Create deduplicated disk info (avoid double-counting btrfs subvolumes/multi-mount entries).
-        let disks = self.collect_disks();
+        let mut disks = self.collect_disks();
+        self.profile_mark("disks");
 
         // Collect GPU info
         let mut gpus: Vec<crate::data::snapshot::GpuInfo> = Vec::with_capacity(4); // Assume max 4 GPUs to pre-allocate
+
+        // Hidden vendors, and an integrated GPU that is left out, are not even queried
         #[cfg(target_os = "linux")]
-        {
+        let intel_wanted = !(self.gpu_filter.hides_vendor("Intel")
+            || (self.gpu_filter.hide_integrated && self.intel_gpu_integrated()));
+        #[cfg(target_os = "linux")]
+        if intel_wanted {
             self.ensure_intel_gpu_paths();
         }
         #[cfg(target_os = "linux")]
-        let (intel_usage, intel_usage_note) = self.get_intel_gpu_usage_with_note();
+        let (intel_usage, intel_usage_note) = if intel_wanted {
+            self.get_intel_gpu_usage_with_note()
+        } else {
+            (None, None)
+        };
         #[cfg(not(target_os = "linux"))]
         let (intel_usage, intel_usage_note): (Option<f32>, Option<String>) = (None, None);
         #[cfg(target_os = "linux")]
-        let (intel_temp, intel_temp_note) =
-            self.get_intel_gpu_temperature_with_note(&temperature_sensors);
+        let (intel_temp, intel_temp_note) = if intel_wanted {
+            self.get_intel_gpu_temperature_with_note(&temperature_sensors)
+        } else {
+            (None, None)
+        };
         #[cfg(not(target_os = "linux"))]
         let (intel_temp, intel_temp_note): (Option<f32>, Option<String>) = (None, None);
         #[cfg(target_os = "linux")]
-        let (intel_mem_used, intel_mem_note) = self.get_intel_gpu_memory_usage_with_note();
+        let (intel_mem_used, intel_mem_note) = if intel_wanted {
+            self.get_intel_gpu_memory_usage_with_note()
+        } else {
+            (None, None)
+        };
         #[cfg(not(target_os = "linux"))]
         let (intel_mem_used, intel_mem_note): (Option<u64>, Option<String>) = (None, None);
         #[cfg(target_os = "linux")]
-        let intel_mem_total = self.get_intel_gpu_shared_memory_total(intel_mem_used);
+        let intel_mem_total = if intel_wanted {
+            self.get_intel_gpu_shared_memory_total(intel_mem_used)
+        } else {
+            None
+        };
         #[cfg(not(target_os = "linux"))]
         let intel_mem_total: Option<u64> = None;
         #[cfg(target_os = "linux")]
-        let (intel_gpu_power, intel_power_note) = self.get_intel_gpu_power_consumption_with_note();
+        let (intel_gpu_power, intel_power_note) = if intel_wanted {
+            self.get_intel_gpu_power_consumption_with_note()
+        } else {
+            (None, None)
+        };
         #[cfg(not(target_os = "linux"))]
         let (intel_gpu_power, intel_power_note): (Option<f32>, Option<String>) = (None, None);
 
         // Collect NVIDIA GPUs using NVML
-        if let Some(nvml) = &self.nvml {
+        let nvml = self
+            .nvml
+            .as_ref()
+            .filter(|_| !self.gpu_filter.hides_vendor("NVIDIA"));
+        let process_gpu_stats = nvml.is_some();
+        let mut process_gpus: HashMap<u32, Vec<ProcessGpuUsage>> = HashMap::new();
+        if let Some(nvml) = nvml {
             if let Ok(device_count) = nvml.device_count() {
                 for i in 0..device_count.min(4) {
                     // Limit to max 4 GPUs to prevent excessive allocation
@@ -351,6 +910,10 @@ impl DataCollector {
                             .memory_info()
                             .map_or((None, None), |mem| (Some(mem.used), Some(mem.total)));
                         let power_usage = device.power_usage().map(|p| p as f32 / 1000.0).ok(); // Convert from milliwatts to watts
+                        let last_seen = self.nvml_sample_seen.entry(i).or_insert(0);
+                        for (pid, usage) in Self::nvml_process_usage(&device, &name, last_seen) {
+                            process_gpus.entry(pid).or_default().push(usage);
+                        }
 
                         gpus.push(crate::data::snapshot::GpuInfo {
                             name,
@@ -510,8 +1073,13 @@ impl DataCollector {
             }
         }
 
+        self.profile_mark("gpu");
+
         // Get CPU power consumption before other operations to avoid borrow checker issues
         let cpu_power = self.get_cpu_power_consumption();
+        let throttle = self.collect_throttle_stats(cpu_count);
+        let run_queue_wait_us = self.collect_run_queue_wait(cpu_count);
+        self.profile_mark("power");
 
         // Linux fallback GPU detection via cached lspci data (captured once at startup)
         #[cfg(target_os = "linux")]
@@ -619,18 +1187,45 @@ impl DataCollector {
             }
         }
 
-        self.update_disk_history();
+        self.gpu_filter.apply(&mut gpus);
+        for process in &mut processes {
+            if let Some(usage) = process_gpus.remove(&process.pid) {
+                process.gpu = usage;
+            }
+        }
+
+        self.update_disk_history(&mut disks);
+        self.profile_mark("disk-trend");
 
-        SystemSnapshot {
-            global_cpu_usage: self.system.global_cpu_info().cpu_usage(),
+        let mut self_usage = self
+            .own_pid
+            .and_then(|own_pid| processes.iter().find(|process| process.pid == own_pid))
+            .map(|process| SelfUsage {
+                cpu_usage: process.cpu_usage,
+                memory: process.memory,
+                collect_ms: 0.0,
+            })
+            .unwrap_or_default();
+        self_usage.collect_ms = collect_started.elapsed().as_secs_f64() * 1000.0;
+        let task_totals = self.collect_task_totals(processes.len());
+
+        let snapshot = SystemSnapshot {
+            global_cpu_usage,
             used_memory: self.system.used_memory(),
             total_memory: self.system.total_memory(),
             used_swap: self.system.used_swap(),   // Added
             total_swap: self.system.total_swap(), // Added
-            cpu_count,
-            cached_memory: self.get_cached_memory(),
+            cpu_count: online_cpus,
+            cpu_online: core_usage.iter().map(Option::is_some).collect(),
+            cached_memory: memory.cached,
+            buffers_memory: memory.buffers,
+            free_memory: memory.free,
+            available_memory: memory.available,
+            zfs_arc: self.collect_zfs_arc(),
             cpu_history: self.cpu_history.clone(),
             memory_history: self.memory_history.clone(),
+            history_times: self.history_times.clone(),
+            history_gap: self.restored_until,
             swap_history: self.swap_history.clone(),
             network_interfaces,
             selected_network_interface: None,
@@ -643,14 +1238,19 @@ impl DataCollector {
             disks,
             networks,
             gpus, // Assign collected GPUs
+            process_gpu_stats,
             hostname: self.get_hostname(),
+            primary_ip: self.get_primary_ip(),
             uptime: self.get_uptime(),
             load_avg: self.get_load_avg(),
             process_sort_by: crate::data::snapshot::ProcessSortBy::CpuUsage,
+            process_sort_reversed: false,
             chart_type: crate::data::snapshot::ChartType::CpuUsage,
             color_scheme: crate::data::snapshot::ColorScheme::Default,
             auto_update: true,
             cpu_power, // Assign the collected CPU power
+            throttle,
+            run_queue_wait_us,
             cpu_name: if let Some(cpu) = self.system.cpus().first() {
                 cpu.brand().to_string()
             } else {
@@ -659,16 +1259,498 @@ impl DataCollector {
             update_interval: 1000, // Default value, should be configurable
             show_colors: true,     // Default value, should be configurable
             show_graphs: true,     // Default value, should be configurable
+            protocol_stats,
+            socket_stats,
+            kernel_limits,
+            inspected_files: self
+                .inspected_pid
+                .and_then(|pid| self.collect_open_files_by_mount(pid)),
+            inspected_process: self
+                .inspected_pid
+                .and_then(|pid| self.collect_process_detail(pid)),
+            elevated: Self::is_elevated(),
+            restricted_sources: self
+                .restricted_sources
+                .iter()
+                .map(|source| source.to_string())
+                .collect(),
+            self_usage,
+            task_totals,
+            probes: self
+                .probes
+                .as_ref()
+                .and_then(|probes| probes.lock().ok().map(|probes| probes.clone()))
+                .unwrap_or_default(),
+        };
+        self.profile_mark("snapshot");
+        snapshot
+    }
+
+    #[cfg(target_os = "linux")]
+    fn collect_protocol_stats(&mut self) -> Option<ProtocolStats> {
+        let mut counters = HashMap::new();
+        for path in ["/proc/net/snmp", "/proc/net/netstat"] {
+            if let Ok(content) = self.reader.read_str(path) {
+                crate::data::procfs::parse_snmp_counters(content, &mut counters);
+            }
+        }
+        if counters.is_empty() {
+            return None;
+        }
+
+        let now = std::time::Instant::now();
+        let previous = self.previous_snmp.replace((counters, now));
+        let (previous, previous_time) = previous?;
+        let (current, _) = self.previous_snmp.as_ref()?;
+        let elapsed = now.duration_since(previous_time).as_secs_f64();
+        if elapsed <= 0.0 {
+            return None;
+        }
+
+        let delta = |key: &str| -> f64 {
+            match (current.get(key), previous.get(key)) {
+                (Some(now), Some(before)) => now.saturating_sub(*before).max(0) as f64,
+                _ => 0.0,
+            }
+        };
+        let rate = |key: &str| delta(key) / elapsed;
+
+        let out_segs = delta("Tcp.OutSegs");
+        let retrans = delta("Tcp.RetransSegs");
+        Some(ProtocolStats {
+            tcp_in_per_sec: rate("Tcp.InSegs"),
+            tcp_out_per_sec: rate("Tcp.OutSegs"),
+            udp_in_per_sec: rate("Udp.InDatagrams"),
+            udp_out_per_sec: rate("Udp.OutDatagrams"),
+            icmp_in_per_sec: rate("Icmp.InMsgs"),
+            icmp_out_per_sec: rate("Icmp.OutMsgs"),
+            ip_in_bytes_per_sec: rate("IpExt.InOctets"),
+            ip_out_bytes_per_sec: rate("IpExt.OutOctets"),
+            tcp_retrans_per_sec: retrans / elapsed,
+            tcp_retrans_percent: if out_segs > 0.0 {
+                retrans * 100.0 / out_segs
+            } else {
+                0.0
+            },
+        })
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    fn collect_protocol_stats(&mut self) -> Option<ProtocolStats> {
+        None
+    }
+
+    #[cfg(target_os = "linux")]
+    fn collect_socket_stats(&mut self) -> Option<SocketStats> {
+        use crate::data::procfs::{count_tcp_states, TcpStateCounts};
+
+        let ephemeral_range = self
+            .reader
+            .read_str("/proc/sys/net/ipv4/ip_local_port_range")
+            .ok()
+            .and_then(|content| {
+                let mut ports = content.split_whitespace().map(|p| p.parse::<u16>().ok());
+                Some((ports.next()??, ports.next()??))
+            })
+            .unwrap_or((32768, 60999));
+
+        let mut counts = TcpStateCounts::default();
+        let mut any_table = false;
+        for path in ["/proc/net/tcp", "/proc/net/tcp6"] {
+            if let Ok(content) = self.reader.read_str(path) {
+                count_tcp_states(content, ephemeral_range, &mut counts);
+                any_table = true;
+            }
+        }
+        if !any_table {
+            return None;
         }
+
+        let mut stats = SocketStats {
+            established: counts.established,
+            time_wait: counts.time_wait,
+            close_wait: counts.close_wait,
+            listen: counts.listen,
+            ephemeral_in_use: counts.ephemeral_in_use,
+            ephemeral_range_size: u32::from(ephemeral_range.1.saturating_sub(ephemeral_range.0))
+                + 1,
+            ..Default::default()
+        };
+
+        if let Ok(content) = self.reader.read_str("/proc/net/sockstat") {
+            for line in content.lines() {
+                let fields: Vec<&str> = line.split_whitespace().collect();
+                let value_of = |key: &str| {
+                    fields
+                        .iter()
+                        .position(|field| *field == key)
+                        .and_then(|i| fields.get(i + 1)?.parse::<u32>().ok())
+                };
+                match fields.first() {
+                    Some(&"sockets:") => stats.sockets_used = value_of("used").unwrap_or(0),
+                    Some(&"TCP:") => stats.orphaned = value_of("orphan").unwrap_or(0),
+                    _ => {}
+                }
+            }
+        }
+
+        Some(stats)
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    fn collect_socket_stats(&mut self) -> Option<SocketStats> {
+        None
+    }
+
+    #[cfg(target_os = "linux")]
+    fn collect_kernel_limits(&mut self) -> Option<KernelLimits> {
+        let mut limits = KernelLimits::default();
+
+        // file-nr: allocated, free (always 0 on modern kernels), max
+        let content = self.reader.read_str("/proc/sys/fs/file-nr").ok()?;
+        let values: Vec<u64> = content
+            .split_whitespace()
+            .filter_map(|v| v.parse().ok())
+            .collect();
+        if let [allocated, _, max] = values[..] {
+            limits.fds_allocated = allocated;
+            limits.fds_max = max;
+        }
+
+        limits.ptys = self
+            .reader
+            .read_value("/proc/sys/kernel/pty/nr")
+            .unwrap_or(0);
+        limits.ptys_max = self
+            .reader
+            .read_value("/proc/sys/kernel/pty/max")
+            .unwrap_or(0);
+        limits.pid_max = self
+            .reader
+            .read_value("/proc/sys/kernel/pid_max")
+            .unwrap_or(0);
+        // loadavg's fourth field is runnable/total scheduling entities, i.e. all threads
+        if let Ok(content) = self.reader.read_str("/proc/loadavg") {
+            limits.tasks = content
+                .split_whitespace()
+                .nth(3)
+                .and_then(|field| field.split_once('/'))
+                .and_then(|(_, total)| total.parse().ok())
+                .unwrap_or(0);
+        }
+        limits.entropy_avail = self
+            .reader
+            .read_value("/proc/sys/kernel/random/entropy_avail");
+        limits.entropy_pool = self.reader.read_value("/proc/sys/kernel/random/poolsize");
+
+        Some(limits)
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    fn collect_kernel_limits(&mut self) -> Option<KernelLimits> {
+        None
+    }
+
+    #[cfg(target_os = "linux")]
+    fn collect_zfs_arc(&mut self) -> Option<ZfsArc> {
+        let content = self.reader.read_str("/proc/spl/kstat/zfs/arcstats").ok()?;
+        Some(ZfsArc {
+            size: crate::data::procfs::parse_kstat(content, "size")?,
+            target: crate::data::procfs::parse_kstat(content, "c").unwrap_or(0),
+            min: crate::data::procfs::parse_kstat(content, "c_min").unwrap_or(0),
+        })
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    fn collect_zfs_arc(&mut self) -> Option<ZfsArc> {
+        None
+    }
+
+    /// Kind of an interface and the interface it is grouped under, from sysfs
+    #[cfg(target_os = "linux")]
+    fn detect_interface_topology(name: &str) -> (InterfaceKind, Option<String>) {
+        let base = std::path::Path::new("/sys/class/net").join(name);
+        let link_name = |path: std::path::PathBuf| {
+            std::fs::read_link(path)
+                .ok()
+                .and_then(|target| target.file_name()?.to_str().map(str::to_string))
+        };
+
+        let is_vlan = std::fs::read_to_string(base.join("uevent"))
+            .map(|uevent| uevent.lines().any(|line| line == "DEVTYPE=vlan"))
+            .unwrap_or(false);
+        let kind = if base.join("bonding").is_dir() {
+            InterfaceKind::Bond
+        } else if base.join("bridge").is_dir() {
+            InterfaceKind::Bridge
+        } else if is_vlan {
+            InterfaceKind::Vlan
+        } else {
+            InterfaceKind::Plain
+        };
+
+        // Bond slaves and bridge ports link to their master; a VLAN has a single lower_<dev>.
+        let parent = link_name(base.join("master")).or_else(|| {
+            if kind != InterfaceKind::Vlan {
+                return None;
+            }
+            std::fs::read_dir(&base).ok()?.flatten().find_map(|entry| {
+                entry
+                    .file_name()
+                    .to_str()?
+                    .strip_prefix("lower_")
+                    .map(str::to_string)
+            })
+        });
+
+        (kind, parent)
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    fn detect_interface_topology(_name: &str) -> (InterfaceKind, Option<String>) {
+        (InterfaceKind::Plain, None)
+    }
+
+    /// The namespace links of `/proc/<process>` a container has its own of, if all are readable
+    #[cfg(target_os = "linux")]
+    fn namespaces(process: &str) -> Option<Vec<std::path::PathBuf>> {
+        crate::data::procfs::CONTAINER_NAMESPACES
+            .iter()
+            .map(|ns| std::fs::read_link(format!("/proc/{}/ns/{}", process, ns)).ok())
+            .collect()
+    }
+
+    /// Whether `pid` runs in a container: in other PID, mount and UTS namespaces than rtop, or
+    /// in a container runtime's cgroup (which also covers other users' processes, whose
+    /// namespace links are not readable)
+    #[cfg(target_os = "linux")]
+    fn detect_container(
+        pid: u32,
+        own_namespaces: Option<&[std::path::PathBuf]>,
+        buf: &mut String,
+    ) -> bool {
+        let namespaces = Self::namespaces(&pid.to_string());
+        if let (Some(own), Some(namespaces)) = (own_namespaces, namespaces) {
+            if crate::data::procfs::in_other_namespaces(own, &namespaces) {
+                return true;
+            }
+        }
+        crate::data::procfs::read_pid_file(pid, "cgroup", buf)
+            && crate::data::procfs::is_container_cgroup(buf)
+    }
+
+    /// Full command line, paths, start time and memory split of `pid`
+    fn collect_process_detail(&self, pid: u32) -> Option<ProcessDetail> {
+        let process = self.system.process(sysinfo::Pid::from_u32(pid))?;
+        let path = |path: &std::path::Path| {
+            Some(path.to_string_lossy().into_owned()).filter(|path| !path.is_empty())
+        };
+        let (rss_anon, rss_file, rss_shmem) = Self::rss_split(pid);
+        Some(ProcessDetail {
+            pid,
+            cmd: process.cmd().to_vec(),
+            exe: path(process.exe()),
+            cwd: path(process.cwd()),
+            start_time: process.start_time(),
+            virtual_memory: process.virtual_memory(),
+            rss_anon,
+            rss_file,
+            rss_shmem,
+        })
     }
 
-    fn update_disk_history(&mut self) {
-        let disks = self.collect_disks();
+    /// Anonymous, file-backed and shared parts of `pid`'s resident memory
+    #[cfg(target_os = "linux")]
+    fn rss_split(pid: u32) -> (Option<u64>, Option<u64>, Option<u64>) {
+        use crate::data::procfs;
+        let mut buf = String::new();
+        if !procfs::read_pid_file(pid, "status", &mut buf) {
+            return (None, None, None);
+        }
+        (
+            procfs::parse_status_kb(&buf, "RssAnon"),
+            procfs::parse_status_kb(&buf, "RssFile"),
+            procfs::parse_status_kb(&buf, "RssShmem"),
+        )
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    fn rss_split(_pid: u32) -> (Option<u64>, Option<u64>, Option<u64>) {
+        (None, None, None)
+    }
+
+    /// Count `pid`'s open files per mount point, and how many are open for writing
+    #[cfg(target_os = "linux")]
+    fn collect_open_files_by_mount(&mut self, pid: u32) -> Option<OpenFilesByMount> {
+        use crate::data::procfs;
+        use crate::data::snapshot::MountOpenFiles;
+
+        // The fd links resolve in the process's own mount namespace (containers, sandboxes)
+        let mut buf = String::new();
+        if !procfs::read_pid_file(pid, "mounts", &mut buf) {
+            return None;
+        }
+        let mounts = procfs::parse_mount_points(&buf);
+        let mut result = OpenFilesByMount {
+            pid,
+            ..OpenFilesByMount::default()
+        };
+        let Ok(entries) = std::fs::read_dir(format!("/proc/{}/fd", pid)) else {
+            return Some(result);
+        };
+        result.accessible = true;
+
+        for entry in entries.flatten() {
+            // Sockets, pipes and anon inodes link to `type:[inode]`, not a path
+            let Ok(target) = std::fs::read_link(entry.path()) else {
+                continue;
+            };
+            let Some(path) = target.to_str().filter(|path| path.starts_with('/')) else {
+                continue;
+            };
+            let path = path.strip_suffix(" (deleted)").unwrap_or(path);
+            let Some(mount) = procfs::mount_for(path, &mounts) else {
+                continue;
+            };
+            let fdinfo = format!("fdinfo/{}", entry.file_name().to_string_lossy());
+            let writable =
+                procfs::read_pid_file(pid, &fdinfo, &mut buf) && procfs::fdinfo_writable(&buf);
+
+            match result.mounts.iter_mut().find(|m| m.mount == mount) {
+                Some(counts) => {
+                    counts.open += 1;
+                    counts.writable += usize::from(writable);
+                }
+                None => result.mounts.push(MountOpenFiles {
+                    mount: mount.to_string(),
+                    open: 1,
+                    writable: usize::from(writable),
+                }),
+            }
+        }
+        result.mounts.sort_by(|a, b| {
+            b.writable
+                .cmp(&a.writable)
+                .then(b.open.cmp(&a.open))
+                .then_with(|| a.mount.cmp(&b.mount))
+        });
+        Some(result)
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    fn collect_open_files_by_mount(&mut self, _pid: u32) -> Option<OpenFilesByMount> {
+        None
+    }
+
+    /// MTU and link state from sysfs and, for Wi-Fi, whether power saving is on. `iw` is a
+    /// subprocess, so its answer is reused for `POWER_SAVE_RECHECK`.
+    #[cfg(target_os = "linux")]
+    fn interface_link_settings(
+        &mut self,
+        name: &str,
+    ) -> (Option<u32>, Option<bool>, Option<String>) {
+        const POWER_SAVE_RECHECK: std::time::Duration = std::time::Duration::from_secs(30);
+
+        let base = std::path::Path::new("/sys/class/net").join(name);
+        let mtu = self.reader.read_value(base.join("mtu"));
+        let oper_state = self.reader.read_value(base.join("operstate"));
+        let wireless = self
+            .reader
+            .read_str(base.join("uevent"))
+            .is_ok_and(|uevent| uevent.lines().any(|line| line == "DEVTYPE=wlan"));
+        if !wireless {
+            return (mtu, None, oper_state);
+        }
+
+        let now = std::time::Instant::now();
+        if let Some(&(checked, state)) = self.wifi_power_save.get(name) {
+            if now.duration_since(checked) < POWER_SAVE_RECHECK {
+                return (mtu, state, oper_state);
+            }
+        }
+        // `iw dev wlan0 get power_save` prints `Power save: on`
+        let state = self
+            .commands
+            .run(
+                "iw",
+                &["dev", name, "get", "power_save"],
+                CommandRunner::TIMEOUT,
+            )
+            .and_then(|stdout| {
+                let value = stdout.split_once("Power save:")?.1.trim().to_string();
+                Some(value == "on")
+            });
+        self.wifi_power_save.insert(name.to_string(), (now, state));
+        (mtu, state, oper_state)
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    fn interface_link_settings(
+        &mut self,
+        _name: &str,
+    ) -> (Option<u32>, Option<bool>, Option<String>) {
+        (None, None, None)
+    }
+
+    /// Fill in login names for UIDs not seen before, one passwd lookup each rather than reading
+    /// the whole users list (slow with LDAP or SSSD). A UID without an entry is remembered as
+    /// unnamed, so it is not looked up again.
+    #[cfg(unix)]
+    fn resolve_user_names(&mut self) {
+        let unknown: Vec<u32> = self
+            .system
+            .processes()
+            .values()
+            .filter_map(|process| process.user_id().map(|uid| **uid))
+            .filter(|uid| !self.user_names.contains_key(uid))
+            .collect();
+        for uid in unknown {
+            self.user_names
+                .entry(uid)
+                .or_insert_with(|| Self::user_name(uid));
+        }
+    }
+
+    /// Login name of `uid` from the passwd database
+    #[cfg(unix)]
+    fn user_name(uid: u32) -> Option<String> {
+        let mut buf: Vec<libc::c_char> = vec![0; 1024];
+        loop {
+            // SAFETY: passwd is plain data; getpwuid_r fills it in
+            let mut entry: libc::passwd = unsafe { std::mem::zeroed() };
+            let mut result = std::ptr::null_mut();
+            // SAFETY: entry, buf and result outlive the call, which is told buf's length
+            let err = unsafe {
+                libc::getpwuid_r(uid, &mut entry, buf.as_mut_ptr(), buf.len(), &mut result)
+            };
+            if err == libc::ERANGE && buf.len() < 1 << 20 {
+                buf.resize(buf.len() * 2, 0);
+                continue;
+            }
+            if err != 0 || result.is_null() {
+                return None;
+            }
+            // SAFETY: on success pw_name points to a NUL-terminated string inside buf
+            let name = unsafe { std::ffi::CStr::from_ptr(entry.pw_name) };
+            return Some(name.to_string_lossy().into_owned());
+        }
+    }
+
+    fn limit_or_unbounded(limit: usize) -> usize {
+        if limit == 0 {
+            usize::MAX
+        } else {
+            limit
+        }
+    }
+
+    fn update_disk_history(&mut self, disks: &mut [DiskInfo]) {
         if self.disk_usage_history.len() > disks.len() {
             self.disk_usage_history.truncate(disks.len());
         }
+        Self::push_history_point(&mut self.disk_history_times, std::time::Instant::now());
 
-        for (i, disk) in disks.iter().enumerate() {
+        for (i, disk) in disks.iter_mut().enumerate() {
             while self.disk_usage_history.len() <= i {
                 self.disk_usage_history
                     .push(VecDeque::with_capacity(Self::HISTORY_LEN));
@@ -676,38 +1758,94 @@ impl DataCollector {
 
             let available_space = disk.available_space;
             let total_space = disk.total_space;
+            if total_space == 0 {
+                // A network mount whose probe has not answered yet; start over once it does
+                self.disk_usage_history[i].clear();
+                continue;
+            }
             Self::push_history_point(
                 &mut self.disk_usage_history[i],
                 (available_space, total_space),
             );
+
+            disk.fill_rate = Self::estimate_disk_fill_rate(
+                &self.disk_usage_history[i],
+                &self.disk_history_times,
+            );
+            disk.time_to_full = disk.fill_rate.and_then(|rate| {
+                if rate > 0.0 {
+                    Some((available_space as f64 / rate) as u64)
+                } else {
+                    None
+                }
+            });
+        }
+    }
+
+    /// Least-squares slope of used space over the recent history window, in bytes per second.
+    fn estimate_disk_fill_rate(
+        history: &VecDeque<(u64, u64)>,
+        times: &VecDeque<std::time::Instant>,
+    ) -> Option<f64> {
+        // Pair samples from the newest end; a volume that appeared recently has a shorter history.
+        let samples: Vec<(f64, f64)> = history
+            .iter()
+            .rev()
+            .zip(times.iter().rev())
+            .map(|(&(available, total), time)| {
+                let used = total.saturating_sub(available) as f64;
+                let age = times
+                    .back()
+                    .map_or(0.0, |newest| newest.duration_since(*time).as_secs_f64());
+                (-age, used)
+            })
+            .collect();
+        if samples.len() < Self::DISK_FILL_MIN_SAMPLES {
+            return None;
+        }
+
+        let n = samples.len() as f64;
+        let mean_t = samples.iter().map(|(t, _)| t).sum::<f64>() / n;
+        let mean_used = samples.iter().map(|(_, used)| used).sum::<f64>() / n;
+        let (mut covariance, mut variance) = (0.0, 0.0);
+        for (t, used) in &samples {
+            covariance += (t - mean_t) * (used - mean_used);
+            variance += (t - mean_t) * (t - mean_t);
+        }
+        if variance <= f64::EPSILON {
+            return None;
+        }
+        let rate = covariance / variance;
+        if rate.is_finite() {
+            Some(rate)
+        } else {
+            None
         }
     }
 
-    fn collect_disks(&self) -> Vec<DiskInfo> {
+    fn collect_disks(&mut self) -> Vec<DiskInfo> {
         // Key by (name, filesystem, total_space) and keep the smallest available space
         // among duplicates to avoid under-reporting usage.
         let mut by_key: HashMap<(String, String, u64), DiskInfo> = HashMap::new();
 
-        for disk in self.system.disks() {
-            let total_space = disk.total_space();
-            if total_space == 0 {
-                continue;
-            }
-
-            let name = disk.name().to_string_lossy().to_string();
-            let fs = String::from_utf8_lossy(disk.file_system()).to_string();
-            let available_space = disk.available_space();
-            let key = (name.clone(), fs, total_space);
-
+        for volume in self.mounted_volumes() {
+            let key = (volume.name.clone(), volume.fs, volume.usage.total);
+            let mount_point = volume.mount_point;
             by_key
                 .entry(key)
                 .and_modify(|entry| {
-                    entry.available_space = entry.available_space.min(available_space);
+                    entry.available_space = entry.available_space.min(volume.usage.available);
+                    entry.mount_points.push(mount_point.clone());
                 })
                 .or_insert(DiskInfo {
-                    name,
-                    total_space,
-                    available_space,
+                    name: volume.name,
+                    mount_points: vec![mount_point],
+                    total_space: volume.usage.total,
+                    available_space: volume.usage.available,
+                    reserved_space: volume.usage.reserved,
+                    remote: volume.remote,
+                    fill_rate: None,
+                    time_to_full: None,
                 });
         }
 
@@ -720,6 +1858,78 @@ impl DataCollector {
         disks
     }
 
+    /// Mounts from `/proc/self/mounts`. Local filesystems are measured here; network ones
+    /// report what their background probe last saw, even before it first answers.
+    #[cfg(target_os = "linux")]
+    fn mounted_volumes(&mut self) -> Vec<MountedVolume> {
+        let mounts = match self.reader.read_str("/proc/self/mounts") {
+            Ok(content) => crate::data::procfs::parse_mounts(content),
+            Err(_) => return Vec::new(),
+        };
+
+        let mut volumes = Vec::new();
+        for mount in mounts {
+            if mounts::is_ignored(&mount.source, &mount.target, &mount.fs_type) {
+                continue;
+            }
+            // Network mounts always report how they answer; local ones only once they hang
+            let network = mounts::is_network(&mount.fs_type);
+            let wait = if network {
+                std::time::Duration::ZERO
+            } else {
+                MountProbes::LOCAL_WAIT
+            };
+            let (usage, health) = self.mount_probes.poll(&mount.target, mounts::statvfs, wait);
+            let (usage, remote) = if network || health.stale_secs.is_some() {
+                (usage.or(Some(FsUsage::default())), Some(health))
+            } else {
+                (usage, None)
+            };
+            let Some(usage) = usage.filter(|usage| usage.total > 0 || remote.is_some()) else {
+                continue;
+            };
+            volumes.push(MountedVolume {
+                name: mount.source,
+                fs: mount.fs_type,
+                mount_point: mount.target,
+                usage,
+                remote,
+            });
+        }
+
+        let mounted: Vec<&str> = volumes
+            .iter()
+            .map(|volume| volume.mount_point.as_str())
+            .collect();
+        self.mount_probes.retain(&mounted);
+        volumes
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    fn mounted_volumes(&mut self) -> Vec<MountedVolume> {
+        self.system.refresh_disks_list();
+        self.system.refresh_disks();
+        self.system
+            .disks()
+            .iter()
+            .filter(|disk| disk.total_space() > 0)
+            .map(|disk| {
+                let mount_point = disk.mount_point().to_string_lossy().to_string();
+                MountedVolume {
+                    name: disk.name().to_string_lossy().to_string(),
+                    fs: String::from_utf8_lossy(disk.file_system()).to_string(),
+                    usage: FsUsage {
+                        total: disk.total_space(),
+                        available: disk.available_space(),
+                        reserved: mounts::statvfs(&mount_point).map_or(0, |usage| usage.reserved),
+                    },
+                    mount_point,
+                    remote: None,
+                }
+            })
+            .collect()
+    }
+
     fn push_history_point<T>(queue: &mut VecDeque<T>, value: T) {
         queue.push_back(value);
         while queue.len() > Self::HISTORY_LEN {
@@ -728,15 +1938,12 @@ impl DataCollector {
     }
 
     #[cfg(target_os = "linux")]
-    fn detect_lspci_gpus() -> Vec<(String, String)> {
-        use std::process::Command;
+    fn detect_lspci_gpus(commands: &mut CommandRunner) -> Vec<(String, String)> {
         let mut candidates = Vec::new();
 
-        let output = match Command::new("lspci").output() {
-            Ok(output) => output,
-            Err(_) => return candidates,
+        let Some(output_str) = commands.run("lspci", &[], CommandRunner::TIMEOUT) else {
+            return candidates;
         };
-        let output_str = String::from_utf8_lossy(&output.stdout);
 
         for line in output_str.lines().take(64) {
             let line_lower = line.to_lowercase();
@@ -782,7 +1989,60 @@ impl DataCollector {
         candidates
     }
 
-    fn collect_cpu_frequencies(&self, cpu_count: usize) -> Vec<u64> {
+    /// Overall and per-core usage since the previous collection, with a slot for every present
+    /// core and `None` for those that are offline (`cpuN/online` is 0), which /proc/stat omits
+    #[cfg(target_os = "linux")]
+    fn collect_core_usage(&mut self) -> (f32, Vec<Option<f32>>) {
+        use crate::data::procfs;
+        let present = self
+            .reader
+            .read_str("/sys/devices/system/cpu/present")
+            .map(procfs::parse_cpu_list)
+            .unwrap_or_default();
+        let Ok(content) = self.reader.read_str("/proc/stat") else {
+            return (0.0, Vec::new());
+        };
+        let (aggregate, cores) = procfs::parse_cpu_times(content);
+
+        let (previous_aggregate, previous_cores) = &mut self.previous_cpu_times;
+        let global = match (aggregate, *previous_aggregate) {
+            (Some(now), Some(previous)) => now.usage_since(&previous),
+            _ => 0.0,
+        };
+        *previous_aggregate = aggregate;
+        let slots = present
+            .iter()
+            .chain(cores.iter().map(|(core, _)| core))
+            .max()
+            .map_or(0, |last| last + 1);
+        let mut usage = vec![None; slots];
+        let mut next = HashMap::with_capacity(cores.len());
+        for (core, times) in cores {
+            // A core that just came online has no earlier sample to compare with
+            usage[core] = Some(
+                previous_cores
+                    .get(&core)
+                    .map_or(0.0, |previous| times.usage_since(previous)),
+            );
+            next.insert(core, times);
+        }
+        *previous_cores = next;
+        (global, usage)
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    fn collect_core_usage(&mut self) -> (f32, Vec<Option<f32>>) {
+        (
+            self.system.global_cpu_info().cpu_usage(),
+            self.system
+                .cpus()
+                .iter()
+                .map(|cpu| Some(cpu.cpu_usage()))
+                .collect(),
+        )
+    }
+
+    fn collect_cpu_frequencies(&mut self, cpu_count: usize) -> Vec<u64> {
         let mut cpu_frequencies: Vec<u64> = self
             .system
             .cpus()
@@ -795,14 +2055,25 @@ impl DataCollector {
 
         #[cfg(target_os = "linux")]
         {
-            let proc_fallback = Self::read_cpu_frequencies_from_proc_cpuinfo(cpu_count);
+            // /proc/cpuinfo is large; only parse it when sysfs leaves gaps.
+            let mut policy_fallback: Option<Vec<Option<u64>>> = None;
+            let mut proc_fallback: Option<Vec<Option<u64>>> = None;
             for idx in 0..cpu_count {
                 if cpu_frequencies[idx] == 0 {
-                    if let Some(freq_mhz) = Self::read_cpu_frequency_from_sysfs(idx) {
+                    if let Some(freq_mhz) = self.read_cpu_frequency_from_sysfs(idx) {
+                        cpu_frequencies[idx] = freq_mhz;
+                        continue;
+                    }
+                    let policies = policy_fallback
+                        .get_or_insert_with(|| self.read_cpu_frequencies_from_policies(cpu_count));
+                    if let Some(freq_mhz) = policies.get(idx).and_then(|v| *v) {
                         cpu_frequencies[idx] = freq_mhz;
                         continue;
                     }
-                    if let Some(freq_mhz) = proc_fallback.get(idx).and_then(|v| *v) {
+                    let fallback = proc_fallback.get_or_insert_with(|| {
+                        self.read_cpu_frequencies_from_proc_cpuinfo(cpu_count)
+                    });
+                    if let Some(freq_mhz) = fallback.get(idx).and_then(|v| *v) {
                         cpu_frequencies[idx] = freq_mhz;
                     }
                 }
@@ -813,9 +2084,7 @@ impl DataCollector {
     }
 
     #[cfg(target_os = "linux")]
-    fn read_cpu_frequency_from_sysfs(cpu_idx: usize) -> Option<u64> {
-        use std::fs;
-
+    fn read_cpu_frequency_from_sysfs(&mut self, cpu_idx: usize) -> Option<u64> {
         let candidates = [
             format!(
                 "/sys/devices/system/cpu/cpu{}/cpufreq/scaling_cur_freq",
@@ -828,33 +2097,84 @@ impl DataCollector {
         ];
 
         for path in candidates {
-            let Ok(raw) = fs::read_to_string(path) else {
-                continue;
-            };
-            let Ok(value) = raw.trim().parse::<u64>() else {
+            let Some(value) = self.reader.read_value::<u64>(path) else {
                 continue;
             };
             if value == 0 {
                 continue;
             }
 
-            // cpufreq exports in kHz on Linux. If already in MHz, keep it.
-            return Some(if value >= 100_000 {
-                value / 1000
-            } else {
-                value
-            });
+            return Some(Self::cpufreq_mhz(value));
         }
 
         None
     }
 
+    /// cpufreq exports in kHz on Linux. If already in MHz, keep it.
     #[cfg(target_os = "linux")]
-    fn read_cpu_frequencies_from_proc_cpuinfo(cpu_count: usize) -> Vec<Option<u64>> {
-        use std::fs;
+    fn cpufreq_mhz(value: u64) -> u64 {
+        if value >= 100_000 {
+            value / 1000
+        } else {
+            value
+        }
+    }
+
+    /// Frequencies from the cpufreq policy directories, which some ARM boards expose without
+    /// per-CPU `cpufreq` links. Each policy covers the cores in its `related_cpus`.
+    #[cfg(target_os = "linux")]
+    fn read_cpu_frequencies_from_policies(&mut self, cpu_count: usize) -> Vec<Option<u64>> {
+        Self::policy_frequencies(
+            &mut self.reader,
+            std::path::Path::new("/sys/devices/system/cpu/cpufreq"),
+            cpu_count,
+        )
+    }
+
+    #[cfg(target_os = "linux")]
+    fn policy_frequencies(
+        reader: &mut SysfsReader,
+        cpufreq: &std::path::Path,
+        cpu_count: usize,
+    ) -> Vec<Option<u64>> {
+        let mut out = vec![None; cpu_count];
+        let Ok(entries) = std::fs::read_dir(cpufreq) else {
+            return out;
+        };
+        for entry in entries.flatten() {
+            if !entry.file_name().to_string_lossy().starts_with("policy") {
+                continue;
+            }
+            let dir = entry.path();
+            let freq = ["scaling_cur_freq", "cpuinfo_cur_freq"]
+                .iter()
+                .find_map(|name| {
+                    reader
+                        .read_value::<u64>(dir.join(name))
+                        .filter(|&value| value > 0)
+                });
+            let Some(freq) = freq else {
+                continue;
+            };
+            let Ok(related) = reader.read_str(dir.join("related_cpus")) else {
+                continue;
+            };
+            for cpu in related
+                .split_whitespace()
+                .filter_map(|v| v.parse::<usize>().ok())
+            {
+                if let Some(slot) = out.get_mut(cpu) {
+                    *slot = Some(Self::cpufreq_mhz(freq));
+                }
+            }
+        }
+        out
+    }
 
+    #[cfg(target_os = "linux")]
+    fn read_cpu_frequencies_from_proc_cpuinfo(&mut self, cpu_count: usize) -> Vec<Option<u64>> {
         let mut out = vec![None; cpu_count];
-        let Ok(content) = fs::read_to_string("/proc/cpuinfo") else {
+        let Ok(content) = self.reader.read_str("/proc/cpuinfo") else {
             return out;
         };
 
@@ -882,35 +2202,43 @@ impl DataCollector {
         out
     }
 
-    fn get_cached_memory(&self) -> u64 {
-        // On Linux, we can get cached memory from /proc/meminfo
+    /// RAM split the way `free` reports it. Elsewhere only used/total are known, so all
+    /// unused memory counts as free and available.
+    fn collect_memory_breakdown(&mut self) -> MemoryBreakdown {
+        let unused = self
+            .system
+            .total_memory()
+            .saturating_sub(self.system.used_memory());
+        let mut breakdown = MemoryBreakdown {
+            free: unused,
+            available: unused,
+            ..MemoryBreakdown::default()
+        };
+
         #[cfg(target_os = "linux")]
         {
-            use std::fs;
-            if let Ok(content) = fs::read_to_string("/proc/meminfo") {
-                for line in content.lines() {
-                    if line.starts_with("Cached:") {
-                        let parts: Vec<&str> = line.split_whitespace().collect();
-                        if parts.len() >= 2 {
-                            if let Ok(cached_kb) = parts[1].parse::<u64>() {
-                                return cached_kb * 1024; // Convert KB to bytes
-                            }
-                        }
-                    }
-                }
+            let mut read = |key| self.read_proc_meminfo_key_bytes(key);
+            if let Some(free) = read("MemFree") {
+                breakdown.free = free;
+            }
+            breakdown.buffers = read("Buffers").unwrap_or(0);
+            // free(1) counts reclaimable slab (dentries, inodes) as cache
+            breakdown.cached = read("Cached").unwrap_or(0) + read("SReclaimable").unwrap_or(0);
+            if let Some(available) = read("MemAvailable") {
+                breakdown.available = available;
             }
         }
-        // For other systems, return 0 as fallback
-        0
+        breakdown
     }
 
-    fn update_temperature_sensors(&self) -> Vec<TemperatureInfo> {
+    fn update_temperature_sensors(&mut self) -> Vec<TemperatureInfo> {
         let mut temperature_sensors = Vec::new();
 
         // Add all current temperature sensors
         for component in self.system.components() {
             temperature_sensors.push(TemperatureInfo {
                 label: component.label().to_string(),
+                source: component.label().to_string(),
                 temperature: component.temperature(),
             });
         }
@@ -928,7 +2256,7 @@ impl DataCollector {
             });
 
             if !has_cpu_sensor {
-                if let Some(temp) = Self::read_linux_thermal_zone_temp(&[
+                if let Some(temp) = self.read_linux_thermal_zone_temp(&[
                     "x86_pkg_temp",
                     "tdie",
                     "tctl",
@@ -938,16 +2266,18 @@ impl DataCollector {
                 ]) {
                     temperature_sensors.push(TemperatureInfo {
                         label: "x86_pkg_temp (thermal)".to_string(),
+                        source: "x86_pkg_temp (thermal)".to_string(),
                         temperature: temp,
                     });
                 }
             }
         }
 
+        self.sensor_overrides.apply(&mut temperature_sensors);
         temperature_sensors
     }
 
-    fn update_battery_info(&self) -> Option<BatteryInfo> {
+    fn update_battery_info(&mut self) -> Option<BatteryInfo> {
         // Get battery information based on the platform
         #[cfg(target_os = "linux")]
         {
@@ -993,11 +2323,10 @@ impl DataCollector {
         #[cfg(target_os = "macos")]
         {
             // For macOS, we could use system commands like 'pmset -g batt'
-            use std::process::Command;
-
-            if let Ok(output) = Command::new("pmset").arg("-g").arg("batt").output() {
-                let output_str = String::from_utf8_lossy(&output.stdout);
-
+            if let Some(output_str) =
+                self.commands
+                    .run("pmset", &["-g", "batt"], CommandRunner::TIMEOUT)
+            {
                 // Parse the output to extract battery percentage and status
                 if let Some(line) = output_str.lines().find(|l| l.contains("InternalBattery")) {
                     let mut level = None;
@@ -1032,33 +2361,31 @@ impl DataCollector {
         #[cfg(windows)]
         {
             // For Windows, we could use WMI or PowerShell
-            use std::process::Command;
-
             let mut level = None;
             let mut status = None;
 
-            if let Ok(output) = Command::new("powershell")
-                .args(&[
+            if let Some(output_str) = self.commands.run(
+                "powershell",
+                &[
                     "-Command",
                     "(Get-WmiObject -Class Win32_Battery).EstimatedChargeRemaining",
-                ])
-                .output()
-            {
-                let output_str = String::from_utf8_lossy(&output.stdout);
+                ],
+                CommandRunner::TIMEOUT,
+            ) {
                 if let Ok(l) = output_str.trim().parse::<f32>() {
                     level = Some(l);
                 }
             }
 
             // Get battery status
-            if let Ok(output) = Command::new("powershell")
-                .args(&[
+            if let Some(output_str) = self.commands.run(
+                "powershell",
+                &[
                     "-Command",
                     "(Get-WmiObject -Class Win32_Battery).BatteryStatus",
-                ])
-                .output()
-            {
-                let output_str = String::from_utf8_lossy(&output.stdout);
+                ],
+                CommandRunner::TIMEOUT,
+            ) {
                 // Map numeric battery status to readable string
                 let s = match output_str.trim() {
                     "1" => "Discharging".to_string(),
@@ -1107,11 +2434,55 @@ impl DataCollector {
             .into_owned()
     }
 
-    fn get_uptime(&self) -> String {
+    /// Running as root, so permission-gated sources are readable
+    #[cfg(unix)]
+    fn is_elevated() -> bool {
+        // SAFETY: geteuid has no preconditions and cannot fail
+        unsafe { libc::geteuid() == 0 }
+    }
+
+    #[cfg(not(unix))]
+    fn is_elevated() -> bool {
+        false
+    }
+
+    /// Remember that `source` could not be read for lack of permission
+    #[cfg(target_os = "linux")]
+    fn note_restricted(&mut self, source: &'static str) {
+        if !self.restricted_sources.contains(&source) {
+            self.restricted_sources.push(source);
+        }
+    }
+
+    /// Address outgoing traffic uses: connecting a UDP socket picks the route without sending
+    /// anything (the targets are documentation addresses)
+    fn get_primary_ip(&mut self) -> Option<String> {
+        // Addresses change with DHCP leases and roaming, not from one tick to the next
+        const RECHECK: std::time::Duration = std::time::Duration::from_secs(30);
+
+        let now = std::time::Instant::now();
+        if let Some((checked, ip)) = &self.primary_ip {
+            if now.duration_since(*checked) < RECHECK {
+                return ip.clone();
+            }
+        }
+        let ip = [("0.0.0.0:0", "192.0.2.1:9"), ("[::]:0", "[2001:db8::1]:9")]
+            .iter()
+            .find_map(|&(bind, target)| {
+                let socket = std::net::UdpSocket::bind(bind).ok()?;
+                socket.connect(target).ok()?;
+                Some(socket.local_addr().ok()?.ip().to_string())
+            });
+        self.primary_ip = Some((now, ip.clone()));
+        ip
+    }
+
+    fn get_uptime(&mut self) -> String {
         // Get uptime in seconds (Linux-specific approach)
         #[cfg(target_os = "linux")]
         {
-            std::fs::read_to_string("/proc/uptime")
+            self.reader
+                .read_str("/proc/uptime")
                 .ok()
                 .and_then(|contents| contents.split_whitespace().next()?.parse::<f64>().ok())
                 .map(|uptime| {
@@ -1130,14 +2501,40 @@ impl DataCollector {
         }
     }
 
-    fn get_load_avg(&self) -> String {
+    #[cfg(target_os = "linux")]
+    fn collect_task_totals(&mut self, processes: usize) -> TaskTotals {
+        use crate::data::procfs::{parse_file_nr, parse_loadavg_threads};
+        TaskTotals {
+            processes,
+            threads: self
+                .reader
+                .read_str("/proc/loadavg")
+                .ok()
+                .and_then(parse_loadavg_threads),
+            open_files: self
+                .reader
+                .read_str("/proc/sys/fs/file-nr")
+                .ok()
+                .and_then(parse_file_nr),
+        }
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    fn collect_task_totals(&mut self, processes: usize) -> TaskTotals {
+        TaskTotals {
+            processes,
+            ..TaskTotals::default()
+        }
+    }
+
+    fn get_load_avg(&mut self) -> String {
         // On Linux, we can get load averages
         #[cfg(target_os = "linux")]
         {
-            std::fs::read_to_string("/proc/loadavg")
+            self.reader
+                .read_str("/proc/loadavg")
+                .map(|content| content.trim().to_string())
                 .unwrap_or_else(|_| "N/A".to_string())
-                .trim()
-                .to_string()
         }
         #[cfg(not(target_os = "linux"))]
         {
@@ -1244,16 +2641,12 @@ impl DataCollector {
 
     #[cfg(target_os = "linux")]
     fn get_intel_gpu_usage_with_note(&mut self) -> (Option<f32>, Option<String>) {
-        use std::fs;
-
-        if let Some(path) = &self.intel_gpu_busy_percent_path {
-            if let Ok(content) = fs::read_to_string(path) {
-                if let Ok(raw_usage) = content.trim().parse::<f32>() {
-                    if raw_usage.is_finite() {
-                        let usage = raw_usage.clamp(0.0, 100.0);
-                        let smooth = self.smooth_intel_gpu_usage(usage);
-                        return (Some(smooth), Some("Busy".to_string()));
-                    }
+        if let Some(path) = self.intel_gpu_busy_percent_path.clone() {
+            if let Some(raw_usage) = self.reader.read_value::<f32>(&path) {
+                if raw_usage.is_finite() {
+                    let usage = raw_usage.clamp(0.0, 100.0);
+                    let smooth = self.smooth_intel_gpu_usage(usage);
+                    return (Some(smooth), Some("Busy".to_string()));
                 }
             }
         }
@@ -1334,17 +2727,12 @@ impl DataCollector {
 
     #[cfg(target_os = "linux")]
     fn read_multi_gt_rc6_busy_by_gt(&mut self) -> (HashMap<String, f32>, bool) {
-        use std::fs;
-
         let mut by_gt_sum: HashMap<String, (f32, u32)> = HashMap::new();
         let mut had_samples = false;
         let now = std::time::Instant::now();
 
         for path in self.intel_rc6_paths.clone() {
-            let Ok(content) = fs::read_to_string(&path) else {
-                continue;
-            };
-            let Ok(current_rc6_ms) = content.trim().parse::<u64>() else {
+            let Some(current_rc6_ms) = self.reader.read_value::<u64>(&path) else {
                 continue;
             };
 
@@ -1386,10 +2774,14 @@ impl DataCollector {
     }
 
     #[cfg(target_os = "linux")]
-    fn read_intel_gt_freq_usage_by_gt(&self) -> HashMap<String, f32> {
-        let mut cur_by_gt = Self::read_numeric_paths_by_gt(&self.intel_gt_cur_freq_paths, true);
-        let mut max_by_gt = Self::read_numeric_paths_by_gt(&self.intel_gt_max_freq_paths, true);
-        let mut min_by_gt = Self::read_numeric_paths_by_gt(&self.intel_gt_min_freq_paths, false);
+    fn read_intel_gt_freq_usage_by_gt(&mut self) -> HashMap<String, f32> {
+        let reader = &mut self.reader;
+        let mut cur_by_gt =
+            Self::read_numeric_paths_by_gt(reader, &self.intel_gt_cur_freq_paths, true);
+        let mut max_by_gt =
+            Self::read_numeric_paths_by_gt(reader, &self.intel_gt_max_freq_paths, true);
+        let mut min_by_gt =
+            Self::read_numeric_paths_by_gt(reader, &self.intel_gt_min_freq_paths, false);
 
         let has_gt_specific = cur_by_gt.keys().any(|k| k.starts_with("gt"));
         if has_gt_specific {
@@ -1419,15 +2811,14 @@ impl DataCollector {
     }
 
     #[cfg(target_os = "linux")]
-    fn read_numeric_paths_by_gt(paths: &[String], pick_max: bool) -> HashMap<String, f32> {
-        use std::fs;
-
+    fn read_numeric_paths_by_gt(
+        reader: &mut SysfsReader,
+        paths: &[String],
+        pick_max: bool,
+    ) -> HashMap<String, f32> {
         let mut values: HashMap<String, f32> = HashMap::new();
         for path in paths {
-            let Ok(content) = fs::read_to_string(path) else {
-                continue;
-            };
-            let Ok(value) = content.trim().parse::<f32>() else {
+            let Some(value) = reader.read_value::<f32>(path) else {
                 continue;
             };
             if !value.is_finite() {
@@ -1489,14 +2880,14 @@ impl DataCollector {
 
     #[cfg(target_os = "linux")]
     fn get_intel_gpu_temperature_with_note(
-        &self,
+        &mut self,
         temperature_sensors: &[TemperatureInfo],
     ) -> (Option<f32>, Option<String>) {
         use std::io::ErrorKind;
 
         let mut permission_denied = false;
-        if let Some(path) = self.intel_temp_input_path.as_ref() {
-            match Self::read_temperature_from_path(path) {
+        if let Some(path) = self.intel_temp_input_path.clone() {
+            match self.read_temperature_from_path(&path) {
                 Ok(temp) => return (Some(temp), None),
                 Err(kind) => {
                     if kind == ErrorKind::PermissionDenied {
@@ -1508,7 +2899,7 @@ impl DataCollector {
 
         // Fallback: package sensor proxy when dedicated iGPU sensor is unavailable.
         if let Some(proxy) = temperature_sensors.iter().find(|sensor| {
-            let label = sensor.label.to_lowercase();
+            let label = sensor.source.to_lowercase();
             label.contains("package id") || label.contains("x86_pkg_temp")
         }) {
             return (Some(proxy.temperature), Some("Pkg proxy".to_string()));
@@ -1516,20 +2907,22 @@ impl DataCollector {
 
         // Fallback: read thermal zones directly when sysinfo components are sparse.
         if let Some(temp) =
-            Self::read_linux_thermal_zone_temp(&["x86_pkg_temp", "tcpu", "acpitz", "cpu"])
+            self.read_linux_thermal_zone_temp(&["x86_pkg_temp", "tcpu", "acpitz", "cpu"])
         {
             return (Some(temp), Some("Thermal".to_string()));
         }
 
         if permission_denied {
-            (None, Some("No perm".to_string()))
+            // The status bar's privileges hint covers it
+            self.note_restricted("GPU temperature (hwmon)");
+            (None, None)
         } else {
             (None, Some("N/A".to_string()))
         }
     }
 
     #[cfg(target_os = "linux")]
-    fn read_linux_thermal_zone_temp(candidates: &[&str]) -> Option<f32> {
+    fn read_linux_thermal_zone_temp(&mut self, candidates: &[&str]) -> Option<f32> {
         use std::fs;
 
         let entries = fs::read_dir("/sys/class/thermal").ok()?;
@@ -1543,7 +2936,7 @@ impl DataCollector {
                 continue;
             }
 
-            let Ok(zone_type_raw) = fs::read_to_string(path.join("type")) else {
+            let Ok(zone_type_raw) = self.reader.read_str(path.join("type")) else {
                 continue;
             };
             let zone_type = zone_type_raw.trim().to_lowercase();
@@ -1554,10 +2947,7 @@ impl DataCollector {
                 continue;
             }
 
-            let Ok(raw) = fs::read_to_string(path.join("temp")) else {
-                continue;
-            };
-            let Ok(value) = raw.trim().parse::<f32>() else {
+            let Some(value) = self.reader.read_value::<f32>(path.join("temp")) else {
                 continue;
             };
             return Some(if value > 1000.0 {
@@ -1571,11 +2961,10 @@ impl DataCollector {
     }
 
     #[cfg(target_os = "linux")]
-    fn read_temperature_from_path(path: &str) -> Result<f32, std::io::ErrorKind> {
-        use std::fs;
-
-        let raw = fs::read_to_string(path)
-            .map_err(|err| err.kind())?
+    fn read_temperature_from_path(&mut self, path: &str) -> Result<f32, std::io::ErrorKind> {
+        let raw = self
+            .reader
+            .read_str(path)?
             .trim()
             .parse::<f32>()
             .map_err(|_| std::io::ErrorKind::InvalidData)?;
@@ -1584,7 +2973,7 @@ impl DataCollector {
     }
 
     #[cfg(target_os = "linux")]
-    fn get_intel_gpu_memory_usage_with_note(&self) -> (Option<u64>, Option<String>) {
+    fn get_intel_gpu_memory_usage_with_note(&mut self) -> (Option<u64>, Option<String>) {
         use std::{fs, io::ErrorKind, path::Path};
 
         let mut no_permission = false;
@@ -1605,9 +2994,20 @@ impl DataCollector {
             }
         }
 
+        // Per-client DRM accounting (kernel 5.19+ for i915, xe) is exact for the clients we
+        // can see; without root only our own user's processes are readable.
+        if let Some((bytes, complete)) = self.intel_gpu_memory_from_fdinfo() {
+            let note = if complete {
+                "fdinfo"
+            } else {
+                "fdinfo (partial)"
+            };
+            return (Some(bytes), Some(note.to_string()));
+        }
+
         // Fallback: GEM objects are often unavailable without debugfs/capabilities.
         // Shmem is a coarse but always-available proxy for shared iGPU allocations.
-        if let Some(shmem_bytes) = Self::read_proc_meminfo_key_bytes("Shmem") {
+        if let Some(shmem_bytes) = self.read_proc_meminfo_key_bytes("Shmem") {
             return (Some(shmem_bytes), Some("Shared".to_string()));
         }
 
@@ -1620,16 +3020,88 @@ impl DataCollector {
         }
 
         if no_permission {
-            return (None, Some("No perm".to_string()));
+            self.note_restricted("GPU memory (debugfs)");
+            return (None, None);
         }
 
         (None, Some("N/A".to_string()))
     }
 
+    /// Sum DRM client memory of Intel GPU clients across all processes.
+    /// Returns the total and whether every process's fds could be inspected.
+    #[cfg(target_os = "linux")]
+    fn intel_gpu_memory_from_fdinfo(&mut self) -> Option<(u64, bool)> {
+        // Listing every fd of every process is costly, so only rescan occasionally and
+        // re-read the known DRM fds in between.
+        const RESCAN_EVERY: u32 = 10;
+        if self.drm_fd_scan_countdown == 0 {
+            let (paths, all_readable) = Self::scan_drm_fdinfo_paths();
+            self.drm_fdinfo_paths = paths;
+            self.drm_fd_scan_complete = all_readable;
+            self.drm_fd_scan_countdown = RESCAN_EVERY;
+        } else {
+            self.drm_fd_scan_countdown -= 1;
+        }
+
+        let mut clients: HashMap<u64, u64> = HashMap::new();
+        self.drm_fdinfo_paths.retain(|path| {
+            let Ok(content) = std::fs::read_to_string(path) else {
+                return false; // fd closed or process exited
+            };
+            if let Some(client) = crate::data::procfs::parse_drm_fdinfo(&content) {
+                if client.driver == "i915" || client.driver == "xe" {
+                    clients.insert(client.client_id, client.total_bytes);
+                }
+            }
+            true
+        });
+
+        if clients.is_empty() {
+            return None;
+        }
+        Some((clients.values().sum(), self.drm_fd_scan_complete))
+    }
+
+    #[cfg(target_os = "linux")]
+    fn scan_drm_fdinfo_paths() -> (Vec<String>, bool) {
+        let mut paths = Vec::new();
+        let mut all_readable = true;
+        let Ok(procs) = std::fs::read_dir("/proc") else {
+            return (paths, false);
+        };
+
+        for entry in procs.flatten() {
+            let pid = entry.file_name();
+            let Some(pid) = pid
+                .to_str()
+                .filter(|p| p.bytes().all(|b| b.is_ascii_digit()))
+            else {
+                continue;
+            };
+            let Ok(fds) = std::fs::read_dir(format!("/proc/{}/fd", pid)) else {
+                all_readable = false;
+                continue;
+            };
+            for fd in fds.flatten() {
+                let is_drm = std::fs::read_link(fd.path())
+                    .map(|target| target.starts_with("/dev/dri"))
+                    .unwrap_or(false);
+                if is_drm {
+                    paths.push(format!(
+                        "/proc/{}/fdinfo/{}",
+                        pid,
+                        fd.file_name().to_string_lossy()
+                    ));
+                }
+            }
+        }
+        (paths, all_readable)
+    }
+
     #[cfg(target_os = "linux")]
-    fn get_intel_gpu_shared_memory_total(&self, used: Option<u64>) -> Option<u64> {
-        let mem_available = Self::read_proc_meminfo_key_bytes("MemAvailable");
-        let mem_total = Self::read_proc_meminfo_key_bytes("MemTotal");
+    fn get_intel_gpu_shared_memory_total(&mut self, used: Option<u64>) -> Option<u64> {
+        let mem_available = self.read_proc_meminfo_key_bytes("MemAvailable");
+        let mem_total = self.read_proc_meminfo_key_bytes("MemTotal");
 
         match (mem_available, mem_total, used) {
             (Some(available), Some(total), Some(used)) => {
@@ -1653,12 +3125,11 @@ impl DataCollector {
     }
 
     #[cfg(target_os = "linux")]
-    fn read_proc_meminfo_key_bytes(key: &str) -> Option<u64> {
-        use std::fs;
-
-        let content = fs::read_to_string("/proc/meminfo").ok()?;
+    fn read_proc_meminfo_key_bytes(&mut self, key: &str) -> Option<u64> {
+        let content = self.reader.read_str("/proc/meminfo").ok()?;
         for line in content.lines() {
-            if !line.starts_with(key) {
+            // Match the full label so "Cached" does not pick up "SwapCached".
+            if line.split(':').next() != Some(key) {
                 continue;
             }
             // Format example: \"MemTotal:       32229848 kB\"
@@ -1696,6 +3167,18 @@ impl DataCollector {
         best
     }
 
+    /// Intel graphics on the CPU package always sit at PCI address 00:02.0; cards never do
+    #[cfg(target_os = "linux")]
+    fn intel_gpu_integrated(&mut self) -> bool {
+        if self.intel_integrated.is_none() {
+            self.intel_integrated = self.intel_drm_card_path.as_ref().map(|card| {
+                std::fs::canonicalize(std::path::Path::new(card).join("device"))
+                    .is_ok_and(|device| device.ends_with("0000:00:02.0"))
+            });
+        }
+        self.intel_integrated.unwrap_or(false)
+    }
+
     #[cfg(target_os = "linux")]
     fn detect_intel_drm_card_path() -> Option<String> {
         use std::fs;
@@ -1769,11 +3252,8 @@ impl DataCollector {
     /// Note: Requires appropriate permissions - user may need to be in 'video' or 'power' group
     #[cfg(target_os = "linux")]
     fn get_cpu_power_consumption(&mut self) -> Option<f32> {
-        use std::fs;
-        use std::path::Path;
-
         // Try multiple RAPL domains to find available power data
-        let rapl_paths = [
+        const RAPL_PATHS: [&str; 7] = [
             "/sys/class/powercap/intel-rapl/intel-rapl:0/energy_uj", // Package 0
             "/sys/class/powercap/intel-rapl:0/energy_uj",            // Package 0 (alternative path)
             "/sys/class/powercap/intel-rapl/intel-rapl:0/core:0/energy_uj", // Core 0 of package 0
@@ -1783,49 +3263,64 @@ impl DataCollector {
             "/sys/class/powercap/intel-rapl/intel-rapl:0/subzone1/energy_uj", // Subzone 1
         ];
 
+        if self.rapl_paths.is_none() {
+            let mut readable = Vec::new();
+            let mut restricted = false;
+            for path in RAPL_PATHS {
+                match self.reader.read_str(path) {
+                    Ok(_) => readable.push(path),
+                    Err(std::io::ErrorKind::PermissionDenied) => restricted = true,
+                    Err(_) => {}
+                }
+            }
+            self.rapl_paths = Some((readable, restricted));
+        }
+        let (rapl_paths, restricted) = self.rapl_paths.clone().unwrap_or_default();
+
         for path in &rapl_paths {
-            if Path::new(path).exists() {
-                if let Ok(content) = fs::read_to_string(path) {
-                    if let Ok(current_energy_uj) = content.trim().parse::<f64>() {
-                        let current_time = std::time::Instant::now();
-
-                        // Calculate power if we have previous readings
-                        if let (Some(prev_energy), Some(prev_time)) =
-                            (self.previous_rapl_energy, self.previous_rapl_time)
-                        {
-                            let time_diff = (current_time - prev_time).as_secs_f64();
-
-                            if time_diff > 0.0 && time_diff <= 10.0 {
-                                // Allow up to 10 seconds between readings
-                                // Power = Energy difference / Time difference
-                                // Energy is in microjoules, time in seconds, so result is in microwatts
-                                // Convert to watts by dividing by 1,000,000
-                                let power_watts =
-                                    (current_energy_uj - prev_energy) / time_diff / 1_000_000.0;
-
-                                // Update stored values
-                                self.previous_rapl_energy = Some(current_energy_uj);
-                                self.previous_rapl_time = Some(current_time);
-
-                                // Only return power if it's a reasonable value (not negative or extremely high)
-                                if power_watts >= 0.0 && power_watts <= 500.0 {
-                                    // Reasonable upper limit for CPU power
-                                    return Some(power_watts as f32);
-                                }
-                            }
-                        }
+            if let Some(current_energy_uj) = self.reader.read_value::<f64>(path) {
+                let current_time = std::time::Instant::now();
+
+                // Calculate power if we have previous readings
+                if let (Some(prev_energy), Some(prev_time)) =
+                    (self.previous_rapl_energy, self.previous_rapl_time)
+                {
+                    let time_diff = (current_time - prev_time).as_secs_f64();
 
-                        // Store the first reading
+                    if time_diff > 0.0 && time_diff <= 10.0 {
+                        // Allow up to 10 seconds between readings
+                        // Power = Energy difference / Time difference
+                        // Energy is in microjoules, time in seconds, so result is in microwatts
+                        // Convert to watts by dividing by 1,000,000
+                        let power_watts =
+                            (current_energy_uj - prev_energy) / time_diff / 1_000_000.0;
+
+                        // Update stored values
                         self.previous_rapl_energy = Some(current_energy_uj);
                         self.previous_rapl_time = Some(current_time);
 
-                        // Continue to next path to see if we can get a better reading
-                        continue;
+                        // Only return power if it's a reasonable value (not negative or extremely high)
+                        if power_watts >= 0.0 && power_watts <= 500.0 {
+                            // Reasonable upper limit for CPU power
+                            return Some(power_watts as f32);
+                        }
                     }
                 }
+
+                // Store the first reading
+                self.previous_rapl_energy = Some(current_energy_uj);
+                self.previous_rapl_time = Some(current_time);
+
+                // Continue to next path to see if we can get a better reading
+                continue;
             }
         }
 
+        // Energy counters have been root-only since the PLATYPUS side channel (CVE-2020-8694)
+        if restricted {
+            self.note_restricted("CPU power (RAPL)");
+        }
+
         // If we've gone through all paths and still don't have a power calculation,
         // return None for the first reading
         None
@@ -1877,7 +3372,6 @@ impl DataCollector {
 
     #[cfg(target_os = "linux")]
     fn get_intel_gpu_power_consumption_with_note(&mut self) -> (Option<f32>, Option<String>) {
-        use std::fs;
         use std::path::Path;
 
         // Resolve and cache the first usable Intel GPU RAPL energy path.
@@ -1898,14 +3392,15 @@ impl DataCollector {
             return (None, Some("No RAPL".to_string()));
         }
 
-        let current_energy_uj = match fs::read_to_string(&energy_path) {
+        let current_energy_uj = match self.reader.read_str(&energy_path) {
             Ok(content) => match content.trim().parse::<f64>() {
                 Ok(value) => value,
                 Err(_) => return (None, Some("Invalid".to_string())),
             },
-            Err(err) => {
-                if err.kind() == std::io::ErrorKind::PermissionDenied {
-                    return (None, Some("No perm".to_string()));
+            Err(kind) => {
+                if kind == std::io::ErrorKind::PermissionDenied {
+                    self.note_restricted("GPU power (RAPL)");
+                    return (None, None);
                 }
                 return (None, Some("Unreadable".to_string()));
             }
@@ -2006,8 +3501,110 @@ impl DataCollector {
         // Power consumption is not available on non-Linux systems
         None
     }
+
+    /// Thermal throttle and power-limit events from `cpu*/thermal_throttle`, relative to startup.
+    /// Package counters are mirrored on every CPU of a package, so each package is counted once.
+    #[cfg(target_os = "linux")]
+    fn collect_throttle_stats(&mut self, cpu_count: usize) -> Option<ThrottleStats> {
+        let mut raw = ThrottleStats::default();
+        let mut packages: HashMap<u64, (u64, u64)> = HashMap::new();
+        let mut found = false;
+
+        for idx in 0..cpu_count {
+            let base = format!("/sys/devices/system/cpu/cpu{}", idx);
+            let mut read = |name: &str| {
+                self.reader
+                    .read_value::<u64>(format!("{}/thermal_throttle/{}", base, name))
+            };
+            let core = read("core_throttle_count");
+            let core_power = read("core_power_limit_count");
+            let package = read("package_throttle_count");
+            let package_power = read("package_power_limit_count");
+            if core.is_none() && package.is_none() {
+                continue;
+            }
+            found = true;
+            raw.core_events += core.unwrap_or(0);
+            raw.power_limit_events += core_power.unwrap_or(0);
+
+            let package_id = self
+                .reader
+                .read_value::<u64>(format!("{}/topology/physical_package_id", base))
+                .unwrap_or(0);
+            let entry = packages.entry(package_id).or_default();
+            entry.0 = entry.0.max(package.unwrap_or(0));
+            entry.1 = entry.1.max(package_power.unwrap_or(0));
+        }
+        if !found {
+            return None;
+        }
+        for (package, package_power) in packages.into_values() {
+            raw.package_events += package;
+            raw.power_limit_events += package_power;
+        }
+
+        if self.throttle_baseline.is_none() {
+            self.throttle_previous = raw;
+        }
+        let baseline = *self.throttle_baseline.get_or_insert(raw);
+        // Counters reset when a CPU goes offline; never report negative events.
+        let since_start = ThrottleStats {
+            core_events: raw.core_events.saturating_sub(baseline.core_events),
+            package_events: raw.package_events.saturating_sub(baseline.package_events),
+            power_limit_events: raw
+                .power_limit_events
+                .saturating_sub(baseline.power_limit_events),
+            active: raw.total() > self.throttle_previous.total(),
+        };
+        self.throttle_previous = raw;
+        Some(since_start)
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    fn collect_throttle_stats(&mut self, _cpu_count: usize) -> Option<ThrottleStats> {
+        None
+    }
+
+    /// Average time a task waited on each CPU's run queue before running, in µs per timeslice
+    /// since the previous collection. `None` for CPUs that ran nothing in between.
+    #[cfg(target_os = "linux")]
+    fn collect_run_queue_wait(&mut self, cpu_count: usize) -> Vec<Option<f32>> {
+        let Ok(content) = self.reader.read_str("/proc/schedstat") else {
+            return Vec::new();
+        };
+        let stats = crate::data::procfs::parse_schedstat(content);
+
+        let mut waits = vec![None; cpu_count];
+        for stat in stats {
+            let previous = self
+                .previous_schedstat
+                .insert(stat.cpu, (stat.wait_ns, stat.timeslices));
+            let Some((prev_wait, prev_slices)) = previous else {
+                continue;
+            };
+            let slices = stat.timeslices.saturating_sub(prev_slices);
+            if let (Some(slot), true) = (waits.get_mut(stat.cpu), slices > 0) {
+                let wait_ns = stat.wait_ns.saturating_sub(prev_wait);
+                *slot = Some((wait_ns as f64 / slices as f64 / 1000.0) as f32);
+            }
+        }
+        waits
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    fn collect_run_queue_wait(&mut self, _cpu_count: usize) -> Vec<Option<f32>> {
+        Vec::new()
+    }
 } // End of impl DataCollector
 
+#[derive(Default)]
+struct MemoryBreakdown {
+    free: u64,
+    buffers: u64,
+    cached: u64,
+    available: u64,
+}
+
 // Helper function to clean up GPU descriptions from lspci
 fn clean_gpu_description(desc: &str) -> String {
     let cleaned = desc.trim();
@@ -2061,3 +3658,56 @@ fn clean_gpu_description(desc: &str) -> String {
 
     cleaned
 }
+
+#[cfg(all(test, target_os = "linux"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cpu_history_is_sent_for_the_selected_process_only() {
+        let mut collector = DataCollector::new();
+        collector.set_selected_process(Some(std::process::id()));
+        let snapshot = collector.collect();
+        let (own, others): (Vec<_>, Vec<_>) = snapshot
+            .processes
+            .iter()
+            .partition(|process| process.pid == std::process::id());
+        assert_eq!(own.len(), 1);
+        assert_eq!(own[0].cpu_history.len(), 1);
+        assert!(others.iter().all(|process| process.cpu_history.is_empty()));
+    }
+
+    #[test]
+    fn test_policy_frequencies_cover_related_cpus() {
+        let root = std::env::temp_dir().join(format!("rtop-cpufreq-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        let write = |path: std::path::PathBuf, text: &str| {
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, text).unwrap();
+        };
+        // A big.LITTLE board: one policy per cluster, reported in kHz
+        write(root.join("policy0/scaling_cur_freq"), "1416000\n");
+        write(root.join("policy0/related_cpus"), "0 1 2 3\n");
+        write(root.join("policy4/scaling_cur_freq"), "0\n");
+        write(root.join("policy4/cpuinfo_cur_freq"), "1800000\n");
+        write(root.join("policy4/related_cpus"), "4 5\n");
+        // A policy without a readable frequency leaves its cores to the next fallback
+        write(root.join("policy6/related_cpus"), "6 7\n");
+        write(root.join("boost"), "1\n");
+
+        let frequencies = DataCollector::policy_frequencies(&mut SysfsReader::new(), &root, 7);
+        let _ = std::fs::remove_dir_all(&root);
+        assert_eq!(
+            frequencies,
+            [
+                Some(1416),
+                Some(1416),
+                Some(1416),
+                Some(1416),
+                Some(1800),
+                Some(1800),
+                None
+            ]
+        );
+    }
+}
@@ -0,0 +1,173 @@
+//! External commands run during collection (`lspci`, `iw`, `pmset`, `powershell`). Each run
+//! has a hard timeout, and a command that keeps failing is left alone for a while instead of
+//! costing every tick another spawn.
+
+use std::collections::HashMap;
+use std::io::Read;
+use std::process::{Command, Stdio};
+use std::sync::mpsc;
+use std::time::{Duration, Instant};
+
+/// Consecutive failures before a command is skipped
+const MAX_FAILURES: u32 = 3;
+/// How long a failing command is skipped before it gets one more try
+const COOLDOWN: Duration = Duration::from_secs(300);
+const POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+#[derive(Default)]
+struct Breaker {
+    failures: u32,
+    /// Skip the command until then
+    open_until: Option<Instant>,
+}
+
+/// Runs collection commands with a timeout and per-command circuit breaking
+#[derive(Default)]
+pub struct CommandRunner {
+    /// Keyed by the whole command line, so `iw dev wlan1` failing does not stop `iw dev wlan0`
+    breakers: HashMap<Vec<String>, Breaker>,
+}
+
+impl CommandRunner {
+    /// Default limit for a probe; collection ticks are about a second apart
+    pub const TIMEOUT: Duration = Duration::from_secs(2);
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Stdout of `program args`, or `None` when it could not run, exited unsuccessfully,
+    /// ran past `timeout` (it is killed), or is being skipped after repeated failures
+    pub fn run(&mut self, program: &str, args: &[&str], timeout: Duration) -> Option<String> {
+        let argv = std::iter::once(program).chain(args.iter().copied());
+        let breaker = self
+            .breakers
+            .entry(argv.map(String::from).collect())
+            .or_default();
+        if breaker
+            .open_until
+            .is_some_and(|until| Instant::now() < until)
+        {
+            return None;
+        }
+
+        let output = run_with_timeout(program, args, timeout);
+        if output.is_some() {
+            *breaker = Breaker::default();
+        } else {
+            breaker.failures += 1;
+            // Past the limit every failure, including the retry after a cooldown, re-opens it
+            if breaker.failures >= MAX_FAILURES {
+                breaker.open_until = Some(Instant::now() + COOLDOWN);
+            }
+        }
+        output
+    }
+
+    /// Whether the command line `argv` is currently being skipped
+    #[cfg(test)]
+    fn is_open(&self, argv: &[&str]) -> bool {
+        let argv: Vec<String> = argv.iter().map(|arg| arg.to_string()).collect();
+        self.breakers
+            .get(&argv)
+            .and_then(|breaker| breaker.open_until)
+            .is_some_and(|until| Instant::now() < until)
+    }
+}
+
+fn run_with_timeout(program: &str, args: &[&str], timeout: Duration) -> Option<String> {
+    let mut child = Command::new(program)
+        .args(args)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .ok()?;
+
+    // Drain stdout on its own thread so a chatty command cannot block on a full pipe
+    let mut stdout = child.stdout.take()?;
+    let (tx, output) = mpsc::channel();
+    std::thread::spawn(move || {
+        let mut buffer = Vec::new();
+        let _ = tx.send(stdout.read_to_end(&mut buffer).ok().map(|_| buffer));
+    });
+
+    let started = Instant::now();
+    let status = loop {
+        match child.try_wait() {
+            Ok(Some(status)) => break Some(status),
+            Ok(None) if started.elapsed() < timeout => std::thread::sleep(POLL_INTERVAL),
+            Ok(None) | Err(_) => {
+                let _ = child.kill();
+                let _ = child.wait();
+                break None;
+            }
+        }
+    };
+
+    if !status?.success() {
+        return None;
+    }
+    // A background grandchild can hold the pipe open after the command exits, so end of file
+    // is only waited for until the timeout; the reader is then left to finish on its own
+    let remaining = timeout.saturating_sub(started.elapsed()).max(POLL_INTERVAL);
+    let buffer = output.recv_timeout(remaining).ok()??;
+    Some(String::from_utf8_lossy(&buffer).into_owned())
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hung_command_is_killed_and_failing_command_skipped() {
+        let mut runner = CommandRunner::new();
+        assert_eq!(
+            runner.run("echo", &["hello"], CommandRunner::TIMEOUT),
+            Some("hello\n".to_string())
+        );
+
+        let started = Instant::now();
+        assert_eq!(
+            runner.run("sleep", &["5"], Duration::from_millis(100)),
+            None
+        );
+        assert!(started.elapsed() < Duration::from_secs(2));
+
+        for _ in 0..MAX_FAILURES {
+            assert!(!runner.is_open(&["false"]));
+            assert_eq!(runner.run("false", &[], CommandRunner::TIMEOUT), None);
+        }
+        assert!(runner.is_open(&["false"]));
+        assert!(!runner.is_open(&["sleep", "5"]));
+
+        // Only the failing command line is skipped, not every run of the program
+        for _ in 0..MAX_FAILURES {
+            assert_eq!(
+                runner.run("sh", &["-c", "exit 1"], CommandRunner::TIMEOUT),
+                None
+            );
+        }
+        assert!(runner.is_open(&["sh", "-c", "exit 1"]));
+        assert_eq!(
+            runner.run("sh", &["-c", "echo ok"], CommandRunner::TIMEOUT),
+            Some("ok\n".to_string())
+        );
+        assert!(runner
+            .run("rtop-no-such-command", &[], CommandRunner::TIMEOUT)
+            .is_none());
+    }
+
+    #[test]
+    fn test_grandchild_holding_the_pipe_does_not_block() {
+        let mut runner = CommandRunner::new();
+        let started = Instant::now();
+        // The shell exits at once, but the backgrounded sleep keeps stdout open
+        runner.run(
+            "sh",
+            &["-c", "sleep 5 & echo hi"],
+            Duration::from_millis(200),
+        );
+        assert!(started.elapsed() < Duration::from_secs(2));
+    }
+}
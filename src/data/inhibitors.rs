@@ -0,0 +1,107 @@
+//! systemd-logind inhibitor locks: what is currently holding off sleep, idle or shutdown, and
+//! which process took the lock, as `systemd-inhibit --list` shows them.
+
+/// One inhibitor lock
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Inhibitor {
+    /// Colon-separated operations held off, e.g. `sleep:idle` or `handle-lid-switch`
+    pub what: String,
+    /// Application name the holder gave
+    pub who: String,
+    pub why: String,
+    /// `block` holds the operation off; `delay` only postpones it briefly
+    pub mode: String,
+    pub uid: u32,
+    pub pid: u32,
+}
+
+impl Inhibitor {
+    pub fn blocks(&self) -> bool {
+        self.mode == "block"
+    }
+}
+
+/// Locks currently held, blocking ones first. `None` when logind cannot be asked, e.g. no
+/// systemd, no system bus, or off Linux.
+#[cfg(target_os = "linux")]
+pub fn list() -> Option<Vec<Inhibitor>> {
+    use crate::data::command::CommandRunner;
+    let output = CommandRunner::new().run(
+        "busctl",
+        &[
+            "--json=short",
+            "call",
+            "org.freedesktop.login1",
+            "/org/freedesktop/login1",
+            "org.freedesktop.login1.Manager",
+            "ListInhibitors",
+        ],
+        CommandRunner::TIMEOUT,
+    )?;
+    let mut inhibitors = parse_busctl_json(&output)?;
+    inhibitors.sort_by(|a, b| b.blocks().cmp(&a.blocks()).then(a.pid.cmp(&b.pid)));
+    Some(inhibitors)
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn list() -> Option<Vec<Inhibitor>> {
+    None
+}
+
+/// Parse the reply of `ListInhibitors` as `busctl --json=short` prints it: an array of
+/// `(what, who, why, mode, uid, pid)` structs
+fn parse_busctl_json(text: &str) -> Option<Vec<Inhibitor>> {
+    let reply: serde_json::Value = serde_json::from_str(text.trim()).ok()?;
+    let entries = reply.get("data")?.get(0)?.as_array()?;
+    entries
+        .iter()
+        .map(|entry| {
+            let text = |index: usize| entry.get(index)?.as_str().map(str::to_string);
+            let number = |index: usize| {
+                entry
+                    .get(index)?
+                    .as_u64()
+                    .and_then(|value| u32::try_from(value).ok())
+            };
+            Some(Inhibitor {
+                what: text(0)?,
+                who: text(1)?,
+                why: text(2)?,
+                mode: text(3)?,
+                uid: number(4)?,
+                pid: number(5)?,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_busctl_json_reply() {
+        let reply = r#"{"type":"a(ssssuu)","data":[[["sleep","ModemManager","ModemManager needs to reset devices","delay",0,931],["sleep:idle","Firefox","Playing video","block",1000,4242]]]}"#;
+        let inhibitors = parse_busctl_json(reply).unwrap();
+        assert_eq!(inhibitors.len(), 2);
+        assert_eq!(inhibitors[0].who, "ModemManager");
+        assert!(!inhibitors[0].blocks());
+        assert_eq!(
+            inhibitors[1],
+            Inhibitor {
+                what: "sleep:idle".to_string(),
+                who: "Firefox".to_string(),
+                why: "Playing video".to_string(),
+                mode: "block".to_string(),
+                uid: 1000,
+                pid: 4242,
+            }
+        );
+
+        assert_eq!(
+            parse_busctl_json(r#"{"type":"a(ssssuu)","data":[[]]}"#),
+            Some(Vec::new())
+        );
+        assert_eq!(parse_busctl_json("Failed to connect to bus"), None);
+    }
+}
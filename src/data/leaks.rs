@@ -0,0 +1,125 @@
+use std::collections::{HashMap, VecDeque};
+use std::time::{Duration, Instant};
+
+/// Samples kept per process across the window
+const MAX_SAMPLES: u32 = 20;
+/// Fewer samples than this cannot establish a trend
+const MIN_SAMPLES: usize = 6;
+/// Growth below this is noise (allocator arenas, caches warming up)
+const MIN_GROWTH_BYTES: u64 = 1024 * 1024;
+
+/// Flags processes whose resident memory only ever grows over a sliding window
+pub struct LeakTracker {
+    window: Duration,
+    samples: HashMap<u32, VecDeque<(Instant, u64)>>,
+    last_sample: Option<Instant>,
+}
+
+impl LeakTracker {
+    pub fn new(window: Duration) -> Self {
+        Self {
+            window,
+            samples: HashMap::new(),
+            last_sample: None,
+        }
+    }
+
+    pub fn set_window(&mut self, window: Duration) {
+        self.window = window;
+        self.samples.clear();
+        self.last_sample = None;
+    }
+
+    /// Record memory for every live process, at most `MAX_SAMPLES` times per window
+    pub fn observe(&mut self, now: Instant, processes: impl Iterator<Item = (u32, u64)>) {
+        if self.window.is_zero() {
+            return;
+        }
+        if let Some(last) = self.last_sample {
+            if now.duration_since(last) < self.window / MAX_SAMPLES {
+                return;
+            }
+        }
+        self.last_sample = Some(now);
+
+        let mut next = HashMap::with_capacity(self.samples.len());
+        for (pid, memory) in processes {
+            let mut history = self.samples.remove(&pid).unwrap_or_default();
+            while history
+                .front()
+                .is_some_and(|&(at, _)| now.duration_since(at) > self.window)
+            {
+                history.pop_front();
+            }
+            history.push_back((now, memory));
+            next.insert(pid, history);
+        }
+        // Exited processes are dropped along with their history.
+        self.samples = next;
+    }
+
+    /// Growth in bytes per second when `pid` grew monotonically over at least half the window
+    pub fn growth_rate(&self, pid: u32) -> Option<f64> {
+        let history = self.samples.get(&pid)?;
+        if history.len() < MIN_SAMPLES {
+            return None;
+        }
+        let (first_at, first) = *history.front()?;
+        let (last_at, last) = *history.back()?;
+        let span = last_at.duration_since(first_at);
+        let growth = last.saturating_sub(first);
+        if span < self.window / 2 || growth < MIN_GROWTH_BYTES {
+            return None;
+        }
+        let monotonic = history
+            .iter()
+            .zip(history.iter().skip(1))
+            .all(|(&(_, a), &(_, b))| b >= a);
+        monotonic.then(|| growth as f64 / span.as_secs_f64())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_flags_only_monotonic_growth() {
+        let window = Duration::from_secs(600);
+        let step = window / MAX_SAMPLES;
+        let start = Instant::now();
+        let mut tracker = LeakTracker::new(window);
+
+        for i in 0..12u64 {
+            let leaking = 100 * 1024 * 1024 + i * 2 * 1024 * 1024;
+            // Grows overall but dips once, like a normal heap
+            let bursty = 100 * 1024 * 1024 + i * 2 * 1024 * 1024 - if i == 6 { 8 << 20 } else { 0 };
+            let flat = 50 * 1024 * 1024;
+            tracker.observe(
+                start + step * i as u32,
+                [(1, leaking), (2, bursty), (3, flat)].into_iter(),
+            );
+        }
+
+        let rate = tracker.growth_rate(1).expect("leak detected");
+        assert!((rate - (2 * 1024 * 1024) as f64 / step.as_secs_f64()).abs() < 1.0);
+        assert_eq!(tracker.growth_rate(2), None);
+        assert_eq!(tracker.growth_rate(3), None);
+
+        tracker.observe(start + step * 12, [(2, 0)].into_iter());
+        assert_eq!(tracker.growth_rate(1), None);
+    }
+
+    #[test]
+    fn test_growth_near_counter_limit_does_not_overflow() {
+        let window = Duration::from_secs(600);
+        let step = window / MAX_SAMPLES;
+        let start = Instant::now();
+        let mut tracker = LeakTracker::new(window);
+
+        for i in 0..12u32 {
+            tracker.observe(start + step * i, [(1, u64::MAX - 1024)].into_iter());
+        }
+        assert_eq!(tracker.growth_rate(1), None);
+    }
+}
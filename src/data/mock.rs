@@ -0,0 +1,144 @@
+use crate::data::snapshot::{
+    DiskInfo, GpuInfo, InterfaceKind, NetworkInfo, ProcessInfo, ProcessState, SchedClass,
+    SystemSnapshot,
+};
+use crate::data::Collector;
+use std::collections::VecDeque;
+
+/// Collector that replays scripted snapshots, repeating the last one once the script runs out
+pub struct MockCollector {
+    script: VecDeque<SystemSnapshot>,
+    last: SystemSnapshot,
+}
+
+impl MockCollector {
+    pub fn new(script: Vec<SystemSnapshot>) -> Self {
+        Self {
+            script: script.into(),
+            last: sample_snapshot(),
+        }
+    }
+
+    /// Queue another snapshot behind the ones already scripted
+    pub fn push(&mut self, snapshot: SystemSnapshot) {
+        self.script.push_back(snapshot);
+    }
+}
+
+impl Collector for MockCollector {
+    fn collect(&mut self) -> SystemSnapshot {
+        if let Some(next) = self.script.pop_front() {
+            self.last = next;
+        }
+        self.last.clone()
+    }
+}
+
+pub fn process(pid: u32, name: &str, cpu_usage: f32, memory: u64) -> ProcessInfo {
+    ProcessInfo {
+        pid,
+        name: name.to_string(),
+        memory,
+        cpu_usage,
+        cumulative_cpu: cpu_usage,
+        disk_usage: 0,
+        disk_read_per_sec: 0,
+        disk_write_per_sec: 0,
+        parent_pid: None,
+        start_time: 0,
+        run_time: 0,
+        uid: None,
+        user: None,
+        cmd: vec![name.to_string()],
+        exe: None,
+        root: None,
+        cwd: None,
+        state: ProcessState::Sleeping,
+        major_faults_per_sec: 0.0,
+        minor_faults_per_sec: 0.0,
+        swap: 0,
+        leak_rate: None,
+        core_samples: Vec::new(),
+        cpu_history: Vec::new(),
+        last_cpu: None,
+        sched_class: SchedClass::Normal,
+        rt_priority: 0,
+        nice: 0,
+        kernel_thread: false,
+        container: false,
+        gpu: Vec::new(),
+    }
+}
+
+pub fn disk(name: &str, total_space: u64, available_space: u64) -> DiskInfo {
+    DiskInfo {
+        name: name.to_string(),
+        mount_points: vec![name.to_string()],
+        total_space,
+        available_space,
+        reserved_space: 0,
+        remote: None,
+        fill_rate: None,
+        time_to_full: None,
+    }
+}
+
+pub fn network(name: &str) -> NetworkInfo {
+    NetworkInfo {
+        name: name.to_string(),
+        total_received: 0,
+        total_transmitted: 0,
+        received_per_sec: 0,
+        transmitted_per_sec: 0,
+        kind: InterfaceKind::Plain,
+        parent: None,
+        mtu: Some(1500),
+        power_save: None,
+        oper_state: Some("up".to_string()),
+    }
+}
+
+pub fn gpu(name: &str, vendor: &str, temp: f32) -> GpuInfo {
+    GpuInfo {
+        name: name.to_string(),
+        vendor: vendor.to_string(),
+        temp: Some(temp),
+        usage: Some(0.0),
+        usage_note: None,
+        memory_used: None,
+        memory_total: None,
+        power_usage: None,
+        temp_note: None,
+        power_note: None,
+        memory_note: None,
+    }
+}
+
+/// A small, fixed machine: 4 cores, 16 GiB of RAM, one disk and a few processes
+pub fn sample_snapshot() -> SystemSnapshot {
+    const GIB: u64 = 1024 * 1024 * 1024;
+    SystemSnapshot {
+        global_cpu_usage: 25.0,
+        used_memory: 6 * GIB,
+        total_memory: 16 * GIB,
+        free_memory: 7 * GIB,
+        buffers_memory: GIB / 2,
+        cached_memory: 3 * GIB,
+        available_memory: 10 * GIB,
+        cpu_count: 4,
+        cpu_history: vec![VecDeque::from(vec![25.0]); 4],
+        cpu_frequencies: vec![2400; 4],
+        processes: vec![
+            process(100, "alpha", 5.0, 900 * 1024 * 1024),
+            process(200, "bravo", 40.0, 100 * 1024 * 1024),
+            process(300, "charlie", 12.0, 400 * 1024 * 1024),
+        ],
+        disks: vec![disk("/", 500 * GIB, 200 * GIB)],
+        hostname: "mockhost".to_string(),
+        primary_ip: Some("192.168.1.20".to_string()),
+        uptime: "1h 0m".to_string(),
+        load_avg: "0.50 0.40 0.30".to_string(),
+        cpu_name: "Mock CPU".to_string(),
+        ..SystemSnapshot::default()
+    }
+}
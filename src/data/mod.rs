@@ -1,9 +1,47 @@
 use crate::data::snapshot::SystemSnapshot;
 use tokio::sync::mpsc;
-use tokio::time::{interval, Duration};
+use tokio::time::{interval, interval_at, Duration, Instant};
 
+pub mod blockdev;
 pub mod collector;
+pub mod command;
+pub mod inhibitors;
+pub mod leaks;
+#[cfg(test)]
+pub mod mock;
+pub mod mounts;
+#[cfg(feature = "ebpf")]
+pub mod offcpu;
+pub mod probes;
+#[cfg(target_os = "linux")]
+pub mod procfs;
 pub mod snapshot;
+pub mod speedtest;
+#[cfg(target_os = "linux")]
+pub mod sysfs;
+
+/// Source of snapshots; the app only depends on this so tests can script the data
+pub trait Collector {
+    fn collect(&mut self) -> SystemSnapshot;
+}
+
+impl Collector for collector::DataCollector {
+    fn collect(&mut self) -> SystemSnapshot {
+        collector::DataCollector::collect(self)
+    }
+}
+
+/// Control messages sent from the UI to the polling task
+pub enum CollectorCommand {
+    /// Change the collection interval
+    SetInterval(Duration),
+    /// Keep complete command lines instead of the configured truncation
+    SetFullCommandLines(bool),
+    /// Attribute this process's open files to mount points (`None` stops)
+    InspectProcess(Option<u32>),
+    /// Send the recent CPU% samples of this process only (`None` sends none)
+    SelectProcess(Option<u32>),
+}
 
 pub struct DataManager {
     pub collector: collector::DataCollector,
@@ -11,18 +49,51 @@ pub struct DataManager {
 }
 
 impl DataManager {
-    pub fn new(update_interval_ms: u64) -> Self {
+    pub fn new(collector: collector::DataCollector, update_interval_ms: u64) -> Self {
         Self {
-            collector: collector::DataCollector::new(),
+            collector,
             update_interval: Duration::from_millis(update_interval_ms),
         }
     }
 
-    pub async fn start_polling(&mut self, sender: mpsc::UnboundedSender<SystemSnapshot>) {
+    pub async fn start_polling(
+        &mut self,
+        sender: mpsc::UnboundedSender<SystemSnapshot>,
+        mut commands: mpsc::UnboundedReceiver<CollectorCommand>,
+    ) {
         let mut interval = interval(self.update_interval);
 
         loop {
-            interval.tick().await;
+            tokio::select! {
+                _ = interval.tick() => {}
+                Some(command) = commands.recv() => {
+                    match command {
+                        CollectorCommand::SetInterval(update_interval) => {
+                            if update_interval == self.update_interval {
+                                continue;
+                            }
+                            // Speeding up collects right away; slowing down waits a full period.
+                            let start = if update_interval < self.update_interval {
+                                Instant::now()
+                            } else {
+                                Instant::now() + update_interval
+                            };
+                            self.update_interval = update_interval;
+                            interval = interval_at(start, update_interval);
+                        }
+                        CollectorCommand::SetFullCommandLines(enabled) => {
+                            self.collector.set_full_command_lines(enabled);
+                        }
+                        CollectorCommand::InspectProcess(pid) => {
+                            self.collector.set_inspected_process(pid);
+                        }
+                        CollectorCommand::SelectProcess(pid) => {
+                            self.collector.set_selected_process(pid);
+                        }
+                    }
+                    continue;
+                }
+            }
             let snapshot = self.collector.collect();
 
             if sender.send(snapshot).is_err() {
@@ -0,0 +1,236 @@
+//! Space on mounted filesystems via statvfs. Every mount is probed on its own thread, one probe
+//! at a time per mount, so a server that stops answering (or a wedged FUSE daemon) marks its
+//! mount stale instead of freezing every refresh behind a hung statvfs.
+
+use crate::data::snapshot::RemoteMount;
+use std::collections::HashMap;
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, TryRecvError};
+use std::time::{Duration, Instant};
+
+/// Filesystems served over the network, whose statvfs blocks while the server is unreachable
+const NETWORK_FILESYSTEMS: &[&str] = &[
+    "nfs",
+    "nfs4",
+    "cifs",
+    "smb3",
+    "smbfs",
+    "9p",
+    "ceph",
+    "glusterfs",
+    "afs",
+    "davfs",
+    "fuse.sshfs",
+    "fuse.rclone",
+    "fuse.glusterfs",
+];
+
+/// Pseudo filesystems and media that hold no user data, as sysinfo skips them
+const PSEUDO_FILESYSTEMS: &[&str] = &[
+    "rootfs",
+    "sysfs",
+    "proc",
+    "tmpfs",
+    "devtmpfs",
+    "cgroup",
+    "cgroup2",
+    "pstore",
+    "squashfs",
+    "rpc_pipefs",
+    "iso9660",
+];
+
+pub fn is_network(fs_type: &str) -> bool {
+    NETWORK_FILESYSTEMS.contains(&fs_type)
+}
+
+/// Mounts left out of the disk panel: pseudo filesystems and kernel or runtime mount points
+pub fn is_ignored(source: &str, target: &str, fs_type: &str) -> bool {
+    PSEUDO_FILESYSTEMS.contains(&fs_type)
+        || target.starts_with("/sys")
+        || target.starts_with("/proc")
+        || (target.starts_with("/run") && !target.starts_with("/run/media"))
+        || source.starts_with("sunrpc")
+}
+
+/// Sizes from statvfs, in bytes
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FsUsage {
+    pub total: u64,
+    /// Free to unprivileged users
+    pub available: u64,
+    /// Free but reserved for root
+    pub reserved: u64,
+}
+
+#[cfg(unix)]
+#[allow(clippy::unnecessary_cast)] // The statvfs field types differ between platforms
+pub fn statvfs(mount_point: &str) -> Option<FsUsage> {
+    let path = std::ffi::CString::new(mount_point).ok()?;
+    let mut stat = std::mem::MaybeUninit::<libc::statvfs>::uninit();
+    // SAFETY: `path` is NUL-terminated and `stat` is only read after statvfs filled it
+    if unsafe { libc::statvfs(path.as_ptr(), stat.as_mut_ptr()) } != 0 {
+        return None;
+    }
+    let stat = unsafe { stat.assume_init() };
+    let block = stat.f_frsize as u64;
+    Some(FsUsage {
+        total: (stat.f_blocks as u64).saturating_mul(block),
+        available: (stat.f_bavail as u64).saturating_mul(block),
+        reserved: (stat.f_bfree as u64)
+            .saturating_sub(stat.f_bavail as u64)
+            .saturating_mul(block),
+    })
+}
+
+#[cfg(not(unix))]
+pub fn statvfs(_mount_point: &str) -> Option<FsUsage> {
+    None
+}
+
+type ProbeResult = (Option<FsUsage>, Duration);
+
+#[derive(Default)]
+struct Probe {
+    /// The last probe that finished: its answer and how long it took
+    last: Option<ProbeResult>,
+    /// A probe still running and when it started
+    pending: Option<(Instant, Receiver<ProbeResult>)>,
+}
+
+/// Background statvfs probes, keyed by mount point
+pub struct MountProbes {
+    probes: HashMap<String, Probe>,
+    stale_after: Duration,
+}
+
+impl MountProbes {
+    /// A probe unanswered for this long marks its mount stale
+    pub const STALE_AFTER: Duration = Duration::from_secs(5);
+    /// How long a local mount's new probe is waited for, so its usage is current this tick
+    pub const LOCAL_WAIT: Duration = Duration::from_millis(20);
+
+    pub fn new(stale_after: Duration) -> Self {
+        Self {
+            probes: HashMap::new(),
+            stale_after,
+        }
+    }
+
+    /// The latest known usage of `mount_point` and how it is responding. A probe is started
+    /// when none is running and waited for up to `wait`; an answer that takes longer is picked
+    /// up by a later call, and a probe still running from an earlier call is not waited for.
+    pub fn poll(
+        &mut self,
+        mount_point: &str,
+        stat: fn(&str) -> Option<FsUsage>,
+        wait: Duration,
+    ) -> (Option<FsUsage>, RemoteMount) {
+        let probe = self.probes.entry(mount_point.to_string()).or_default();
+
+        if let Some((_, rx)) = &probe.pending {
+            match rx.try_recv() {
+                Ok(result) => {
+                    probe.last = Some(result);
+                    probe.pending = None;
+                }
+                Err(TryRecvError::Disconnected) => probe.pending = None,
+                Err(TryRecvError::Empty) => {}
+            }
+        }
+        if probe.pending.is_none() {
+            let (tx, rx) = mpsc::channel();
+            let path = mount_point.to_string();
+            std::thread::spawn(move || {
+                let started = Instant::now();
+                let usage = stat(&path);
+                let _ = tx.send((usage, started.elapsed()));
+            });
+            match rx.recv_timeout(wait) {
+                Ok(result) => probe.last = Some(result),
+                Err(RecvTimeoutError::Disconnected) => {}
+                Err(RecvTimeoutError::Timeout) => probe.pending = Some((Instant::now(), rx)),
+            }
+        }
+
+        let stale_secs = probe
+            .pending
+            .as_ref()
+            .map(|(started, _)| started.elapsed())
+            .filter(|waited| *waited >= self.stale_after)
+            .map(|waited| waited.as_secs());
+        let usage = probe.last.and_then(|(usage, _)| usage);
+        let latency_ms = probe.last.map(|(_, took)| took.as_secs_f64() * 1000.0);
+        (
+            usage,
+            RemoteMount {
+                latency_ms,
+                stale_secs,
+            },
+        )
+    }
+
+    /// Forget mounts that are gone; a probe stuck on one is left to finish on its own
+    pub fn retain(&mut self, mounted: &[&str]) {
+        self.probes
+            .retain(|mount_point, _| mounted.contains(&mount_point.as_str()));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hung_mount_goes_stale_without_blocking() {
+        fn answers(_: &str) -> Option<FsUsage> {
+            Some(FsUsage {
+                total: 100,
+                available: 40,
+                reserved: 0,
+            })
+        }
+        fn hangs(_: &str) -> Option<FsUsage> {
+            std::thread::sleep(Duration::from_secs(2));
+            None
+        }
+
+        let mut probes = MountProbes::new(Duration::from_millis(50));
+        let started = Instant::now();
+        let (usage, health) = probes.poll("/mnt/nfs", answers, Duration::ZERO);
+        assert_eq!((usage, health.latency_ms), (None, None));
+        assert_eq!(
+            probes.poll("/mnt/hung", hangs, Duration::ZERO).1.stale_secs,
+            None
+        );
+        // Local mounts wait briefly, so a healthy one answers within the same call
+        let (usage, _) = probes.poll("/", answers, Duration::from_secs(1));
+        assert_eq!(usage.map(|usage| usage.total), Some(100));
+        assert_eq!(
+            probes.poll("/fuse", hangs, Duration::from_millis(10)).0,
+            None
+        );
+
+        std::thread::sleep(Duration::from_millis(100));
+        let (usage, health) = probes.poll("/mnt/nfs", answers, Duration::ZERO);
+        assert_eq!(usage.map(|usage| usage.available), Some(40));
+        assert!(health.latency_ms.is_some());
+        assert_eq!(health.stale_secs, None);
+
+        let (usage, health) = probes.poll("/mnt/hung", hangs, Duration::ZERO);
+        assert_eq!(usage, None);
+        assert_eq!(health.stale_secs, Some(0));
+        // The running probe of a hung local mount is not waited for again
+        let (usage, health) = probes.poll("/fuse", hangs, Duration::from_secs(1));
+        assert_eq!(usage, None);
+        assert_eq!(health.stale_secs, Some(0));
+        assert!(started.elapsed() < Duration::from_secs(1));
+    }
+
+    #[test]
+    fn test_classify_mounts() {
+        assert!(!is_network("ext4"));
+        assert!(is_network("nfs4"));
+        assert!(is_ignored("proc", "/proc", "proc"));
+        assert!(!is_ignored("/dev/sda1", "/run/media/usb", "vfat"));
+    }
+}
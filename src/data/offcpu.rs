@@ -0,0 +1,215 @@
+use std::collections::HashMap;
+use std::process::Stdio;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::io::{AsyncBufReadExt, BufReader};
+use tokio::process::Command;
+
+/// Rows kept in the top offenders list
+const MAX_OFFENDERS: usize = 15;
+
+/// Stall time attributed to one process over the last sampling window
+#[derive(Debug, Clone, Default)]
+pub struct Offender {
+    pub pid: u32,
+    pub name: String,
+    /// Time spent blocked (sleeping or waiting, not runnable), in ms per second
+    pub off_cpu_ms: f64,
+    /// Block I/O request latency issued by this process, in ms per second
+    pub io_ms: f64,
+    pub io_requests: u64,
+}
+
+impl Offender {
+    pub fn stall_ms(&self) -> f64 {
+        self.off_cpu_ms + self.io_ms
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct OffenderReport {
+    /// Worst offenders first
+    pub offenders: Vec<Offender>,
+    pub window_secs: u64,
+    /// Why sampling stopped (bpftrace missing, not root, kernel without tracepoints)
+    pub error: Option<String>,
+}
+
+pub type SharedOffenders = Arc<Mutex<OffenderReport>>;
+
+/// Off-CPU time is measured from a blocking switch-out to the next switch-in; block I/O
+/// latency from request issue to completion, charged to the issuing task.
+const PROGRAM: &str = r#"
+tracepoint:sched:sched_switch
+{
+    if (args->prev_state != 0) {
+        @off_start[args->prev_pid] = nsecs;
+    }
+    $start = @off_start[args->next_pid];
+    if ($start != 0) {
+        @offcpu[args->next_pid, args->next_comm] = sum(nsecs - $start);
+        delete(@off_start[args->next_pid]);
+    }
+}
+
+tracepoint:block:block_rq_issue
+{
+    @io_start[args->dev, args->sector] = nsecs;
+    @io_pid[args->dev, args->sector] = pid;
+    @io_comm[args->dev, args->sector] = comm;
+}
+
+tracepoint:block:block_rq_complete
+/@io_start[args->dev, args->sector]/
+{
+    $key_pid = @io_pid[args->dev, args->sector];
+    $key_comm = @io_comm[args->dev, args->sector];
+    @io[$key_pid, $key_comm] = sum(nsecs - @io_start[args->dev, args->sector]);
+    @ios[$key_pid, $key_comm] = count();
+    delete(@io_start[args->dev, args->sector]);
+    delete(@io_pid[args->dev, args->sector]);
+    delete(@io_comm[args->dev, args->sector]);
+}
+
+interval:s:WINDOW
+{
+    print(@offcpu);
+    print(@io);
+    print(@ios);
+    clear(@offcpu);
+    clear(@io);
+    clear(@ios);
+    printf("--\n");
+}
+
+END
+{
+    clear(@off_start);
+    clear(@io_start);
+    clear(@io_pid);
+    clear(@io_comm);
+}
+"#;
+
+/// Start sampling with `bpftrace`; results are replaced once per `window`
+pub fn spawn_sampler(window: Duration) -> SharedOffenders {
+    let window_secs = window.as_secs().max(1);
+    let shared: SharedOffenders = Arc::new(Mutex::new(OffenderReport {
+        window_secs,
+        ..OffenderReport::default()
+    }));
+    let state = Arc::clone(&shared);
+
+    tokio::spawn(async move {
+        let err = run_bpftrace(window_secs, &state).await;
+        if let Ok(mut report) = state.lock() {
+            report.error = Some(err);
+        }
+    });
+
+    shared
+}
+
+/// Run until bpftrace exits, publishing each window; returns why it stopped
+async fn run_bpftrace(window_secs: u64, state: &SharedOffenders) -> String {
+    let program = PROGRAM.replace("WINDOW", &window_secs.to_string());
+    let mut child = match Command::new("bpftrace")
+        .arg("-e")
+        .arg(program)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()
+    {
+        Ok(child) => child,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
+            return "bpftrace not installed".to_string()
+        }
+        Err(err) => return err.to_string(),
+    };
+
+    let Some(stdout) = child.stdout.take() else {
+        return "bpftrace output unavailable".to_string();
+    };
+    let mut lines = BufReader::new(stdout).lines();
+    let mut batch: HashMap<u32, Offender> = HashMap::new();
+
+    while let Ok(Some(line)) = lines.next_line().await {
+        if line == "--" {
+            let mut offenders: Vec<Offender> = batch.drain().map(|(_, o)| o).collect();
+            for offender in &mut offenders {
+                offender.off_cpu_ms /= window_secs as f64;
+                offender.io_ms /= window_secs as f64;
+            }
+            offenders.sort_by(|a, b| b.stall_ms().total_cmp(&a.stall_ms()));
+            offenders.truncate(MAX_OFFENDERS);
+            let Ok(mut report) = state.lock() else {
+                return "sampler state poisoned".to_string();
+            };
+            report.offenders = offenders;
+            continue;
+        }
+
+        let Some((map, pid, name, value)) = parse_map_line(&line) else {
+            continue;
+        };
+        if pid == 0 {
+            continue; // idle task and I/O completed without a task context
+        }
+        let entry = batch.entry(pid).or_insert_with(|| Offender {
+            pid,
+            name: name.to_string(),
+            ..Offender::default()
+        });
+        match map {
+            "offcpu" => entry.off_cpu_ms += value as f64 / 1_000_000.0,
+            "io" => entry.io_ms += value as f64 / 1_000_000.0,
+            "ios" => entry.io_requests += value,
+            _ => {}
+        }
+    }
+
+    // Output closed: bpftrace exited, usually with the reason on stderr.
+    match child.wait_with_output().await {
+        Ok(output) => String::from_utf8_lossy(&output.stderr)
+            .lines()
+            .map(|line| line.trim_start_matches("ERROR: ").trim())
+            .find(|line| !line.is_empty())
+            .unwrap_or("bpftrace exited")
+            .to_string(),
+        Err(err) => err.to_string(),
+    }
+}
+
+/// Parse a printed two-key map entry such as `@offcpu[1234, firefox]: 5678`
+fn parse_map_line(line: &str) -> Option<(&str, u32, &str, u64)> {
+    let rest = line.strip_prefix('@')?;
+    let (map, rest) = rest.split_once('[')?;
+    let (keys, value) = rest.rsplit_once("]: ")?;
+    let (pid, name) = keys.split_once(", ")?;
+    Some((
+        map,
+        pid.trim().parse().ok()?,
+        name.trim(),
+        value.trim().parse().ok()?,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_map_line() {
+        assert_eq!(
+            parse_map_line("@offcpu[812, Web Content]: 40213377"),
+            Some(("offcpu", 812, "Web Content", 40213377))
+        );
+        assert_eq!(
+            parse_map_line("@ios[77, kworker/u16:2]: 9"),
+            Some(("ios", 77, "kworker/u16:2", 9))
+        );
+        assert_eq!(parse_map_line("Attaching 5 probes..."), None);
+    }
+}
@@ -0,0 +1,194 @@
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::process::Command;
+
+/// Probes kept per target for the RTT graph and packet-loss percentage
+pub const PROBE_HISTORY_LEN: usize = 60;
+
+/// What a probe measures
+#[derive(Debug, Clone)]
+pub enum ProbeTarget {
+    /// ICMP round trip to a host (`gateway` resolves to the default route)
+    Ping(String),
+    /// Time to resolve a hostname through the system resolver
+    Dns(String),
+}
+
+impl ProbeTarget {
+    fn label(&self) -> String {
+        match self {
+            ProbeTarget::Ping(host) => host.clone(),
+            ProbeTarget::Dns(host) => format!("dns {}", host),
+        }
+    }
+}
+
+/// Rolling results for one network probe target
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct ProbeStats {
+    pub label: String,
+    /// Round-trip (or resolution) times in milliseconds; `None` marks a lost probe
+    pub history: VecDeque<Option<f64>>,
+    /// Why the target cannot be probed (e.g. ping missing, no default gateway)
+    pub error: Option<String>,
+}
+
+impl ProbeStats {
+    fn new(label: String) -> Self {
+        Self {
+            label,
+            history: VecDeque::with_capacity(PROBE_HISTORY_LEN),
+            error: None,
+        }
+    }
+
+    fn push(&mut self, rtt_ms: Option<f64>) {
+        if self.history.len() >= PROBE_HISTORY_LEN {
+            self.history.pop_front();
+        }
+        self.history.push_back(rtt_ms);
+    }
+
+    pub fn last_rtt(&self) -> Option<f64> {
+        self.history.back().copied().flatten()
+    }
+
+    /// Enough recent probes lost to call the link unhealthy
+    pub fn is_degraded(&self) -> bool {
+        self.history.len() >= 10 && self.loss_percent() >= 10.0
+    }
+
+    pub fn loss_percent(&self) -> f64 {
+        if self.history.is_empty() {
+            return 0.0;
+        }
+        let lost = self.history.iter().filter(|rtt| rtt.is_none()).count();
+        lost as f64 * 100.0 / self.history.len() as f64
+    }
+}
+
+pub type SharedProbes = Arc<Mutex<Vec<ProbeStats>>>;
+
+/// Spawn a task that runs every probe concurrently once per `interval`
+pub fn spawn_probes(targets: Vec<ProbeTarget>, interval: Duration) -> SharedProbes {
+    let shared: SharedProbes = Arc::new(Mutex::new(
+        targets
+            .iter()
+            .map(|target| ProbeStats::new(target.label()))
+            .collect(),
+    ));
+    let state = Arc::clone(&shared);
+
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(interval);
+        // A probe should not outlive its slot, otherwise results pile up behind slow hosts.
+        let timeout = interval.min(Duration::from_secs(2));
+
+        loop {
+            ticker.tick().await;
+            let handles: Vec<_> = targets
+                .iter()
+                .map(|target| match target {
+                    ProbeTarget::Ping(host) => tokio::spawn(ping_target(host.clone(), timeout)),
+                    ProbeTarget::Dns(host) => tokio::spawn(resolve_target(host.clone(), timeout)),
+                })
+                .collect();
+            let mut results = Vec::with_capacity(handles.len());
+            for handle in handles {
+                results.push(handle.await.unwrap_or_else(|err| Err(err.to_string())));
+            }
+
+            let Ok(mut stats) = state.lock() else {
+                break;
+            };
+            for (entry, result) in stats.iter_mut().zip(results) {
+                match result {
+                    Ok(rtt) => {
+                        entry.error = None;
+                        entry.push(rtt);
+                    }
+                    Err(err) => entry.error = Some(err),
+                }
+            }
+        }
+    });
+
+    shared
+}
+
+/// Ping once; `Ok(None)` is a lost packet, `Err` means the target cannot be probed at all
+async fn ping_target(target: String, timeout: Duration) -> Result<Option<f64>, String> {
+    let host = if target == "gateway" {
+        default_gateway().ok_or_else(|| "no default gateway".to_string())?
+    } else {
+        target
+    };
+
+    let mut command = Command::new("ping");
+    #[cfg(windows)]
+    command
+        .args(["-n", "1", "-w"])
+        .arg(timeout.as_millis().to_string());
+    #[cfg(target_os = "macos")]
+    command
+        .args(["-n", "-c", "1", "-W"])
+        .arg(timeout.as_millis().to_string());
+    #[cfg(not(any(windows, target_os = "macos")))]
+    command
+        .args(["-n", "-c", "1", "-W"])
+        .arg(timeout.as_secs().max(1).to_string());
+    command.arg(&host).kill_on_drop(true);
+
+    let output =
+        match tokio::time::timeout(timeout + Duration::from_secs(1), command.output()).await {
+            Ok(Ok(output)) => output,
+            Ok(Err(err)) if err.kind() == std::io::ErrorKind::NotFound => {
+                return Err("ping not installed".to_string())
+            }
+            Ok(Err(err)) => return Err(err.to_string()),
+            Err(_) => return Ok(None),
+        };
+
+    Ok(parse_ping_rtt(&String::from_utf8_lossy(&output.stdout)))
+}
+
+/// Resolve once through the system resolver; a failed or timed-out lookup counts as lost
+async fn resolve_target(host: String, timeout: Duration) -> Result<Option<f64>, String> {
+    let started = std::time::Instant::now();
+    match tokio::time::timeout(timeout, tokio::net::lookup_host((host.as_str(), 0))).await {
+        Ok(Ok(mut addrs)) => Ok(addrs
+            .next()
+            .map(|_| started.elapsed().as_secs_f64() * 1000.0)),
+        _ => Ok(None),
+    }
+}
+
+/// Extract the RTT from ping output (`time=12.3 ms`, `time<1ms`)
+fn parse_ping_rtt(output: &str) -> Option<f64> {
+    let start = output.find("time=").or_else(|| output.find("time<"))? + "time=".len();
+    let rest = &output[start..];
+    let end = rest
+        .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+        .unwrap_or(rest.len());
+    rest[..end].parse::<f64>().ok()
+}
+
+#[cfg(target_os = "linux")]
+fn default_gateway() -> Option<String> {
+    // Columns: Iface Destination Gateway Flags ...; values are little-endian hex.
+    let routes = std::fs::read_to_string("/proc/net/route").ok()?;
+    routes.lines().skip(1).find_map(|line| {
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        if fields.get(1) != Some(&"00000000") {
+            return None;
+        }
+        let gateway = u32::from_str_radix(fields.get(2)?, 16).ok()?;
+        Some(std::net::Ipv4Addr::from(gateway.to_le_bytes()).to_string())
+    })
+}
+
+#[cfg(not(target_os = "linux"))]
+fn default_gateway() -> Option<String> {
+    None
+}
@@ -0,0 +1,656 @@
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::Read;
+
+/// Fields of `/proc/<pid>/stat` used by the collector
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ProcStat {
+    pub flags: u64,
+    pub minor_faults: u64,
+    pub major_faults: u64,
+    pub rt_priority: u64,
+    pub policy: u64,
+    /// Nice value, -20 (favored) to 19
+    pub nice: i32,
+    /// CPU the task last ran on
+    pub processor: Option<u32>,
+}
+
+/// `PF_KTHREAD` in the `flags` field: the task is a kernel thread
+const PF_KTHREAD: u64 = 0x0020_0000;
+
+impl ProcStat {
+    pub fn is_kernel_thread(&self) -> bool {
+        self.flags & PF_KTHREAD != 0
+    }
+}
+
+/// Read `/proc/<pid>/<name>` into `buf`, reusing its allocation between processes
+pub fn read_pid_file(pid: u32, name: &str, buf: &mut String) -> bool {
+    buf.clear();
+    File::open(format!("/proc/{}/{}", pid, name))
+        .and_then(|mut file| file.read_to_string(buf))
+        .is_ok()
+}
+
+/// Parse `/proc/<pid>/stat`.
+///
+/// The command name (field 2) may contain spaces and parentheses, so fields are
+/// counted from the last `)` rather than split from the start of the line.
+pub fn parse_stat(content: &str) -> Option<ProcStat> {
+    let after_comm = &content[content.rfind(')')? + 1..];
+    // `fields[0]` is field 3 (state) in proc(5) numbering.
+    let fields: Vec<&str> = after_comm.split_whitespace().collect();
+    let field = |number: usize| fields.get(number - 3)?.parse::<u64>().ok();
+
+    Some(ProcStat {
+        flags: field(9)?,
+        minor_faults: field(10)?,
+        major_faults: field(12)?,
+        // Scheduling fields were appended in 2.5.19; treat their absence as SCHED_OTHER.
+        rt_priority: field(40).unwrap_or(0),
+        policy: field(41).unwrap_or(0),
+        nice: fields.get(19 - 3)?.parse().ok()?,
+        processor: field(39).map(|cpu| cpu as u32),
+    })
+}
+
+/// Value of a `Key:   N kB` line from `/proc/<pid>/status`, in bytes
+pub fn parse_status_kb(content: &str, key: &str) -> Option<u64> {
+    content.lines().find_map(|line| {
+        let (label, value) = line.split_once(':')?;
+        if label != key {
+            return None;
+        }
+        let kb = value.split_whitespace().next()?.parse::<u64>().ok()?;
+        Some(kb * 1024)
+    })
+}
+
+/// Parse header/value line pairs as found in `/proc/net/snmp` and `/proc/net/netstat`
+/// into `Section.Field` keys, e.g. `Tcp.RetransSegs`
+pub fn parse_snmp_counters(content: &str, out: &mut HashMap<String, i64>) {
+    let mut lines = content.lines();
+    while let (Some(header), Some(values)) = (lines.next(), lines.next()) {
+        let (Some((section, names)), Some((_, values))) =
+            (header.split_once(':'), values.split_once(':'))
+        else {
+            continue;
+        };
+        for (name, value) in names.split_whitespace().zip(values.split_whitespace()) {
+            if let Ok(value) = value.parse::<i64>() {
+                out.insert(format!("{}.{}", section, name), value);
+            }
+        }
+    }
+}
+
+/// TCP connection counts by state from `/proc/net/tcp` or `/proc/net/tcp6`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TcpStateCounts {
+    pub established: u32,
+    pub time_wait: u32,
+    pub close_wait: u32,
+    pub listen: u32,
+    /// Non-listening sockets whose local port lies in `ephemeral_range`
+    pub ephemeral_in_use: u32,
+}
+
+/// Tally a `/proc/net/tcp{,6}` table into `counts`
+pub fn count_tcp_states(content: &str, ephemeral_range: (u16, u16), counts: &mut TcpStateCounts) {
+    for line in content.lines().skip(1) {
+        let mut fields = line.split_whitespace();
+        let (Some(local), Some(state)) = (fields.nth(1), fields.nth(1)) else {
+            continue;
+        };
+        match state {
+            "01" => counts.established += 1,
+            "06" => counts.time_wait += 1,
+            "08" => counts.close_wait += 1,
+            "0A" => {
+                counts.listen += 1;
+                continue;
+            }
+            _ => {}
+        }
+        let port = local
+            .rsplit_once(':')
+            .and_then(|(_, port)| u16::from_str_radix(port, 16).ok());
+        if port.is_some_and(|port| port >= ephemeral_range.0 && port <= ephemeral_range.1) {
+            counts.ephemeral_in_use += 1;
+        }
+    }
+}
+
+/// Memory held by one DRM client, from `/proc/<pid>/fdinfo/<fd>` of a DRM device fd
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DrmClientMemory {
+    pub driver: String,
+    /// Shared by every fd (and process) that refers to the same client
+    pub client_id: u64,
+    /// Sum of `drm-total-local0` and `drm-total-system0`
+    pub total_bytes: u64,
+}
+
+/// Parse the DRM client keys of an fdinfo file; `None` for non-DRM fds or drivers without memory stats
+pub fn parse_drm_fdinfo(content: &str) -> Option<DrmClientMemory> {
+    let mut driver = None;
+    let mut client_id = None;
+    let mut total_bytes = 0u64;
+    let mut has_totals = false;
+
+    for line in content.lines() {
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+        let value = value.trim();
+        match key {
+            "drm-driver" => driver = Some(value.to_string()),
+            "drm-client-id" => client_id = value.parse::<u64>().ok(),
+            "drm-total-local0" | "drm-total-system0" => {
+                let mut parts = value.split_whitespace();
+                let amount = parts.next().and_then(|v| v.parse::<u64>().ok());
+                let scale = match parts.next() {
+                    Some("KiB") => 1024,
+                    Some("MiB") => 1024 * 1024,
+                    Some("GiB") => 1024 * 1024 * 1024,
+                    _ => 1,
+                };
+                if let Some(amount) = amount {
+                    total_bytes += amount * scale;
+                    has_totals = true;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    if !has_totals {
+        return None;
+    }
+    Some(DrmClientMemory {
+        driver: driver?,
+        client_id: client_id?,
+        total_bytes,
+    })
+}
+
+/// Per-CPU run-queue counters from `/proc/schedstat`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CpuSchedStat {
+    pub cpu: usize,
+    /// Total time tasks spent runnable but waiting on this CPU's run queue, in ns
+    pub wait_ns: u64,
+    /// Number of timeslices run on this CPU
+    pub timeslices: u64,
+}
+
+/// Parse the `cpuN` lines of `/proc/schedstat` (the last three fields are run time, wait time, timeslices)
+pub fn parse_schedstat(content: &str) -> Vec<CpuSchedStat> {
+    content
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+            let cpu = fields.next()?.strip_prefix("cpu")?.parse::<usize>().ok()?;
+            let values: Vec<u64> = fields.filter_map(|v| v.parse::<u64>().ok()).collect();
+            if values.len() < 9 {
+                return None;
+            }
+            Some(CpuSchedStat {
+                cpu,
+                wait_ns: values[7],
+                timeslices: values[8],
+            })
+        })
+        .collect()
+}
+
+/// Cumulative jiffies of one `cpu` line of `/proc/stat`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CpuTimes {
+    pub busy: u64,
+    pub total: u64,
+}
+
+impl CpuTimes {
+    /// Busy share of the time between `previous` and now, in percent
+    pub fn usage_since(&self, previous: &CpuTimes) -> f32 {
+        let total = self.total.saturating_sub(previous.total);
+        if total == 0 {
+            return 0.0;
+        }
+        let busy = self.busy.saturating_sub(previous.busy);
+        (busy as f64 / total as f64 * 100.0).min(100.0) as f32
+    }
+}
+
+/// Parse `/proc/stat` into the aggregate `cpu` line and one `cpuN` line per online CPU;
+/// offline CPUs have no line. Idle and iowait count as not busy; guest time is already part
+/// of user time.
+pub fn parse_cpu_times(content: &str) -> (Option<CpuTimes>, Vec<(usize, CpuTimes)>) {
+    let mut aggregate = None;
+    let mut cores = Vec::new();
+    for line in content.lines() {
+        let mut fields = line.split_whitespace();
+        let Some(core) = fields.next().and_then(|name| name.strip_prefix("cpu")) else {
+            continue;
+        };
+        let values: Vec<u64> = fields
+            .take(8)
+            .filter_map(|v| v.parse::<u64>().ok())
+            .collect();
+        if values.len() < 4 {
+            continue;
+        }
+        let total: u64 = values.iter().sum();
+        let idle = values[3] + values.get(4).copied().unwrap_or(0);
+        let times = CpuTimes {
+            busy: total.saturating_sub(idle),
+            total,
+        };
+        if core.is_empty() {
+            aggregate = Some(times);
+        } else if let Ok(core) = core.parse::<usize>() {
+            cores.push((core, times));
+        }
+    }
+    (aggregate, cores)
+}
+
+/// Parse a kernel CPU list such as `/sys/devices/system/cpu/present` (`0-3,6,8-9`)
+pub fn parse_cpu_list(text: &str) -> Vec<usize> {
+    text.trim()
+        .split(',')
+        .filter_map(|range| match range.split_once('-') {
+            Some((start, end)) => Some(start.parse::<usize>().ok()?..=end.parse::<usize>().ok()?),
+            None => range.parse::<usize>().ok().map(|cpu| cpu..=cpu),
+        })
+        .flatten()
+        .collect()
+}
+
+/// Value of `key` in a named kstat file such as `/proc/spl/kstat/zfs/arcstats`,
+/// whose rows are `name type data`
+pub fn parse_kstat(content: &str, key: &str) -> Option<u64> {
+    content.lines().find_map(|line| {
+        let mut fields = line.split_whitespace();
+        if fields.next()? != key {
+            return None;
+        }
+        fields.nth(1)?.parse().ok()
+    })
+}
+
+/// Threads in the system: the total after the slash in the fourth field of `/proc/loadavg`
+/// (`0.50 0.40 0.30 2/1834 5678`)
+pub fn parse_loadavg_threads(content: &str) -> Option<u64> {
+    let (_, total) = content.split_whitespace().nth(3)?.split_once('/')?;
+    total.parse().ok()
+}
+
+/// Allocated file handles: the first field of `/proc/sys/fs/file-nr`
+pub fn parse_file_nr(content: &str) -> Option<u64> {
+    content.split_whitespace().next()?.parse().ok()
+}
+
+/// A field of `/proc/mounts` with its octal escapes (`\040` for space) decoded
+fn unescape_mount_field(field: &str) -> String {
+    let mut out = String::with_capacity(field.len());
+    let mut rest = field;
+    while let Some(i) = rest.find('\\') {
+        out.push_str(&rest[..i]);
+        let code = rest
+            .get(i + 1..i + 4)
+            .and_then(|digits| u8::from_str_radix(digits, 8).ok());
+        match code {
+            Some(byte) => {
+                out.push(char::from(byte));
+                rest = &rest[i + 4..];
+            }
+            None => {
+                out.push('\\');
+                rest = &rest[i + 1..];
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Mount points from `/proc/mounts`
+pub fn parse_mount_points(content: &str) -> Vec<String> {
+    content
+        .lines()
+        .filter_map(|line| line.split_whitespace().nth(1))
+        .map(unescape_mount_field)
+        .collect()
+}
+
+/// One line of `/proc/mounts`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MountEntry {
+    pub source: String,
+    pub target: String,
+    pub fs_type: String,
+    pub options: String,
+}
+
+/// Every line of `/proc/mounts`
+pub fn parse_mounts(content: &str) -> Vec<MountEntry> {
+    content
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace().map(unescape_mount_field);
+            Some(MountEntry {
+                source: fields.next()?,
+                target: fields.next()?,
+                fs_type: fields.next()?,
+                options: fields.next()?,
+            })
+        })
+        .collect()
+}
+
+/// The mount point `path` lives on: the longest one that is a whole-component prefix
+pub fn mount_for<'a>(path: &str, mounts: &'a [String]) -> Option<&'a str> {
+    mounts
+        .iter()
+        .filter(|mount| {
+            mount.as_str() == "/"
+                || path == mount.as_str()
+                || path
+                    .strip_prefix(mount.as_str())
+                    .is_some_and(|rest| rest.starts_with('/'))
+        })
+        .max_by_key(|mount| mount.len())
+        .map(String::as_str)
+}
+
+/// Whether a `/proc/<pid>/fdinfo/<fd>` entry was opened for writing (`O_WRONLY` or `O_RDWR`)
+pub fn fdinfo_writable(content: &str) -> bool {
+    content
+        .lines()
+        .find_map(|line| line.strip_prefix("flags:"))
+        .and_then(|flags| u32::from_str_radix(flags.trim(), 8).ok())
+        .is_some_and(|flags| flags & 0o3 != 0)
+}
+
+/// The systemd service a process runs in, from `/proc/<pid>/cgroup`, and whether it belongs to
+/// a user's service manager (`systemctl --user`). Only a service that is the process's own
+/// (leaf) cgroup counts: a process in a scope (login sessions, desktop apps) has no service to
+/// restart, and `user@<uid>.service` is the whole user manager, whose restart ends the session.
+pub fn parse_systemd_unit(content: &str) -> Option<(String, bool)> {
+    // cgroup v2 has a single `0::/path` line; v1 keeps systemd's tree under `name=systemd`
+    let path = content.lines().find_map(|line| {
+        let mut fields = line.splitn(3, ':');
+        let (_, controllers, path) = (fields.next()?, fields.next()?, fields.next()?);
+        (controllers.is_empty() || controllers == "name=systemd").then_some(path)
+    })?;
+    let components: Vec<&str> = path.split('/').filter(|c| !c.is_empty()).collect();
+    let (&unit, parents) = components.split_last()?;
+    if !unit.ends_with(".service") || unit.starts_with("user@") {
+        return None;
+    }
+    let user = parents
+        .iter()
+        .any(|component| component.starts_with("user@") && component.ends_with(".service"));
+    Some((unit.to_string(), user))
+}
+
+/// Prefixes of the systemd scopes container runtimes start each container in
+/// (`docker-<id>.scope`); the runtimes' own daemons run in `.service` units instead
+const CONTAINER_SCOPE_PREFIXES: [&str; 4] = ["docker-", "cri-containerd-", "crio-", "libpod-"];
+
+/// Whether `/proc/<pid>/cgroup` places the process in a container started by Docker, containerd,
+/// Kubernetes, Podman, CRI-O or LXC, with either the systemd or the cgroupfs layout
+pub fn is_container_cgroup(content: &str) -> bool {
+    content.lines().any(|line| {
+        let path = line.splitn(3, ':').nth(2).unwrap_or_default();
+        let mut parent = "";
+        path.split('/').any(|component| {
+            let container = component.starts_with("kubepods")
+                || component.starts_with("lxc.payload.")
+                // `/docker/<id>`, `/lxc/<name>`
+                || (matches!(parent, "docker" | "lxc") && !component.is_empty())
+                || component.strip_suffix(".scope").is_some_and(|scope| {
+                    CONTAINER_SCOPE_PREFIXES.iter().any(|prefix| {
+                        // Podman's conmon monitor runs next to the container, on the host
+                        scope
+                            .strip_prefix(prefix)
+                            .is_some_and(|id| !id.is_empty() && !id.starts_with("conmon-"))
+                    })
+                });
+            parent = component;
+            container
+        })
+    })
+}
+
+/// Namespaces every container gets its own of: PID, mount and UTS (its host name)
+pub const CONTAINER_NAMESPACES: [&str; 3] = ["pid", "mnt", "uts"];
+
+/// Whether a process with the `/proc/<pid>/ns` links `theirs` (in [`CONTAINER_NAMESPACES`]
+/// order) is in another container than one with `own`. Sandboxes such as Chromium's and
+/// Flatpak's unshare the PID and sometimes the mount namespace but keep the host's name, so
+/// only a process apart in all three counts.
+pub fn in_other_namespaces<T: PartialEq>(own: &[T], theirs: &[T]) -> bool {
+    own.len() == CONTAINER_NAMESPACES.len()
+        && theirs.len() == own.len()
+        && own.iter().zip(theirs).all(|(own, theirs)| own != theirs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_thread_and_file_handle_totals() {
+        assert_eq!(
+            parse_loadavg_threads("0.50 0.40 0.30 2/1834 5678\n"),
+            Some(1834)
+        );
+        assert_eq!(parse_loadavg_threads("0.50 0.40 0.30"), None);
+        assert_eq!(parse_file_nr("9120\t0\t9223372036854775807\n"), Some(9120));
+        assert_eq!(parse_file_nr(""), None);
+    }
+
+    #[test]
+    fn test_parse_cpu_times_skips_offline_cores() {
+        let stat = "cpu  400 0 100 1400 100 0 0 0 0 0\n\
+                    cpu0 300 0 50 600 50 0 0 0 0 0\n\
+                    cpu2 100 0 50 800 50 0 0 0 0 0\n\
+                    intr 12345 0 0\n\
+                    ctxt 999\n";
+        let (aggregate, cores) = parse_cpu_times(stat);
+        assert_eq!(
+            aggregate,
+            Some(CpuTimes {
+                busy: 500,
+                total: 2000
+            })
+        );
+        assert_eq!(
+            cores.iter().map(|(core, _)| *core).collect::<Vec<_>>(),
+            [0, 2]
+        );
+
+        let later = CpuTimes {
+            busy: 350 + 75,
+            total: 1000 + 100,
+        };
+        assert_eq!(later.usage_since(&cores[0].1), 75.0);
+        assert_eq!(cores[0].1.usage_since(&cores[0].1), 0.0);
+
+        assert_eq!(parse_cpu_list("0-3,6,8-9\n"), [0, 1, 2, 3, 6, 8, 9]);
+        assert_eq!(parse_cpu_list("0\n"), [0]);
+        assert!(parse_cpu_list("\n").is_empty());
+    }
+
+    #[test]
+    fn test_parse_stat_with_unusual_comm() {
+        let stat = "1234 (tmux: server) (x) S 1 1234 1234 0 -1 4194560 \
+                    5120 0 17 0 30 12 0 0 20 0 1 0 4567 10000000 500";
+        assert_eq!(
+            parse_stat(stat),
+            Some(ProcStat {
+                flags: 4194560,
+                minor_faults: 5120,
+                major_faults: 17,
+                rt_priority: 0,
+                policy: 0,
+                nice: 0,
+                processor: None,
+            })
+        );
+
+        let reniced = parse_stat(&stat.replace(" 20 0 1 0 ", " 25 5 1 0 ")).unwrap();
+        assert_eq!(reniced.nice, 5);
+
+        let kthread = "17 (migration/0) S 2 0 0 0 -1 69238848 0 0 0 0 0 3 0 0 -100 0 1 0 \
+                       4 0 0 18446744073709551615 0 0 0 0 0 0 0 2147483647 0 0 0 0 17 0 99 1 0 0 0 0 0";
+        let parsed = parse_stat(kthread).unwrap();
+        assert!(parsed.is_kernel_thread());
+        assert_eq!((parsed.rt_priority, parsed.policy), (99, 1));
+        assert_eq!(parsed.processor, Some(0));
+
+        let schedstat = "version 15\ntimestamp 4295\n\
+                         cpu0 0 0 812 120 400 300 9000000 2500000 1000\n\
+                         domain0 3 1 2 3 4 5 6 7 8 9 10\n\
+                         cpu1 0 0 5 1 2 1 100 0 10\n";
+        assert_eq!(
+            parse_schedstat(schedstat),
+            vec![
+                CpuSchedStat {
+                    cpu: 0,
+                    wait_ns: 2500000,
+                    timeslices: 1000
+                },
+                CpuSchedStat {
+                    cpu: 1,
+                    wait_ns: 0,
+                    timeslices: 10
+                },
+            ]
+        );
+
+        let status = "Name:\tbash\nVmSwap:\t     128 kB\n";
+        assert_eq!(parse_status_kb(status, "VmSwap"), Some(128 * 1024));
+
+        let arcstats = "13 1 0x01 123 33456 1390791432 9384123987\n\
+                        name                            type data\n\
+                        hits                            4    3126473\n\
+                        c                               4    4294967296\n\
+                        c_min                           4    1073741824\n\
+                        size                            4    3221225472\n";
+        assert_eq!(parse_kstat(arcstats, "size"), Some(3 << 30));
+        assert_eq!(parse_kstat(arcstats, "c"), Some(4 << 30));
+        assert_eq!(parse_kstat(arcstats, "c_min"), Some(1 << 30));
+        assert_eq!(parse_kstat(arcstats, "c_max"), None);
+
+        let mounts = parse_mount_points(
+            "/dev/sda2 / ext4 rw 0 0\n/dev/sdb1 /home ext4 rw 0 0\n\
+             /dev/sdc1 /mnt/my\\040disk vfat rw 0 0\n",
+        );
+        assert_eq!(mounts, vec!["/", "/home", "/mnt/my disk"]);
+        assert_eq!(mount_for("/home/me/log.txt", &mounts), Some("/home"));
+        assert_eq!(mount_for("/homework/a", &mounts), Some("/"));
+        assert_eq!(mount_for("/mnt/my disk/x", &mounts), Some("/mnt/my disk"));
+        let entries = parse_mounts("/dev/sdc1 /mnt/my\\040disk vfat rw,noatime 0 0\n");
+        assert_eq!(entries[0].source, "/dev/sdc1");
+        assert_eq!(entries[0].target, "/mnt/my disk");
+        assert_eq!(entries[0].options, "rw,noatime");
+        assert!(fdinfo_writable("pos:\t0\nflags:\t0100001\nmnt_id:\t29\n"));
+        assert!(!fdinfo_writable("pos:\t0\nflags:\t0100000\n"));
+
+        assert_eq!(
+            parse_systemd_unit("0::/system.slice/sshd.service\n"),
+            Some(("sshd.service".to_string(), false))
+        );
+        assert_eq!(
+            parse_systemd_unit(
+                "0::/user.slice/user-1000.slice/user@1000.service/app.slice/syncthing.service\n"
+            ),
+            Some(("syncthing.service".to_string(), true))
+        );
+        assert_eq!(
+            parse_systemd_unit("12:cpu,cpuacct:/\n1:name=systemd:/system.slice/cron.service\n"),
+            Some(("cron.service".to_string(), false))
+        );
+        assert_eq!(
+            parse_systemd_unit("0::/user.slice/user-1000.slice/session-2.scope\n"),
+            None
+        );
+        // A desktop app: restarting the enclosing user@1000.service would end the session
+        assert_eq!(
+            parse_systemd_unit(
+                "0::/user.slice/user-1000.slice/user@1000.service/app.slice/\
+                 app-gnome-org.gnome.Terminal-4123.scope\n"
+            ),
+            None
+        );
+        // The user manager itself
+        assert_eq!(
+            parse_systemd_unit("0::/user.slice/user-1000.slice/user@1000.service/init.scope\n"),
+            None
+        );
+        assert_eq!(
+            parse_systemd_unit("0::/user.slice/user-1000.slice/user@1000.service\n"),
+            None
+        );
+
+        assert!(is_container_cgroup(
+            "0::/system.slice/docker-4f1c2a9e.scope\n"
+        ));
+        assert!(is_container_cgroup(
+            "12:pids:/kubepods/besteffort/pod1234/abcd\n1:name=systemd:/kubepods/besteffort\n"
+        ));
+        assert!(is_container_cgroup(
+            "0::/user.slice/user-1000.slice/user@1000.service/user.slice/libpod-9b2e.scope/container\n"
+        ));
+        assert!(is_container_cgroup(
+            "0::/system.slice/cri-containerd-7d3f.scope\n"
+        ));
+        assert!(is_container_cgroup("12:pids:/docker/4f1c2a9e\n"));
+        assert!(is_container_cgroup("0::/lxc.payload.web/init.scope\n"));
+        assert!(!is_container_cgroup("0::/system.slice/sshd.service\n"));
+        assert!(!is_container_cgroup("0::/init.scope\n"));
+    }
+
+    #[test]
+    fn test_container_runtimes_and_sandboxes_are_not_containers() {
+        // The runtimes' own daemons
+        for daemon in ["docker", "containerd", "crio", "lxc", "podman"] {
+            let cgroup = format!("0::/system.slice/{}.service\n", daemon);
+            assert!(!is_container_cgroup(&cgroup), "{}", cgroup);
+        }
+        assert!(!is_container_cgroup(
+            "0::/user.slice/user-1000.slice/user@1000.service/user.slice/libpod-conmon-9b2e.scope\n"
+        ));
+        assert!(!is_container_cgroup("0::/system.slice/docker.socket\n"));
+        // Browser and Flatpak sandboxes live in the desktop session's app scopes
+        assert!(!is_container_cgroup(
+            "0::/user.slice/user-1000.slice/user@1000.service/app.slice/app-gnome-chromium-4321.scope\n"
+        ));
+        assert!(!is_container_cgroup(
+            "0::/user.slice/user-1000.slice/user@1000.service/app.slice/app-flatpak-org.mozilla.firefox-2211.scope\n"
+        ));
+
+        let host = ["pid:[1]", "mnt:[2]", "uts:[3]"];
+        // Chromium's sandbox: a PID namespace of its own
+        assert!(!in_other_namespaces(
+            &host,
+            &["pid:[9]", "mnt:[2]", "uts:[3]"]
+        ));
+        // Flatpak (bubblewrap): PID and mount namespaces, the host's name
+        assert!(!in_other_namespaces(
+            &host,
+            &["pid:[9]", "mnt:[8]", "uts:[3]"]
+        ));
+        assert!(in_other_namespaces(
+            &host,
+            &["pid:[9]", "mnt:[8]", "uts:[7]"]
+        ));
+        // A link that could not be read is not evidence either way
+        assert!(!in_other_namespaces(&host, &["pid:[9]", "mnt:[8]"]));
+    }
+}
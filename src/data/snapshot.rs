@@ -14,12 +14,15 @@ pub enum ColorScheme {
     Rtop,
 }
 
-#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub enum ProcessSortBy {
     CpuUsage,
     Memory,
     Pid,
     Name,
+    /// Bytes read and written per second
+    DiskIo,
+    StartTime,
 }
 
 #[derive(Clone, Copy, Debug, Serialize, Deserialize)]
@@ -30,23 +33,287 @@ pub enum ChartType {
     DiskUsage,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 #[allow(dead_code)]
 pub struct ProcessInfo {
     pub pid: u32,
     pub name: String,
     pub memory: u64,
+    /// Percent of one core: a process keeping two cores busy shows 200%
     pub cpu_usage: f32,
+    /// Average CPU% since rtop started (or since the process appeared), also per core
+    pub cumulative_cpu: f32,
     pub disk_usage: u64,
+    /// Bytes read from and written to storage per second since the previous collection
+    pub disk_read_per_sec: u64,
+    pub disk_write_per_sec: u64,
     pub parent_pid: Option<u32>,
+    /// Unix time the process started
+    pub start_time: u64,
+    /// Seconds the process has been running
+    pub run_time: u64,
+    /// Real user ID of the owner (Unix)
+    pub uid: Option<u32>,
+    /// Login name of the owner; `None` when the UID has no entry in the users list
+    pub user: Option<String>,
     pub cmd: Vec<String>,
     pub exe: Option<String>,
     pub root: Option<String>,
     pub cwd: Option<String>,
-    pub status: String,
+    pub state: ProcessState,
+    pub major_faults_per_sec: f32,
+    pub minor_faults_per_sec: f32,
+    /// Bytes of this process swapped out (VmSwap)
+    pub swap: u64,
+    /// Memory growth in bytes per second when RSS has only grown over the leak window
+    pub leak_rate: Option<f64>,
+    /// How many recent samples found the task last running on each core (index = core)
+    pub core_samples: Vec<u8>,
+    /// Recent CPU% samples, oldest first, on the same scale as `cpu_usage`; only filled in for
+    /// the selected and the inspected process
+    pub cpu_history: Vec<f32>,
+    /// Core the task last ran on (the last-CPU field of `/proc/<pid>/stat`)
+    pub last_cpu: Option<u32>,
+    pub sched_class: SchedClass,
+    /// Real-time priority (1-99) for FIFO/RR tasks, 0 otherwise
+    pub rt_priority: u32,
+    /// Nice value, -20 (favored) to 19; 0 where it is not read
+    pub nice: i32,
+    pub kernel_thread: bool,
+    /// Runs in a container (its own PID, mount and UTS namespaces, or a container runtime's
+    /// cgroup)
+    pub container: bool,
+    /// Use of each NVIDIA GPU the process runs on, from NVML; empty for everything else
+    pub gpu: Vec<ProcessGpuUsage>,
+}
+
+impl ProcessInfo {
+    /// 3D/compute utilization summed over the GPUs the process runs on, `None` when NVML has
+    /// no sample of it
+    pub fn gpu_usage(&self) -> Option<f32> {
+        self.gpu
+            .iter()
+            .filter_map(|usage| usage.utilization)
+            .map(|percent| percent as f32)
+            .reduce(|a, b| a + b)
+    }
+}
+
+/// One process's share of one NVIDIA GPU
+#[derive(Debug, Clone, Serialize)]
+pub struct ProcessGpuUsage {
+    /// Name of the GPU, as in `GpuInfo::name`
+    pub gpu: String,
+    /// Percent of the GPU's 3D/compute time over the last sampling window
+    pub utilization: Option<u32>,
+    pub encoder: u32,
+    pub decoder: u32,
+    /// Video memory allocated, `None` when the driver does not say (e.g. under WDDM)
+    pub memory: Option<u64>,
+}
+
+/// Process state as `ps` abbreviates it
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub enum ProcessState {
+    Running,
+    #[default]
+    Sleeping,
+    /// Uninterruptible sleep, usually waiting on I/O
+    DiskSleep,
+    /// Exited but not yet reaped by its parent
+    Zombie,
+    Stopped,
+    Traced,
+    Idle,
+    Other,
+}
+
+impl ProcessState {
+    pub fn from_status(status: sysinfo::ProcessStatus) -> Self {
+        use sysinfo::ProcessStatus;
+        match status {
+            ProcessStatus::Run | ProcessStatus::Waking => ProcessState::Running,
+            ProcessStatus::Sleep => ProcessState::Sleeping,
+            ProcessStatus::UninterruptibleDiskSleep => ProcessState::DiskSleep,
+            ProcessStatus::Zombie => ProcessState::Zombie,
+            ProcessStatus::Stop => ProcessState::Stopped,
+            ProcessStatus::Tracing => ProcessState::Traced,
+            ProcessStatus::Idle => ProcessState::Idle,
+            _ => ProcessState::Other,
+        }
+    }
+
+    /// One-letter code, as in the `S` column of top
+    pub fn code(self) -> char {
+        match self {
+            ProcessState::Running => 'R',
+            ProcessState::Sleeping => 'S',
+            ProcessState::DiskSleep => 'D',
+            ProcessState::Zombie => 'Z',
+            ProcessState::Stopped => 'T',
+            ProcessState::Traced => 't',
+            ProcessState::Idle => 'I',
+            ProcessState::Other => '?',
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            ProcessState::Running => "Running",
+            ProcessState::Sleeping => "Sleeping",
+            ProcessState::DiskSleep => "Disk sleep",
+            ProcessState::Zombie => "Zombie",
+            ProcessState::Stopped => "Stopped",
+            ProcessState::Traced => "Traced",
+            ProcessState::Idle => "Idle",
+            ProcessState::Other => "Unknown",
+        }
+    }
+}
+
+/// Linux scheduling policy of a task
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub enum SchedClass {
+    #[default]
+    Normal,
+    Batch,
+    Idle,
+    Fifo,
+    RoundRobin,
+    Deadline,
+}
+
+impl SchedClass {
+    /// Map the `policy` field of `/proc/<pid>/stat` (SCHED_* constants)
+    pub fn from_policy(policy: u64) -> Self {
+        match policy {
+            1 => SchedClass::Fifo,
+            2 => SchedClass::RoundRobin,
+            3 => SchedClass::Batch,
+            5 => SchedClass::Idle,
+            6 => SchedClass::Deadline,
+            _ => SchedClass::Normal,
+        }
+    }
+
+    /// Short label as printed by `ps -o cls`
+    pub fn label(self) -> &'static str {
+        match self {
+            SchedClass::Normal => "TS",
+            SchedClass::Batch => "B",
+            SchedClass::Idle => "IDL",
+            SchedClass::Fifo => "FF",
+            SchedClass::RoundRobin => "RR",
+            SchedClass::Deadline => "DLN",
+        }
+    }
+
+    pub fn is_realtime(self) -> bool {
+        matches!(
+            self,
+            SchedClass::Fifo | SchedClass::RoundRobin | SchedClass::Deadline
+        )
+    }
+}
+
+/// System-wide per-protocol rates from `/proc/net/snmp` (Linux only)
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ProtocolStats {
+    pub tcp_in_per_sec: f64, // segments
+    pub tcp_out_per_sec: f64,
+    pub udp_in_per_sec: f64, // datagrams
+    pub udp_out_per_sec: f64,
+    pub icmp_in_per_sec: f64, // messages
+    pub icmp_out_per_sec: f64,
+    pub ip_in_bytes_per_sec: f64,
+    pub ip_out_bytes_per_sec: f64,
+    pub tcp_retrans_per_sec: f64,
+    /// Share of outgoing TCP segments that were retransmissions
+    pub tcp_retrans_percent: f64,
+}
+
+/// Socket usage and the limits it can run into (Linux only)
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct SocketStats {
+    pub established: u32,
+    pub time_wait: u32,
+    pub close_wait: u32,
+    pub listen: u32,
+    pub orphaned: u32,
+    /// Sockets of all kinds in use (`/proc/net/sockstat`)
+    pub sockets_used: u32,
+    pub ephemeral_in_use: u32,
+    pub ephemeral_range_size: u32,
+}
+
+/// System-wide kernel tables that fail silently when full (Linux only)
+#[derive(Debug, Clone, Copy, Default, Serialize)]
+pub struct KernelLimits {
+    pub fds_allocated: u64,
+    pub fds_max: u64,
+    pub ptys: u64,
+    pub ptys_max: u64,
+    /// Processes and threads, which share the PID space
+    pub tasks: u64,
+    pub pid_max: u64,
+    /// Bits in the input entropy pool (pinned at the pool size since Linux 5.18)
+    pub entropy_avail: Option<u64>,
+    pub entropy_pool: Option<u64>,
+}
+
+/// What the process detail view shows beyond the list columns, for the inspected process
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ProcessDetail {
+    pub pid: u32,
+    /// Untruncated, whatever the command-line limits of the list
+    pub cmd: Vec<String>,
+    pub exe: Option<String>,
+    pub cwd: Option<String>,
+    /// Seconds since the Unix epoch
+    pub start_time: u64,
+    pub virtual_memory: u64,
+    /// Resident memory split into anonymous, file-backed and shared pages (Linux only)
+    pub rss_anon: Option<u64>,
+    pub rss_file: Option<u64>,
+    pub rss_shmem: Option<u64>,
 }
 
-#[derive(Debug, Clone)]
+/// Open files of the process shown in the detail view, grouped by mount point (Linux only)
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct OpenFilesByMount {
+    pub pid: u32,
+    /// False when the process's fd table could not be read (another user's process)
+    pub accessible: bool,
+    /// Mounts with files open for writing first, then by open files
+    pub mounts: Vec<MountOpenFiles>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct MountOpenFiles {
+    pub mount: String,
+    pub open: usize,
+    /// Of `open`, files opened for writing
+    pub writable: usize,
+}
+
+/// ZFS adaptive replacement cache, which the kernel reports as used memory (Linux only)
+#[derive(Debug, Clone, Copy, Default, Serialize)]
+pub struct ZfsArc {
+    pub size: u64,
+    /// Size ZFS is currently aiming for (`c` in arcstats)
+    pub target: u64,
+    /// Floor the ARC never shrinks below under pressure (`c_min` in arcstats)
+    pub min: u64,
+}
+
+impl ZfsArc {
+    /// What the ARC gives back when memory runs short
+    pub fn reclaimable(&self) -> u64 {
+        self.size.saturating_sub(self.min)
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
 #[allow(dead_code)]
 pub struct NetworkInfo {
     pub name: String,
@@ -54,31 +321,115 @@ pub struct NetworkInfo {
     pub total_transmitted: u64,
     pub received_per_sec: u64,
     pub transmitted_per_sec: u64,
+    pub kind: InterfaceKind,
+    /// Bond/bridge this interface is enslaved to, or the lower device of a VLAN.
+    /// Its traffic is already counted on the parent.
+    pub parent: Option<String>,
+    pub mtu: Option<u32>,
+    /// Wi-Fi power saving; `None` for wired interfaces or when `iw` is unavailable
+    pub power_save: Option<bool>,
+    /// Link state as the kernel reports it (`up`, `down`, `dormant`, ...)
+    pub oper_state: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum InterfaceKind {
+    Plain,
+    Bond,
+    Bridge,
+    Vlan,
+}
+
+/// rtop's own footprint, to show that monitoring overhead stays low
+#[derive(Debug, Clone, Copy, Default, Serialize)]
+pub struct SelfUsage {
+    pub cpu_usage: f32,
+    pub memory: u64,
+    /// Wall time spent in the collection that produced this snapshot
+    pub collect_ms: f64,
 }
 
-#[derive(Debug, Clone)]
+/// System-wide task totals for the status bar, as `top` summarizes them
+#[derive(Debug, Clone, Copy, Default, Serialize)]
+pub struct TaskTotals {
+    pub processes: usize,
+    /// Threads of every process, kernel threads included (Linux)
+    pub threads: Option<u64>,
+    /// File handles allocated across the system (Linux)
+    pub open_files: Option<u64>,
+}
+
+/// Thermal throttle and power-limit events counted since rtop started
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize)]
+pub struct ThrottleStats {
+    /// Per-core thermal throttle events, summed over all cores
+    pub core_events: u64,
+    /// Package thermal throttle events, summed over all packages
+    pub package_events: u64,
+    /// Core and package power-limit (RAPL) notifications
+    pub power_limit_events: u64,
+    /// Any counter grew during the last collection
+    pub active: bool,
+}
+
+impl ThrottleStats {
+    pub fn total(&self) -> u64 {
+        self.core_events + self.package_events + self.power_limit_events
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
 #[allow(dead_code)]
 pub struct DiskInfo {
     pub name: String,
+    /// Every mount point of this volume (bind mounts and subvolumes are merged into one entry)
+    pub mount_points: Vec<String>,
     pub total_space: u64,
     pub available_space: u64,
+    /// Free space only root may use (the filesystem's reserved blocks)
+    pub reserved_space: u64,
+    /// How a network filesystem is responding; `None` for local volumes unless they hang
+    pub remote: Option<RemoteMount>,
+    pub fill_rate: Option<f64>, // Bytes per second, positive while the volume is filling
+    pub time_to_full: Option<u64>, // Seconds until full at the current fill rate
 }
 
-#[derive(Debug, Clone)]
+/// How a network filesystem (NFS, CIFS, ...) answered its background statvfs probes
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct RemoteMount {
+    /// Time the last answered probe took
+    pub latency_ms: Option<f64>,
+    /// Seconds the current probe has gone unanswered, once that counts as stale
+    pub stale_secs: Option<u64>,
+}
+
+impl RemoteMount {
+    /// Answers slower than this are flagged
+    pub const SLOW_MS: f64 = 500.0;
+
+    pub fn is_slow(&self) -> bool {
+        self.latency_ms.is_some_and(|ms| ms >= Self::SLOW_MS)
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
 #[allow(dead_code)]
 pub struct TemperatureInfo {
+    /// Shown name: `source`, or the friendly name configured for it
     pub label: String,
+    /// Name the sensor is found under: the hwmon chip and its channel, e.g. `acpitz temp1`
+    pub source: String,
     pub temperature: f32,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 #[allow(dead_code)]
 pub struct BatteryInfo {
     pub level: Option<f32>,
     pub status: Option<String>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 #[allow(dead_code)]
 pub struct GpuInfo {
     pub name: String,
@@ -94,7 +445,46 @@ pub struct GpuInfo {
     pub memory_note: Option<String>,
 }
 
-#[derive(Debug, Clone)]
+impl GpuInfo {
+    /// Graphics on the CPU package rather than a card, guessed from the name: Intel parts other
+    /// than the Arc A/B-series cards (`Arc A770`, `Arc Pro A60`), and AMD APUs, which report a
+    /// generic "Radeon Graphics", a small Vega (`Vega 8`) or a mobile `Radeon 780M`
+    pub fn is_integrated(&self) -> bool {
+        let name = self.name.to_lowercase();
+        let after = |word: &str| {
+            name.split_whitespace()
+                .skip_while(|&w| w != word)
+                .skip(1)
+                .find(|&w| w != "pro")
+                .unwrap_or("")
+        };
+        match self.vendor.as_str() {
+            "Intel" => {
+                let mut model = after("arc").chars();
+                !(matches!(model.next(), Some('a' | 'b'))
+                    && model.next().is_some_and(|c| c.is_ascii_digit()))
+            }
+            "AMD" => {
+                let mobile = name.split_whitespace().any(|word| {
+                    word.len() == 4
+                        && word.ends_with('m')
+                        && word[..3].chars().all(|c| c.is_ascii_digit())
+                });
+                // Vega 56/64 and the Radeon Pro Vega parts are cards
+                let vega_card = name.contains("rx")
+                    || name.contains("radeon pro")
+                    || name.contains("frontier")
+                    || matches!(after("vega"), "56" | "64");
+                name.contains("radeon graphics")
+                    || (name.contains("vega") && !vega_card)
+                    || (name.contains("radeon") && mobile)
+            }
+            _ => false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
 #[allow(dead_code)]
 pub struct SystemSnapshot {
     pub global_cpu_usage: f32,
@@ -102,11 +492,21 @@ pub struct SystemSnapshot {
     pub total_memory: u64,
     pub used_swap: u64,
     pub total_swap: u64,
+    /// Online cores. Per-core series have a slot for every present core, offline ones included
     pub cpu_count: usize,
-    pub cached_memory: u64, // Cached memory
+    /// Whether each core is online; empty when unknown, which counts as all online
+    pub cpu_online: Vec<bool>,
+    pub cached_memory: u64, // Page cache plus reclaimable slab, as `free` counts it
+    pub buffers_memory: u64, // Block device buffers
+    pub free_memory: u64,   // Completely unused RAM
+    pub available_memory: u64, // Kernel's estimate of RAM available without swapping
+    pub zfs_arc: Option<ZfsArc>, // None without the zfs module loaded
     pub cpu_history: Vec<VecDeque<f32>>,
     pub memory_history: VecDeque<(u64, u64)>, // Changed to VecDeque for efficient operations
     pub swap_history: VecDeque<(u64, u64)>,   // Changed to VecDeque for efficient operations
+    pub history_times: VecDeque<i64>, // Unix time of each collection, aligned with the newest history points
+    /// Unix time of the last sample restored from the previous session; graphs mark the break
+    pub history_gap: Option<i64>,
     pub network_interfaces: HashMap<String, (u64, u64)>,
     pub selected_network_interface: Option<String>,
     pub cpu_frequencies: Vec<u64>,
@@ -114,22 +514,90 @@ pub struct SystemSnapshot {
     pub disk_usage_history: Vec<VecDeque<(u64, u64)>>, // Changed to VecDeque for efficient operations
     pub temperature_sensors: Vec<TemperatureInfo>,
     pub gpus: Vec<GpuInfo>,
+    /// NVML reports per-process GPU use, so processes without any are known to use none
+    pub process_gpu_stats: bool,
     pub battery_info: Option<BatteryInfo>,
     pub processes: Vec<ProcessInfo>,
     pub disks: Vec<DiskInfo>,
     pub networks: Vec<NetworkInfo>,
     pub hostname: String,
+    /// Source address of the default route, when there is one
+    pub primary_ip: Option<String>,
     pub uptime: String,
     pub load_avg: String,
     pub process_sort_by: ProcessSortBy,
+    /// Sort the process list against the natural direction of `process_sort_by`
+    pub process_sort_reversed: bool,
     pub chart_type: ChartType,
     pub color_scheme: ColorScheme,
     pub auto_update: bool,
     pub update_interval: u64,
     pub show_colors: bool,
     pub show_graphs: bool,
-    pub cpu_power: Option<f32>, // CPU power consumption in Watts
-    pub cpu_name: String,       // CPU name/model
+    pub cpu_power: Option<f32>,          // CPU power consumption in Watts
+    pub throttle: Option<ThrottleStats>, // None when the kernel exposes no throttle counters
+    pub run_queue_wait_us: Vec<Option<f32>>, // Average run-queue wait per timeslice, per core
+    pub cpu_name: String,                // CPU name/model
+    pub probes: Vec<crate::data::probes::ProbeStats>, // Latency monitor results
+    pub protocol_stats: Option<ProtocolStats>,
+    pub socket_stats: Option<SocketStats>,
+    pub kernel_limits: Option<KernelLimits>,
+    pub inspected_files: Option<OpenFilesByMount>,
+    pub inspected_process: Option<ProcessDetail>,
+    /// rtop runs as root
+    pub elevated: bool,
+    /// Sources that could not be read without elevated privileges, e.g. "CPU power (RAPL)"
+    pub restricted_sources: Vec<String>,
+    pub self_usage: SelfUsage,
+    pub task_totals: TaskTotals,
+}
+
+impl SystemSnapshot {
+    /// Index of the first point collected after a restart in a series of `len` points aligned
+    /// like `history_time`, while restored points are still in the series
+    pub fn history_gap_index(&self, len: usize) -> Option<usize> {
+        let last_restored = self.history_gap?;
+        let position = self
+            .history_times
+            .iter()
+            .position(|&time| time == last_restored)?;
+        let live = self.history_times.len() - position - 1;
+        let index = len.checked_sub(live)?;
+        (index > 0 && index < len).then_some(index)
+    }
+
+    /// Unix time of point `index` in a history of `len` points. Histories can be shorter
+    /// than the timestamps (e.g. after the CPU count changed), so they align at the newest end.
+    pub fn history_time(&self, len: usize, index: usize) -> Option<i64> {
+        let from_end = len.checked_sub(index)?;
+        let times = self.history_times.len();
+        self.history_times
+            .get(times.checked_sub(from_end)?)
+            .copied()
+    }
+
+    /// Number of per-core slots: every present core, whether online or not
+    pub fn core_slots(&self) -> usize {
+        self.cpu_online.len().max(self.cpu_count)
+    }
+
+    pub fn core_online(&self, core: usize) -> bool {
+        self.cpu_online.get(core).copied().unwrap_or(true)
+    }
+
+    /// Share of RAM the kernel cannot hand out without swapping, from MemAvailable. Unlike
+    /// used/total this stays low while reclaimable cache fills memory, including the part of
+    /// the ZFS ARC above its floor, which MemAvailable does not count.
+    pub fn memory_pressure_percent(&self) -> f64 {
+        if self.total_memory == 0 {
+            return 0.0;
+        }
+        let available = self
+            .available_memory
+            .saturating_add(self.zfs_arc.map_or(0, |arc| arc.reclaimable()));
+        let unavailable = self.total_memory.saturating_sub(available);
+        unavailable as f64 * 100.0 / self.total_memory as f64
+    }
 }
 
 impl Default for SystemSnapshot {
@@ -141,10 +609,17 @@ impl Default for SystemSnapshot {
             used_swap: 0,
             total_swap: 0,
             cpu_count: 0,
+            cpu_online: vec![],
             cached_memory: 0,
+            buffers_memory: 0,
+            free_memory: 0,
+            available_memory: 0,
+            zfs_arc: None,
             cpu_history: vec![VecDeque::with_capacity(25)], // Reduced capacity
             memory_history: VecDeque::with_capacity(25),    // Changed to VecDeque
             swap_history: VecDeque::with_capacity(25),      // Changed to VecDeque
+            history_times: VecDeque::with_capacity(25),
+            history_gap: None,
             network_interfaces: HashMap::new(),
             selected_network_interface: None,
             cpu_frequencies: vec![],
@@ -152,14 +627,17 @@ impl Default for SystemSnapshot {
             disk_usage_history: vec![],                   // Will be sized appropriately
             temperature_sensors: vec![],
             gpus: vec![],
+            process_gpu_stats: false,
             battery_info: None,
             processes: vec![],
             disks: vec![],
             networks: vec![],
             hostname: String::new(),
+            primary_ip: None,
             uptime: String::new(),
             load_avg: String::new(),
             process_sort_by: ProcessSortBy::CpuUsage,
+            process_sort_reversed: false,
             chart_type: ChartType::CpuUsage,
             color_scheme: ColorScheme::Default,
             auto_update: true,
@@ -167,7 +645,19 @@ impl Default for SystemSnapshot {
             show_colors: true,
             show_graphs: true,
             cpu_power: None,
+            throttle: None,
+            run_queue_wait_us: vec![],
             cpu_name: String::new(),
+            probes: vec![],
+            protocol_stats: None,
+            socket_stats: None,
+            kernel_limits: None,
+            inspected_files: None,
+            inspected_process: None,
+            elevated: false,
+            restricted_sources: vec![],
+            self_usage: SelfUsage::default(),
+            task_totals: TaskTotals::default(),
         }
     }
 }
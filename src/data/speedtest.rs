@@ -0,0 +1,227 @@
+use std::io::Write;
+use std::process::{Command, Stdio};
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+
+/// Seconds each direction of an iperf3 test runs
+const IPERF_SECONDS: &str = "5";
+/// Upper bound for each curl transfer, in seconds
+const HTTP_MAX_SECONDS: &str = "15";
+/// Bytes posted to an HTTP(S) endpoint for the upload half
+const UPLOAD_BYTES: usize = 16 * 1024 * 1024;
+
+/// Server an on-demand speed test runs against
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SpeedTestEndpoint {
+    /// `iperf3://host[:port]`, measured in both directions with `iperf3`
+    Iperf3 { host: String, port: Option<u16> },
+    /// An `http(s)://` URL downloaded, then posted to, with `curl`
+    Http(String),
+}
+
+impl SpeedTestEndpoint {
+    pub fn parse(endpoint: &str) -> Option<Self> {
+        let endpoint = endpoint.trim();
+        if let Some(rest) = endpoint.strip_prefix("iperf3://") {
+            let rest = rest.trim_end_matches('/');
+            let (host, port) = match rest.rsplit_once(':') {
+                Some((host, port)) => (host, Some(port.parse().ok()?)),
+                None => (rest, None),
+            };
+            return (!host.is_empty()).then(|| SpeedTestEndpoint::Iperf3 {
+                host: host.to_string(),
+                port,
+            });
+        }
+        (endpoint.starts_with("http://") || endpoint.starts_with("https://"))
+            .then(|| SpeedTestEndpoint::Http(endpoint.to_string()))
+    }
+}
+
+/// Progress of the most recent speed test; rates are in bytes per second
+#[derive(Debug, Clone, Default)]
+pub enum SpeedTestStatus {
+    #[default]
+    Idle,
+    Running {
+        phase: &'static str,
+        started: Instant,
+    },
+    Done {
+        download: u64,
+        upload: u64,
+        finished: Instant,
+    },
+    Failed(String),
+}
+
+pub type SharedSpeedTest = Arc<Mutex<SpeedTestStatus>>;
+
+/// Run a test on a background thread unless one is already in progress
+pub fn start(shared: &SharedSpeedTest, endpoint: SpeedTestEndpoint) {
+    let Ok(mut status) = shared.lock() else {
+        return;
+    };
+    if matches!(*status, SpeedTestStatus::Running { .. }) {
+        return;
+    }
+    *status = SpeedTestStatus::Running {
+        phase: "download",
+        started: Instant::now(),
+    };
+    drop(status);
+
+    let state = Arc::clone(shared);
+    std::thread::spawn(move || {
+        let set = |next: SpeedTestStatus| {
+            if let Ok(mut status) = state.lock() {
+                *status = next;
+            }
+        };
+        let result = measure(&endpoint, true).and_then(|download| {
+            set(SpeedTestStatus::Running {
+                phase: "upload",
+                started: Instant::now(),
+            });
+            measure(&endpoint, false).map(|upload| (download, upload))
+        });
+        set(match result {
+            Ok((download, upload)) => SpeedTestStatus::Done {
+                download,
+                upload,
+                finished: Instant::now(),
+            },
+            Err(err) => SpeedTestStatus::Failed(err),
+        });
+    });
+}
+
+/// Throughput in one direction, in bytes per second
+fn measure(endpoint: &SpeedTestEndpoint, download: bool) -> Result<u64, String> {
+    match endpoint {
+        SpeedTestEndpoint::Iperf3 { host, port } => {
+            let mut command = Command::new("iperf3");
+            command.args(["-c", host, "-t", IPERF_SECONDS, "-J"]);
+            if let Some(port) = port {
+                command.args(["-p", &port.to_string()]);
+            }
+            // iperf3 sends from the client by default; -R makes the server send
+            if download {
+                command.arg("-R");
+            }
+            let output = run(&mut command, "iperf3", None)?;
+            // -J reports errors inside the JSON on stdout; stderr only matters without any
+            let stdout = String::from_utf8_lossy(&output.stdout);
+            if stdout.trim().is_empty() {
+                if let Some(err) = failure("iperf3", &output) {
+                    return Err(err);
+                }
+            }
+            parse_iperf3_json(&stdout)
+        }
+        SpeedTestEndpoint::Http(url) => {
+            let mut command = Command::new("curl");
+            command.args(["-sS", "-o", "/dev/null", "--max-time", HTTP_MAX_SECONDS]);
+            let output = if download {
+                command.args(["-w", "%{speed_download}", url]);
+                run(&mut command, "curl", None)?
+            } else {
+                command.args(["-w", "%{speed_upload}", "--data-binary", "@-", url]);
+                run(&mut command, "curl", Some(&vec![0; UPLOAD_BYTES]))?
+            };
+            if let Some(err) = failure("curl", &output) {
+                return Err(err);
+            }
+            String::from_utf8_lossy(&output.stdout)
+                .trim()
+                .parse::<f64>()
+                .map(|rate| rate as u64)
+                .map_err(|_| "unexpected curl output".to_string())
+        }
+    }
+}
+
+/// Run `command` to completion, feeding it `input`
+fn run(
+    command: &mut Command,
+    program: &str,
+    input: Option<&[u8]>,
+) -> Result<std::process::Output, String> {
+    command
+        .stdin(if input.is_some() {
+            Stdio::piped()
+        } else {
+            Stdio::null()
+        })
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+    let mut child = command.spawn().map_err(|err| {
+        if err.kind() == std::io::ErrorKind::NotFound {
+            format!("{} not installed", program)
+        } else {
+            err.to_string()
+        }
+    })?;
+    if let (Some(input), Some(mut stdin)) = (input, child.stdin.take()) {
+        // A write error means the program exited early; its stderr explains why.
+        let _ = stdin.write_all(input);
+    }
+    child.wait_with_output().map_err(|err| err.to_string())
+}
+
+/// First line of stderr (or the exit status) when the program failed
+fn failure(program: &str, output: &std::process::Output) -> Option<String> {
+    if output.status.success() {
+        return None;
+    }
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    Some(match stderr.lines().next().map(str::trim) {
+        Some(reason) if !reason.is_empty() => reason.to_string(),
+        _ => format!("{} exited with {}", program, output.status),
+    })
+}
+
+/// Received throughput from `iperf3 -J` output, in bytes per second
+fn parse_iperf3_json(output: &str) -> Result<u64, String> {
+    let json: serde_json::Value =
+        serde_json::from_str(output).map_err(|_| "unexpected iperf3 output".to_string())?;
+    if let Some(error) = json.get("error").and_then(|error| error.as_str()) {
+        return Err(error.to_string());
+    }
+    json.pointer("/end/sum_received/bits_per_second")
+        .and_then(|bits| bits.as_f64())
+        .map(|bits| (bits / 8.0) as u64)
+        .ok_or_else(|| "unexpected iperf3 output".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_endpoints_and_iperf3_output() {
+        assert_eq!(
+            SpeedTestEndpoint::parse("iperf3://nas.lan:5202"),
+            Some(SpeedTestEndpoint::Iperf3 {
+                host: "nas.lan".to_string(),
+                port: Some(5202)
+            })
+        );
+        assert_eq!(
+            SpeedTestEndpoint::parse(" https://example.com/10MB.bin "),
+            Some(SpeedTestEndpoint::Http(
+                "https://example.com/10MB.bin".to_string()
+            ))
+        );
+        assert_eq!(SpeedTestEndpoint::parse("iperf3://"), None);
+        assert_eq!(SpeedTestEndpoint::parse("nas.lan"), None);
+
+        let report = r#"{"start":{},"end":{"sum_sent":{"bits_per_second":9.5e8},
+                         "sum_received":{"bits_per_second":9.4e8}}}"#;
+        assert_eq!(parse_iperf3_json(report), Ok(117_500_000));
+        assert_eq!(
+            parse_iperf3_json(r#"{"error":"unable to connect to server"}"#),
+            Err("unable to connect to server".to_string())
+        );
+    }
+}
@@ -0,0 +1,124 @@
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::ErrorKind;
+use std::os::unix::fs::FileExt;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+/// Reader for small, frequently polled kernel files (sysfs, procfs).
+///
+/// Files are opened once and re-read with `pread` from offset 0, which makes the kernel
+/// regenerate the contents without another `open`/`close` pair. All reads share one
+/// buffer, so a tick of polling does not allocate per file.
+pub struct SysfsReader {
+    handles: HashMap<PathBuf, File>,
+    failures: HashMap<PathBuf, (ErrorKind, Instant)>,
+    buf: Vec<u8>,
+}
+
+impl SysfsReader {
+    const INITIAL_BUF: usize = 4096;
+    /// Paths that failed to open are not retried more often than this
+    const RETRY_AFTER: Duration = Duration::from_secs(30);
+
+    pub fn new() -> Self {
+        Self {
+            handles: HashMap::new(),
+            failures: HashMap::new(),
+            buf: vec![0; Self::INITIAL_BUF],
+        }
+    }
+
+    /// Read the whole file, returning its contents or the reason it is unreadable
+    pub fn read_str(&mut self, path: impl AsRef<Path>) -> Result<&str, ErrorKind> {
+        let path = path.as_ref();
+
+        if !self.handles.contains_key(path) {
+            if let Some((kind, since)) = self.failures.get(path) {
+                if since.elapsed() < Self::RETRY_AFTER {
+                    return Err(*kind);
+                }
+            }
+            match File::open(path) {
+                Ok(file) => {
+                    self.failures.remove(path);
+                    self.handles.insert(path.to_path_buf(), file);
+                }
+                Err(err) => {
+                    self.failures
+                        .insert(path.to_path_buf(), (err.kind(), Instant::now()));
+                    return Err(err.kind());
+                }
+            }
+        }
+
+        // seq_file-backed procfs files (maps, mounts, net/tcp) hand out about a page per read,
+        // so keep reading at the next offset until end of file
+        let mut len = 0;
+        loop {
+            let Some(file) = self.handles.get(path) else {
+                return Err(ErrorKind::NotFound);
+            };
+            if len == self.buf.len() {
+                let grown = self.buf.len() * 2;
+                self.buf.resize(grown, 0);
+            }
+            match file.read_at(&mut self.buf[len..], len as u64) {
+                Ok(0) => break,
+                Ok(n) => len += n,
+                Err(err) => {
+                    // The device may have gone away (hot-unplug, driver reload); reopen later.
+                    self.handles.remove(path);
+                    self.failures
+                        .insert(path.to_path_buf(), (err.kind(), Instant::now()));
+                    return Err(err.kind());
+                }
+            }
+        }
+
+        std::str::from_utf8(&self.buf[..len]).map_err(|_| ErrorKind::InvalidData)
+    }
+
+    /// Read a file holding a single value, such as most sysfs attributes
+    pub fn read_value<T: FromStr>(&mut self, path: impl AsRef<Path>) -> Option<T> {
+        self.read_str(path).ok()?.trim().parse::<T>().ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reread_and_grow_buffer() {
+        let path = std::env::temp_dir().join(format!("rtop-sysfs-{}", std::process::id()));
+        std::fs::write(&path, "42\n").unwrap();
+
+        let mut reader = SysfsReader::new();
+        assert_eq!(reader.read_value::<u64>(&path), Some(42));
+
+        // The cached handle must observe new contents, including ones larger than the buffer.
+        let large = "x".repeat(SysfsReader::INITIAL_BUF * 3);
+        std::fs::write(&path, &large).unwrap();
+        assert_eq!(reader.read_str(&path).map(str::len), Ok(large.len()));
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_reads_multi_page_seq_files_whole() {
+        // smaps runs to several pages even for a small process, and seq_file hands out about
+        // one page per read
+        let expected = std::fs::read_to_string("/proc/self/smaps").unwrap();
+        assert!(expected.len() > 2 * SysfsReader::INITIAL_BUF);
+
+        let mut reader = SysfsReader::new();
+        let smaps = reader.read_str("/proc/self/smaps").unwrap();
+        // Mappings can come and go between the two reads, but not pages of them
+        let regions = |text: &str| text.lines().filter(|l| l.starts_with("Size:")).count();
+        assert!(regions(smaps).abs_diff(regions(&expected)) < 4);
+        assert!(smaps.len() > 2 * SysfsReader::INITIAL_BUF);
+        assert!(smaps.ends_with('\n'));
+    }
+}
@@ -0,0 +1,218 @@
+//! Number, unit and time formatting shared by every panel: byte sizes and rates in the
+//! configured unit base, with the locale's decimal separator, and durations and timestamps.
+
+use serde::{Deserialize, Serialize};
+use std::sync::OnceLock;
+
+/// How byte sizes are scaled and labelled
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum UnitBase {
+    /// Powers of 1024 labelled `KB`, `MB`, ... as most system monitors do
+    #[default]
+    Jedec,
+    /// Powers of 1024 labelled `KiB`, `MiB`, ...
+    Iec,
+    /// Powers of 1000 labelled `kB`, `MB`, ...
+    Si,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Settings {
+    unit_base: UnitBase,
+    decimal_separator: char,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            unit_base: UnitBase::Jedec,
+            decimal_separator: '.',
+        }
+    }
+}
+
+static SETTINGS: OnceLock<Settings> = OnceLock::new();
+
+/// Languages that write `1,5` rather than `1.5`
+const DECIMAL_COMMA: &[&str] = &[
+    "bg", "ca", "cs", "da", "de", "el", "es", "et", "eu", "fi", "fr", "gl", "hr", "hu", "id", "it",
+    "lt", "lv", "nb", "nl", "nn", "no", "pl", "pt", "ro", "ru", "sk", "sl", "sr", "sv", "tr", "uk",
+    "vi",
+];
+
+/// Apply the configured unit base and separator (`.`, `,`, or empty to follow the locale).
+/// Until this runs (and in tests) sizes use JEDEC units and a point.
+pub fn init(unit_base: UnitBase, decimal_separator: &str) {
+    let decimal_separator = decimal_separator.chars().next().unwrap_or_else(|| {
+        let language = crate::i18n::locale_language(&["LC_ALL", "LC_NUMERIC", "LANG"], |name| {
+            std::env::var(name).ok()
+        });
+        locale_separator(language.as_deref())
+    });
+    SETTINGS.get_or_init(|| Settings {
+        unit_base,
+        decimal_separator,
+    });
+}
+
+fn locale_separator(language: Option<&str>) -> char {
+    match language {
+        Some(language) if DECIMAL_COMMA.contains(&language) => ',',
+        _ => '.',
+    }
+}
+
+fn settings() -> Settings {
+    SETTINGS.get().copied().unwrap_or_default()
+}
+
+/// `value` with `precision` decimals and the locale's separator
+pub fn decimal(value: f64, precision: usize) -> String {
+    decimal_with(settings(), value, precision)
+}
+
+fn decimal_with(settings: Settings, value: f64, precision: usize) -> String {
+    let text = format!("{:.*}", precision, value);
+    if settings.decimal_separator == '.' {
+        text
+    } else {
+        text.replace('.', &settings.decimal_separator.to_string())
+    }
+}
+
+fn scaled(settings: Settings, mut value: f64, base: f64, units: &[&str]) -> String {
+    let mut unit = 0;
+    while value >= base && unit < units.len() - 1 {
+        value /= base;
+        unit += 1;
+    }
+    format!("{}{}", decimal_with(settings, value, 1), units[unit])
+}
+
+/// A byte size such as `1.5GB` (`1.5GiB`, `1.6GB` in SI)
+pub fn bytes(bytes: u64) -> String {
+    bytes_with(settings(), bytes)
+}
+
+fn bytes_with(settings: Settings, bytes: u64) -> String {
+    let (base, units): (f64, [&str; 6]) = match settings.unit_base {
+        UnitBase::Jedec => (1024.0, ["B", "KB", "MB", "GB", "TB", "PB"]),
+        UnitBase::Iec => (1024.0, ["B", "KiB", "MiB", "GiB", "TiB", "PiB"]),
+        UnitBase::Si => (1000.0, ["B", "kB", "MB", "GB", "TB", "PB"]),
+    };
+    scaled(settings, bytes as f64, base, &units)
+}
+
+/// Bytes per second, such as `1.2MB/s`
+pub fn rate(bytes_per_sec: u64) -> String {
+    format!("{}/s", bytes(bytes_per_sec))
+}
+
+/// Bytes per second in bits, such as `9.6Mb/s`; link speeds are always decimal
+pub fn bits_rate(bytes_per_sec: u64) -> String {
+    let units = ["b", "Kb", "Mb", "Gb", "Tb"];
+    format!(
+        "{}/s",
+        scaled(settings(), bytes_per_sec as f64 * 8.0, 1000.0, &units)
+    )
+}
+
+/// A running time in at most 8 characters: `04:09`, `3:25:07`, then whole days and hours
+/// (`12d03h`)
+pub fn elapsed(seconds: u64) -> String {
+    let (days, hours) = (seconds / 86_400, seconds % 86_400 / 3600);
+    let (minutes, secs) = (seconds % 3600 / 60, seconds % 60);
+    if days > 0 {
+        format!("{}d{:02}h", days, hours)
+    } else if hours > 0 {
+        format!("{}:{:02}:{:02}", hours, minutes, secs)
+    } else {
+        format!("{:02}:{:02}", minutes, secs)
+    }
+}
+
+/// A duration in seconds as `2d 3h 4m 5s`, leaving out the leading units that are zero
+pub fn duration(seconds: u64) -> String {
+    let days = seconds / (24 * 3600);
+    let hours = (seconds % (24 * 3600)) / 3600;
+    let minutes = (seconds % 3600) / 60;
+    let secs = seconds % 60;
+
+    if days > 0 {
+        format!("{}d {}h {}m {}s", days, hours, minutes, secs)
+    } else if hours > 0 {
+        format!("{}h {}m {}s", hours, minutes, secs)
+    } else if minutes > 0 {
+        format!("{}m {}s", minutes, secs)
+    } else {
+        format!("{}s", secs)
+    }
+}
+
+/// A duration in seconds as a coarse estimate such as "~3d" or "~45m"
+pub fn eta(seconds: u64) -> String {
+    if seconds >= 24 * 3600 {
+        format!("~{}d", seconds / (24 * 3600))
+    } else if seconds >= 3600 {
+        format!("~{}h", seconds / 3600)
+    } else if seconds >= 60 {
+        format!("~{}m", seconds / 60)
+    } else {
+        "<1m".to_string()
+    }
+}
+
+/// Local wall-clock time of a Unix timestamp, as `HH:MM:SS`
+pub fn clock(unix_secs: i64) -> String {
+    use chrono::TimeZone;
+    chrono::Local
+        .timestamp_opt(unix_secs, 0)
+        .single()
+        .map(|time| time.format("%H:%M:%S").to_string())
+        .unwrap_or_else(|| "--:--:--".to_string())
+}
+
+/// Local date and time of a Unix timestamp, as `YYYY-MM-DD HH:MM:SS`
+pub fn date_time(unix_secs: i64) -> String {
+    use chrono::TimeZone;
+    chrono::Local
+        .timestamp_opt(unix_secs, 0)
+        .single()
+        .map(|time| time.format("%Y-%m-%d %H:%M:%S").to_string())
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unit_bases_and_separators() {
+        let with = |unit_base, decimal_separator| Settings {
+            unit_base,
+            decimal_separator,
+        };
+        let size = 1536 * 1024;
+        assert_eq!(bytes_with(with(UnitBase::Jedec, '.'), size), "1.5MB");
+        assert_eq!(bytes_with(with(UnitBase::Iec, '.'), size), "1.5MiB");
+        assert_eq!(bytes_with(with(UnitBase::Si, ','), size), "1,6MB");
+        assert_eq!(bytes_with(with(UnitBase::Si, '.'), 999), "999.0B");
+
+        assert_eq!(locale_separator(Some("de")), ',');
+        assert_eq!(locale_separator(Some("en")), '.');
+        assert_eq!(locale_separator(None), '.');
+
+        // Defaults until `init` runs
+        assert_eq!(rate(2048), "2.0KB/s");
+        assert_eq!(bits_rate(125_000), "1.0Mb/s");
+    }
+
+    #[test]
+    fn test_elapsed() {
+        assert_eq!(elapsed(0), "00:00");
+        assert_eq!(elapsed(249), "04:09");
+        assert_eq!(elapsed(3 * 3600 + 25 * 60 + 7), "3:25:07");
+        assert_eq!(elapsed(12 * 86_400 + 3 * 3600 + 59), "12d03h");
+    }
+}
@@ -0,0 +1,487 @@
+//! Graphs drawn as inline images on terminals that speak the kitty graphics protocol or sixel.
+//! Graph widgets queue an image instead of painting braille while a frame is drawn, and the
+//! queue is written out once ratatui has flushed the frame; any other terminal keeps braille.
+
+use ratatui::{layout::Rect, style::Color};
+use std::hash::{Hash, Hasher};
+use std::io::Write;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Mutex, OnceLock};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Protocol {
+    Kitty,
+    Sixel,
+}
+
+impl Protocol {
+    /// `RTOP_GRAPHICS` (`kitty`, `sixel` or `off`) if set, otherwise guessed from the terminal
+    pub fn detect() -> Option<Self> {
+        Self::from_env(|name| std::env::var(name).ok())
+    }
+
+    fn from_env(var: impl Fn(&str) -> Option<String>) -> Option<Self> {
+        match var("RTOP_GRAPHICS").as_deref() {
+            Some("kitty") => return Some(Protocol::Kitty),
+            Some("sixel") => return Some(Protocol::Sixel),
+            Some("off") => return None,
+            _ => {}
+        }
+        let term = var("TERM").unwrap_or_default();
+        let program = var("TERM_PROGRAM").unwrap_or_default();
+        if var("KITTY_WINDOW_ID").is_some()
+            || matches!(term.as_str(), "xterm-kitty" | "xterm-ghostty")
+            || matches!(program.as_str(), "WezTerm" | "ghostty")
+        {
+            Some(Protocol::Kitty)
+        } else if term.starts_with("foot")
+            || term.starts_with("mlterm")
+            || term.contains("sixel")
+            || program == "iTerm.app"
+        {
+            Some(Protocol::Sixel)
+        } else {
+            None
+        }
+    }
+}
+
+/// A graph to draw over `area`, one point per sample
+#[derive(Debug, Clone)]
+pub struct GraphImage {
+    pub area: Rect,
+    /// Samples scaled to `0.0..=1.0` with their color, oldest first
+    pub points: Vec<(f64, Color)>,
+    pub fill: bool,
+    /// Index of a point to mark with a vertical line
+    pub cursor: Option<usize>,
+}
+
+static PROTOCOL: OnceLock<Option<Protocol>> = OnceLock::new();
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static QUEUE: Mutex<Vec<GraphImage>> = Mutex::new(Vec::new());
+/// What the last frame drew, by image id
+static PLACED: Mutex<Vec<Placed>> = Mutex::new(Vec::new());
+
+/// An image on screen: where it is and a hash of what it shows at the cell size it was drawn at
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Placed {
+    area: Rect,
+    content: u64,
+}
+
+impl Placed {
+    fn of(image: &GraphImage, cell: (u16, u16)) -> Self {
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        (image.area.width, image.area.height, cell).hash(&mut hasher);
+        for (value, color) in &image.points {
+            (value.to_bits(), color).hash(&mut hasher);
+        }
+        (image.fill, image.cursor).hash(&mut hasher);
+        Self {
+            area: image.area,
+            content: hasher.finish(),
+        }
+    }
+}
+
+/// Detect the terminal's protocol; until this runs (and in tests) graphs stay braille
+pub fn init() {
+    PROTOCOL.get_or_init(Protocol::detect);
+}
+
+fn protocol() -> Option<Protocol> {
+    PROTOCOL.get().copied().flatten()
+}
+
+/// Start a frame. Popups are plain text, so while one is open graphs fall back to braille
+/// rather than being drawn over it.
+pub fn begin_frame(enabled: bool) {
+    ENABLED.store(enabled && protocol().is_some(), Ordering::Relaxed);
+    if let Ok(mut queue) = QUEUE.lock() {
+        queue.clear();
+    }
+}
+
+/// Queue `image` for this frame; false when the widget should draw braille itself
+pub fn queue(image: GraphImage) -> bool {
+    if !ENABLED.load(Ordering::Relaxed) {
+        return false;
+    }
+    QUEUE.lock().map(|mut queue| queue.push(image)).is_ok()
+}
+
+/// Write the frame's images, `cell` being the size of a character cell in pixels. Returns true
+/// when sixel images were moved, resized or dropped and the screen must be redrawn to wipe them.
+pub fn flush(out: &mut impl Write, cell: (u16, u16)) -> std::io::Result<bool> {
+    let Some(protocol) = protocol() else {
+        return Ok(false);
+    };
+    let images = QUEUE
+        .lock()
+        .map(|mut queue| std::mem::take(&mut *queue))
+        .unwrap_or_default();
+    let Ok(mut placed) = PLACED.lock() else {
+        return Ok(false);
+    };
+    let (now_placed, stale) = write_images(out, protocol, &images, &placed, cell)?;
+    *placed = now_placed;
+    Ok(stale)
+}
+
+/// Forget what is on screen, after the terminal was cleared and took the images with it
+pub fn forget_placements() {
+    if let Ok(mut placed) = PLACED.lock() {
+        placed.clear();
+    }
+}
+
+/// Draw `images` over a screen showing `previous`. Kitty keeps images by id, so one whose
+/// content is unchanged is only re-placed if it moved, and is otherwise left alone. Sixel
+/// pixels are part of the screen: everything is drawn again, and the second value is true when
+/// an area drawn last frame is not covered this frame.
+fn write_images(
+    out: &mut impl Write,
+    protocol: Protocol,
+    images: &[GraphImage],
+    previous: &[Placed],
+    cell: (u16, u16),
+) -> std::io::Result<(Vec<Placed>, bool)> {
+    let placed: Vec<Placed> = images.iter().map(|image| Placed::of(image, cell)).collect();
+    for (i, (image, now)) in images.iter().zip(&placed).enumerate() {
+        let id = i + 1;
+        let before = previous.get(i);
+        if protocol == Protocol::Kitty && before.is_some_and(|b| b.content == now.content) {
+            if before.is_some_and(|b| b.area != now.area) {
+                write!(out, "\x1b[{};{}H", image.area.y + 1, image.area.x + 1)?;
+                place_kitty(out, id, image.area)?;
+            }
+            continue;
+        }
+        let width = usize::from(image.area.width) * usize::from(cell.0);
+        let height = usize::from(image.area.height) * usize::from(cell.1);
+        let pixels = rasterize(image, width, height);
+        write!(out, "\x1b[{};{}H", image.area.y + 1, image.area.x + 1)?;
+        match protocol {
+            Protocol::Kitty => {
+                if before.is_some() {
+                    delete_kitty(out, id)?;
+                }
+                write_kitty(out, id, image.area, &pixels, (width, height))?
+            }
+            Protocol::Sixel => write_sixel(out, &pixels, (width, height))?,
+        }
+    }
+    if protocol == Protocol::Kitty {
+        for id in placed.len() + 1..=previous.len() {
+            delete_kitty(out, id)?;
+        }
+    }
+    out.flush()?;
+
+    let stale = protocol == Protocol::Sixel
+        && previous
+            .iter()
+            .any(|before| !placed.iter().any(|now| now.area == before.area));
+    Ok((placed, stale))
+}
+
+fn rgb(color: Color) -> [u8; 3] {
+    match color {
+        Color::Rgb(r, g, b) => [r, g, b],
+        Color::Black => [0, 0, 0],
+        Color::Red => [205, 49, 49],
+        Color::Green => [13, 188, 121],
+        Color::Yellow => [229, 229, 16],
+        Color::Blue => [36, 114, 200],
+        Color::Magenta => [188, 63, 188],
+        Color::Cyan => [17, 168, 205],
+        Color::Gray => [204, 204, 204],
+        Color::DarkGray => [118, 118, 118],
+        Color::LightRed => [241, 76, 76],
+        Color::LightGreen => [35, 209, 139],
+        Color::LightYellow => [245, 245, 67],
+        Color::LightBlue => [59, 142, 234],
+        Color::LightMagenta => [214, 112, 214],
+        Color::LightCyan => [41, 184, 219],
+        _ => [229, 229, 229],
+    }
+}
+
+/// RGBA pixels, row by row; alpha 0 is left transparent
+fn rasterize(image: &GraphImage, width: usize, height: usize) -> Vec<[u8; 4]> {
+    let mut pixels = vec![[0; 4]; width * height];
+    let count = image.points.len();
+    if width < 2 || height < 2 || count == 0 {
+        return pixels;
+    }
+    let mut put = |x: usize, y: usize, [r, g, b]: [u8; 3], alpha: u8| {
+        if x < width && y < height && pixels[y * width + x][3] <= alpha {
+            pixels[y * width + x] = [r, g, b, alpha];
+        }
+    };
+    let x_of = |i: usize| {
+        if count == 1 {
+            0.0
+        } else {
+            i as f64 * (width - 1) as f64 / (count - 1) as f64
+        }
+    };
+    let y_of = |value: f64| (1.0 - value.clamp(0.0, 1.0)) * (height - 1) as f64;
+    // Two pixels thick, like a braille dot at typical cell sizes
+    let thickness = (height / 40).max(2);
+
+    for pair in image.points.windows(2).enumerate() {
+        let (i, [(from, _), (to, color)]) = pair else {
+            continue;
+        };
+        let (x0, x1) = (x_of(i), x_of(i + 1));
+        let (y0, y1) = (y_of(*from), y_of(*to));
+        let steps = ((x1 - x0).abs().max((y1 - y0).abs()) * 2.0).ceil().max(1.0) as usize;
+        for step in 0..=steps {
+            let t = step as f64 / steps as f64;
+            let x = (x0 + (x1 - x0) * t).round() as usize;
+            let y = (y0 + (y1 - y0) * t).round() as usize;
+            if image.fill {
+                for below in y..height {
+                    put(x, below, rgb(*color), 0x50);
+                }
+            }
+            for dy in 0..thickness {
+                put(x, y + dy, rgb(*color), 0xff);
+            }
+        }
+    }
+    if count == 1 {
+        let (value, color) = image.points[0];
+        put(0, y_of(value) as usize, rgb(color), 0xff);
+    }
+    if let Some(cursor) = image.cursor.filter(|&i| i < count) {
+        let x = x_of(cursor).round() as usize;
+        for y in 0..height {
+            put(x, y, rgb(Color::Yellow), 0xff);
+        }
+    }
+    pixels
+}
+
+fn base64(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut out = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let n = chunk
+            .iter()
+            .enumerate()
+            .fold(0u32, |n, (i, &byte)| n | u32::from(byte) << (16 - 8 * i));
+        for i in 0..4 {
+            if i <= chunk.len() {
+                out.push(char::from(ALPHABET[(n >> (18 - 6 * i)) as usize & 63]));
+            } else {
+                out.push('=');
+            }
+        }
+    }
+    out
+}
+
+/// Free image `id` along with its placement
+fn delete_kitty(out: &mut impl Write, id: usize) -> std::io::Result<()> {
+    write!(out, "\x1b_Ga=d,d=I,i={},q=2\x1b\\", id)
+}
+
+/// Move the placement of the already transmitted image `id` to the cursor, sized to `area`
+fn place_kitty(out: &mut impl Write, id: usize, area: Rect) -> std::io::Result<()> {
+    write!(
+        out,
+        "\x1b_Ga=p,i={},p=1,c={},r={},C=1,q=2\x1b\\",
+        id, area.width, area.height
+    )
+}
+
+/// Transmit and place an RGBA image over `area`, split into the protocol's 4096-byte chunks
+fn write_kitty(
+    out: &mut impl Write,
+    id: usize,
+    area: Rect,
+    pixels: &[[u8; 4]],
+    (width, height): (usize, usize),
+) -> std::io::Result<()> {
+    let payload = base64(pixels.as_flattened());
+    let chunks: Vec<&[u8]> = payload.as_bytes().chunks(4096).collect();
+    for (i, chunk) in chunks.iter().enumerate() {
+        let more = u8::from(i + 1 < chunks.len());
+        if i == 0 {
+            write!(
+                out,
+                "\x1b_Ga=T,f=32,s={},v={},i={},p=1,c={},r={},C=1,q=2,m={};",
+                width, height, id, area.width, area.height, more
+            )?;
+        } else {
+            write!(out, "\x1b_Gm={};", more)?;
+        }
+        out.write_all(chunk)?;
+        write!(out, "\x1b\\")?;
+    }
+    Ok(())
+}
+
+/// Sixel image with a transparent background; colors are quantized to keep the palette small
+fn write_sixel(
+    out: &mut impl Write,
+    pixels: &[[u8; 4]],
+    (width, height): (usize, usize),
+) -> std::io::Result<()> {
+    // Sixel has no alpha: blend translucent pixels towards a dark background instead
+    let quantize = |[r, g, b, a]: [u8; 4]| {
+        let scale = |channel: u8| (u16::from(channel) * u16::from(a) / 255 / 16 * 16) as u8;
+        [scale(r), scale(g), scale(b)]
+    };
+    let mut palette: Vec<[u8; 3]> = Vec::new();
+    let indexes: Vec<Option<usize>> = pixels
+        .iter()
+        .map(|&pixel| {
+            if pixel[3] == 0 {
+                return None;
+            }
+            let color = quantize(pixel);
+            let index = palette.iter().position(|&c| c == color).unwrap_or_else(|| {
+                palette.push(color);
+                palette.len() - 1
+            });
+            Some(index.min(255))
+        })
+        .collect();
+
+    write!(out, "\x1bP0;1;0q\"1;1;{};{}", width, height)?;
+    for (i, [r, g, b]) in palette.iter().take(256).enumerate() {
+        let percent = |channel: u8| u32::from(channel) * 100 / 255;
+        write!(
+            out,
+            "#{};2;{};{};{}",
+            i,
+            percent(*r),
+            percent(*g),
+            percent(*b)
+        )?;
+    }
+    for band in (0..height).step_by(6) {
+        for color in 0..palette.len().min(256) {
+            let column = |x: usize| {
+                (0..6)
+                    .filter(|dy| band + dy < height)
+                    .filter(|dy| indexes[(band + dy) * width + x] == Some(color))
+                    .fold(0u8, |bits, dy| bits | 1 << dy)
+            };
+            let row: Vec<u8> = (0..width).map(column).collect();
+            if row.iter().all(|&bits| bits == 0) {
+                continue;
+            }
+            write!(out, "#{}", color)?;
+            let mut x = 0;
+            while x < width {
+                let run = row[x..].iter().take_while(|&&bits| bits == row[x]).count();
+                let symbol = char::from(63 + row[x]);
+                if run > 3 {
+                    write!(out, "!{}{}", run, symbol)?;
+                } else {
+                    write!(out, "{}", symbol.to_string().repeat(run))?;
+                }
+                x += run;
+            }
+            write!(out, "$")?;
+        }
+        write!(out, "-")?;
+    }
+    write!(out, "\x1b\\")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_protocol_from_environment() {
+        let env = |vars: &'static [(&'static str, &'static str)]| {
+            move |name: &str| {
+                vars.iter()
+                    .find(|(key, _)| *key == name)
+                    .map(|(_, value)| value.to_string())
+            }
+        };
+        assert_eq!(
+            Protocol::from_env(env(&[("TERM", "xterm-kitty")])),
+            Some(Protocol::Kitty)
+        );
+        assert_eq!(
+            Protocol::from_env(env(&[("TERM", "foot")])),
+            Some(Protocol::Sixel)
+        );
+        assert_eq!(Protocol::from_env(env(&[("TERM", "xterm-256color")])), None);
+        assert_eq!(
+            Protocol::from_env(env(&[("TERM", "xterm-kitty"), ("RTOP_GRAPHICS", "off")])),
+            None
+        );
+    }
+
+    #[test]
+    fn test_encodings() {
+        assert_eq!(base64(b"rtop"), "cnRvcA==");
+        assert_eq!(base64(b"graphs"), "Z3JhcGhz");
+
+        let image = GraphImage {
+            area: Rect::new(0, 0, 2, 1),
+            points: vec![(0.0, Color::Green), (1.0, Color::Green)],
+            fill: false,
+            cursor: None,
+        };
+        let pixels = rasterize(&image, 12, 6);
+        // The line runs from the bottom left to the top right corner
+        assert_eq!(pixels[5 * 12][3], 0xff);
+        assert_eq!(pixels[11][3], 0xff);
+        assert_eq!(pixels[11 * 5][3], 0);
+
+        let mut sixel = Vec::new();
+        write_sixel(&mut sixel, &pixels, (12, 6)).unwrap();
+        let sixel = String::from_utf8(sixel).unwrap();
+        assert!(sixel.starts_with("\x1bP0;1;0q\"1;1;12;6#0;2;"));
+        assert!(sixel.ends_with("$-\x1b\\"));
+    }
+
+    #[test]
+    fn test_unchanged_images_are_not_sent_again() {
+        let image = |x: u16, value: f64| GraphImage {
+            area: Rect::new(x, 0, 4, 2),
+            points: vec![(0.0, Color::Green), (value, Color::Green)],
+            fill: true,
+            cursor: None,
+        };
+        let frame = |protocol, images: &[GraphImage], previous: &[Placed]| {
+            let mut out = Vec::new();
+            let result = write_images(&mut out, protocol, images, previous, (8, 16)).unwrap();
+            (String::from_utf8(out).unwrap(), result)
+        };
+
+        let (first, (placed, _)) = frame(Protocol::Kitty, &[image(0, 0.5)], &[]);
+        assert!(first.contains("a=T,"));
+        let (same, (placed, _)) = frame(Protocol::Kitty, &[image(0, 0.5)], &placed);
+        assert!(same.is_empty());
+        // Moved but not redrawn: only the placement changes
+        let (moved, (placed, _)) = frame(Protocol::Kitty, &[image(2, 0.5)], &placed);
+        assert!(moved.contains("a=p,i=1,p=1,c=4,r=2"));
+        assert!(!moved.contains("a=T"));
+        let (changed, (placed, _)) = frame(Protocol::Kitty, &[image(2, 0.9)], &placed);
+        assert!(changed.starts_with("\x1b[1;3H\x1b_Ga=d,d=I,i=1,"));
+        assert!(changed.contains("a=T,"));
+        let (popup, (placed, _)) = frame(Protocol::Kitty, &[], &placed);
+        assert_eq!(popup, "\x1b_Ga=d,d=I,i=1,q=2\x1b\\");
+        assert!(placed.is_empty());
+
+        // Sixel needs a full redraw once an area is no longer covered
+        let (_, (placed, stale)) = frame(Protocol::Sixel, &[image(0, 0.5)], &[]);
+        assert!(!stale);
+        let (_, (placed, stale)) = frame(Protocol::Sixel, &[image(0, 0.6)], &placed);
+        assert!(!stale);
+        let (_, (_, stale)) = frame(Protocol::Sixel, &[image(2, 0.6)], &placed);
+        assert!(stale);
+    }
+}
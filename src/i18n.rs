@@ -0,0 +1,200 @@
+//! Translations of UI strings, kept as Fluent (`.ftl`) catalogs in `locales/`. Strings are
+//! looked up by message id in the catalog picked by the `language` setting or the locale
+//! environment (`LC_ALL`, `LC_MESSAGES`, `LANG`); messages it lacks come from the bundled
+//! English catalog. A file in `<config dir>/rtop/locales/<language>.ftl` is used before the
+//! bundled one, so translations can be written and tried without rebuilding rtop.
+
+use fluent_bundle::{concurrent::FluentBundle, FluentArgs, FluentResource};
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::sync::OnceLock;
+use unic_langid::LanguageIdentifier;
+
+/// The English text of every message, which translations fall back to
+const ENGLISH: &str = include_str!("../locales/en.ftl");
+
+/// Catalogs shipped with rtop, by language code
+const BUNDLED: &[(&str, &str)] = &[("es", include_str!("../locales/es.ftl"))];
+
+struct Catalog {
+    bundle: FluentBundle<FluentResource>,
+    /// Messages without placeables, formatted once so `tr` can hand out `&'static str`
+    plain: HashMap<String, String>,
+}
+
+static CATALOG: OnceLock<Catalog> = OnceLock::new();
+
+/// Load the catalog for `language`, or for the locale when it is empty. Until this runs (and in
+/// tests) every string is English.
+pub fn init(language: &str) {
+    let language = if language.is_empty() {
+        locale_language(&["LC_ALL", "LC_MESSAGES", "LANG"], |name| {
+            std::env::var(name).ok()
+        })
+    } else {
+        Some(language.to_lowercase())
+    };
+    CATALOG.get_or_init(|| build_catalog(language.as_deref()));
+}
+
+/// `es` from `LANG=es_ES.UTF-8`, checking the first set of `categories`; `None` for the
+/// C/POSIX locale
+pub fn locale_language(
+    categories: &[&str],
+    var: impl Fn(&str) -> Option<String>,
+) -> Option<String> {
+    let locale = categories
+        .iter()
+        .filter_map(|name| var(name))
+        .find(|value| !value.is_empty())?;
+    let language = locale.split(['_', '.', '@']).next()?.to_lowercase();
+    (!language.is_empty() && language != "c" && language != "posix").then_some(language)
+}
+
+/// The Fluent source of the translation into `language`: the user's file, else a bundled one
+fn translation_source(language: &str) -> Option<String> {
+    let user = dirs::config_dir()
+        .map(|dir| {
+            dir.join("rtop")
+                .join("locales")
+                .join(format!("{}.ftl", language))
+        })
+        .and_then(|path| std::fs::read_to_string(path).ok());
+    user.or_else(|| {
+        BUNDLED
+            .iter()
+            .find(|(code, _)| *code == language)
+            .map(|(_, text)| text.to_string())
+    })
+}
+
+/// A bundle of the translation into `language`, if there is one, over the English messages.
+/// Entries that do not parse are skipped, so one typo does not lose the whole translation.
+fn build_catalog(language: Option<&str>) -> Catalog {
+    let langid = language
+        .and_then(|l| l.parse::<LanguageIdentifier>().ok())
+        .unwrap_or_default();
+    let mut bundle = FluentBundle::new_concurrent(vec![langid]);
+    // Isolation marks around placeables only show up as stray characters in a terminal
+    bundle.set_use_isolating(false);
+    let translation = language.and_then(translation_source);
+    for source in translation.into_iter().chain([ENGLISH.to_string()]) {
+        let resource = FluentResource::try_new(source).unwrap_or_else(|(partial, _)| partial);
+        // Translated messages were added first; the English duplicates are refused
+        let _ = bundle.add_resource(resource);
+    }
+
+    let mut plain = HashMap::new();
+    for id in message_ids(ENGLISH) {
+        if let Some(text) = format(&bundle, &id, None) {
+            plain.insert(id, text);
+        }
+    }
+    Catalog { bundle, plain }
+}
+
+fn message_ids(source: &str) -> Vec<String> {
+    let resource =
+        FluentResource::try_new(source.to_string()).unwrap_or_else(|(partial, _)| partial);
+    resource
+        .entries()
+        .filter_map(|entry| match entry {
+            fluent_syntax::ast::Entry::Message(message) => Some(message.id.name.to_string()),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Message `id` filled in with `args`; `None` when it is missing or an argument it uses is not
+/// given
+fn format(
+    bundle: &FluentBundle<FluentResource>,
+    id: &str,
+    args: Option<&FluentArgs>,
+) -> Option<String> {
+    let pattern = bundle.get_message(id)?.value()?;
+    let mut errors = Vec::new();
+    let text = bundle.format_pattern(pattern, args, &mut errors);
+    errors.is_empty().then(|| text.into_owned())
+}
+
+fn catalog() -> &'static Catalog {
+    CATALOG.get_or_init(|| build_catalog(None))
+}
+
+/// Message `id` in the user's language, or the id itself when no catalog has it
+pub fn tr(id: &'static str) -> &'static str {
+    catalog().plain.get(id).map_or(id, String::as_str)
+}
+
+/// `tr` for a message with `{ $name }` placeables, filled in with `args`. Values are inserted
+/// as they are, so braces inside one (a process named `{ $pid }`) stay literal.
+pub fn tr_args(id: &'static str, args: &[(&str, &dyn Display)]) -> String {
+    let mut fluent_args = FluentArgs::new();
+    for (name, value) in args {
+        fluent_args.set(*name, value.to_string());
+    }
+    format(&catalog().bundle, id, Some(&fluent_args)).unwrap_or_else(|| id.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_language() {
+        const MESSAGES: &[&str] = &["LC_ALL", "LC_MESSAGES", "LANG"];
+        let env = |vars: &'static [(&'static str, &'static str)]| {
+            move |name: &str| {
+                vars.iter()
+                    .find(|(key, _)| *key == name)
+                    .map(|(_, value)| value.to_string())
+            }
+        };
+        assert_eq!(
+            locale_language(MESSAGES, env(&[("LANG", "es_ES.UTF-8")])),
+            Some("es".to_string())
+        );
+        assert_eq!(
+            locale_language(
+                MESSAGES,
+                env(&[("LANG", "es_ES.UTF-8"), ("LC_ALL", "de_DE")])
+            ),
+            Some("de".to_string())
+        );
+        assert_eq!(locale_language(MESSAGES, env(&[("LANG", "C.UTF-8")])), None);
+        assert_eq!(locale_language(MESSAGES, env(&[])), None);
+    }
+
+    #[test]
+    fn test_bundled_catalogs_parse_and_fill_placeables() {
+        assert!(FluentResource::try_new(ENGLISH.to_string()).is_ok());
+        let mut args = FluentArgs::new();
+        args.set("name", "sh");
+        args.set("pid", "7");
+        for (code, text) in BUNDLED {
+            assert!(
+                FluentResource::try_new(text.to_string()).is_ok(),
+                "{}",
+                code
+            );
+            let catalog = build_catalog(Some(code));
+            let translated = format(&catalog.bundle, "kill-confirm", Some(&args)).unwrap();
+            assert!(translated.contains("'sh'") && translated.contains("7"));
+            assert_ne!(catalog.plain["processes"], "Processes");
+        }
+        // Without a loaded catalog strings are English
+        assert_eq!(
+            tr_args("kill-confirm", &[("name", &"sh"), ("pid", &7)]),
+            "Terminate 'sh' (PID 7)?"
+        );
+        // A value that looks like a placeable is not substituted again
+        assert_eq!(
+            tr_args("kill-confirm", &[("name", &"{ $pid }"), ("pid", &7)]),
+            "Terminate '{ $pid }' (PID 7)?"
+        );
+        assert_eq!(tr("processes"), "Processes");
+        assert_eq!(tr("no-such-message"), "no-such-message");
+        assert_eq!(tr_args("kill-confirm", &[("pid", &7)]), "kill-confirm");
+    }
+}
@@ -0,0 +1,376 @@
+use serde::{Deserialize, Serialize};
+
+/// A side panel that can be placed in any layout slot
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Panel {
+    Cpu,
+    Gpu,
+    Memory,
+    Network,
+    Disk,
+}
+
+impl Panel {
+    /// Every panel, in the default order
+    pub const ALL: [Panel; 5] = [
+        Panel::Cpu,
+        Panel::Gpu,
+        Panel::Memory,
+        Panel::Network,
+        Panel::Disk,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Panel::Cpu => "CPU",
+            Panel::Gpu => "GPU",
+            Panel::Memory => crate::i18n::tr("memory"),
+            Panel::Network => crate::i18n::tr("network"),
+            Panel::Disk => crate::i18n::tr("disk"),
+        }
+    }
+}
+
+/// A border between two panes that can be dragged to resize them
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PaneBorder {
+    /// Between column `i` and `i + 1` (left panels, process list, right panels)
+    Column(usize),
+    /// Between slot `i` and `i + 1`, stacked in the same column
+    Slot(usize),
+}
+
+/// Order and size of the side panels: the first `LEFT_SLOTS` stack left of the process list,
+/// the rest right. Hidden panels keep their slot; the visible ones in a column share its
+/// height, and a column with nothing left to show gives its width to the process list.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PanelLayout {
+    order: Vec<Panel>,
+    /// Percent of the width for the left panels, the process list and the right panels
+    columns: Vec<u16>,
+    /// Percent of its column's height per slot
+    heights: Vec<u16>,
+    hidden: Vec<Panel>,
+}
+
+impl PanelLayout {
+    pub const LEFT_SLOTS: usize = 3;
+    /// Smallest share a border can be dragged down to
+    pub const MIN_PERCENT: u16 = 10;
+    pub const DEFAULT_COLUMNS: [u16; 3] = [33, 34, 33];
+    pub const DEFAULT_HEIGHTS: [u16; 5] = [33, 34, 33, 50, 50];
+
+    /// Build from a configured order, dropping duplicates and appending any panel it leaves out
+    pub fn new(order: &[Panel]) -> Self {
+        let mut normalized = Vec::with_capacity(Panel::ALL.len());
+        for &panel in order.iter().chain(Panel::ALL.iter()) {
+            if !normalized.contains(&panel) {
+                normalized.push(panel);
+            }
+        }
+        Self {
+            order: normalized,
+            columns: Self::DEFAULT_COLUMNS.to_vec(),
+            heights: Self::DEFAULT_HEIGHTS.to_vec(),
+            hidden: Vec::new(),
+        }
+    }
+
+    /// Start with `hidden` panels switched off
+    pub fn with_hidden(mut self, hidden: &[Panel]) -> Self {
+        for &panel in hidden {
+            if !self.hidden.contains(&panel) {
+                self.hidden.push(panel);
+            }
+        }
+        self
+    }
+
+    /// Use configured pane sizes; sizes that do not add up to 100% per column are ignored
+    pub fn with_sizes(mut self, columns: &[u16], heights: &[u16]) -> Self {
+        // Summed wider than u16 so a hand-edited config with huge values is rejected, not wrapped
+        let valid = |sizes: &[u16]| {
+            sizes.iter().map(|&size| u32::from(size)).sum::<u32>() == 100
+                && sizes.iter().all(|&size| size >= Self::MIN_PERCENT)
+        };
+        if columns.len() == self.columns.len() && valid(columns) {
+            self.columns = columns.to_vec();
+        }
+        if heights.len() == self.heights.len()
+            && valid(&heights[..Self::LEFT_SLOTS])
+            && valid(&heights[Self::LEFT_SLOTS..])
+        {
+            self.heights = heights.to_vec();
+        }
+        self
+    }
+
+    pub fn order(&self) -> &[Panel] {
+        &self.order
+    }
+
+    pub fn columns(&self) -> &[u16] {
+        &self.columns
+    }
+
+    pub fn heights(&self) -> &[u16] {
+        &self.heights
+    }
+
+    pub fn hidden(&self) -> &[Panel] {
+        &self.hidden
+    }
+
+    pub fn is_visible(&self, panel: Panel) -> bool {
+        !self.hidden.contains(&panel)
+    }
+
+    /// Show `panel` if it is hidden, hide it otherwise
+    pub fn toggle_visible(&mut self, panel: Panel) {
+        match self.hidden.iter().position(|&p| p == panel) {
+            Some(i) => {
+                self.hidden.remove(i);
+            }
+            None => self.hidden.push(panel),
+        }
+    }
+
+    /// Visible panels in slot order
+    pub fn visible(&self) -> Vec<Panel> {
+        self.order
+            .iter()
+            .copied()
+            .filter(|&panel| self.is_visible(panel))
+            .collect()
+    }
+
+    /// Whether every panel in the column holding `slot` is shown
+    pub fn column_complete(&self, slot: usize) -> bool {
+        let column = if slot < Self::LEFT_SLOTS {
+            self.left()
+        } else {
+            self.right()
+        };
+        column.iter().all(|&panel| self.is_visible(panel))
+    }
+
+    /// Column widths as laid out: a side column with no visible panel collapses and its
+    /// share goes to the process list
+    pub fn visible_columns(&self) -> [u16; 3] {
+        let mut columns = [self.columns[0], self.columns[1], self.columns[2]];
+        for (side, panels) in [(0, self.left()), (2, self.right())] {
+            if !panels.iter().any(|&panel| self.is_visible(panel)) {
+                columns[1] += columns[side];
+                columns[side] = 0;
+            }
+        }
+        columns
+    }
+
+    /// Visible left panels with their share of the column height
+    pub fn visible_left(&self) -> Vec<(Panel, u16)> {
+        self.visible_stack(self.left(), self.left_heights())
+    }
+
+    /// Visible right panels with their share of the column height
+    pub fn visible_right(&self) -> Vec<(Panel, u16)> {
+        self.visible_stack(self.right(), self.right_heights())
+    }
+
+    /// Scale the heights of the visible panels in a column back up to 100%
+    fn visible_stack(&self, panels: &[Panel], heights: &[u16]) -> Vec<(Panel, u16)> {
+        let shown: Vec<(Panel, u16)> = panels
+            .iter()
+            .copied()
+            .zip(heights.iter().copied())
+            .filter(|&(panel, _)| self.is_visible(panel))
+            .collect();
+        let total: u16 = shown.iter().map(|&(_, height)| height).sum();
+        let mut left = 100;
+        let last = shown.len().saturating_sub(1);
+        shown
+            .into_iter()
+            .enumerate()
+            .map(|(i, (panel, height))| {
+                let scaled = if i == last {
+                    left
+                } else {
+                    (u32::from(height) * 100 / u32::from(total.max(1))) as u16
+                };
+                left -= scaled;
+                (panel, scaled)
+            })
+            .collect()
+    }
+
+    pub fn left_heights(&self) -> &[u16] {
+        &self.heights[..Self::LEFT_SLOTS]
+    }
+
+    pub fn right_heights(&self) -> &[u16] {
+        &self.heights[Self::LEFT_SLOTS..]
+    }
+
+    /// Drag `border` to `at` percent of the screen width, or of its column's height
+    pub fn resize(&mut self, border: PaneBorder, at: u16) {
+        match border {
+            PaneBorder::Column(i) => Self::move_border(&mut self.columns, i, at),
+            PaneBorder::Slot(i) if i < Self::LEFT_SLOTS => {
+                Self::move_border(&mut self.heights[..Self::LEFT_SLOTS], i, at)
+            }
+            PaneBorder::Slot(i) => Self::move_border(
+                &mut self.heights[Self::LEFT_SLOTS..],
+                i - Self::LEFT_SLOTS,
+                at,
+            ),
+        }
+    }
+
+    /// Move the border after `sizes[border]` to `at`, leaving both sides `MIN_PERCENT` or more
+    fn move_border(sizes: &mut [u16], border: usize, at: u16) {
+        if border + 1 >= sizes.len() {
+            return;
+        }
+        let before: u16 = sizes[..border].iter().sum();
+        let pair = sizes[border] + sizes[border + 1];
+        let at = at.clamp(
+            before + Self::MIN_PERCENT,
+            before + pair - Self::MIN_PERCENT,
+        );
+        sizes[border] = at - before;
+        sizes[border + 1] = pair - sizes[border];
+    }
+
+    pub fn left(&self) -> &[Panel] {
+        &self.order[..Self::LEFT_SLOTS]
+    }
+
+    pub fn right(&self) -> &[Panel] {
+        &self.order[Self::LEFT_SLOTS..]
+    }
+
+    pub fn swap(&mut self, a: Panel, b: Panel) {
+        let (Some(i), Some(j)) = (self.position(a), self.position(b)) else {
+            return;
+        };
+        self.order.swap(i, j);
+    }
+
+    /// Move `panel` past the previous (`forward == false`) or next visible panel, crossing
+    /// between columns
+    pub fn shift(&mut self, panel: Panel, forward: bool) {
+        let Some(i) = self.position(panel) else {
+            return;
+        };
+        let visible = |&j: &usize| self.is_visible(self.order[j]);
+        let j = if forward {
+            (i + 1..self.order.len()).find(visible)
+        } else {
+            (0..i).rev().find(visible)
+        };
+        if let Some(j) = j {
+            self.order.swap(i, j);
+        }
+    }
+
+    /// The visible panel after `panel` in slot order, wrapping around
+    pub fn next(&self, panel: Panel) -> Panel {
+        let i = self.position(panel).unwrap_or(0);
+        (1..=self.order.len())
+            .map(|step| self.order[(i + step) % self.order.len()])
+            .find(|&next| self.is_visible(next))
+            .unwrap_or(panel)
+    }
+
+    fn position(&self, panel: Panel) -> Option<usize> {
+        self.order.iter().position(|&p| p == panel)
+    }
+}
+
+impl Default for PanelLayout {
+    fn default() -> Self {
+        Self::new(&Panel::ALL)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_and_reorder() {
+        let mut layout = PanelLayout::new(&[Panel::Disk, Panel::Cpu, Panel::Disk]);
+        assert_eq!(
+            layout.order(),
+            &[
+                Panel::Disk,
+                Panel::Cpu,
+                Panel::Gpu,
+                Panel::Memory,
+                Panel::Network
+            ]
+        );
+
+        layout.shift(Panel::Gpu, true);
+        assert_eq!(layout.left(), &[Panel::Disk, Panel::Cpu, Panel::Memory]);
+        assert_eq!(layout.right(), &[Panel::Gpu, Panel::Network]);
+
+        layout.shift(Panel::Disk, false);
+        layout.swap(Panel::Disk, Panel::Network);
+        assert_eq!(layout.left()[0], Panel::Network);
+        assert_eq!(layout.right()[1], Panel::Disk);
+        assert_eq!(layout.next(Panel::Disk), Panel::Network);
+    }
+
+    #[test]
+    fn test_resize_panes() {
+        let mut layout = PanelLayout::default().with_sizes(&[50, 50], &[20, 20, 60, 30, 70]);
+        assert_eq!(layout.columns(), &[33, 34, 33]);
+        assert_eq!(layout.right_heights(), &[30, 70]);
+
+        layout.resize(PaneBorder::Column(0), 20);
+        assert_eq!(layout.columns(), &[20, 47, 33]);
+        // The process list cannot be squeezed below the minimum
+        layout.resize(PaneBorder::Column(1), 5);
+        assert_eq!(layout.columns(), &[20, 10, 70]);
+
+        layout.resize(PaneBorder::Slot(1), 90);
+        assert_eq!(layout.left_heights(), &[20, 70, 10]);
+        layout.resize(PaneBorder::Slot(3), 45);
+        assert_eq!(layout.right_heights(), &[45, 55]);
+        // Slot 2 is the bottom of the left column, not a border
+        layout.resize(PaneBorder::Slot(2), 50);
+        assert_eq!(layout.heights(), &[20, 70, 10, 45, 55]);
+
+        // 65500 + 100 + 36 wraps to 100 in u16 arithmetic
+        let layout = PanelLayout::default().with_sizes(&[65500, 100, 36], &[0; 5]);
+        assert_eq!(layout.columns(), PanelLayout::default().columns());
+    }
+
+    #[test]
+    fn test_hidden_panels_reflow() {
+        let mut layout = PanelLayout::default().with_hidden(&[Panel::Gpu, Panel::Gpu]);
+        assert_eq!(layout.hidden(), &[Panel::Gpu]);
+        assert_eq!(
+            layout.visible_left(),
+            vec![(Panel::Cpu, 50), (Panel::Memory, 50)]
+        );
+        assert_eq!(layout.visible_columns(), [33, 34, 33]);
+        assert_eq!(layout.next(Panel::Cpu), Panel::Memory);
+        assert!(!layout.column_complete(0));
+        assert!(layout.column_complete(3));
+
+        // Moving skips over the hidden panel
+        layout.shift(Panel::Cpu, true);
+        assert_eq!(layout.left(), &[Panel::Memory, Panel::Gpu, Panel::Cpu]);
+
+        layout.toggle_visible(Panel::Network);
+        layout.toggle_visible(Panel::Disk);
+        assert_eq!(layout.visible_columns(), [33, 67, 0]);
+        assert!(layout.visible_right().is_empty());
+        layout.toggle_visible(Panel::Gpu);
+        assert_eq!(layout.hidden(), &[Panel::Network, Panel::Disk]);
+        assert_eq!(layout.visible_left().len(), 3);
+    }
+}
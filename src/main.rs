@@ -1,36 +1,83 @@
 use app::App;
+use cli::{CliArgs, CliCommand};
 use std::time::Duration;
 
 mod action;
+mod alerts;
 mod app;
+mod check;
+mod cli;
+mod compare;
 mod components;
 mod config;
+mod crash;
 mod data;
+mod format;
+#[cfg(feature = "graphics")]
+mod graphics;
+mod i18n;
+mod layout;
+mod once;
+mod profile;
+mod rollups;
+mod saved_history;
+mod search;
+mod signal;
+mod stats;
+mod status_line;
 mod theme;
 mod tui;
 mod utils;
 mod widgets;
 
+#[cfg(feature = "alloc-count")]
+#[global_allocator]
+static ALLOCATOR: profile::CountingAllocator = profile::CountingAllocator;
+
 fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let args = match CliArgs::parse() {
+        Ok(CliCommand::Run(args)) => args,
+        Ok(CliCommand::Help) => {
+            println!("{}", cli::USAGE);
+            return Ok(());
+        }
+        Ok(CliCommand::Check(expressions)) => {
+            std::process::exit(check::run_checks(&expressions));
+        }
+        Ok(CliCommand::Version) => {
+            println!("rtop {}", env!("CARGO_PKG_VERSION"));
+            return Ok(());
+        }
+        Err(err) => {
+            eprintln!("rtop: {}\n\n{}", err, cli::USAGE);
+            std::process::exit(2);
+        }
+    };
+
+    if let Some(cycles) = args.profile_collect {
+        profile::run_collect_profile(cycles);
+        return Ok(());
+    }
+    if args.once {
+        return once::run(args.low_bandwidth);
+    }
+
     // Setup panic handler
-    std::panic::set_hook(Box::new(|panic_info| {
-        use crossterm::{
-            execute,
-            terminal::{disable_raw_mode, LeaveAlternateScreen},
-        };
-        // Restore terminal
-        let _ = disable_raw_mode();
-        let mut stderr = std::io::stderr();
-        execute!(stderr, LeaveAlternateScreen).ok();
+    crash::install_panic_hook();
 
-        // Print panic info
-        eprintln!("{}", panic_info);
-    }));
+    #[cfg(feature = "graphics")]
+    graphics::init();
 
     // Create and run the app
     let rt = tokio::runtime::Runtime::new()?;
     rt.block_on(async {
         let mut app = App::new(Duration::from_millis(250)).await?;
+        let over_ssh = std::env::var_os("SSH_CONNECTION").is_some();
+        let low_bandwidth = args
+            .low_bandwidth
+            .unwrap_or_else(|| app.config.low_bandwidth.enabled(over_ssh));
+        app.set_low_bandwidth(low_bandwidth);
+        app.set_read_only(args.read_only);
         app.run().await
     })
 }
@@ -0,0 +1,122 @@
+//! `--once`: collect, print a single frame to stdout with ANSI colors and exit, without raw
+//! mode or the alternate screen, so the output can be piped or kept in a session log.
+
+use crate::app::App;
+use crate::config::AppConfig;
+use crossterm::{
+    queue,
+    style::{
+        Attribute, Color as CColor, Print, SetAttribute, SetBackgroundColor, SetForegroundColor,
+    },
+};
+use ratatui::{
+    buffer::Buffer,
+    style::{Modifier, Style},
+};
+use std::io::{self, Write};
+use std::time::Duration;
+use sysinfo::SystemExt;
+
+/// Frame size when stdout is not a terminal
+const DEFAULT_SIZE: (u16, u16) = (120, 40);
+
+pub fn run(low_bandwidth: Option<bool>) -> Result<(), Box<dyn std::error::Error>> {
+    let config = AppConfig::load().unwrap_or_default();
+    // A single frame has nothing to animate, so only an explicit `on` applies
+    let low_bandwidth = low_bandwidth.unwrap_or_else(|| config.low_bandwidth.enabled(false));
+    let mut collector = App::configured_collector(&config);
+    // CPU usage is a delta between two refreshes, so prime once before the snapshot shown.
+    crate::data::Collector::collect(&mut collector);
+    std::thread::sleep(
+        sysinfo::System::MINIMUM_CPU_UPDATE_INTERVAL.max(Duration::from_millis(250)),
+    );
+    let mut app = App::with_collector(&mut collector, config, Duration::from_millis(250));
+    app.set_low_bandwidth(low_bandwidth);
+
+    // One row short of the terminal so the shell prompt does not scroll the top line away
+    let (width, height) = crossterm::terminal::size()
+        .map(|(width, height)| (width, height.saturating_sub(1)))
+        .unwrap_or(DEFAULT_SIZE);
+    let buffer = app.render_buffer(width, height)?;
+
+    let color = std::env::var_os("NO_COLOR").is_none();
+    let mut out = io::stdout().lock();
+    write_ansi(&mut out, &buffer, color)?;
+    out.flush()?;
+    Ok(())
+}
+
+/// SGR attributes for a cell's modifiers
+fn attributes(modifier: Modifier) -> Vec<Attribute> {
+    [
+        (Modifier::BOLD, Attribute::Bold),
+        (Modifier::DIM, Attribute::Dim),
+        (Modifier::ITALIC, Attribute::Italic),
+        (Modifier::UNDERLINED, Attribute::Underlined),
+        (Modifier::REVERSED, Attribute::Reverse),
+        (Modifier::CROSSED_OUT, Attribute::CrossedOut),
+    ]
+    .into_iter()
+    .filter(|(flag, _)| modifier.contains(*flag))
+    .map(|(_, attribute)| attribute)
+    .collect()
+}
+
+/// Write `buffer` as lines of text, with escape codes only where the style changes
+fn write_ansi(out: &mut impl Write, buffer: &Buffer, color: bool) -> io::Result<()> {
+    let area = buffer.area;
+    for y in area.top()..area.bottom() {
+        let mut current: Option<Style> = None;
+        for x in area.left()..area.right() {
+            let cell = &buffer[(x, y)];
+            // The trailing half of a wide character is drawn by the character itself
+            if cell.skip {
+                continue;
+            }
+            let style = cell.style();
+            if color && current != Some(style) {
+                queue!(out, SetAttribute(Attribute::Reset))?;
+                for attribute in attributes(cell.modifier) {
+                    queue!(out, SetAttribute(attribute))?;
+                }
+                queue!(
+                    out,
+                    SetForegroundColor(CColor::from(cell.fg)),
+                    SetBackgroundColor(CColor::from(cell.bg))
+                )?;
+                current = Some(style);
+            }
+            queue!(out, Print(cell.symbol()))?;
+        }
+        if color {
+            queue!(out, SetAttribute(Attribute::Reset))?;
+        }
+        queue!(out, Print("\n"))?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::{layout::Rect, style::Color};
+
+    #[test]
+    fn test_write_ansi_changes_style_only_between_runs() {
+        let mut buffer = Buffer::empty(Rect::new(0, 0, 4, 2));
+        buffer.set_string(0, 0, "ab", Style::default().fg(Color::Red));
+        buffer.set_string(2, 0, "c", Style::default().add_modifier(Modifier::BOLD));
+        buffer.set_string(0, 1, "xy", Style::default());
+
+        let mut plain = Vec::new();
+        write_ansi(&mut plain, &buffer, false).unwrap();
+        assert_eq!(String::from_utf8(plain).unwrap(), "abc \nxy  \n");
+
+        let mut colored = Vec::new();
+        write_ansi(&mut colored, &buffer, true).unwrap();
+        let colored = String::from_utf8(colored).unwrap();
+        assert!(colored.starts_with("\u{1b}[0m\u{1b}[38;5;1m\u{1b}[49mab\u{1b}[0m\u{1b}[1m"));
+        assert_eq!(colored.matches("\u{1b}[0m").count(), 6);
+        assert!(colored.ends_with("xy  \u{1b}[0m\n"));
+    }
+}
@@ -0,0 +1,155 @@
+use crate::data::collector::DataCollector;
+#[cfg(feature = "alloc-count")]
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+/// System allocator wrapper that counts allocations while profiling is enabled. Installed only
+/// with the `alloc-count` feature, so normal builds do not pay for the check on every allocation.
+#[cfg(feature = "alloc-count")]
+pub struct CountingAllocator;
+
+static COUNTING: AtomicBool = AtomicBool::new(false);
+static ALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+
+#[cfg(feature = "alloc-count")]
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        if COUNTING.load(Ordering::Relaxed) {
+            ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        }
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        if COUNTING.load(Ordering::Relaxed) {
+            ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        }
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+fn allocation_count() -> u64 {
+    ALLOCATIONS.load(Ordering::Relaxed)
+}
+
+#[derive(Clone)]
+struct PhaseStats {
+    name: &'static str,
+    samples: Vec<(Duration, u64)>, // (elapsed, allocations) per cycle
+}
+
+/// Records per-phase timings of `DataCollector::collect`
+#[derive(Default)]
+pub struct PhaseProfiler {
+    phases: Vec<PhaseStats>,
+    mark: Option<(Instant, u64)>,
+}
+
+impl PhaseProfiler {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Start timing a new collection cycle
+    pub fn begin(&mut self) {
+        self.mark = Some((Instant::now(), allocation_count()));
+    }
+
+    /// Close the phase that started at the previous mark
+    pub fn mark(&mut self, name: &'static str) {
+        let Some((since, allocs_before)) = self.mark else {
+            return;
+        };
+        let now = Instant::now();
+        let allocs_now = allocation_count();
+        let sample = (now - since, allocs_now.saturating_sub(allocs_before));
+
+        match self.phases.iter_mut().find(|phase| phase.name == name) {
+            Some(phase) => phase.samples.push(sample),
+            None => self.phases.push(PhaseStats {
+                name,
+                samples: vec![sample],
+            }),
+        }
+        self.mark = Some((now, allocs_now));
+    }
+
+    fn print_report(&self, cycles: usize) {
+        println!("rtop collection profile: {} cycles", cycles);
+        println!(
+            "{:<12} {:>10} {:>10} {:>10} {:>12}",
+            "phase", "min ms", "avg ms", "max ms", "allocs/cycle"
+        );
+
+        let mut total = PhaseStats {
+            name: "total",
+            samples: vec![(Duration::ZERO, 0); cycles],
+        };
+        for phase in &self.phases {
+            Self::print_row(phase);
+            for (cycle, (elapsed, allocs)) in phase.samples.iter().enumerate() {
+                if let Some(entry) = total.samples.get_mut(cycle) {
+                    entry.0 += *elapsed;
+                    entry.1 += allocs;
+                }
+            }
+        }
+        Self::print_row(&total);
+    }
+
+    fn print_row(phase: &PhaseStats) {
+        if phase.samples.is_empty() {
+            return;
+        }
+        let to_ms = |d: &Duration| d.as_secs_f64() * 1000.0;
+        let min = phase
+            .samples
+            .iter()
+            .map(|(d, _)| to_ms(d))
+            .fold(f64::MAX, f64::min);
+        let max = phase
+            .samples
+            .iter()
+            .map(|(d, _)| to_ms(d))
+            .fold(0.0, f64::max);
+        let avg =
+            phase.samples.iter().map(|(d, _)| to_ms(d)).sum::<f64>() / phase.samples.len() as f64;
+        let allocs = if cfg!(feature = "alloc-count") {
+            let per_cycle = phase.samples.iter().map(|(_, a)| *a).sum::<u64>() as f64
+                / phase.samples.len() as f64;
+            format!("{:.1}", per_cycle)
+        } else {
+            "-".to_string()
+        };
+        println!(
+            "{:<12} {:>10.3} {:>10.3} {:>10.3} {:>12}",
+            phase.name, min, avg, max, allocs
+        );
+    }
+}
+
+/// Run `cycles` collections without a terminal UI and print per-phase statistics
+pub fn run_collect_profile(cycles: usize) {
+    let mut collector = DataCollector::new();
+    // The first collection primes deltas (network rates, RAPL, RC6) and is not representative.
+    collector.collect();
+
+    collector.enable_profiling();
+    COUNTING.store(true, Ordering::Relaxed);
+    for _ in 0..cycles {
+        collector.collect();
+    }
+    COUNTING.store(false, Ordering::Relaxed);
+
+    if let Some(profiler) = collector.take_profiler() {
+        profiler.print_report(cycles);
+        if !cfg!(feature = "alloc-count") {
+            println!("(build with --features alloc-count to count allocations)");
+        }
+    }
+}
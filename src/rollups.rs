@@ -0,0 +1,193 @@
+use crate::data::snapshot::SystemSnapshot;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::path::{Path, PathBuf};
+
+/// Averages of the headline metrics over one clock hour
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct HourRollup {
+    /// Unix time the hour started at
+    pub hour: i64,
+    pub cpu_percent: f64,
+    pub memory_percent: f64,
+    /// Received plus transmitted, bytes per second
+    pub net_bytes_per_sec: f64,
+    /// Read plus written by all processes, bytes per second
+    pub disk_bytes_per_sec: f64,
+    pub samples: u32,
+}
+
+impl HourRollup {
+    fn fold(&mut self, other: &HourRollup) {
+        self.samples += 1;
+        let n = f64::from(self.samples);
+        self.cpu_percent += (other.cpu_percent - self.cpu_percent) / n;
+        self.memory_percent += (other.memory_percent - self.memory_percent) / n;
+        self.net_bytes_per_sec += (other.net_bytes_per_sec - self.net_bytes_per_sec) / n;
+        self.disk_bytes_per_sec += (other.disk_bytes_per_sec - self.disk_bytes_per_sec) / n;
+    }
+}
+
+/// A week of hourly rollups, kept on disk so the history view survives restarts
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Rollups {
+    /// Oldest first; the last entry is the hour in progress
+    pub hours: VecDeque<HourRollup>,
+    /// Where the rollups are saved; `None` keeps them in memory only
+    #[serde(skip)]
+    path: Option<PathBuf>,
+    /// Why the saved rollups could not be restored, shown in the history view
+    #[serde(skip)]
+    pub warning: Option<String>,
+}
+
+impl Rollups {
+    pub const MAX_HOURS: usize = 7 * 24;
+
+    /// Rollups from earlier sessions, or an empty set saved to the same place
+    pub fn load() -> Self {
+        match dirs::data_dir() {
+            Some(dir) => Self::load_from(dir.join("rtop").join("rollups.json")),
+            None => Self::default(),
+        }
+    }
+
+    /// A file that does not parse is moved aside to `<name>.corrupt` rather than overwritten
+    /// by the next save, so the history in it can still be recovered by hand
+    fn load_from(path: PathBuf) -> Self {
+        let mut rollups = match std::fs::read_to_string(&path) {
+            Ok(text) => serde_json::from_str::<Rollups>(&text).unwrap_or_else(|err| {
+                let warning = match Self::move_aside(&path) {
+                    Ok(moved) => format!(
+                        "Could not read {} ({}); moved it to {}",
+                        path.display(),
+                        err,
+                        moved.display()
+                    ),
+                    Err(_) => format!("Could not read {} ({})", path.display(), err),
+                };
+                Rollups {
+                    warning: Some(warning),
+                    ..Rollups::default()
+                }
+            }),
+            Err(_) => Rollups::default(),
+        };
+        rollups.path = Some(path);
+        rollups
+    }
+
+    fn move_aside(path: &Path) -> std::io::Result<PathBuf> {
+        let mut moved = path.as_os_str().to_owned();
+        moved.push(".corrupt");
+        let moved = PathBuf::from(moved);
+        std::fs::rename(path, &moved)?;
+        Ok(moved)
+    }
+
+    /// Fold one snapshot into its hour; returns true when it started a new hour
+    pub fn observe(&mut self, snapshot: &SystemSnapshot, unix_secs: i64) -> bool {
+        let (rx, tx) = snapshot
+            .networks
+            .iter()
+            .filter(|net| net.parent.is_none())
+            .fold((0, 0), |(rx, tx), net| {
+                (rx + net.received_per_sec, tx + net.transmitted_per_sec)
+            });
+        let disk: u64 = snapshot
+            .processes
+            .iter()
+            .map(|process| process.disk_read_per_sec + process.disk_write_per_sec)
+            .sum();
+        let sample = HourRollup {
+            hour: unix_secs - unix_secs.rem_euclid(3600),
+            cpu_percent: f64::from(snapshot.global_cpu_usage),
+            memory_percent: snapshot.memory_pressure_percent(),
+            net_bytes_per_sec: (rx + tx) as f64,
+            disk_bytes_per_sec: disk as f64,
+            samples: 1,
+        };
+
+        match self.hours.back_mut() {
+            // A clock stepping backwards keeps adding to the latest hour
+            Some(current) if sample.hour <= current.hour => {
+                current.fold(&sample);
+                false
+            }
+            _ => {
+                self.hours.push_back(sample);
+                let oldest = sample.hour - (Self::MAX_HOURS as i64 - 1) * 3600;
+                while self.hours.front().is_some_and(|h| h.hour < oldest) {
+                    self.hours.pop_front();
+                }
+                true
+            }
+        }
+    }
+
+    pub fn save(&self) -> Result<(), Box<dyn std::error::Error>> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        crate::utils::write_atomic(path, &serde_json::to_string(self)?)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_averages_per_hour_and_keeps_a_week() {
+        let mut rollups = Rollups::default();
+        let mut snapshot = SystemSnapshot::default();
+        let start = 1_700_000_000 - 1_700_000_000 % 3600;
+
+        snapshot.global_cpu_usage = 20.0;
+        assert!(rollups.observe(&snapshot, start + 10));
+        snapshot.global_cpu_usage = 40.0;
+        assert!(!rollups.observe(&snapshot, start + 3599));
+        assert!(rollups.observe(&snapshot, start + 3600));
+        assert_eq!(rollups.hours.len(), 2);
+        assert_eq!(rollups.hours[0].cpu_percent, 30.0);
+        assert_eq!(rollups.hours[0].samples, 2);
+
+        // Eight days later only the last week is left
+        rollups.observe(&snapshot, start + 8 * 24 * 3600);
+        assert_eq!(rollups.hours.len(), 1);
+
+        let json = serde_json::to_string(&rollups).unwrap();
+        let restored: Rollups = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.hours, rollups.hours);
+    }
+
+    #[test]
+    fn test_corrupt_file_is_moved_aside() {
+        let dir = std::env::temp_dir().join(format!("rtop-rollups-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("rollups.json");
+
+        let mut rollups = Rollups::load_from(path.clone());
+        assert!(rollups.warning.is_none());
+        rollups.observe(&SystemSnapshot::default(), 1_700_000_000);
+        rollups.save().unwrap();
+        assert_eq!(Rollups::load_from(path.clone()).hours, rollups.hours);
+        assert!(!dir.join("rollups.json.tmp").exists());
+
+        // A save cut short by a crash
+        std::fs::write(&path, r#"{"hours":[{"hour":1700"#).unwrap();
+        let restored = Rollups::load_from(path.clone());
+        assert!(restored.hours.is_empty());
+        assert!(restored.warning.unwrap().contains("rollups.json.corrupt"));
+        assert!(!path.exists());
+        assert!(std::fs::read_to_string(dir.join("rollups.json.corrupt"))
+            .unwrap()
+            .starts_with(r#"{"hours""#));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
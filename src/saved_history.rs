@@ -0,0 +1,117 @@
+use crate::data::snapshot::SystemSnapshot;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::path::PathBuf;
+
+/// The CPU, memory and network graph histories, saved on exit when `persist_history` is on so
+/// a restart picks up where the last session left off
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SavedHistory {
+    /// Unix time the history was saved
+    pub saved_at: i64,
+    /// One series per core
+    pub cpu: Vec<VecDeque<f32>>,
+    pub memory: VecDeque<(u64, u64)>,
+    pub swap: VecDeque<(u64, u64)>,
+    pub network: VecDeque<(u64, u64)>,
+    /// Unix time of each sample, aligned with the newest points
+    pub times: VecDeque<i64>,
+}
+
+impl SavedHistory {
+    /// A history saved longer ago than this is too stale to continue
+    const MAX_AGE_SECS: i64 = 30 * 60;
+
+    fn path() -> Option<PathBuf> {
+        dirs::data_dir().map(|dir| dir.join("rtop").join("history.json"))
+    }
+
+    pub fn from_snapshot(snapshot: &SystemSnapshot, now: i64) -> Self {
+        Self {
+            saved_at: now,
+            cpu: snapshot.cpu_history.clone(),
+            memory: snapshot.memory_history.clone(),
+            swap: snapshot.swap_history.clone(),
+            network: snapshot.network_history.clone(),
+            times: snapshot.history_times.clone(),
+        }
+    }
+
+    /// The history saved by the previous session, if it is recent enough to be worth showing
+    pub fn load(now: i64) -> Option<Self> {
+        let text = std::fs::read_to_string(Self::path()?).ok()?;
+        let saved: SavedHistory = serde_json::from_str(&text).ok()?;
+        saved.is_fresh(now).then_some(saved)
+    }
+
+    fn is_fresh(&self, now: i64) -> bool {
+        (0..=Self::MAX_AGE_SECS).contains(&(now - self.saved_at))
+    }
+
+    /// Only the newest `len` points of each series, for a file saved by a build that kept longer
+    /// histories (or edited by hand)
+    pub fn capped(mut self, len: usize) -> Self {
+        fn keep_newest<T>(series: &mut VecDeque<T>, len: usize) {
+            let excess = series.len().saturating_sub(len);
+            series.drain(..excess);
+        }
+        for core in &mut self.cpu {
+            keep_newest(core, len);
+        }
+        keep_newest(&mut self.memory, len);
+        keep_newest(&mut self.swap, len);
+        keep_newest(&mut self.network, len);
+        keep_newest(&mut self.times, len);
+        self
+    }
+
+    pub fn save(&self) -> Result<(), Box<dyn std::error::Error>> {
+        let Some(path) = Self::path() else {
+            return Ok(());
+        };
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        crate::utils::write_atomic(&path, &serde_json::to_string(self)?)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trips_and_expires() {
+        let snapshot = SystemSnapshot {
+            cpu_history: vec![VecDeque::from([10.0, 20.0])],
+            memory_history: VecDeque::from([(1, 4), (2, 4)]),
+            history_times: VecDeque::from([100, 101]),
+            ..SystemSnapshot::default()
+        };
+        let saved = SavedHistory::from_snapshot(&snapshot, 101);
+
+        let json = serde_json::to_string(&saved).unwrap();
+        let restored: SavedHistory = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored, saved);
+
+        assert!(saved.is_fresh(101 + 60));
+        assert!(!saved.is_fresh(101 + 2 * 3600));
+        // A clock that stepped backwards does not revive it either
+        assert!(!saved.is_fresh(50));
+    }
+
+    #[test]
+    fn test_capped_keeps_the_newest_points() {
+        let saved = SavedHistory {
+            cpu: vec![(0..10).map(|i| i as f32).collect()],
+            memory: (0..10).map(|i| (i, 10)).collect(),
+            times: (100..110).collect(),
+            ..SavedHistory::default()
+        }
+        .capped(3);
+        assert_eq!(saved.cpu[0], [7.0, 8.0, 9.0]);
+        assert_eq!(saved.memory, [(7, 10), (8, 10), (9, 10)]);
+        assert_eq!(saved.times, [107, 108, 109]);
+    }
+}
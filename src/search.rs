@@ -0,0 +1,223 @@
+//! Matching for the process search: plain substring, regex and fuzzy (subsequence) matching,
+//! all case-insensitive and reporting which characters matched so the list can highlight them.
+//!
+//! Regexes use the `regex` crate, which matches in linear time in the text whatever the
+//! pattern: the search runs on the UI thread, over every command line, on every frame.
+
+/// How the search text is matched against processes
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum MatchMode {
+    #[default]
+    Substring,
+    Regex,
+    Fuzzy,
+}
+
+impl MatchMode {
+    pub fn next(self) -> Self {
+        match self {
+            Self::Substring => Self::Regex,
+            Self::Regex => Self::Fuzzy,
+            Self::Fuzzy => Self::Substring,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Substring => "substring",
+            Self::Regex => "regex",
+            Self::Fuzzy => "fuzzy",
+        }
+    }
+}
+
+/// A compiled search query
+#[derive(Debug, Clone)]
+pub struct Matcher {
+    kind: Kind,
+}
+
+#[derive(Debug, Clone)]
+enum Kind {
+    Substring(Vec<char>),
+    Regex(regex::Regex),
+    Fuzzy(Vec<char>),
+    /// Stands in for a query that does not compile
+    Never,
+}
+
+impl Matcher {
+    /// Compile `query` for `mode`; only a malformed regex fails, with a short reason
+    pub fn new(mode: MatchMode, query: &str) -> Result<Self, String> {
+        let kind = match mode {
+            MatchMode::Substring => Kind::Substring(query.chars().map(fold).collect()),
+            MatchMode::Regex => Kind::Regex(
+                regex::RegexBuilder::new(query)
+                    .case_insensitive(true)
+                    .build()
+                    .map_err(|err| short_reason(&err))?,
+            ),
+            MatchMode::Fuzzy => Kind::Fuzzy(
+                query
+                    .chars()
+                    .filter(|c| !c.is_whitespace())
+                    .map(fold)
+                    .collect(),
+            ),
+        };
+        Ok(Self { kind })
+    }
+
+    /// A matcher that matches nothing, for a query that failed to compile
+    pub fn never() -> Self {
+        Self { kind: Kind::Never }
+    }
+
+    pub fn is_match(&self, text: &str) -> bool {
+        self.find(text).is_some()
+    }
+
+    /// Character positions in `text` the query matched, or `None` when it does not match
+    pub fn find(&self, text: &str) -> Option<Vec<usize>> {
+        if let Kind::Regex(regex) = &self.kind {
+            return find_regex(regex, text);
+        }
+        let text: Vec<char> = text.chars().map(fold).collect();
+        match &self.kind {
+            Kind::Substring(needle) => find_substring(needle, &text),
+            Kind::Fuzzy(needle) => find_fuzzy(needle, &text),
+            Kind::Regex(_) | Kind::Never => None,
+        }
+    }
+}
+
+/// The last line of a regex error, which names the problem without the pattern and caret the
+/// lines above it repeat
+fn short_reason(err: &regex::Error) -> String {
+    let message = err.to_string();
+    let last = message.lines().last().unwrap_or_default();
+    last.strip_prefix("error: ").unwrap_or(last).to_string()
+}
+
+/// Lowercase `c` one character for one, so match positions line up with the original text
+fn fold(c: char) -> char {
+    c.to_lowercase().next().unwrap_or(c)
+}
+
+fn find_substring(needle: &[char], text: &[char]) -> Option<Vec<usize>> {
+    if needle.is_empty() {
+        return Some(Vec::new());
+    }
+    let start = text
+        .windows(needle.len())
+        .position(|window| window == needle)?;
+    Some((start..start + needle.len()).collect())
+}
+
+/// The tightest run ending at the first place all of `needle` appears in order: scan forward to
+/// where the last character is found, then back for the latest start
+fn find_fuzzy(needle: &[char], text: &[char]) -> Option<Vec<usize>> {
+    if needle.is_empty() {
+        return Some(Vec::new());
+    }
+    let mut next = 0;
+    let mut end = None;
+    for (i, &c) in text.iter().enumerate() {
+        if c == needle[next] {
+            next += 1;
+            if next == needle.len() {
+                end = Some(i);
+                break;
+            }
+        }
+    }
+    let end = end?;
+
+    let mut positions = Vec::with_capacity(needle.len());
+    let mut wanted = needle.iter().rev().peekable();
+    for i in (0..=end).rev() {
+        let Some(&&c) = wanted.peek() else {
+            break;
+        };
+        if text[i] == c {
+            positions.push(i);
+            wanted.next();
+        }
+    }
+    positions.reverse();
+    Some(positions)
+}
+
+/// Character positions of the leftmost match of `regex` in `text`
+fn find_regex(regex: &regex::Regex, text: &str) -> Option<Vec<usize>> {
+    let found = regex.find(text)?;
+    Some(
+        text.char_indices()
+            .enumerate()
+            .filter(|(_, (byte, _))| found.range().contains(byte))
+            .map(|(position, _)| position)
+            .collect(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn find(mode: MatchMode, query: &str, text: &str) -> Option<Vec<usize>> {
+        Matcher::new(mode, query).unwrap().find(text)
+    }
+
+    #[test]
+    fn test_substring_and_fuzzy_report_matched_positions() {
+        assert_eq!(
+            find(MatchMode::Substring, "FIRE", "Firefox"),
+            Some(vec![0, 1, 2, 3])
+        );
+        assert_eq!(find(MatchMode::Substring, "fox", "firefly"), None);
+
+        assert_eq!(
+            find(MatchMode::Fuzzy, "ffx", "firefox"),
+            Some(vec![0, 4, 6])
+        );
+        // The tightest run is preferred over the first letters seen
+        assert_eq!(find(MatchMode::Fuzzy, "fo", "fire-fox"), Some(vec![5, 6]));
+        assert_eq!(find(MatchMode::Fuzzy, "xf", "firefox"), None);
+    }
+
+    #[test]
+    fn test_regex_matches_case_insensitively_by_character() {
+        let regex = |query: &str, text: &str| find(MatchMode::Regex, query, text);
+        assert_eq!(regex("^py.*3$", "python3"), Some((0..7).collect()));
+        assert_eq!(regex("^py", "mypy"), None);
+        assert_eq!(
+            regex("k?worker/\\d+", "kworker/12:0"),
+            Some((0..10).collect())
+        );
+        assert_eq!(regex("SSH\\w*", "sshd: root"), Some(vec![0, 1, 2, 3]));
+        // Positions count characters, not bytes
+        assert_eq!(regex("é+x", "caféx"), Some(vec![3, 4]));
+
+        assert_eq!(
+            Matcher::new(MatchMode::Regex, "(abc").err().as_deref(),
+            Some("unclosed group")
+        );
+        assert!(!Matcher::never().is_match("anything"));
+    }
+
+    #[test]
+    fn test_pathological_patterns_match_in_linear_time() {
+        let started = std::time::Instant::now();
+        let classpath = "/usr/share/java/dependency.jar:".repeat(130);
+        let words = "a".repeat(200);
+        for (query, text) in [
+            (".*java.*foo", classpath.as_str()),
+            ("\\w+\\w+\\w+\\w+z", words.as_str()),
+            ("(a*)*b", &words[..24]),
+            ("(a|a)*(a|a)*(a|a)*c", words.as_str()),
+        ] {
+            assert_eq!(find(MatchMode::Regex, query, text), None, "{}", query);
+        }
+        assert!(started.elapsed() < std::time::Duration::from_secs(1));
+    }
+}
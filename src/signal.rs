@@ -0,0 +1,147 @@
+//! Sending a signal to a process with the platform's own call, so killing works where there is
+//! no `kill` binary to run: Windows, and minimal containers.
+
+use std::io;
+
+/// Signals offered by the kill dialog
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Signal {
+    #[default]
+    Term,
+    Kill,
+    Stop,
+    Cont,
+    Hup,
+    Int,
+}
+
+impl Signal {
+    pub const ALL: [Signal; 6] = [
+        Signal::Term,
+        Signal::Kill,
+        Signal::Stop,
+        Signal::Cont,
+        Signal::Hup,
+        Signal::Int,
+    ];
+
+    /// Name as `kill -<name>` takes it
+    pub fn name(self) -> &'static str {
+        match self {
+            Signal::Term => "TERM",
+            Signal::Kill => "KILL",
+            Signal::Stop => "STOP",
+            Signal::Cont => "CONT",
+            Signal::Hup => "HUP",
+            Signal::Int => "INT",
+        }
+    }
+
+    /// The next signal in the picker, wrapping around, or the previous one
+    pub fn cycle(self, forward: bool) -> Self {
+        let at = Self::ALL
+            .iter()
+            .position(|&signal| signal == self)
+            .unwrap_or(0);
+        let len = Self::ALL.len();
+        Self::ALL[if forward {
+            (at + 1) % len
+        } else {
+            (at + len - 1) % len
+        }]
+    }
+}
+
+/// Send `signal` to `pid`. A permission failure comes back as `ErrorKind::PermissionDenied`;
+/// see [`no_such_process`] for a process that already exited.
+#[cfg(unix)]
+pub fn send(pid: u32, signal: Signal) -> io::Result<()> {
+    // 0 and negative PIDs address process groups, never a single process
+    let pid = libc::pid_t::try_from(pid)
+        .ok()
+        .filter(|&pid| pid > 0)
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "not a process ID"))?;
+    let number = match signal {
+        Signal::Term => libc::SIGTERM,
+        Signal::Kill => libc::SIGKILL,
+        Signal::Stop => libc::SIGSTOP,
+        Signal::Cont => libc::SIGCONT,
+        Signal::Hup => libc::SIGHUP,
+        Signal::Int => libc::SIGINT,
+    };
+    // SAFETY: kill only reads its arguments
+    if unsafe { libc::kill(pid, number) } == 0 {
+        Ok(())
+    } else {
+        Err(io::Error::last_os_error())
+    }
+}
+
+/// Windows has no signals: the terminating ones end the process with `TerminateProcess`, and
+/// the rest fail with an `ErrorKind::Unsupported` error
+#[cfg(windows)]
+pub fn send(pid: u32, signal: Signal) -> io::Result<()> {
+    use windows_sys::Win32::Foundation::CloseHandle;
+    use windows_sys::Win32::System::Threading::{OpenProcess, TerminateProcess, PROCESS_TERMINATE};
+
+    if !matches!(signal, Signal::Term | Signal::Kill | Signal::Int) {
+        return Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            format!("SIG{} has no Windows equivalent", signal.name()),
+        ));
+    }
+    // SAFETY: the handle is checked before use and closed once, on every path
+    unsafe {
+        let handle = OpenProcess(PROCESS_TERMINATE, 0, pid);
+        if handle.is_null() {
+            return Err(io::Error::last_os_error());
+        }
+        let terminated = TerminateProcess(handle, 1) != 0;
+        let error = io::Error::last_os_error();
+        CloseHandle(handle);
+        if terminated {
+            Ok(())
+        } else {
+            Err(error)
+        }
+    }
+}
+
+#[cfg(not(any(unix, windows)))]
+pub fn send(_pid: u32, _signal: Signal) -> io::Result<()> {
+    Err(io::Error::new(
+        io::ErrorKind::Unsupported,
+        "signalling processes is not supported on this platform",
+    ))
+}
+
+/// Whether `err` from [`send`] means the process was gone before the signal got there
+pub fn no_such_process(err: &io::Error) -> bool {
+    #[cfg(unix)]
+    let code = Some(libc::ESRCH);
+    // OpenProcess rejects the PID of a process that no longer exists as an invalid parameter
+    #[cfg(windows)]
+    let code = Some(windows_sys::Win32::Foundation::ERROR_INVALID_PARAMETER as i32);
+    #[cfg(not(any(unix, windows)))]
+    let code: Option<i32> = None;
+    code.is_some() && err.raw_os_error() == code
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_send_reports_missing_processes_and_bad_pids() {
+        // Signal continue is harmless to a running process
+        assert!(send(std::process::id(), Signal::Cont).is_ok());
+
+        // PIDs above pid_max are never in use
+        let err = send(i32::MAX as u32, Signal::Term).unwrap_err();
+        assert!(no_such_process(&err));
+
+        let err = send(0, Signal::Term).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+        assert!(!no_such_process(&err));
+    }
+}
@@ -0,0 +1,160 @@
+use crate::data::snapshot::SystemSnapshot;
+use chrono::{DateTime, Local};
+use serde::{Serialize, Serializer};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+fn rfc3339<S: Serializer>(time: &DateTime<Local>, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(&time.to_rfc3339())
+}
+
+/// Running min/avg/max of one metric, with when the extremes were seen
+#[derive(Debug, Clone, Serialize)]
+pub struct MetricStats {
+    pub min: f64,
+    pub avg: f64,
+    pub max: f64,
+    #[serde(serialize_with = "rfc3339")]
+    pub min_at: DateTime<Local>,
+    #[serde(serialize_with = "rfc3339")]
+    pub max_at: DateTime<Local>,
+    pub samples: u64,
+}
+
+impl MetricStats {
+    fn new(value: f64, at: DateTime<Local>) -> Self {
+        Self {
+            min: value,
+            avg: value,
+            max: value,
+            min_at: at,
+            max_at: at,
+            samples: 1,
+        }
+    }
+
+    fn observe(&mut self, value: f64, at: DateTime<Local>) {
+        self.samples += 1;
+        self.avg += (value - self.avg) / self.samples as f64;
+        if value < self.min {
+            self.min = value;
+            self.min_at = at;
+        }
+        if value > self.max {
+            self.max = value;
+            self.max_at = at;
+        }
+    }
+}
+
+/// Min/avg/max of the headline metrics since rtop started
+#[derive(Debug, Clone, Serialize)]
+pub struct SessionStats {
+    #[serde(serialize_with = "rfc3339")]
+    pub started: DateTime<Local>,
+    /// CPU, memory pressure and network rates, keyed by the constants below
+    pub metrics: BTreeMap<&'static str, MetricStats>,
+    /// Per sensor, in °C
+    pub temperatures: BTreeMap<String, MetricStats>,
+}
+
+impl SessionStats {
+    pub const CPU: &'static str = "cpu_percent";
+    pub const MEMORY: &'static str = "memory_pressure_percent";
+    pub const NET_RX: &'static str = "net_rx_bytes_per_sec";
+    pub const NET_TX: &'static str = "net_tx_bytes_per_sec";
+
+    pub fn new(started: DateTime<Local>) -> Self {
+        Self {
+            started,
+            metrics: BTreeMap::new(),
+            temperatures: BTreeMap::new(),
+        }
+    }
+
+    /// Whole seconds the session has been running at `now`
+    pub fn running_secs(&self, now: DateTime<Local>) -> u64 {
+        (now - self.started).num_seconds().max(0) as u64
+    }
+
+    /// Fold one collected snapshot into the running statistics
+    pub fn observe(&mut self, snapshot: &SystemSnapshot, at: DateTime<Local>) {
+        // Bond/bridge members and VLANs are already counted on their parent.
+        let (rx, tx) = snapshot
+            .networks
+            .iter()
+            .filter(|net| net.parent.is_none())
+            .fold((0, 0), |(rx, tx), net| {
+                (rx + net.received_per_sec, tx + net.transmitted_per_sec)
+            });
+        let values = [
+            (Self::CPU, f64::from(snapshot.global_cpu_usage)),
+            (Self::MEMORY, snapshot.memory_pressure_percent()),
+            (Self::NET_RX, rx as f64),
+            (Self::NET_TX, tx as f64),
+        ];
+        for (name, value) in values {
+            self.metrics
+                .entry(name)
+                .and_modify(|stats| stats.observe(value, at))
+                .or_insert_with(|| MetricStats::new(value, at));
+        }
+        for sensor in &snapshot.temperature_sensors {
+            let value = f64::from(sensor.temperature);
+            self.temperatures
+                .entry(sensor.label.clone())
+                .and_modify(|stats| stats.observe(value, at))
+                .or_insert_with(|| MetricStats::new(value, at));
+        }
+    }
+
+    /// Write the statistics as JSON next to crash reports and return the file
+    pub fn export(&self) -> Result<PathBuf, Box<dyn std::error::Error>> {
+        let dir = dirs::cache_dir().ok_or("no cache directory")?.join("rtop");
+        std::fs::create_dir_all(&dir)?;
+        let path = dir.join(format!(
+            "stats-{}.json",
+            Local::now().format("%Y%m%d-%H%M%S")
+        ));
+        std::fs::write(&path, serde_json::to_string_pretty(self)?)?;
+        Ok(path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::snapshot::TemperatureInfo;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_tracks_extremes_and_when() {
+        let start = Local.with_ymd_and_hms(2026, 1, 5, 22, 0, 0).unwrap();
+        let mut stats = SessionStats::new(start);
+        let mut snapshot = SystemSnapshot::default();
+
+        for (hour, cpu) in [(22, 10.0), (23, 95.0), (24, 30.0)] {
+            snapshot.global_cpu_usage = cpu;
+            snapshot.temperature_sensors = vec![TemperatureInfo {
+                label: "Package id 0".to_string(),
+                source: "Package id 0".to_string(),
+                temperature: cpu / 2.0 + 30.0,
+            }];
+            stats.observe(&snapshot, start + chrono::Duration::hours(hour - 22));
+        }
+        assert_eq!(stats.running_secs(start + chrono::Duration::hours(2)), 7200);
+        assert_eq!(stats.running_secs(start - chrono::Duration::hours(1)), 0);
+
+        let cpu = &stats.metrics[SessionStats::CPU];
+        assert_eq!((cpu.min, cpu.max, cpu.samples), (10.0, 95.0, 3));
+        assert!((cpu.avg - 45.0).abs() < 1e-9);
+        assert_eq!(cpu.max_at, start + chrono::Duration::hours(1));
+        assert_eq!(stats.temperatures["Package id 0"].max, 77.5);
+
+        let json = serde_json::to_value(&stats).unwrap();
+        assert_eq!(
+            json["metrics"]["cpu_percent"]["max_at"],
+            cpu.max_at.to_rfc3339()
+        );
+    }
+}
@@ -0,0 +1,134 @@
+//! Content of the top status bar, composed from a template such as
+//! `{hostname}  {uptime}  {load}` set in the config
+
+/// What the status bar shows when the config leaves `status_template` alone
+pub const DEFAULT_TEMPLATE: &str =
+    "{hostname}  {uptime}  {load}  {tasks}  {time}  {theme}  {battery}  {self}";
+
+/// Clock format used when the configured one is empty or not valid strftime
+pub const DEFAULT_CLOCK_FORMAT: &str = "%H:%M:%S";
+
+/// strftime format for the `{time}` field: the configured time format, falling back to
+/// `DEFAULT_CLOCK_FORMAT` when chrono cannot format it, with the date before it and the UTC
+/// offset after it when asked for
+pub fn clock_format(format: &str, show_date: bool, show_timezone: bool) -> String {
+    use chrono::format::{Item, StrftimeItems};
+    let valid = !format.trim().is_empty()
+        && !StrftimeItems::new(format).any(|item| matches!(item, Item::Error));
+    let mut clock = if valid { format } else { DEFAULT_CLOCK_FORMAT }.to_string();
+    if show_date {
+        clock.insert_str(0, "%Y-%m-%d ");
+    }
+    if show_timezone {
+        clock.push_str(" %:z");
+    }
+    clock
+}
+
+/// A run of literal text or a `{field}` placeholder
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Segment {
+    Text(String),
+    Field(String),
+}
+
+/// Split a template into text and placeholders; a `{` without a closing `}` is kept as text
+pub fn parse(template: &str) -> Vec<Segment> {
+    let mut segments = Vec::new();
+    let mut rest = template;
+    while let Some(open) = rest.find('{') {
+        let Some(close) = rest[open..].find('}').map(|close| open + close) else {
+            break;
+        };
+        if open > 0 {
+            segments.push(Segment::Text(rest[..open].to_string()));
+        }
+        segments.push(Segment::Field(rest[open + 1..close].trim().to_string()));
+        rest = &rest[close + 1..];
+    }
+    if !rest.is_empty() {
+        segments.push(Segment::Text(rest.to_string()));
+    }
+    segments
+}
+
+/// Lay out `segments`, rendering each field with `field` and each piece of text with `text`.
+/// Fields that render nothing (unknown names, no battery) are skipped along with the
+/// whitespace around them, so a missing field never leaves a double gap.
+pub fn compose<T>(
+    segments: &[Segment],
+    mut field: impl FnMut(&str) -> Vec<T>,
+    text: impl Fn(&str) -> T,
+) -> Vec<T> {
+    let mut out = Vec::new();
+    // Whitespace seen since the last shown item, emitted only if another item follows
+    let mut gap: Option<&str> = None;
+    let mut shown_any = false;
+    for segment in segments {
+        let items = match segment {
+            Segment::Text(literal) if literal.trim().is_empty() => {
+                if shown_any {
+                    gap.get_or_insert(literal);
+                }
+                continue;
+            }
+            Segment::Text(literal) => vec![text(literal)],
+            Segment::Field(name) => field(name),
+        };
+        if items.is_empty() {
+            continue;
+        }
+        if let Some(gap) = gap.take() {
+            out.push(text(gap));
+        }
+        out.extend(items);
+        shown_any = true;
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_template_skips_unknown_and_empty_fields_with_their_gaps() {
+        assert_eq!(
+            parse("{hostname} | up {uptime} {"),
+            vec![
+                Segment::Field("hostname".to_string()),
+                Segment::Text(" | up ".to_string()),
+                Segment::Field("uptime".to_string()),
+                Segment::Text(" {".to_string()),
+            ]
+        );
+
+        let render = |template: &str| -> String {
+            compose(
+                &parse(template),
+                |name| match name {
+                    "hostname" => vec!["box".to_string()],
+                    "load" => vec!["0.5".to_string()],
+                    _ => Vec::new(),
+                },
+                str::to_string,
+            )
+            .concat()
+        };
+        assert_eq!(render("{hostname}  {battery}  {load}"), "box  0.5");
+        assert_eq!(render("{battery} {hostname} {nope}"), "box");
+        assert_eq!(render("{hostname} / {load}"), "box / 0.5");
+        assert_eq!(render("[{ip}]"), "[]");
+    }
+
+    #[test]
+    fn test_clock_format_falls_back_and_adds_date_and_offset() {
+        assert_eq!(clock_format("%H:%M", false, false), "%H:%M");
+        assert_eq!(clock_format("%Q oops", false, false), DEFAULT_CLOCK_FORMAT);
+        assert_eq!(clock_format("", false, false), DEFAULT_CLOCK_FORMAT);
+        assert_eq!(
+            clock_format("%I:%M %p", true, true),
+            "%Y-%m-%d %I:%M %p %:z"
+        );
+    }
+}
@@ -6,12 +6,15 @@ mod default;
 #[derive(Debug, Clone)]
 pub struct Theme {
     pub color_scheme: ColorScheme,
+    /// Snap gradients to three bands so neighbouring cells share a style (low-bandwidth mode)
+    pub simple_gradients: bool,
 }
 
 impl Theme {
     pub fn new(color_scheme: ColorScheme) -> Self {
         Self {
             color_scheme: Self::canonicalize_color_scheme(color_scheme),
+            simple_gradients: false,
         }
     }
 
@@ -40,6 +43,19 @@ impl Theme {
         ratatui::style::Style::default().fg(self.get_color(Color::White))
     }
 
+    /// Position along a usage gradient, snapped to low/mid/high with `simple_gradients`
+    pub fn gradient_position(&self, ratio: f32) -> f32 {
+        if !self.simple_gradients {
+            ratio
+        } else if ratio < 1.0 / 3.0 {
+            0.0
+        } else if ratio < 2.0 / 3.0 {
+            0.5
+        } else {
+            1.0
+        }
+    }
+
     pub fn get_color(&self, default_color: Color) -> Color {
         match self.color_scheme {
             ColorScheme::Default => self.get_graphite_color(default_color),
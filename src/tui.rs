@@ -1,13 +1,39 @@
 use crossterm::{
     event::{DisableMouseCapture, EnableMouseCapture},
-    execute,
-    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
+    execute, queue,
+    terminal::{
+        disable_raw_mode, enable_raw_mode, BeginSynchronizedUpdate, EndSynchronizedUpdate,
+        EnterAlternateScreen, LeaveAlternateScreen,
+    },
 };
 use ratatui::{backend::CrosstermBackend, Terminal};
-use std::io;
+use std::io::{self, Write};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+/// Stdout that counts the bytes written, to measure terminal output per frame
+pub struct CountingStdout {
+    stdout: io::Stdout,
+    written: Arc<AtomicUsize>,
+}
+
+impl Write for CountingStdout {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let written = self.stdout.write(buf)?;
+        self.written.fetch_add(written, Ordering::Relaxed);
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.stdout.flush()
+    }
+}
 
 pub struct Tui {
-    terminal: Terminal<CrosstermBackend<std::io::Stdout>>,
+    terminal: Terminal<CrosstermBackend<CountingStdout>>,
+    written: Arc<AtomicUsize>,
+    /// Bytes sent to the terminal by the last `draw`
+    pub last_frame_bytes: usize,
 }
 
 impl Tui {
@@ -17,21 +43,53 @@ impl Tui {
         let mut stdout = io::stdout();
         execute!(stdout, EnterAlternateScreen, EnableMouseCapture)
             .map_err(|e| format!("Failed to execute terminal commands: {}", e))?;
-        let backend = CrosstermBackend::new(stdout);
+        let written = Arc::new(AtomicUsize::new(0));
+        let backend = CrosstermBackend::new(CountingStdout {
+            stdout,
+            written: written.clone(),
+        });
         let terminal =
             Terminal::new(backend).map_err(|e| format!("Failed to create terminal: {}", e))?;
 
-        Ok(Self { terminal })
+        Ok(Self {
+            terminal,
+            written,
+            last_frame_bytes: 0,
+        })
     }
 
+    /// Draw a frame as one synchronized update, so terminals show it whole rather than as it
+    /// arrives; only cells that changed since the last frame are sent
     pub fn draw<F>(&mut self, f: F) -> Result<(), Box<dyn std::error::Error>>
     where
         F: FnOnce(&mut ratatui::Frame),
     {
+        let started_at = self.written.load(Ordering::Relaxed);
+        queue!(self.terminal.backend_mut(), BeginSynchronizedUpdate)?;
         self.terminal.draw(f)?;
+        execute!(self.terminal.backend_mut(), EndSynchronizedUpdate)?;
+        self.last_frame_bytes = self.written.load(Ordering::Relaxed) - started_at;
         Ok(())
     }
 
+    /// Draw the graph images queued during the last `draw`, redrawing the frame's text if
+    /// stale sixel images have to be wiped
+    #[cfg(feature = "graphics")]
+    pub fn draw_graphics(&mut self) -> Result<bool, Box<dyn std::error::Error>> {
+        let size = crossterm::terminal::window_size()?;
+        let cell = if size.width > 0 && size.columns > 0 && size.rows > 0 {
+            (size.width / size.columns, size.height / size.rows)
+        } else {
+            (10, 20)
+        };
+        let stale = crate::graphics::flush(self.terminal.backend_mut(), cell)?;
+        if stale {
+            self.terminal.clear()?;
+            crate::graphics::forget_placements();
+        }
+        Ok(stale)
+    }
+
     pub fn exit(&mut self) -> Result<(), Box<dyn std::error::Error>> {
         // Restore terminal
         disable_raw_mode()?;
@@ -44,9 +102,82 @@ impl Tui {
         Ok(())
     }
 
+    /// Hand the terminal to a child process: leave the alternate screen and raw mode
+    pub fn suspend(&mut self) -> Result<(), Box<dyn std::error::Error>> {
+        self.exit()
+    }
+
+    /// Take the terminal back after `suspend` and force a full redraw
+    pub fn resume(&mut self) -> Result<(), Box<dyn std::error::Error>> {
+        enable_raw_mode()?;
+        execute!(
+            self.terminal.backend_mut(),
+            EnterAlternateScreen,
+            EnableMouseCapture
+        )?;
+        self.terminal.clear()?;
+        #[cfg(feature = "graphics")]
+        crate::graphics::forget_placements();
+        Ok(())
+    }
+
+    /// Ring the terminal bell
+    pub fn bell(&mut self) -> Result<(), Box<dyn std::error::Error>> {
+        let backend = self.terminal.backend_mut();
+        backend.write_all(b"\x07")?;
+        backend.flush()?;
+        Ok(())
+    }
+
+    /// Put `text` on the system clipboard with an OSC 52 escape, which the terminal (and ssh
+    /// and tmux with `set-clipboard on`) passes through, so it works on remote hosts too
+    pub fn copy_to_clipboard(&mut self, text: &str) -> Result<(), Box<dyn std::error::Error>> {
+        let backend = self.terminal.backend_mut();
+        write!(backend, "\x1b]52;c;{}\x07", base64(text.as_bytes()))?;
+        backend.flush()?;
+        Ok(())
+    }
+
     pub fn resize(&mut self, width: u16, height: u16) -> Result<(), Box<dyn std::error::Error>> {
         self.terminal
             .resize(ratatui::prelude::Rect::new(0, 0, width, height))?;
         Ok(())
     }
 }
+
+/// Standard base64 with padding, for the OSC 52 payload
+fn base64(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut out = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let n = chunk
+            .iter()
+            .enumerate()
+            .fold(0u32, |n, (i, &byte)| n | u32::from(byte) << (16 - 8 * i));
+        for i in 0..4 {
+            if i <= chunk.len() {
+                out.push(ALPHABET[(n >> (18 - 6 * i) & 0x3f) as usize] as char);
+            } else {
+                out.push('=');
+            }
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_base64() {
+        assert_eq!(base64(b""), "");
+        assert_eq!(base64(b"f"), "Zg==");
+        assert_eq!(base64(b"fo"), "Zm8=");
+        assert_eq!(base64(b"foo"), "Zm9v");
+        assert_eq!(
+            base64(b"42 nginx -g daemon off;"),
+            "NDIgbmdpbnggLWcgZGFlbW9uIG9mZjs="
+        );
+    }
+}
@@ -1,45 +1,11 @@
 /// Utility functions for the application
 
-#[allow(dead_code)]
-/// Convert bytes to human-readable format
-pub fn bytes_to_human_readable(bytes: u64) -> String {
-    const UNITS: [&str; 6] = ["B", "KB", "MB", "GB", "TB", "PB"];
-    let mut size = bytes as f64;
-    let mut unit_idx = 0;
-
-    while size >= 1024.0 && unit_idx < UNITS.len() - 1 {
-        size /= 1024.0;
-        unit_idx += 1;
-    }
-
-    format!("{:.1}{}", size, UNITS[unit_idx])
-}
-
 #[allow(dead_code)]
 /// Convert frequency in MHz to GHz
 pub fn freq_to_ghz(mhz: u64) -> f64 {
     mhz as f64 / 1000.0
 }
 
-#[allow(dead_code)]
-/// Format a duration in seconds to a human-readable string
-pub fn format_duration(seconds: u64) -> String {
-    let days = seconds / (24 * 3600);
-    let hours = (seconds % (24 * 3600)) / 3600;
-    let minutes = (seconds % 3600) / 60;
-    let secs = seconds % 60;
-
-    if days > 0 {
-        format!("{}d {}h {}m {}s", days, hours, minutes, secs)
-    } else if hours > 0 {
-        format!("{}h {}m {}s", hours, minutes, secs)
-    } else if minutes > 0 {
-        format!("{}m {}s", minutes, secs)
-    } else {
-        format!("{}s", secs)
-    }
-}
-
 #[allow(dead_code)]
 /// Format a percentage value to a string with 2 decimal places
 pub fn format_percentage(value: f64) -> String {
@@ -125,3 +91,12 @@ pub fn read_intel_gpu_usage() -> Option<f32> {
     // For now, we'll return None if we can't get usage from the simpler methods
     None
 }
+
+/// Replace `path` with `contents` through a temporary file and a rename, so a crash mid-write
+/// leaves the previous file intact rather than a truncated one
+pub fn write_atomic(path: &std::path::Path, contents: &str) -> std::io::Result<()> {
+    let mut temp = path.as_os_str().to_owned();
+    temp.push(".tmp");
+    std::fs::write(&temp, contents)?;
+    std::fs::rename(&temp, path)
+}
@@ -24,6 +24,10 @@ pub struct BrailleGraph<'a> {
     smoothing: usize,
     /// Draw a subtle baseline when not filling
     show_baseline: bool,
+    /// Index of a data point to mark with a vertical line
+    cursor: Option<usize>,
+    /// Index of the first point after a break in the data, such as a restart
+    gap: Option<usize>,
 }
 
 impl<'a> BrailleGraph<'a> {
@@ -37,6 +41,8 @@ impl<'a> BrailleGraph<'a> {
             use_gradient: false,
             smoothing: 0,
             show_baseline: true,
+            cursor: None,
+            gap: None,
         }
     }
 
@@ -75,6 +81,44 @@ impl<'a> BrailleGraph<'a> {
         self
     }
 
+    pub fn cursor(mut self, cursor: Option<usize>) -> Self {
+        self.cursor = cursor;
+        self
+    }
+
+    /// Leave the points before and after `gap` unconnected and mark the break with a dotted line
+    pub fn gap(mut self, gap: Option<usize>) -> Self {
+        self.gap = gap;
+        self
+    }
+
+    /// The same graph as an inline image, for terminals with a graphics protocol
+    #[cfg(feature = "graphics")]
+    fn image(&self, area: Rect) -> crate::graphics::GraphImage {
+        let (min_val, max_val) = self.value_range;
+        let range = (max_val - min_val).max(f64::EPSILON);
+        let base_color = self.style.fg.unwrap_or(Color::White);
+        let points = self
+            .smoothed_values()
+            .into_iter()
+            .map(|value| {
+                let normalized = ((value - min_val) / range).clamp(0.0, 1.0);
+                let color = if self.use_gradient {
+                    super::gauge::palette(normalized as f32)
+                } else {
+                    base_color
+                };
+                (normalized, color)
+            })
+            .collect();
+        crate::graphics::GraphImage {
+            area,
+            points,
+            fill: self.fill,
+            cursor: self.cursor,
+        }
+    }
+
     fn smoothed_values(&self) -> Vec<f64> {
         if self.data.is_empty() {
             return Vec::new();
@@ -111,6 +155,11 @@ impl<'a> Widget for BrailleGraph<'a> {
             return;
         }
 
+        #[cfg(feature = "graphics")]
+        if crate::graphics::queue(self.image(inner_area)) {
+            return;
+        }
+
         // Create canvas for drawing
         let canvas = Canvas::default()
             .marker(ratatui::symbols::Marker::Braille)
@@ -144,14 +193,36 @@ impl<'a> Widget for BrailleGraph<'a> {
                     }
                 }
 
-                let mut prev: Option<(f64, f64)> = None;
-                for (i, value) in values.iter().enumerate() {
-                    let x = if point_count == 1 {
+                let x_of = |i: usize| {
+                    if point_count == 1 {
                         0.0
                     } else {
                         i as f64 * (inner_area.width.saturating_sub(1) as f64)
                             / (point_count.saturating_sub(1) as f64)
-                    };
+                    }
+                };
+
+                if let Some(gap) = self.gap.filter(|&i| i > 0 && i < point_count) {
+                    let x = (x_of(gap - 1) + x_of(gap)) / 2.0;
+                    for y in (0..=bottom_y as u16).step_by(2) {
+                        draw_point(ctx, x, f64::from(y), Color::DarkGray);
+                    }
+                }
+
+                if let Some(cursor) = self.cursor.filter(|&i| i < point_count) {
+                    draw_line(
+                        ctx,
+                        x_of(cursor),
+                        0.0,
+                        x_of(cursor),
+                        bottom_y,
+                        Color::Yellow,
+                    );
+                }
+
+                let mut prev: Option<(f64, f64)> = None;
+                for (i, value) in values.iter().enumerate() {
+                    let x = x_of(i);
                     // Normalize value to 0-1 range
                     let normalized_value = ((*value - min_val) / range).clamp(0.0, 1.0);
 
@@ -160,12 +231,12 @@ impl<'a> Widget for BrailleGraph<'a> {
 
                     // Determine color based on value if using gradient
                     let color = if self.use_gradient {
-                        calculate_gradient_color(normalized_value)
+                        super::gauge::palette(normalized_value as f32)
                     } else {
                         base_color
                     };
 
-                    if let Some((prev_x, prev_y)) = prev {
+                    if let Some((prev_x, prev_y)) = prev.filter(|_| self.gap != Some(i)) {
                         draw_line(ctx, prev_x, prev_y, x, y, color);
                     } else {
                         draw_point(ctx, x, y, color);
@@ -189,26 +260,6 @@ impl<'a> Widget for BrailleGraph<'a> {
     }
 }
 
-/// Helper function to calculate gradient color based on value
-fn calculate_gradient_color(value: f64) -> Color {
-    // Match bar gradient palette for a cohesive UI.
-    if value < 0.5 {
-        // #00ff87 -> #f9ff00
-        let ratio = (value * 2.0) as f32;
-        let r = (0.0 + (249.0 - 0.0) * ratio) as u8;
-        let g = 255;
-        let b = (135.0 + (0.0 - 135.0) * ratio) as u8;
-        Color::Rgb(r, g, b)
-    } else {
-        // #f9ff00 -> #ff003c
-        let ratio = ((value - 0.5) * 2.0) as f32;
-        let r = (249.0 + (255.0 - 249.0) * ratio) as u8;
-        let g = (255.0 + (0.0 - 255.0) * ratio) as u8;
-        let b = (0.0 + (60.0 - 0.0) * ratio) as u8;
-        Color::Rgb(r, g, b)
-    }
-}
-
 fn draw_point(ctx: &mut Context<'_>, x: f64, y: f64, color: Color) {
     ctx.draw(&Points {
         coords: &[(x, y)],
@@ -0,0 +1,397 @@
+//! Pieces shared by the usage bars: the green → yellow → red palette, its dimmed track,
+//! eighth-block partial cells, and the [`GradientBar`] and [`StackedBar`] widgets built on them.
+
+use crate::theme::Theme;
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::Widget,
+};
+
+/// The bar palette at `ratio` along it: #00ff87 at 0.0, #f9ff00 at 0.5, #ff003c at 1.0
+pub fn palette(ratio: f32) -> Color {
+    if ratio < 0.5 {
+        // Interpolate between #00ff87 (0, 255, 135) and #f9ff00 (249, 255, 0)
+        let t = ratio * 2.0;
+        let r = (249.0 * t) as u8;
+        let b = (135.0 - 135.0 * t) as u8;
+        Color::Rgb(r, 255, b)
+    } else {
+        // Interpolate between #f9ff00 (249, 255, 0) and #ff003c (255, 0, 60)
+        let t = (ratio - 0.5) * 2.0;
+        let r = (249.0 + (255.0 - 249.0) * t) as u8;
+        let g = (255.0 - 255.0 * t) as u8;
+        let b = (60.0 * t) as u8;
+        Color::Rgb(r, g, b)
+    }
+}
+
+/// The palette as the theme shows it (banded in low-bandwidth mode)
+pub fn gradient_color(theme: &Theme, ratio: f32) -> Color {
+    palette(theme.gradient_position(ratio))
+}
+
+/// `color` at `brightness` (0.0..=1.0); `None` for colors that are not RGB
+pub fn dimmed(color: Color, brightness: f32) -> Option<Color> {
+    match color {
+        Color::Rgb(r, g, b) => Some(Color::Rgb(
+            (r as f32 * brightness) as u8,
+            (g as f32 * brightness) as u8,
+            (b as f32 * brightness) as u8,
+        )),
+        _ => None,
+    }
+}
+
+/// The empty part of a bar: the gradient at 30% brightness so the scale stays visible
+pub fn track_color(theme: &Theme, ratio: f32) -> Color {
+    dimmed(gradient_color(theme, ratio), 0.3).unwrap_or(Color::Rgb(50, 50, 50))
+}
+
+/// Cells left for a bar after a `prefix_chars` label, at most `max_width`
+pub fn bar_width_for_area(area_width: u16, prefix_chars: usize, max_width: usize) -> usize {
+    (area_width as usize)
+        .saturating_sub(prefix_chars)
+        .min(max_width)
+}
+
+/// Left-aligned block covering `units` eighths of a cell
+pub fn fractional_block(units: usize) -> char {
+    match units {
+        1 => '▏',
+        2 => '▎',
+        3 => '▍',
+        4 => '▌',
+        5 => '▋',
+        6 => '▊',
+        7 => '▉',
+        _ => '░',
+    }
+}
+
+/// Where cell `index` of a `width`-cell bar sits along the palette
+pub fn position_ratio(index: usize, width: usize) -> f32 {
+    index as f32 / width.saturating_sub(1).max(1) as f32
+}
+
+/// A percentage bar in eighth-cell steps, colored along the palette over its dimmed track,
+/// optionally after a label
+pub struct GradientBar<'a> {
+    theme: &'a Theme,
+    percent: f64,
+    label: Option<Span<'a>>,
+    max_width: usize,
+}
+
+impl<'a> GradientBar<'a> {
+    pub fn new(theme: &'a Theme, percent: f64) -> Self {
+        Self {
+            theme,
+            percent,
+            label: None,
+            max_width: usize::MAX,
+        }
+    }
+
+    /// Text drawn before the bar, which takes whatever width is left
+    pub fn label(mut self, text: impl Into<std::borrow::Cow<'a, str>>, style: Style) -> Self {
+        self.label = Some(Span::styled(text, style));
+        self
+    }
+
+    /// Widest the bar may grow, not counting the label
+    pub fn max_width(mut self, max_width: usize) -> Self {
+        self.max_width = max_width;
+        self
+    }
+
+    /// The bar's cells for a bar `width` cells wide
+    pub fn spans(&self, width: usize) -> Vec<Span<'static>> {
+        let total_units = width * 8; // 8 sub-units per block
+        let filled_units =
+            (self.percent.clamp(0.0, 100.0) / 100.0 * total_units as f64).round() as usize;
+
+        (0..width)
+            .map(|i| {
+                let start_unit = i * 8;
+                let end_unit = start_unit + 8;
+                let ratio = position_ratio(i, width);
+                let active = gradient_color(self.theme, ratio);
+                let track = track_color(self.theme, ratio);
+
+                let (char, style) = if filled_units <= start_unit {
+                    (' ', Style::default().bg(track))
+                } else if filled_units >= end_unit {
+                    (' ', Style::default().bg(active))
+                } else {
+                    (
+                        fractional_block(filled_units - start_unit),
+                        Style::default().fg(active).bg(track),
+                    )
+                };
+                Span::styled(char.to_string(), style)
+            })
+            .collect()
+    }
+}
+
+impl Widget for GradientBar<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let label_width = self.label.as_ref().map_or(0, Span::width);
+        let width = bar_width_for_area(area.width, label_width, self.max_width);
+        let mut spans = self.spans(width);
+        if let Some(label) = self.label {
+            spans.insert(0, label);
+        }
+        Line::from(spans).render(area, buf);
+    }
+}
+
+/// How a [`StackedBar`] segment is colored
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SegmentColor {
+    Solid(Color),
+    /// The palette at each cell's position, at this brightness
+    Gradient(f32),
+}
+
+impl SegmentColor {
+    fn at(self, theme: &Theme, ratio: f32) -> Color {
+        match self {
+            SegmentColor::Solid(color) => color,
+            SegmentColor::Gradient(brightness) => {
+                dimmed(gradient_color(theme, ratio), brightness).unwrap_or(Color::Rgb(50, 50, 50))
+            }
+        }
+    }
+}
+
+/// One part of a [`StackedBar`] and its legend entry
+#[derive(Debug, Clone)]
+pub struct Segment {
+    pub value: f64,
+    pub color: SegmentColor,
+    /// Legend text after the segment's swatch; empty leaves it out of the legend
+    pub label: String,
+}
+
+/// Several values stacked left to right over a track, in eighth-cell steps, with a legend of
+/// colored swatches that [`Widget::render`] puts on the line below the bar when there is room
+pub struct StackedBar<'a> {
+    theme: &'a Theme,
+    total: f64,
+    segments: Vec<Segment>,
+    track: SegmentColor,
+    track_label: String,
+    label: Line<'a>,
+    legend_suffix: Vec<Span<'a>>,
+    max_width: usize,
+}
+
+impl<'a> StackedBar<'a> {
+    /// A bar where `total` fills every cell; the track is the dimmed palette
+    pub fn new(theme: &'a Theme, total: f64) -> Self {
+        Self {
+            theme,
+            total,
+            segments: Vec::new(),
+            track: SegmentColor::Gradient(0.3),
+            track_label: String::new(),
+            label: Line::default(),
+            legend_suffix: Vec::new(),
+            max_width: usize::MAX,
+        }
+    }
+
+    pub fn segment(mut self, value: f64, color: SegmentColor, label: impl Into<String>) -> Self {
+        self.segments.push(Segment {
+            value,
+            color,
+            label: label.into(),
+        });
+        self
+    }
+
+    /// Color of the unfilled rest, and its legend text (empty to leave it out)
+    pub fn track(mut self, color: SegmentColor, label: impl Into<String>) -> Self {
+        self.track = color;
+        self.track_label = label.into();
+        self
+    }
+
+    /// Text drawn before the bar, which takes whatever width is left
+    pub fn label(mut self, label: impl Into<Line<'a>>) -> Self {
+        self.label = label.into();
+        self
+    }
+
+    /// Spans appended to the legend
+    pub fn legend_suffix(mut self, spans: Vec<Span<'a>>) -> Self {
+        self.legend_suffix = spans;
+        self
+    }
+
+    /// Widest the bar may grow, not counting the label
+    pub fn max_width(mut self, max_width: usize) -> Self {
+        self.max_width = max_width;
+        self
+    }
+
+    /// Cumulative end of each segment in eighths of a cell; anything past the last is track
+    fn segment_ends(&self, width: usize) -> Vec<usize> {
+        let total_units = width * 8;
+        let units_of = |value: f64| {
+            if self.total > 0.0 {
+                (value.max(0.0) / self.total * total_units as f64) as usize
+            } else {
+                0
+            }
+        };
+        let mut end = 0;
+        self.segments
+            .iter()
+            .map(|segment| {
+                end = (end + units_of(segment.value)).min(total_units);
+                end
+            })
+            .collect()
+    }
+
+    /// The bar's cells for a bar `width` cells wide
+    pub fn spans(&self, width: usize) -> Vec<Span<'static>> {
+        let ends = self.segment_ends(width);
+        let segment_at = |unit: usize| ends.iter().position(|&end| unit < end);
+        let color = |segment: Option<usize>, ratio: f32| {
+            segment
+                .map_or(self.track, |i| self.segments[i].color)
+                .at(self.theme, ratio)
+        };
+
+        (0..width)
+            .map(|i| {
+                let start_unit = i * 8;
+                let ratio = position_ratio(i, width);
+                let here = segment_at(start_unit);
+                let next = segment_at(start_unit + 7);
+                let fill = color(here, ratio);
+
+                let (char, style) = match here {
+                    Some(segment) if here != next => (
+                        // The cell straddles a boundary: this segment's share as a partial block
+                        fractional_block(ends[segment] - start_unit),
+                        Style::default().fg(fill).bg(color(next, ratio)),
+                    ),
+                    _ => (' ', Style::default().bg(fill)),
+                };
+                Span::styled(char.to_string(), style)
+            })
+            .collect()
+    }
+
+    /// A swatch and label per labelled segment (and the track), then the suffix. Gradient
+    /// swatches take the color where the first segment ends.
+    pub fn legend(&self, width: usize) -> Vec<Span<'a>> {
+        let first_end = self.segment_ends(width).first().copied().unwrap_or(0);
+        let ratio = first_end as f32 / (width * 8).max(1) as f32;
+        let entries = self
+            .segments
+            .iter()
+            .map(|segment| (segment.color, &segment.label))
+            .chain(std::iter::once((self.track, &self.track_label)))
+            .filter(|(_, label)| !label.is_empty());
+
+        let mut spans = Vec::new();
+        for (color, label) in entries {
+            spans.push(Span::styled(
+                "■",
+                Style::default().fg(color.at(self.theme, ratio)),
+            ));
+            spans.push(Span::styled(
+                format!(" {} ", label),
+                self.theme.text_style(),
+            ));
+        }
+        spans.extend(self.legend_suffix.iter().cloned());
+        spans
+    }
+}
+
+impl Widget for StackedBar<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let width = bar_width_for_area(area.width, self.label.width(), self.max_width);
+        let mut bar = self.label.clone();
+        bar.spans.extend(self.spans(width));
+        bar.render(area, buf);
+
+        if area.height > 1 {
+            let legend_area = Rect {
+                y: area.y + 1,
+                height: 1,
+                ..area
+            };
+            Line::from(self.legend(width)).render(legend_area, buf);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bar_fills_in_eighths() {
+        let theme = Theme::new(crate::data::snapshot::ColorScheme::Default);
+        let cells: String = GradientBar::new(&theme, 40.0)
+            .spans(4)
+            .iter()
+            .map(|span| span.content.to_string())
+            .collect();
+        // 40% of 32 eighths is 12.8, rounded to one full cell and five eighths
+        assert_eq!(cells, " ▋  ");
+
+        assert_eq!(bar_width_for_area(20, 8, 48), 12);
+        assert_eq!(bar_width_for_area(80, 8, 48), 48);
+        assert_eq!(palette(0.0), Color::Rgb(0, 255, 135));
+        assert_eq!(palette(1.0), Color::Rgb(255, 0, 60));
+        assert_eq!(
+            track_color(&theme, 0.0),
+            Color::Rgb(0, 76, 40),
+            "the track is the palette at 30%"
+        );
+
+        let mut buf = Buffer::empty(Rect::new(0, 0, 10, 1));
+        GradientBar::new(&theme, 100.0)
+            .label("CPU ", Style::default())
+            .max_width(4)
+            .render(buf.area, &mut buf);
+        assert_eq!(buf[(4, 0)].bg, palette(0.0));
+        assert_eq!(buf[(7, 0)].bg, palette(1.0));
+        assert_eq!(buf[(8, 0)].bg, Color::Reset);
+    }
+
+    #[test]
+    fn test_stacked_segments_and_legend() {
+        let theme = Theme::new(crate::data::snapshot::ColorScheme::Default);
+        let blue = SegmentColor::Solid(Color::Blue);
+        let bar = StackedBar::new(&theme, 100.0)
+            .segment(25.0, SegmentColor::Gradient(1.0), "used")
+            .segment(12.5, blue, "")
+            .track(SegmentColor::Solid(Color::DarkGray), "free");
+        let cells = bar.spans(4);
+        // 25% fills the first cell, the next 12.5% half of the second
+        assert_eq!(cells[0].style.bg, Some(palette(0.0)));
+        assert_eq!(cells[1].content, "▌");
+        assert_eq!(cells[1].style.fg, Some(Color::Blue));
+        assert_eq!(cells[1].style.bg, Some(Color::DarkGray));
+        assert_eq!(cells[3].style.bg, Some(Color::DarkGray));
+
+        let legend: String = bar
+            .legend(4)
+            .iter()
+            .map(|span| span.content.to_string())
+            .collect();
+        assert_eq!(legend, "■ used ■ free ");
+    }
+}
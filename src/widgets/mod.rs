@@ -1 +1,2 @@
 pub mod braille_graph;
+pub mod gauge;